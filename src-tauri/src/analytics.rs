@@ -0,0 +1,149 @@
+// Analytics over recent test results for the factory dashboard
+
+use crate::test_criteria::{TestResult, TestVerdict};
+use serde::Serialize;
+
+/// Most buckets a histogram may have; a narrower width is widened to fit
+const MAX_HISTOGRAM_BUCKETS: usize = 200;
+
+/// One histogram bucket covering `[start_seconds, end_seconds)`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TtffBucket {
+    pub start_seconds: f64,
+    pub end_seconds: f64,
+    pub count: u32,
+}
+
+/// TTFF distribution across recent results
+#[derive(Debug, Clone, Serialize)]
+pub struct TtffHistogram {
+    /// Bucket width used, wider than requested if that would exceed `MAX_HISTOGRAM_BUCKETS`
+    pub bucket_seconds: f64,
+    /// Contiguous buckets from 0s up to the slowest TTFF, including empty ones
+    pub buckets: Vec<TtffBucket>,
+    pub sample_count: u32,
+    /// Results without a TTFF (no fix), left out of the distribution
+    pub skipped: u32,
+    pub mean_seconds: Option<f64>,
+    pub median_seconds: Option<f64>,
+    pub p95_seconds: Option<f64>,
+}
+
+/// The results with `verdict`, in their original order
+pub fn filter_by_verdict(results: &[TestResult], verdict: &TestVerdict) -> Vec<TestResult> {
+    results.iter().filter(|r| &r.verdict == verdict).cloned().collect()
+}
+
+/// Bucket the TTFF of each result. Returns None unless the bucket width is a positive number.
+pub fn ttff_histogram(results: &[TestResult], bucket_seconds: f64) -> Option<TtffHistogram> {
+    if bucket_seconds <= 0.0 || !bucket_seconds.is_finite() {
+        return None;
+    }
+
+    let mut ttffs: Vec<f64> = results.iter().filter_map(|r| r.ttff_seconds).collect();
+    ttffs.sort_by(|a, b| a.total_cmp(b));
+    let skipped = (results.len() - ttffs.len()) as u32;
+
+    let slowest = ttffs.last().copied().unwrap_or(0.0);
+    let bucket_seconds = bucket_seconds.max(slowest / (MAX_HISTOGRAM_BUCKETS - 1) as f64);
+    let bucket_count = ttffs
+        .last()
+        .map_or(0, |max| (max / bucket_seconds).floor() as usize + 1);
+    let mut buckets: Vec<TtffBucket> = (0..bucket_count)
+        .map(|i| TtffBucket {
+            start_seconds: i as f64 * bucket_seconds,
+            end_seconds: (i + 1) as f64 * bucket_seconds,
+            count: 0,
+        })
+        .collect();
+    for ttff in &ttffs {
+        buckets[(ttff / bucket_seconds).floor() as usize].count += 1;
+    }
+
+    let n = ttffs.len();
+    Some(TtffHistogram {
+        bucket_seconds,
+        buckets,
+        sample_count: n as u32,
+        skipped,
+        mean_seconds: (n > 0).then(|| ttffs.iter().sum::<f64>() / n as f64),
+        // Middle value, or the mean of the middle two for an even count
+        median_seconds: (n > 0).then(|| (ttffs[(n - 1) / 2] + ttffs[n / 2]) / 2.0),
+        // Nearest-rank percentile
+        p95_seconds: (n > 0).then(|| ttffs[((n as f64 * 0.95).ceil() as usize).max(1) - 1]),
+    })
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_criteria::{TestCriteria, TestRunner};
+    use crate::test_support::test_device;
+
+    fn result_with_ttff(ttff: Option<f64>) -> TestResult {
+        TestResult {
+            ttff_seconds: ttff,
+            ..TestRunner::new(TestCriteria::default(), test_device()).get_result(None)
+        }
+    }
+
+    #[test]
+    fn test_ttff_histogram_buckets_and_stats() {
+        let results: Vec<TestResult> = [Some(3.0), Some(12.0), None, Some(14.5), Some(31.0)]
+            .into_iter()
+            .map(result_with_ttff)
+            .collect();
+
+        let hist = ttff_histogram(&results, 10.0).unwrap();
+        let counts: Vec<u32> = hist.buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, [1, 2, 0, 1]);
+        assert_eq!(hist.buckets[3].start_seconds, 30.0);
+        assert_eq!(hist.sample_count, 4);
+        assert_eq!(hist.skipped, 1);
+        assert_eq!(hist.mean_seconds, Some(15.125));
+        assert_eq!(hist.median_seconds, Some(13.25));
+        assert_eq!(hist.p95_seconds, Some(31.0));
+    }
+
+    #[test]
+    fn test_filter_by_verdict_keeps_only_fails() {
+        let results: Vec<TestResult> = [
+            TestVerdict::Pass,
+            TestVerdict::Fail,
+            TestVerdict::TimedOut,
+            TestVerdict::Fail,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, verdict)| TestResult {
+            verdict,
+            ..result_with_ttff(Some(i as f64))
+        })
+        .collect();
+
+        let fails = filter_by_verdict(&results, &TestVerdict::Fail);
+        let ttffs: Vec<_> = fails.iter().map(|r| r.ttff_seconds).collect();
+        assert_eq!(ttffs, [Some(1.0), Some(3.0)]);
+        assert!(filter_by_verdict(&results, &TestVerdict::Interrupted).is_empty());
+    }
+
+    #[test]
+    fn test_ttff_histogram_edge_cases() {
+        assert!(ttff_histogram(&[], 0.0).is_none());
+        assert!(ttff_histogram(&[], f64::NAN).is_none());
+
+        let empty = ttff_histogram(&[result_with_ttff(None)], 5.0).unwrap();
+        assert!(empty.buckets.is_empty());
+        assert_eq!(empty.skipped, 1);
+        assert_eq!(empty.median_seconds, None);
+
+        // A tiny width over a slow TTFF is widened rather than allocating millions of buckets
+        let results = [result_with_ttff(Some(0.5)), result_with_ttff(Some(3600.0))];
+        let hist = ttff_histogram(&results, 0.001).unwrap();
+        assert!(hist.buckets.len() <= MAX_HISTOGRAM_BUCKETS);
+        assert!(hist.bucket_seconds > 0.001);
+        assert_eq!(hist.buckets.iter().map(|b| b.count).sum::<u32>(), 2);
+    }
+}
@@ -0,0 +1,225 @@
+// Active vs passive antenna inference from the u-blox antenna supervisor (UBX-MON-HW)
+//
+// With the antenna supply on (aPower = ON), the supervisor measures the current drawn
+// through the RF input. An active antenna's LNA draws current, so the supervisor
+// settles on OK; a passive patch draws none and reads OPEN. During a cold start the
+// status usually begins at INIT and settles within a few seconds, so the inference uses
+// the latest settled status rather than the first report.
+//
+// Limitations:
+// - Needs a module with the supervisor wired up and enabled (CFG-ANT). Without it the
+//   status stays INIT/DONTKNOW and the antenna type is reported as unknown.
+// - OPEN cannot tell a passive antenna from a missing or broken cable.
+// - With the supply off (aPower = OFF) nothing can be inferred.
+// - Some boards tie the sense pins so the supervisor reports OK regardless of the
+//   antenna; the check is only as good as the hardware's current sensing.
+
+use serde::{Deserialize, Serialize};
+
+/// MON-HW `aStatus`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AntennaStatus {
+    Init,
+    DontKnow,
+    Ok,
+    Short,
+    Open,
+}
+
+impl AntennaStatus {
+    pub fn from_ubx(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Init),
+            1 => Some(Self::DontKnow),
+            2 => Some(Self::Ok),
+            3 => Some(Self::Short),
+            4 => Some(Self::Open),
+            _ => None,
+        }
+    }
+
+    /// Status from a u-blox `ANTSTATUS=` TXT message, e.g. "OPEN"
+    pub fn from_text(value: &str) -> Option<Self> {
+        match value {
+            "INIT" => Some(Self::Init),
+            "DONTKNOW" => Some(Self::DontKnow),
+            "OK" => Some(Self::Ok),
+            "SHORT" => Some(Self::Short),
+            "OPEN" => Some(Self::Open),
+            _ => None,
+        }
+    }
+
+    /// The supervisor has measured something (not still starting up or unconfigured)
+    fn is_settled(self) -> bool {
+        matches!(self, Self::Ok | Self::Short | Self::Open)
+    }
+}
+
+/// MON-HW `aPower`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AntennaPower {
+    Off,
+    On,
+    DontKnow,
+}
+
+impl AntennaPower {
+    pub fn from_ubx(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Off),
+            1 => Some(Self::On),
+            2 => Some(Self::DontKnow),
+            _ => None,
+        }
+    }
+}
+
+/// Inferred antenna type
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AntennaKind {
+    /// Drawing current from the antenna supply
+    Active,
+    /// No current drawn: a passive antenna, or none connected
+    Passive,
+    /// Short circuit on the antenna supply
+    Short,
+    /// Supervisor not reporting, or supply off
+    Unknown,
+}
+
+/// One change in the supervisor's reported state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AntennaObservation {
+    pub at_seconds: f64, // Since the test started
+    pub status: AntennaStatus,
+    pub power: AntennaPower,
+}
+
+/// Antenna section of a test report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AntennaReport {
+    pub kind: AntennaKind,
+    /// Supervisor state changes over the test, starting with the first report
+    pub transitions: Vec<AntennaObservation>,
+    /// The criteria expect an active antenna but one was not detected
+    pub mismatch: bool,
+}
+
+/// Collects MON-HW antenna states over a test
+#[derive(Debug, Clone, Default)]
+pub struct AntennaMonitor {
+    transitions: Vec<AntennaObservation>,
+}
+
+impl AntennaMonitor {
+    /// Record a MON-HW report, keeping it only if the state changed
+    pub fn observe(&mut self, at_seconds: f64, status: AntennaStatus, power: AntennaPower) {
+        let changed = self
+            .transitions
+            .last()
+            .is_none_or(|last| last.status != status || last.power != power);
+        if changed {
+            self.transitions.push(AntennaObservation { at_seconds, status, power });
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+    }
+
+    /// Infer the antenna type from the latest settled status. A short seen at any
+    /// point wins, since it is a fault even if the supervisor later recovers.
+    pub fn kind(&self) -> AntennaKind {
+        let powered = |o: &&AntennaObservation| o.power == AntennaPower::On;
+        if self
+            .transitions
+            .iter()
+            .filter(powered)
+            .any(|o| o.status == AntennaStatus::Short)
+        {
+            return AntennaKind::Short;
+        }
+        match self.transitions.last() {
+            Some(last) if last.power != AntennaPower::On => AntennaKind::Unknown,
+            _ => match self.transitions.iter().rev().find(|o| o.status.is_settled()) {
+                Some(o) if o.power == AntennaPower::On && o.status == AntennaStatus::Ok => {
+                    AntennaKind::Active
+                }
+                Some(o) if o.power == AntennaPower::On && o.status == AntennaStatus::Open => {
+                    AntennaKind::Passive
+                }
+                _ => AntennaKind::Unknown,
+            },
+        }
+    }
+
+    /// Report for the test result; None if no MON-HW was ever received
+    pub fn report(&self, expect_active: bool) -> Option<AntennaReport> {
+        if self.transitions.is_empty() {
+            return None;
+        }
+        let kind = self.kind();
+        Some(AntennaReport {
+            kind,
+            transitions: self.transitions.clone(),
+            mismatch: expect_active && kind != AntennaKind::Active,
+        })
+    }
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_antenna_after_cold_start() {
+        let mut monitor = AntennaMonitor::default();
+        monitor.observe(0.0, AntennaStatus::Init, AntennaPower::On);
+        monitor.observe(1.0, AntennaStatus::Init, AntennaPower::On);
+        monitor.observe(2.0, AntennaStatus::Ok, AntennaPower::On);
+        monitor.observe(3.0, AntennaStatus::Ok, AntennaPower::On);
+
+        let report = monitor.report(true).unwrap();
+        assert_eq!(report.kind, AntennaKind::Active);
+        assert_eq!(report.transitions.len(), 2);
+        assert!(!report.mismatch);
+    }
+
+    #[test]
+    fn test_passive_antenna_flags_mismatch() {
+        let mut monitor = AntennaMonitor::default();
+        monitor.observe(0.0, AntennaStatus::Init, AntennaPower::On);
+        monitor.observe(2.0, AntennaStatus::Open, AntennaPower::On);
+
+        let report = monitor.report(true).unwrap();
+        assert_eq!(report.kind, AntennaKind::Passive);
+        assert!(report.mismatch);
+        assert!(!monitor.report(false).unwrap().mismatch);
+    }
+
+    #[test]
+    fn test_unknown_and_short() {
+        let mut monitor = AntennaMonitor::default();
+        assert!(monitor.report(true).is_none());
+
+        // Supervisor not configured
+        monitor.observe(0.0, AntennaStatus::DontKnow, AntennaPower::DontKnow);
+        assert_eq!(monitor.kind(), AntennaKind::Unknown);
+
+        // Supply switched off after a good reading: nothing can be inferred
+        monitor.observe(1.0, AntennaStatus::Ok, AntennaPower::On);
+        monitor.observe(2.0, AntennaStatus::Ok, AntennaPower::Off);
+        assert_eq!(monitor.kind(), AntennaKind::Unknown);
+
+        // A short is a fault even if it clears
+        monitor.observe(3.0, AntennaStatus::Short, AntennaPower::On);
+        monitor.observe(4.0, AntennaStatus::Ok, AntennaPower::On);
+        assert_eq!(monitor.kind(), AntennaKind::Short);
+    }
+}
@@ -0,0 +1,162 @@
+// Antenna cable loss estimate from the per-satellite elevation and SNR in GSV
+//
+// Every dB lost between the antenna's LNA and the receiver (a long or damaged
+// cable, a bad connector, a splitter) comes off every satellite's SNR alike, so an
+// install with excess loss shows the usual open-sky SNR-vs-elevation curve shifted
+// down. The estimate compares each satellite's SNR with `REFERENCE_CURVE`, a typical
+// open-sky C/N0 for a good active antenna on a short cable into a u-blox receiver,
+// and takes the median shortfall as the excess loss. The median keeps a few blocked
+// or reflected satellites from dragging the figure.
+//
+// Reference curve (C/N0 in dB-Hz by elevation, linear in between, flat beyond the
+// ends): 5° 34, 15° 38, 30° 42, 45° 45, 60° 47, 90° 48.
+//
+// Satellites below `MIN_ELEVATION_DEG` are left out: near the horizon, obstructions
+// and multipath vary too much from site to site. Confidence grows with the number of
+// satellites used, up to `FULL_CONFIDENCE_SATELLITES`, and falls as the shortfalls
+// disagree with each other (their median absolute deviation approaching
+// `MAX_SPREAD_DB`), since a uniform loss should shift every satellite alike.
+//
+// Limitations: it needs open sky, and it cannot separate cable loss from a weak
+// antenna, low LNA gain or in-band interference raising the noise floor; all of
+// these lower SNR everywhere. Receivers and antennas differ by a few dB, so treat
+// figures under about 3 dB as within normal variation.
+
+use crate::nmea::SatelliteInfo;
+use serde::Serialize;
+
+/// Open-sky C/N0 (dB-Hz) by elevation (degrees) for a good antenna and short cable
+const REFERENCE_CURVE: [(f64, f64); 6] = [
+    (5.0, 34.0),
+    (15.0, 38.0),
+    (30.0, 42.0),
+    (45.0, 45.0),
+    (60.0, 47.0),
+    (90.0, 48.0),
+];
+/// Satellites below this elevation are not compared
+pub const MIN_ELEVATION_DEG: f32 = 10.0;
+/// Satellites with SNR and elevation needed for any estimate
+pub const MIN_LOSS_SATELLITES: usize = 4;
+/// Satellites at which the count stops limiting confidence
+const FULL_CONFIDENCE_SATELLITES: usize = 10;
+/// Spread of the per-satellite shortfalls (MAD, dB) at which confidence reaches zero
+const MAX_SPREAD_DB: f64 = 6.0;
+
+/// Estimated excess attenuation against the open-sky reference
+#[derive(Debug, Clone, Serialize)]
+pub struct AntennaLossEstimate {
+    /// Median shortfall from the reference curve; negative means stronger than it
+    pub excess_loss_db: f32,
+    /// 0.0-1.0, from the satellites used and how well their shortfalls agree
+    pub confidence: f32,
+    /// Satellites compared with the reference
+    pub satellites: usize,
+    /// Median absolute deviation of the per-satellite shortfalls
+    pub spread_db: f32,
+}
+
+/// Reference C/N0 at an elevation, interpolated from `REFERENCE_CURVE`
+fn reference_snr(elevation: f64) -> f64 {
+    let (first, last) = (REFERENCE_CURVE[0], REFERENCE_CURVE[REFERENCE_CURVE.len() - 1]);
+    if elevation <= first.0 {
+        return first.1;
+    }
+    REFERENCE_CURVE
+        .windows(2)
+        .find(|w| elevation <= w[1].0)
+        .map_or(last.1, |w| {
+            let ((e0, s0), (e1, s1)) = (w[0], w[1]);
+            s0 + (s1 - s0) * (elevation - e0) / (e1 - e0)
+        })
+}
+
+/// Median of a non-empty series
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let n = values.len();
+    (values[(n - 1) / 2] + values[n / 2]) / 2.0
+}
+
+/// Estimate excess antenna-path loss from the satellites in view, or None if fewer
+/// than `MIN_LOSS_SATELLITES` have a signal above `MIN_ELEVATION_DEG`
+pub fn estimate_antenna_loss(satellites: &[SatelliteInfo]) -> Option<AntennaLossEstimate> {
+    let shortfalls: Vec<f64> = satellites
+        .iter()
+        .filter_map(|s| match (s.elevation, s.snr) {
+            (Some(elevation), Some(snr)) if snr > 0.0 && elevation >= MIN_ELEVATION_DEG => {
+                Some(reference_snr(elevation as f64) - snr as f64)
+            }
+            _ => None,
+        })
+        .collect();
+    if shortfalls.len() < MIN_LOSS_SATELLITES {
+        return None;
+    }
+
+    let loss = median(shortfalls.clone());
+    let spread = median(shortfalls.iter().map(|d| (d - loss).abs()).collect());
+    let count_factor = (shortfalls.len() as f64 / FULL_CONFIDENCE_SATELLITES as f64).min(1.0);
+    let spread_factor = (1.0 - spread / MAX_SPREAD_DB).clamp(0.0, 1.0);
+
+    Some(AntennaLossEstimate {
+        excess_loss_db: loss as f32,
+        confidence: (count_factor * spread_factor) as f32,
+        satellites: shortfalls.len(),
+        spread_db: spread as f32,
+    })
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn sat(elevation: f32, snr: f32) -> SatelliteInfo {
+        test_support::sat(1, elevation, 0.0, snr)
+    }
+
+    /// Ten satellites on the reference curve, less `loss` dB
+    fn sky(loss: f64) -> Vec<SatelliteInfo> {
+        [12.0, 20.0, 28.0, 36.0, 44.0, 52.0, 60.0, 68.0, 76.0, 84.0]
+            .into_iter()
+            .map(|e| sat(e as f32, (reference_snr(e) - loss) as f32))
+            .collect()
+    }
+
+    #[test]
+    fn test_uniform_loss_recovered() {
+        let clean = estimate_antenna_loss(&sky(0.0)).unwrap();
+        assert!(clean.excess_loss_db.abs() < 0.1);
+        assert!(clean.confidence > 0.95, "confidence {}", clean.confidence);
+
+        // A long cable: 6 dB off every satellite, plus one blocked satellite
+        let mut lossy = sky(6.0);
+        lossy[4].snr = Some(20.0);
+        let estimate = estimate_antenna_loss(&lossy).unwrap();
+        assert!((estimate.excess_loss_db - 6.0).abs() < 0.1, "{:?}", estimate);
+        assert_eq!(estimate.satellites, 10);
+        assert!(estimate.confidence > 0.9);
+
+        // Low satellites are ignored, and too few left gives no estimate
+        let few = [sat(5.0, 30.0), sat(8.0, 31.0), sat(40.0, 44.0), sat(60.0, 47.0)];
+        assert!(estimate_antenna_loss(&few).is_none());
+    }
+
+    #[test]
+    fn test_scattered_shortfalls_lower_confidence() {
+        let scattered: Vec<_> = sky(0.0)
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut s)| {
+                let offset = if i % 2 == 0 { 8.0 } else { -8.0 };
+                s.snr = s.snr.map(|snr| snr + offset);
+                s
+            })
+            .collect();
+        let estimate = estimate_antenna_loss(&scattered).unwrap();
+        assert!(estimate.confidence < 0.1, "{:?}", estimate);
+    }
+}
@@ -0,0 +1,62 @@
+// Crash-safe file replacement for reports and config files
+//
+// `fs::write` truncates the target and then writes into it, so a crash or power loss
+// part way through leaves a truncated file: a report that no longer parses, or a
+// settings file that silently falls back to defaults. Here the contents go to a
+// temporary file in the same directory, are flushed to disk, and are renamed over
+// the target. A rename within one filesystem is atomic, so readers see either the
+// old file or the complete new one.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Temporary file `write_atomic` stages `path` in, e.g. `settings.json.tmp`
+fn staging_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Replace `path` with `contents` so that it is never seen half-written
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let tmp = staging_path(path);
+    let written = File::create(&tmp).and_then(|mut file| {
+        file.write_all(contents.as_ref())?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|_| fs::rename(&tmp, path)) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_write_atomic_replaces_whole_file() {
+        let dir = temp_dir("atomic");
+        let path = dir.join("report.json");
+
+        let long = format!("{{\"data\": \"{}\"}}", "x".repeat(64 * 1024));
+        write_atomic(&path, &long).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), long);
+
+        // A shorter replacement leaves no tail of the old contents behind
+        write_atomic(&path, "{}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+        assert!(!staging_path(&path).exists());
+
+        // A failed write leaves neither the target nor a staging file
+        let missing = dir.join("no-such-dir").join("report.json");
+        assert!(write_atomic(&missing, "{}").is_err());
+        assert!(!missing.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
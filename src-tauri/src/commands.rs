@@ -1,13 +1,36 @@
 // Tauri command handlers for GPS operations and test engine
 
-use crate::gps::{self, DetectedPort, GpsManager, GpsSourceStatus};
-use crate::nmea::GpsData;
-use crate::test_criteria::{DeviceInfo, TestCriteria, TestResult, TestRunner, TestVerdict};
-use crate::test_report;
-use crate::ubx_optimizer::OptimizeStatus;
+use crate::analytics::{self, TtffHistogram};
+use crate::antenna_loss::{self, AntennaLossEstimate};
+use crate::atomic_file::write_atomic;
+use crate::gps::{
+    self, ConfigResetReport, ConnectionInfo, DetectedGps, DetectedPort, GpsError, GpsManager,
+    GpsSourceStatus, LastPort, PortProbe, ProfileApplyReport, SerialSettings, UbxConfigResult,
+};
+use crate::log_buffer::{self, LogEntry};
+use crate::metrics_csv::MetricsCsv;
+use crate::nmea::{GpsData, NmeaFilter};
+use crate::nmea_log::{self, ParseBenchmark};
+use crate::raw_log::{self, RawReplay};
+use crate::regression::{self, ReplayCheck, ReplayExpectation};
+use crate::self_test::{self, SelfTestReport};
+use crate::settings::AppSettings;
+use crate::sky_plot::{self, SkyPlot};
+use crate::test_criteria::{
+    self, Baseline, Baselines, CriteriaProfiles, CriterionResult, DeviceInfo, TestCriteria,
+    TestResult, TestRunner, TestVerdict,
+};
+use crate::test_report::{self, LabelFormat, ReportValidation, ResultsArchive};
+use crate::ttff_benchmark::{self, TtffBenchmark, MAX_BENCHMARK_ITERATIONS};
+use crate::ubx_config::{self, Constellation, DynamicModel, StartState, UbxCapabilities};
+use crate::ubx_optimizer::{OptimizePhase, OptimizeStatus};
 use serde::Serialize;
-use std::sync::RwLock;
-use tauri::State;
+use std::path::Path;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
 
 /// Standard command response wrapper
 #[derive(Debug, Serialize)]
@@ -31,9 +54,29 @@ impl<T: Serialize> CommandResult<T> {
 pub struct AppState {
     pub gps_manager: GpsManager,
     pub test_runner: RwLock<Option<TestRunner>>,
-    pub test_criteria: RwLock<TestCriteria>,
+    /// Settings from `settings.json`, including the default test criteria
+    pub settings: RwLock<AppSettings>,
+    /// Per-model criteria, selected by `start_test` from the device's USB strings
+    pub criteria_profiles: RwLock<CriteriaProfiles>,
+    /// Golden-unit baselines, referenced by `TestCriteria::baseline_name`
+    pub baselines: RwLock<Baselines>,
     pub recent_results: RwLock<Vec<TestResult>>,
     pub results_dir: std::path::PathBuf,
+    /// `.partial.json` the running test is periodically saved to, if interim saves are on
+    pub interim_report_path: Mutex<Option<std::path::PathBuf>>,
+    /// Config directory holding settings.json and last_port.json
+    pub config_dir: std::path::PathBuf,
+    /// Set by `cancel_auto_detect` to stop a running port scan
+    pub auto_detect_cancel: Arc<AtomicBool>,
+    /// Bumped on every optimizer start/abort so a superseded driver thread exits
+    pub optimize_generation: AtomicU64,
+    /// Bumped on every test start/abort so a superseded evaluation thread exits
+    pub test_generation: AtomicU64,
+    /// Metrics CSV receiving a row per test evaluation tick, between
+    /// `start_metrics_csv` and `stop_metrics_csv`
+    pub metrics_csv: Mutex<Option<MetricsCsv>>,
+    /// Set by `cancel_ttff_benchmark` to stop a running TTFF benchmark
+    pub ttff_benchmark_cancel: Arc<AtomicBool>,
 }
 
 // ============ GPS Commands ============
@@ -47,48 +90,444 @@ pub async fn list_serial_ports() -> CommandResult<Vec<DetectedPort>> {
 }
 
 #[tauri::command]
-pub async fn auto_detect_gps() -> CommandResult<(DetectedPort, u32)> {
-    match GpsManager::auto_detect_gps() {
+pub async fn auto_detect_gps(
+    state: State<'_, AppState>,
+) -> Result<CommandResult<(DetectedPort, u32)>, String> {
+    let cancel = Arc::clone(&state.auto_detect_cancel);
+    cancel.store(false, Ordering::SeqCst);
+
+    let result =
+        tauri::async_runtime::spawn_blocking(move || GpsManager::auto_detect_gps(&cancel))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(match result {
         Ok(result) => CommandResult::ok(result),
         Err(e) => CommandResult::err(e.to_string()),
-    }
+    })
+}
+
+/// Probe every port and return each one with a GPS on it, for the user to choose from
+#[tauri::command]
+pub async fn auto_detect_all_gps(
+    state: State<'_, AppState>,
+) -> Result<CommandResult<Vec<DetectedGps>>, String> {
+    let cancel = Arc::clone(&state.auto_detect_cancel);
+    cancel.store(false, Ordering::SeqCst);
+
+    let result =
+        tauri::async_runtime::spawn_blocking(move || GpsManager::auto_detect_all_gps(&cancel))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(found) => CommandResult::ok(found),
+        Err(e) => CommandResult::err(e.to_string()),
+    })
 }
 
 #[tauri::command]
-pub async fn test_gps_port(port_name: String, baud_rate: u32) -> CommandResult<bool> {
-    match GpsManager::test_port(&port_name, baud_rate, 3000) {
+pub fn cancel_auto_detect(state: State<'_, AppState>) -> CommandResult<bool> {
+    state.auto_detect_cancel.store(true, Ordering::SeqCst);
+    CommandResult::ok(true)
+}
+
+#[tauri::command]
+pub async fn test_gps_port(
+    port_name: String,
+    baud_rate: u32,
+    settings: Option<SerialSettings>,
+) -> CommandResult<bool> {
+    match GpsManager::test_port(&port_name, baud_rate, 3000, &settings.unwrap_or_default()) {
         Ok(result) => CommandResult::ok(result),
         Err(e) => CommandResult::err(e.to_string()),
     }
 }
 
+/// Diagnose a port without connecting: bytes, NMEA sentence types, talkers and
+/// whether a u-blox answers MON-VER. `duration_ms` defaults to 3 s, at most 10 s.
+#[tauri::command]
+pub async fn probe_port(
+    port_name: String,
+    baud_rate: u32,
+    duration_ms: Option<u64>,
+    settings: Option<SerialSettings>,
+) -> Result<CommandResult<PortProbe>, String> {
+    let duration_ms = duration_ms.unwrap_or(gps::DEFAULT_PROBE_MS);
+    let settings = settings.unwrap_or_default();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        GpsManager::probe_port(&port_name, baud_rate, duration_ms, &settings)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(probe) => CommandResult::ok(probe),
+        Err(e) => CommandResult::err(e.to_string()),
+    })
+}
+
+/// Connect to a port; `settings` defaults to 8N1 with no flow control
 #[tauri::command]
-pub fn connect_gps(state: State<'_, AppState>, port_name: String, baud_rate: u32) -> CommandResult<bool> {
-    match state.gps_manager.connect(&port_name, baud_rate) {
+pub fn connect_gps(
+    state: State<'_, AppState>,
+    port_name: String,
+    baud_rate: u32,
+    settings: Option<SerialSettings>,
+) -> CommandResult<bool> {
+    match connect_and_remember(&state, &port_name, baud_rate, settings.unwrap_or_default()) {
         Ok(()) => CommandResult::ok(true),
         Err(e) => CommandResult::err(e.to_string()),
     }
 }
 
+/// Reconnect to the last good port, falling back to auto-detect if it is gone
+#[tauri::command]
+pub async fn auto_connect_last(
+    state: State<'_, AppState>,
+) -> Result<CommandResult<(DetectedPort, u32)>, String> {
+    let saved = LastPort::load(&state.config_dir);
+    let cancel = Arc::clone(&state.auto_detect_cancel);
+    cancel.store(false, Ordering::SeqCst);
+
+    let found = tauri::async_runtime::spawn_blocking(move || {
+        GpsManager::find_last_port(saved.as_ref(), &cancel)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (port, baud_rate) = match found {
+        Ok(found) => found,
+        Err(e) => return Ok(CommandResult::err(e.to_string())),
+    };
+    let settings = SerialSettings::default();
+    if let Err(e) = connect_and_remember(&state, &port.port_name, baud_rate, settings) {
+        return Ok(CommandResult::err(e.to_string()));
+    }
+    Ok(CommandResult::ok((port, baud_rate)))
+}
+
+/// Connect, saving the port for `auto_connect_last` once the reader has actually opened
+/// it, so a mistyped or busy port is never remembered. Save failures are only logged.
+fn connect_and_remember(
+    state: &AppState,
+    port_name: &str,
+    baud_rate: u32,
+    settings: SerialSettings,
+) -> Result<(), GpsError> {
+    let last = LastPort { port_name: port_name.to_string(), baud_rate };
+    let config_dir = state.config_dir.clone();
+    state.gps_manager.connect(port_name, baud_rate, settings, move || {
+        if let Err(e) = last.save(&config_dir) {
+            log::warn!("Failed to save last GPS port: {}", e);
+        }
+    })
+}
+
 #[tauri::command]
 pub fn disconnect_gps(state: State<'_, AppState>) -> CommandResult<bool> {
     state.gps_manager.disconnect();
     CommandResult::ok(true)
 }
 
+/// Change the receiver's (u-blox or MediaTek) and the port's baud rate without
+/// reconnecting. The receiver change is not saved, so it reverts on a power cycle.
+#[tauri::command]
+pub async fn change_baud(app: AppHandle, new_baud: u32) -> Result<CommandResult<bool>, String> {
+    // Verifying the new rate takes up to a few seconds
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        app.state::<AppState>().gps_manager.change_baud(new_baud)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(()) => CommandResult::ok(true),
+        Err(e) => CommandResult::err(e.to_string()),
+    })
+}
+
+/// Read the connected u-blox receiver's unique chip ID (UBX-SEC-UNIQID, M8 and later)
+#[tauri::command]
+pub fn get_chip_id(state: State<'_, AppState>) -> CommandResult<String> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return CommandResult::err("No GPS connected. Connect a GPS device first.");
+    }
+    match state.gps_manager.chip_id() {
+        Ok(Some(id)) => CommandResult::ok(id),
+        Ok(None) => CommandResult::err("Receiver did not report a chip ID (u-blox M8 or later)"),
+        Err(e) => CommandResult::err(e.to_string()),
+    }
+}
+
+/// Return the connected u-blox receiver to its factory configuration before a test
+/// and report whether the reset was confirmed by reading the config back
+#[tauri::command]
+pub async fn reset_to_defaults(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<CommandResult<ConfigResetReport>, String> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return Ok(CommandResult::err("No GPS connected. Connect a GPS device first."));
+    }
+    // Waits for the ACK and the restart before reading back: seconds, not milliseconds
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        app.state::<AppState>().gps_manager.reset_to_defaults()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(report) => {
+            if !report.confirmed {
+                log::warn!(
+                    "Receiver reset not confirmed: cleared {:?}, dynamic model {:?}",
+                    report.cleared,
+                    report.dynamic_model
+                );
+            }
+            CommandResult::ok(report)
+        }
+        Err(e) => CommandResult::err(e.to_string()),
+    })
+}
+
+/// Send a raw UBX config file (one hex-encoded frame per line, as exported from
+/// u-center) to the connected u-blox receiver, returning each message's ACK/NAK
+#[tauri::command]
+pub async fn load_ubx_config_file(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<CommandResult<Vec<UbxConfigResult>>, String> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return Ok(CommandResult::err("No GPS connected. Connect a GPS device first."));
+    }
+    // Every line waits for its ACK, so a long file takes a while
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        app.state::<AppState>().gps_manager.apply_ubx_config_file(Path::new(&path))
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(results) => CommandResult::ok(results),
+        Err(e) => CommandResult::err(e.to_string()),
+    })
+}
+
+/// Apply the marine profile once and report which commands the receiver ACKed and
+/// whether the configuration was saved, without the optimizer's before/after test
+#[tauri::command]
+pub async fn apply_marine_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<CommandResult<ProfileApplyReport>, String> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return Ok(CommandResult::err("No GPS connected. Connect a GPS device first."));
+    }
+    // Each command waits for its ACK, so this takes seconds: keep it off the main thread
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        app.state::<AppState>().gps_manager.apply_marine_profile()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(report) => {
+            log::info!(
+                "Marine profile applied: {} ACK, {} NAK, {} unanswered, saved: {}",
+                report.acked,
+                report.nacked,
+                report.unanswered,
+                report.saved
+            );
+            CommandResult::ok(report)
+        }
+        Err(e) => CommandResult::err(e.to_string()),
+    })
+}
+
 #[tauri::command]
 pub fn get_gps_data(state: State<'_, AppState>) -> CommandResult<GpsData> {
     CommandResult::ok(state.gps_manager.get_data())
 }
 
+#[tauri::command]
+pub fn get_sky_plot(state: State<'_, AppState>) -> CommandResult<SkyPlot> {
+    CommandResult::ok(sky_plot::build_sky_plot(&state.gps_manager.get_data()))
+}
+
+/// Estimate excess loss in the antenna path (cable, connectors) from the current
+/// satellites' SNR against an open-sky reference; see `antenna_loss`
+#[tauri::command]
+pub fn estimate_antenna_loss(state: State<'_, AppState>) -> CommandResult<AntennaLossEstimate> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return CommandResult::err("No GPS connected. Connect a GPS device first.");
+    }
+    match antenna_loss::estimate_antenna_loss(&state.gps_manager.get_data().satellites_info) {
+        Some(estimate) => CommandResult::ok(estimate),
+        None => CommandResult::err(format!(
+            "Need at least {} satellites with SNR above {:.0}° elevation",
+            antenna_loss::MIN_LOSS_SATELLITES,
+            antenna_loss::MIN_ELEVATION_DEG
+        )),
+    }
+}
+
 #[tauri::command]
 pub fn get_gps_status(state: State<'_, AppState>) -> CommandResult<GpsSourceStatus> {
     CommandResult::ok(state.gps_manager.get_status())
 }
 
 #[tauri::command]
-pub fn get_nmea_buffer(state: State<'_, AppState>) -> CommandResult<Vec<String>> {
-    CommandResult::ok(state.gps_manager.get_nmea_buffer())
+pub fn get_connection_info(state: State<'_, AppState>) -> CommandResult<ConnectionInfo> {
+    match state.gps_manager.get_connection_info() {
+        Some(info) => CommandResult::ok(info),
+        None => CommandResult::err("No GPS connected"),
+    }
+}
+
+/// Recent NMEA sentences, optionally only those of one sentence type (e.g. "GSV") and/or
+/// talker (e.g. "GL")
+#[tauri::command]
+pub fn get_nmea_buffer(
+    state: State<'_, AppState>,
+    sentence_type: Option<String>,
+    talker: Option<String>,
+) -> CommandResult<Vec<String>> {
+    let filter = NmeaFilter { sentence_type, talker };
+    CommandResult::ok(state.gps_manager.get_nmea_buffer(&filter))
+}
+
+/// Collect raw NMEA for `duration_seconds` (capped) and return it as one newline-joined string.
+/// Runs off the main thread and leaves the ring buffer and any running test untouched.
+#[tauri::command]
+pub async fn capture_nmea(
+    state: State<'_, AppState>,
+    duration_seconds: u64,
+) -> Result<CommandResult<String>, String> {
+    let duration = duration_seconds.clamp(1, gps::MAX_NMEA_CAPTURE_SECONDS);
+
+    if let Err(e) = state.gps_manager.start_nmea_capture() {
+        return Ok(CommandResult::err(e.to_string()));
+    }
+    log::info!("Capturing NMEA for {}s", duration);
+
+    let _ = tauri::async_runtime::spawn_blocking(move || {
+        std::thread::sleep(std::time::Duration::from_secs(duration));
+    })
+    .await;
+
+    let sentences = state.gps_manager.finish_nmea_capture();
+    log::info!("NMEA capture complete: {} sentences", sentences.len());
+    Ok(CommandResult::ok(sentences.join("\n")))
+}
+
+/// Record the raw byte stream (NMEA and UBX binary) for `duration_seconds` (capped) and
+/// save it to `path` as a `.ubx` capture. Returns the number of bytes recorded.
+#[tauri::command]
+pub async fn capture_raw(
+    state: State<'_, AppState>,
+    duration_seconds: u64,
+    path: String,
+) -> Result<CommandResult<usize>, String> {
+    let duration = duration_seconds.clamp(1, gps::MAX_NMEA_CAPTURE_SECONDS);
+
+    if let Err(e) = state.gps_manager.start_raw_capture() {
+        return Ok(CommandResult::err(e.to_string()));
+    }
+    log::info!("Capturing raw stream for {}s to {}", duration, path);
+
+    let _ = tauri::async_runtime::spawn_blocking(move || {
+        std::thread::sleep(std::time::Duration::from_secs(duration));
+    })
+    .await;
+
+    let chunks = state.gps_manager.finish_raw_capture();
+    let bytes: usize = chunks.iter().map(|c| c.bytes.len()).sum();
+    match raw_log::save_capture(Path::new(&path), &chunks) {
+        Ok(()) => {
+            log::info!("Raw capture complete: {} bytes in {} reads", bytes, chunks.len());
+            Ok(CommandResult::ok(bytes))
+        }
+        Err(e) => Ok(CommandResult::err(format!("Failed to write {}: {}", path, e))),
+    }
+}
+
+/// Replay a saved `.nmea` or `.nmea.gz` log and return the GPS state at its end
+#[tauri::command]
+pub async fn replay_nmea_log(path: String) -> CommandResult<GpsData> {
+    match nmea_log::replay_log(std::path::Path::new(&path)) {
+        Ok(data) => CommandResult::ok(data),
+        Err(e) => CommandResult::err(format!("Failed to read {}: {}", path, e)),
+    }
+}
+
+/// Replay a `.ubx` raw capture, splitting it into NMEA sentences and UBX frames
+#[tauri::command]
+pub async fn replay_raw_log(path: String) -> CommandResult<RawReplay> {
+    match raw_log::replay_capture(Path::new(&path)) {
+        Ok(replay) => CommandResult::ok(replay),
+        Err(e) => CommandResult::err(format!("Failed to read {}: {}", path, e)),
+    }
+}
+
+/// Time how fast the parser gets through a (large) NMEA log, for catching parser
+/// performance regressions and sizing buffers for high-rate receivers
+#[tauri::command]
+pub async fn benchmark_parse(path: String) -> CommandResult<ParseBenchmark> {
+    match nmea_log::benchmark_parse(Path::new(&path)) {
+        Ok(benchmark) => CommandResult::ok(benchmark),
+        Err(e) => CommandResult::err(format!("Failed to read {}: {}", path, e)),
+    }
+}
+
+/// Replay a log through the test engine with the current criteria and compare the
+/// outcome with the expected verdict and, optionally, per-criterion pass/fail
+#[tauri::command]
+pub async fn replay_test_log(
+    state: State<'_, AppState>,
+    path: String,
+    expected_verdict: TestVerdict,
+    expected_criteria: Option<BTreeMap<String, bool>>,
+) -> Result<CommandResult<ReplayCheck>, String> {
+    let criteria = state.settings.read().unwrap().criteria.clone();
+    let expected = ReplayExpectation {
+        verdict: expected_verdict,
+        criteria_results: expected_criteria.unwrap_or_default(),
+    };
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        regression::check_log(Path::new(&path), &criteria, &expected)
+            .map_err(|e| format!("Failed to replay {}: {}", path, e))
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(check) => CommandResult::ok(check),
+        Err(e) => CommandResult::err(e),
+    })
+}
+
+/// Check every labeled log in a corpus directory against the current criteria
+#[tauri::command]
+pub async fn replay_test_corpus(
+    state: State<'_, AppState>,
+    dir: String,
+) -> Result<CommandResult<Vec<ReplayCheck>>, String> {
+    let criteria = state.settings.read().unwrap().criteria.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        regression::check_corpus(Path::new(&dir), &criteria)
+            .map_err(|e| format!("Failed to check {}: {}", dir, e))
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(checks) => CommandResult::ok(checks),
+        Err(e) => CommandResult::err(e),
+    })
 }
 
 #[tauri::command]
@@ -97,31 +536,218 @@ pub fn clear_nmea_buffer(state: State<'_, AppState>) -> CommandResult<bool> {
     CommandResult::ok(true)
 }
 
+/// Run known NMEA sentences and UBX payloads through the parsers; needs no GPS connected
+#[tauri::command]
+pub fn run_self_test() -> CommandResult<SelfTestReport> {
+    CommandResult::ok(self_test::run_self_test())
+}
+
 // ============ Test Criteria Commands ============
 
+#[tauri::command]
+pub fn get_settings(state: State<'_, AppState>) -> CommandResult<AppSettings> {
+    CommandResult::ok(state.settings.read().unwrap().clone())
+}
+
+/// Replace and save all settings. A changed `results_dir` takes effect on restart.
+#[tauri::command]
+pub fn set_settings(state: State<'_, AppState>, settings: AppSettings) -> CommandResult<bool> {
+    if let Err(e) = settings.validate() {
+        return CommandResult::err(e);
+    }
+    let mut current = state.settings.write().unwrap();
+    *current = settings;
+    save_settings(&current, &state.config_dir)
+}
+
 #[tauri::command]
 pub fn get_test_criteria(state: State<'_, AppState>) -> CommandResult<TestCriteria> {
-    let criteria = state.test_criteria.read().unwrap().clone();
+    let criteria = state.settings.read().unwrap().criteria.clone();
     CommandResult::ok(criteria)
 }
 
 #[tauri::command]
 pub fn set_test_criteria(state: State<'_, AppState>, criteria: TestCriteria) -> CommandResult<bool> {
-    *state.test_criteria.write().unwrap() = criteria;
-    CommandResult::ok(true)
+    if let Err(e) = criteria.validate() {
+        return CommandResult::err(e.to_string());
+    }
+    let mut settings = state.settings.write().unwrap();
+    settings.criteria = criteria;
+    save_settings(&settings, &state.config_dir)
 }
 
 #[tauri::command]
 pub fn reset_test_criteria(state: State<'_, AppState>) -> CommandResult<TestCriteria> {
     let defaults = TestCriteria::default();
-    *state.test_criteria.write().unwrap() = defaults.clone();
-    CommandResult::ok(defaults)
+    let mut settings = state.settings.write().unwrap();
+    settings.criteria = defaults.clone();
+    match settings.save(&state.config_dir) {
+        Ok(()) => CommandResult::ok(defaults),
+        Err(e) => CommandResult::err(format!("Failed to save settings: {}", e)),
+    }
+}
+
+fn save_settings(settings: &AppSettings, config_dir: &Path) -> CommandResult<bool> {
+    match settings.save(config_dir) {
+        Ok(()) => CommandResult::ok(true),
+        Err(e) => CommandResult::err(format!("Failed to save settings: {}", e)),
+    }
+}
+
+/// Check the current fix against `criteria` without starting a test, e.g. while
+/// tuning thresholds. Only criteria judged from a single fix are returned.
+#[tauri::command]
+pub fn preview_criteria(
+    state: State<'_, AppState>,
+    criteria: TestCriteria,
+) -> CommandResult<Vec<CriterionResult>> {
+    if let Err(e) = criteria.validate() {
+        return CommandResult::err(e.to_string());
+    }
+    if state.gps_manager.get_status().port_name.is_none() {
+        return CommandResult::err("No GPS connected. Connect a GPS device first.");
+    }
+    let data = state.gps_manager.get_data();
+    CommandResult::ok(test_criteria::evaluate_snapshot(&criteria, &data))
+}
+
+/// Criteria profiles file, beside settings.json in the config dir
+pub const CRITERIA_PROFILES_FILE: &str = "criteria_profiles.json";
+
+#[tauri::command]
+pub fn get_criteria_profiles(state: State<'_, AppState>) -> CommandResult<CriteriaProfiles> {
+    CommandResult::ok(state.criteria_profiles.read().unwrap().clone())
+}
+
+/// Add or replace the profile for a device model and save all profiles
+#[tauri::command]
+pub fn set_criteria_profile(
+    state: State<'_, AppState>,
+    name: String,
+    criteria: TestCriteria,
+) -> CommandResult<bool> {
+    if name.trim().is_empty() {
+        return CommandResult::err("Profile name must not be empty");
+    }
+    if let Err(e) = criteria.validate() {
+        return CommandResult::err(e.to_string());
+    }
+    let mut profiles = state.criteria_profiles.write().unwrap();
+    profiles.insert(name.trim().to_string(), criteria);
+    match save_criteria_profiles(&profiles, &state.config_dir) {
+        Ok(()) => CommandResult::ok(true),
+        Err(e) => CommandResult::err(format!("Failed to save criteria profiles: {}", e)),
+    }
+}
+
+#[tauri::command]
+pub fn delete_criteria_profile(state: State<'_, AppState>, name: String) -> CommandResult<bool> {
+    let mut profiles = state.criteria_profiles.write().unwrap();
+    if profiles.remove(&name).is_none() {
+        return CommandResult::err(format!("No criteria profile named '{}'", name));
+    }
+    match save_criteria_profiles(&profiles, &state.config_dir) {
+        Ok(()) => CommandResult::ok(true),
+        Err(e) => CommandResult::err(format!("Failed to save criteria profiles: {}", e)),
+    }
+}
+
+fn save_criteria_profiles(
+    profiles: &CriteriaProfiles,
+    config_dir: &std::path::Path,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(config_dir)?;
+    let json = serde_json::to_string_pretty(profiles).map_err(std::io::Error::from)?;
+    write_atomic(&config_dir.join(CRITERIA_PROFILES_FILE), json)
+}
+
+/// Golden-unit baselines file, beside settings.json in the config dir
+pub const BASELINES_FILE: &str = "baselines.json";
+
+#[tauri::command]
+pub fn get_baselines(state: State<'_, AppState>) -> CommandResult<Baselines> {
+    CommandResult::ok(state.baselines.read().unwrap().clone())
+}
+
+/// Save the current test's metrics as a named baseline (e.g. after testing the golden unit)
+#[tauri::command]
+pub fn save_baseline(state: State<'_, AppState>, name: String) -> CommandResult<Baseline> {
+    if name.trim().is_empty() {
+        return CommandResult::err("Baseline name must not be empty");
+    }
+    let result = match state.test_runner.read().unwrap().as_ref() {
+        Some(runner) => runner.get_result(Some(&state.gps_manager.get_data())),
+        None => return CommandResult::err("No test results to save as a baseline"),
+    };
+    let Some(baseline) = Baseline::from_result(&result) else {
+        return CommandResult::err("Test has no fix to use as a baseline");
+    };
+
+    let mut baselines = state.baselines.write().unwrap();
+    baselines.insert(name.trim().to_string(), baseline.clone());
+    match save_baselines(&baselines, &state.config_dir) {
+        Ok(()) => CommandResult::ok(baseline),
+        Err(e) => CommandResult::err(format!("Failed to save baselines: {}", e)),
+    }
+}
+
+#[tauri::command]
+pub fn delete_baseline(state: State<'_, AppState>, name: String) -> CommandResult<bool> {
+    let mut baselines = state.baselines.write().unwrap();
+    if baselines.remove(&name).is_none() {
+        return CommandResult::err(format!("No baseline named '{}'", name));
+    }
+    match save_baselines(&baselines, &state.config_dir) {
+        Ok(()) => CommandResult::ok(true),
+        Err(e) => CommandResult::err(format!("Failed to save baselines: {}", e)),
+    }
+}
+
+fn save_baselines(baselines: &Baselines, config_dir: &std::path::Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(config_dir)?;
+    let json = serde_json::to_string_pretty(baselines).map_err(std::io::Error::from)?;
+    write_atomic(&config_dir.join(BASELINES_FILE), json)
 }
 
 // ============ Test Execution Commands ============
 
 #[tauri::command]
-pub fn start_test(state: State<'_, AppState>) -> CommandResult<bool> {
+pub async fn start_test(app: AppHandle) -> Result<CommandResult<bool>, String> {
+    begin_test_blocking(app, None).await
+}
+
+/// Run one test with ad-hoc criteria from the frontend. The stored criteria and
+/// profiles are left untouched; the result records the criteria used.
+#[tauri::command]
+pub async fn start_test_with_criteria(
+    app: AppHandle,
+    criteria: TestCriteria,
+) -> Result<CommandResult<bool>, String> {
+    if let Err(e) = criteria.validate() {
+        return Ok(CommandResult::err(e.to_string()));
+    }
+    begin_test_blocking(app, Some(criteria)).await
+}
+
+/// Start a test off the main thread: the chip ID, start state and MON-HW polls each
+/// wait on the receiver, together for several seconds on one that doesn't answer
+async fn begin_test_blocking(
+    app: AppHandle,
+    adhoc: Option<TestCriteria>,
+) -> Result<CommandResult<bool>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        begin_test(app.clone(), &state, adhoc)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn begin_test(
+    app: AppHandle,
+    state: &AppState,
+    adhoc: Option<TestCriteria>,
+) -> CommandResult<bool> {
     let status = state.gps_manager.get_status();
 
     // Get device info from current GPS connection
@@ -130,72 +756,267 @@ pub fn start_test(state: State<'_, AppState>) -> CommandResult<bool> {
         None => return CommandResult::err("No GPS connected. Connect a GPS device first."),
     };
 
-    // Try to get device details from port list
-    let device_info = match GpsManager::list_serial_ports() {
-        Ok(ports) => {
-            if let Some(port) = ports.iter().find(|p| p.port_name == port_name) {
-                DeviceInfo {
-                    port_name: port.port_name.clone(),
-                    port_type: port.port_type.clone(),
-                    manufacturer: port.manufacturer.clone(),
-                    product: port.product.clone(),
-                    serial_number: port.serial_number.clone(),
-                    vid: port.vid,
-                    pid: port.pid,
-                }
-            } else {
-                DeviceInfo {
-                    port_name,
-                    port_type: "Unknown".into(),
-                    manufacturer: None,
-                    product: None,
-                    serial_number: None,
-                    vid: None,
-                    pid: None,
-                }
-            }
-        }
-        Err(_) => DeviceInfo {
-            port_name,
-            port_type: "Unknown".into(),
-            manufacturer: None,
-            product: None,
-            serial_number: None,
-            vid: None,
-            pid: None,
-        },
+    // Fill in device details from the port list, if the port is listed
+    let mut device_info = DeviceInfo {
+        port_name,
+        port_type: "Unknown".into(),
+        manufacturer: None,
+        product: None,
+        serial_number: None,
+        vid: None,
+        pid: None,
+        chip_id: None,
     };
+    let listed = GpsManager::list_serial_ports()
+        .ok()
+        .and_then(|ports| ports.into_iter().find(|p| p.port_name == device_info.port_name));
+    if let Some(port) = listed {
+        device_info.port_type = port.port_type;
+        device_info.manufacturer = port.manufacturer;
+        device_info.product = port.product;
+        device_info.serial_number = port.serial_number;
+        device_info.vid = port.vid;
+        device_info.pid = port.pid;
+    }
+
+    // Non-u-blox receivers are refused the poll and simply have no chip ID
+    device_info.chip_id = state.gps_manager.chip_id().ok().flatten();
+    // Likewise only a confirmed u-blox receiver is asked for MON-HW (antenna report)
+    match state.gps_manager.enable_antenna_status() {
+        Ok(()) | Err(GpsError::NotUblox) => {}
+        Err(e) => log::warn!("Failed to enable antenna status output: {}", e),
+    }
+
+    let mut runner = match build_runner(state, device_info, adhoc) {
+        Ok(runner) => runner,
+        Err(e) => return CommandResult::err(e),
+    };
+
+    // A test that was still running is replaced, so its interim report goes too
+    discard_interim_report(state);
+    if runner.interim_save_interval_seconds.is_some() {
+        let path = test_report::partial_report_path(
+            runner.device_info.serial_number.as_deref(),
+            &state.results_dir,
+        );
+        *state.interim_report_path.lock().unwrap() = Some(path);
+    }
+
+    // Tune and verify: the first test after an optimization is linked to its report
+    if let Some(report) = state.gps_manager.optimizer.write().unwrap().unverified_report() {
+        test_report::link_verification(report, &mut runner);
+        log::info!("Test {} verifies optimization {}", runner.id, report.id);
+        if let Err(e) = test_report::save_optimization_report_json(report, &state.results_dir) {
+            log::warn!("Failed to link optimization report to test: {}", e);
+        }
+    }
 
-    let criteria = state.test_criteria.read().unwrap().clone();
-    let mut runner = TestRunner::new(criteria, device_info);
     runner.start();
 
     *state.test_runner.write().unwrap() = Some(runner);
+
+    // Evaluate from a backend thread at the settings' interval, independent of polling
+    let generation = state.test_generation.fetch_add(1, Ordering::SeqCst) + 1;
+    std::thread::spawn(move || drive_test(app, generation));
+
     CommandResult::ok(true)
 }
 
-#[tauri::command]
-pub fn get_test_status(state: State<'_, AppState>) -> CommandResult<TestResult> {
+/// A runner for the device with ad-hoc criteria if given, else the device model's
+/// profile, else the stored defaults, none of which are changed by the run
+fn build_runner(
+    state: &AppState,
+    device_info: DeviceInfo,
+    adhoc: Option<TestCriteria>,
+) -> Result<TestRunner, String> {
+    let criteria_override = adhoc.is_some();
+    let settings = state.settings.read().unwrap().clone();
+    let (profile, criteria) = test_criteria::criteria_for_run(
+        &state.criteria_profiles.read().unwrap(),
+        &settings.criteria,
+        &device_info,
+        adhoc,
+    );
+    if criteria_override {
+        log::info!("Using ad-hoc criteria for this run");
+    } else if let Some(name) = &profile {
+        log::info!("Using criteria profile '{}'", name);
+    }
+
+    let baseline = match criteria.baseline_name.as_deref() {
+        Some(name) => match state.baselines.read().unwrap().get(name) {
+            Some(baseline) => Some(baseline.clone()),
+            None => return Err(format!("No baseline named '{}'", name)),
+        },
+        None => None,
+    };
+
+    let mut runner = TestRunner::new(criteria, device_info);
+    runner.criteria_profile = profile;
+    runner.criteria_override = criteria_override;
+    runner.baseline = baseline;
+    runner.chip_info = state.gps_manager.chip_info();
+    runner.coord_format = settings.coord_format;
+    runner.datum = settings.datum;
+    runner.continuous = settings.continuous;
+    runner.interim_save_interval_seconds = settings.interim_save_interval_seconds;
+    if runner.criteria.cold_start_ttff_factor.is_some() {
+        // Unclassified (non-u-blox, or no answer) keeps the plain TTFF limit
+        runner.start_type = state.gps_manager.start_state().ok().map(|s| s.start_type);
+    }
+    Ok(runner)
+}
+
+/// Event emitted with the report's path when `auto_save_on_complete` saves a report
+pub const REPORT_SAVED_EVENT: &str = "report-saved";
+
+/// Evaluate the running test every `evaluation_interval_ms` until it reaches a verdict
+/// or is superseded
+fn drive_test(app: AppHandle, generation: u64) {
+    let state = app.state::<AppState>();
+    while state.test_generation.load(Ordering::SeqCst) == generation {
+        match advance_test(&state) {
+            Some(interval) => std::thread::sleep(interval),
+            None => {
+                auto_save_report(&app);
+                break;
+            }
+        }
+    }
+}
+
+/// Save the report of a test that just reached a verdict, if `auto_save_on_complete`
+/// is set, and emit its path as `REPORT_SAVED_EVENT`
+fn auto_save_report(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    match auto_save_completed(&state) {
+        None => {}
+        Some(Ok(path)) => {
+            log::info!("Auto-saved test report to {}", path);
+            if let Err(e) = app.emit(REPORT_SAVED_EVENT, &path) {
+                log::warn!("Failed to emit report saved event: {}", e);
+            }
+        }
+        Some(Err(e)) => log::warn!("{}", e),
+    }
+}
+
+/// Save the report if the test just reached a verdict and auto-save is on; None if
+/// there was nothing to save
+fn auto_save_completed(state: &AppState) -> Option<Result<String, String>> {
     let mut runner_lock = state.test_runner.write().unwrap();
+    let runner = runner_lock.as_mut()?;
+    if !runner.take_completion() || !state.settings.read().unwrap().auto_save_on_complete {
+        return None;
+    }
+    Some(save_runner_report(state, runner))
+}
 
-    match runner_lock.as_mut() {
-        Some(runner) => {
-            // If test is running, evaluate current GPS data
-            if runner.verdict == TestVerdict::Running {
-                let gps_data = state.gps_manager.get_data();
-                runner.evaluate(&gps_data);
+/// Evaluate the current GPS data and keep the monitor snapshots and interim report
+/// up to date. Returns the wait before the next tick, or None once the test is over.
+fn advance_test(state: &AppState) -> Option<Duration> {
+    let mut runner_lock = state.test_runner.write().unwrap();
+    let runner = runner_lock.as_mut()?;
+
+    if runner.verdict == TestVerdict::Running && !runner.is_paused() {
+        let data = state.gps_manager.get_data();
+        runner.evaluate(&data);
+        if let Some(csv) = state.metrics_csv.lock().unwrap().as_mut() {
+            if let Err(e) = csv.write_row(&data) {
+                log::warn!("Failed to write metrics row to {}: {}", csv.path().display(), e);
+            }
+        }
+    }
+
+    if runner.verdict != TestVerdict::Running {
+        // Crash resilience: the interim report only lives while the test runs
+        discard_interim_report(state);
+        return None;
+    }
+
+    let due_monitor = runner.monitor_snapshot_due();
+    let due_interim = runner.interim_save_due();
+    if due_monitor || due_interim {
+        let result = runner.get_result(Some(&state.gps_manager.get_data()));
+
+        // Continuous monitoring: periodically persist a snapshot
+        if due_monitor {
+            if let Err(e) = test_report::append_monitor_snapshot(&result, &state.results_dir) {
+                log::warn!("Failed to write monitor snapshot: {}", e);
+            }
+        }
+        if due_interim {
+            if let Some(path) = state.interim_report_path.lock().unwrap().as_ref() {
+                if let Err(e) = test_report::save_partial_report(&result, path) {
+                    log::warn!("Failed to write interim report: {}", e);
+                }
+            }
+        }
+    }
+
+    Some(state.settings.read().unwrap().evaluation_interval())
+}
+
+/// Append a row of fix metrics (satellites, SNR, DOP, fix quality) to the CSV at `path`
+/// on every evaluation tick of a running test, until `stop_metrics_csv`. Replaces any
+/// metrics CSV already being written.
+#[tauri::command]
+pub fn start_metrics_csv(state: State<'_, AppState>, path: String) -> CommandResult<bool> {
+    match MetricsCsv::open(Path::new(&path)) {
+        Ok(csv) => {
+            log::info!("Writing test metrics to {}", path);
+            *state.metrics_csv.lock().unwrap() = Some(csv);
+            CommandResult::ok(true)
+        }
+        Err(e) => CommandResult::err(format!("Failed to open {}: {}", path, e)),
+    }
+}
+
+/// Stop writing the metrics CSV. Returns the number of rows written.
+#[tauri::command]
+pub fn stop_metrics_csv(state: State<'_, AppState>) -> CommandResult<u64> {
+    match state.metrics_csv.lock().unwrap().take() {
+        Some(csv) => {
+            log::info!("Wrote {} metrics rows to {}", csv.rows, csv.path().display());
+            CommandResult::ok(csv.rows)
+        }
+        None => CommandResult::err("No metrics CSV is being written"),
+    }
+}
+
+/// Remove the running test's interim report once the test has ended
+fn discard_interim_report(state: &AppState) {
+    if let Some(path) = state.interim_report_path.lock().unwrap().take() {
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("Failed to remove interim report {}: {}", path.display(), e);
             }
+        }
+    }
+}
 
+/// Current test result. Evaluation runs on the backend tick started by `start_test`;
+/// polling this only reads the latest state.
+#[tauri::command]
+pub fn get_test_status(state: State<'_, AppState>) -> CommandResult<TestResult> {
+    let runner_lock = state.test_runner.read().unwrap();
+
+    match runner_lock.as_ref() {
+        Some(runner) => {
             let gps_data = state.gps_manager.get_data();
-            let result = runner.get_result(Some(&gps_data));
-            CommandResult::ok(result)
+            CommandResult::ok(runner.get_result(Some(&gps_data)))
         }
         None => {
             // No test running
             CommandResult::ok(TestResult {
+                id: String::new(),
+                related_optimization_id: None,
                 verdict: TestVerdict::NotStarted,
+                verdict_summary: "Not started".into(),
                 criteria_results: Vec::new(),
                 ttff_seconds: None,
+                ttff_2d_seconds: None,
+                ttff_3d_seconds: None,
                 test_duration_seconds: 0.0,
                 device_info: DeviceInfo {
                     port_name: "None".into(),
@@ -205,9 +1026,26 @@ pub fn get_test_status(state: State<'_, AppState>) -> CommandResult<TestResult>
                     serial_number: None,
                     vid: None,
                     pid: None,
+                    chip_id: None,
                 },
+                chip_info: None,
+                start_type: None,
                 timestamp: chrono::Utc::now().to_rfc3339(),
                 best_gps_data: None,
+                best_fix_type: None,
+                formatted_position: None,
+                criteria_profile: None,
+                criteria_override: false,
+                criteria: None,
+                antenna: None,
+                position_outliers: 0,
+                fix_downgrades: 0,
+                multipath_score: None,
+                gsv_missing: false,
+                hint: None,
+                paused: false,
+                constellation_acquisition: Vec::new(),
+                collected_fixes: Vec::new(),
             })
         }
     }
@@ -215,92 +1053,589 @@ pub fn get_test_status(state: State<'_, AppState>) -> CommandResult<TestResult>
 
 #[tauri::command]
 pub fn abort_test(state: State<'_, AppState>) -> CommandResult<bool> {
+    state.test_generation.fetch_add(1, Ordering::SeqCst);
     let mut runner_lock = state.test_runner.write().unwrap();
     if let Some(runner) = runner_lock.as_mut() {
         runner.abort();
     }
+    discard_interim_report(&state);
+    CommandResult::ok(true)
+}
+
+/// Pause the running test without losing progress. Its clocks stop, so the timeout
+/// and stability windows count only the time it runs; data is ignored until resumed.
+#[tauri::command]
+pub fn pause_test(state: State<'_, AppState>) -> CommandResult<bool> {
+    let mut runner_lock = state.test_runner.write().unwrap();
+    let Some(runner) = runner_lock.as_mut() else {
+        return CommandResult::err("No test is running");
+    };
+    if runner.is_paused() {
+        return CommandResult::err("Test is already paused");
+    }
+    if !runner.pause() {
+        return CommandResult::err("No test is running");
+    }
+    log::info!("Test paused at {:.1}s", runner.elapsed_seconds());
     CommandResult::ok(true)
 }
 
+/// Resume a test paused with `pause_test`
+#[tauri::command]
+pub fn resume_test(state: State<'_, AppState>) -> CommandResult<bool> {
+    let mut runner_lock = state.test_runner.write().unwrap();
+    match runner_lock.as_mut() {
+        Some(runner) if runner.is_paused() => {
+            runner.resume();
+            log::info!("Test resumed at {:.1}s", runner.elapsed_seconds());
+            CommandResult::ok(true)
+        }
+        _ => CommandResult::err("Test is not paused"),
+    }
+}
+
 #[tauri::command]
 pub fn save_test_report(state: State<'_, AppState>) -> CommandResult<String> {
-    let runner_lock = state.test_runner.read().unwrap();
+    let mut runner_lock = state.test_runner.write().unwrap();
 
-    match runner_lock.as_ref() {
+    match runner_lock.as_mut() {
+        Some(runner) => match save_runner_report(&state, runner) {
+            Ok(path) => CommandResult::ok(path),
+            Err(e) => CommandResult::err(e),
+        },
+        None => CommandResult::err("No test results to save"),
+    }
+}
+
+/// Save the runner's report and add its result to the recent results, returning the
+/// report's path. A finished run is saved once; saving it again, e.g. after an
+/// auto-save, returns the first path without adding another result. A failed save
+/// adds nothing, so a retry cannot list the result twice.
+fn save_runner_report(state: &AppState, runner: &mut TestRunner) -> Result<String, String> {
+    if let Some(path) = runner.saved_report() {
+        return Ok(path.to_string());
+    }
+    let gps_data = state.gps_manager.get_data();
+    // Collected fixes go in the report file only, not the in-memory history
+    let report = runner.get_report(Some(&gps_data));
+    let result = TestResult {
+        collected_fixes: Vec::new(),
+        ..report.clone()
+    };
+
+    // Save to file
+    let path = test_report::save_report(&report, &state.results_dir)
+        .map(|path| path.display().to_string())
+        .map_err(|e| format!("Failed to save report: {}", e))?;
+    runner.mark_saved(&path);
+
+    // Save to recent results
+    {
+        let mut recent = state.recent_results.write().unwrap();
+        recent.push(result.clone());
+        // Keep last 50
+        if recent.len() > 50 {
+            recent.remove(0);
+        }
+    }
+
+    // The per-file report is the record, so the NDJSON line only follows a saved one
+    // and is best effort
+    if state.settings.read().unwrap().append_ndjson {
+        if let Err(e) = test_report::append_ndjson(&result, &state.results_dir) {
+            log::warn!("Failed to append to {}: {}", test_report::NDJSON_FILE, e);
+        }
+    }
+    Ok(path)
+}
+
+/// Pass/fail label for the current test's unit, as plain text or ZPL for the label printer
+#[tauri::command]
+pub fn format_test_label(
+    state: State<'_, AppState>,
+    format: Option<LabelFormat>,
+) -> CommandResult<String> {
+    match state.test_runner.read().unwrap().as_ref() {
         Some(runner) => {
             let gps_data = state.gps_manager.get_data();
             let result = runner.get_result(Some(&gps_data));
+            CommandResult::ok(test_report::format_label(&result, format.unwrap_or_default()))
+        }
+        None => CommandResult::err("No test results to label"),
+    }
+}
 
-            // Save to recent results
-            {
-                let mut recent = state.recent_results.write().unwrap();
-                recent.push(result.clone());
-                // Keep last 50
-                if recent.len() > 50 {
-                    recent.remove(0);
-                }
-            }
+/// Check a saved report in the results directory for missing fields and inconsistencies
+#[tauri::command]
+pub fn validate_report(
+    state: State<'_, AppState>,
+    filename: String,
+) -> CommandResult<ReportValidation> {
+    // Only bare file names, so the command can't be pointed outside the archive
+    let path = Path::new(&filename);
+    if path.file_name() != Some(path.as_os_str()) {
+        return CommandResult::err(format!("Invalid report file name: {}", filename));
+    }
 
-            // Save to file
-            match test_report::save_report(&result, &state.results_dir) {
-                Ok(path) => CommandResult::ok(path.display().to_string()),
-                Err(e) => CommandResult::err(format!("Failed to save report: {}", e)),
-            }
-        }
-        None => CommandResult::err("No test results to save"),
+    match test_report::validate_report(&state.results_dir.join(path)) {
+        Ok(validation) => CommandResult::ok(validation),
+        Err(e) => CommandResult::err(format!("Failed to read report {}: {}", filename, e)),
     }
 }
 
+/// Bundle the saved reports in the results directory into one ZIP at `path`, with a
+/// manifest. `include_extras` also adds the NDJSON and monitor logs and CSV/HTML files;
+/// `verdict` keeps only the test reports with that verdict, e.g. just the failures.
+#[tauri::command]
+pub async fn export_results_archive(
+    state: State<'_, AppState>,
+    path: String,
+    include_extras: Option<bool>,
+    verdict: Option<TestVerdict>,
+) -> Result<CommandResult<ResultsArchive>, String> {
+    let results_dir = state.results_dir.clone();
+    let dest = std::path::PathBuf::from(&path);
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        test_report::export_results_archive(
+            &results_dir,
+            &dest,
+            include_extras.unwrap_or(false),
+            verdict.as_ref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(archive) => CommandResult::ok(archive),
+        Err(e) => CommandResult::err(format!("Failed to write archive {}: {}", path, e)),
+    })
+}
+
 #[tauri::command]
 pub fn get_recent_results(state: State<'_, AppState>) -> CommandResult<Vec<TestResult>> {
     let recent = state.recent_results.read().unwrap().clone();
     CommandResult::ok(recent)
 }
 
+/// Recent results with the given verdict, e.g. `fail` to review a batch's failures
+#[tauri::command]
+pub fn get_recent_results_filtered(
+    state: State<'_, AppState>,
+    verdict: TestVerdict,
+) -> CommandResult<Vec<TestResult>> {
+    let recent = state.recent_results.read().unwrap();
+    CommandResult::ok(analytics::filter_by_verdict(&recent, &verdict))
+}
+
+/// TTFF distribution across the recent results, in buckets of `bucket_seconds`
+#[tauri::command]
+pub fn get_ttff_histogram(
+    state: State<'_, AppState>,
+    bucket_seconds: f64,
+) -> CommandResult<TtffHistogram> {
+    let recent = state.recent_results.read().unwrap();
+    match analytics::ttff_histogram(&recent, bucket_seconds) {
+        Some(histogram) => CommandResult::ok(histogram),
+        None => CommandResult::err("Bucket width must be greater than zero"),
+    }
+}
+
 // ============ GPS Optimization Commands ============
 
+/// Event emitted with the current `OptimizeStatus` on every optimizer tick
+pub const OPTIMIZE_STATUS_EVENT: &str = "optimize-status";
+/// Event emitted once with the final `OptimizationReport` when optimization completes
+pub const OPTIMIZE_COMPLETE_EVENT: &str = "optimize-complete";
+/// How often the backend driver ticks the optimizer
+const OPTIMIZE_TICK_INTERVAL: Duration = Duration::from_millis(250);
+
 #[tauri::command]
-pub fn start_optimize(state: State<'_, AppState>) -> CommandResult<bool> {
+pub fn start_optimize(app: AppHandle, state: State<'_, AppState>) -> CommandResult<bool> {
     // Verify GPS is connected
-    let status = state.gps_manager.get_status();
-    let port_name = match status.port_name {
-        Some(ref name) => name.clone(),
-        None => return CommandResult::err("No GPS connected. Connect a GPS device first."),
-    };
+    if state.gps_manager.get_status().port_name.is_none() {
+        return CommandResult::err("No GPS connected. Connect a GPS device first.");
+    }
 
     // Verify it's a u-blox device
-    if !gps::is_ublox_device(&port_name) {
+    if !state.gps_manager.is_ublox_confirmed() {
         return CommandResult::err(
             "Connected device is not a u-blox receiver. Optimization requires a u-blox GPS chip.",
         );
     }
 
     // Start optimizer — queues MON-VER poll
-    state.gps_manager.optimizer.write().unwrap().start();
+    {
+        let mut optimizer = state.gps_manager.optimizer.write().unwrap();
+        optimizer.config = state.settings.read().unwrap().optimizer.clone();
+        optimizer.start();
+    }
 
     // Send the MON-VER poll command immediately
     state.gps_manager.send_pending_commands();
 
+    // Drive the optimizer from a backend thread; progress goes out as events
+    let generation = state.optimize_generation.fetch_add(1, Ordering::SeqCst) + 1;
+    std::thread::spawn(move || drive_optimizer(app, generation));
+
     CommandResult::ok(true)
 }
 
+/// Current optimizer status. The optimizer is ticked by the backend driver; this is
+/// kept for callers that poll rather than listen for `optimize-status` events.
 #[tauri::command]
 pub fn get_optimize_status(state: State<'_, AppState>) -> CommandResult<OptimizeStatus> {
-    // Feed current GPS data into optimizer's tick
+    CommandResult::ok(state.gps_manager.optimizer.read().unwrap().get_status())
+}
+
+#[tauri::command]
+pub fn abort_optimize(state: State<'_, AppState>) -> CommandResult<bool> {
+    state.optimize_generation.fetch_add(1, Ordering::SeqCst);
+    state.gps_manager.optimizer.write().unwrap().reset();
+    CommandResult::ok(true)
+}
+
+/// Tick the optimizer and emit its status until it finishes or the run is superseded
+fn drive_optimizer(app: AppHandle, generation: u64) {
+    let state = app.state::<AppState>();
+    while state.optimize_generation.load(Ordering::SeqCst) == generation {
+        let status = advance_optimizer(&state);
+        if let Err(e) = app.emit(OPTIMIZE_STATUS_EVENT, &status) {
+            log::warn!("Failed to emit optimizer status: {}", e);
+        }
+
+        match status.phase {
+            OptimizePhase::Complete => {
+                if let Some(report) = &status.report {
+                    let _ = app.emit(OPTIMIZE_COMPLETE_EVENT, report);
+                }
+                break;
+            }
+            OptimizePhase::Error | OptimizePhase::Idle => break,
+            _ => std::thread::sleep(OPTIMIZE_TICK_INTERVAL),
+        }
+    }
+}
+
+/// Feed the latest GPS data to the optimizer, send queued commands and persist a
+/// completed report once
+fn advance_optimizer(state: &AppState) -> OptimizeStatus {
     let gps_data = state.gps_manager.get_data();
     let has_pending = state.gps_manager.optimizer.write().unwrap().tick(&gps_data);
 
+    // Keep the identity for test reports; the optimizer forgets it on its next run
+    state.gps_manager.remember_chip_info();
+
     // Send any queued commands (e.g. optimization profile after baseline)
     if has_pending {
         state.gps_manager.send_pending_commands();
     }
 
-    let status = state.gps_manager.optimizer.read().unwrap().get_status();
-    CommandResult::ok(status)
+    // Persist the report once the optimizer completes
+    let unsaved = state.gps_manager.optimizer.write().unwrap().take_unsaved_report();
+    if let Some(report) = unsaved {
+        match test_report::save_optimization_report_json(&report, &state.results_dir) {
+            Ok(path) => {
+                state.gps_manager.optimizer.write().unwrap().report_path =
+                    Some(path.display().to_string());
+            }
+            Err(e) => log::warn!("Failed to save optimization report: {}", e),
+        }
+    }
+
+    state.gps_manager.optimizer.read().unwrap().get_status()
 }
 
+// ============ Log Commands ============
+
 #[tauri::command]
-pub fn abort_optimize(state: State<'_, AppState>) -> CommandResult<bool> {
-    state.gps_manager.optimizer.write().unwrap().reset();
+pub fn get_logs() -> CommandResult<Vec<LogEntry>> {
+    CommandResult::ok(log_buffer::recent_logs())
+}
+
+#[tauri::command]
+pub fn clear_logs() -> CommandResult<bool> {
+    log_buffer::clear_logs();
+    CommandResult::ok(true)
+}
+
+// ============ Live Diagnostic Commands ============
+
+/// Configuration builders and marine profile steps, so the UI can list what can be
+/// tuned on each chip series instead of hardcoding it
+#[tauri::command]
+pub fn list_ubx_capabilities() -> CommandResult<UbxCapabilities> {
+    CommandResult::ok(ubx_config::list_ubx_capabilities())
+}
+
+/// Classify the start (hot/warm/cold) the receiver is about to make from its
+/// NAV-STATUS and NAV-ORB, to anticipate TTFF before a test
+#[tauri::command]
+pub fn get_start_state(state: State<AppState>) -> CommandResult<StartState> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return CommandResult::err("No GPS connected. Connect a GPS device first.");
+    }
+
+    // start_state refuses receivers not confirmed as u-blox
+    match state.gps_manager.start_state() {
+        Ok(start) => CommandResult::ok(start),
+        Err(e) => CommandResult::err(e.to_string()),
+    }
+}
+
+/// Cold start the receiver `iterations` times and report the spread of the TTFFs.
+/// Runs until done, cancelled or `ttff_benchmark::MAX_BENCHMARK_SECONDS` have passed.
+#[tauri::command]
+pub async fn run_ttff_benchmark(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    iterations: u32,
+) -> Result<CommandResult<TtffBenchmark>, String> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return Ok(CommandResult::err("No GPS connected. Connect a GPS device first."));
+    }
+    if !(1..=MAX_BENCHMARK_ITERATIONS).contains(&iterations) {
+        return Ok(CommandResult::err(format!(
+            "Iterations must be 1-{}, got {}",
+            MAX_BENCHMARK_ITERATIONS, iterations
+        )));
+    }
+    // Cold starts would wreck a test in progress
+    let testing = state.test_runner.read().unwrap().as_ref().map(|r| r.verdict.clone());
+    if testing == Some(TestVerdict::Running) {
+        return Ok(CommandResult::err("Stop the running test before benchmarking TTFF"));
+    }
+
+    let cancel = Arc::clone(&state.ttff_benchmark_cancel);
+    cancel.store(false, Ordering::SeqCst);
+    log::info!("Starting TTFF benchmark: {} cold starts", iterations);
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        ttff_benchmark::run_ttff_benchmark(&state.gps_manager, iterations, &cancel)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(match result {
+        Ok(benchmark) => CommandResult::ok(benchmark),
+        Err(e) => CommandResult::err(e.to_string()),
+    })
+}
+
+#[tauri::command]
+pub fn cancel_ttff_benchmark(state: State<'_, AppState>) -> CommandResult<bool> {
+    state.ttff_benchmark_cancel.store(true, Ordering::SeqCst);
     CommandResult::ok(true)
 }
+
+/// Send a single UBX command to the connected u-blox receiver
+fn send_ublox_command(state: &AppState, cmd: Vec<u8>) -> CommandResult<bool> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return CommandResult::err("No GPS connected. Connect a GPS device first.");
+    }
+
+    // send_commands refuses anything not confirmed as u-blox
+    match state.gps_manager.send_commands(&[cmd]) {
+        Ok(()) => CommandResult::ok(true),
+        Err(e) => CommandResult::err(e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub fn enable_sbas(state: State<'_, AppState>) -> CommandResult<bool> {
+    log::info!("Enabling SBAS (live toggle)");
+    send_ublox_command(&state, ubx_config::build_cfg_sbas_enable())
+}
+
+#[tauri::command]
+pub fn disable_sbas(state: State<'_, AppState>) -> CommandResult<bool> {
+    log::info!("Disabling SBAS (live toggle)");
+    send_ublox_command(&state, ubx_config::build_cfg_sbas_disable())
+}
+
+#[tauri::command]
+pub fn set_nmea_41(state: State<'_, AppState>) -> CommandResult<bool> {
+    log::info!("Forcing NMEA 4.10 output (per-constellation talker IDs)");
+    send_ublox_command(&state, ubx_config::build_cfg_nmea_41())
+}
+
+/// NMEA high-precision mode on high-precision receivers (M8P, F9P), so RTK positions
+/// keep their centimetres in GGA/RMC instead of being rounded
+#[tauri::command]
+pub fn set_nmea_high_precision(state: State<'_, AppState>, enable: bool) -> CommandResult<bool> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return CommandResult::err("No GPS connected. Connect a GPS device first.");
+    }
+    log::info!("{} NMEA high-precision mode", if enable { "Enabling" } else { "Disabling" });
+    match state.gps_manager.set_nmea_high_precision(enable) {
+        Ok(()) => CommandResult::ok(true),
+        Err(e) => CommandResult::err(e.to_string()),
+    }
+}
+
+/// Enable only the given constellations for the next test (SBAS stays on)
+#[tauri::command]
+pub fn set_constellations(
+    state: State<'_, AppState>,
+    constellations: Vec<Constellation>,
+) -> CommandResult<bool> {
+    if state.gps_manager.get_status().port_name.is_none() {
+        return CommandResult::err("No GPS connected. Connect a GPS device first.");
+    }
+    let names: Vec<String> = constellations.iter().map(|c| c.to_string()).collect();
+    log::info!("Setting constellations: {}", names.join(", "));
+    match state.gps_manager.set_constellations(&constellations) {
+        Ok(()) => CommandResult::ok(true),
+        Err(e) => CommandResult::err(e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub fn set_dynamic_model(state: State<'_, AppState>, model: DynamicModel) -> CommandResult<bool> {
+    log::info!("Setting dynamic model: {}", model);
+    send_ublox_command(&state, ubx_config::build_cfg_nav5_model(model))
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{temp_dir, test_device};
+
+    fn test_state(name: &str) -> AppState {
+        let dir = temp_dir(name);
+        AppState {
+            gps_manager: GpsManager::new(),
+            test_runner: RwLock::new(None),
+            settings: RwLock::new(AppSettings::default()),
+            criteria_profiles: RwLock::new(CriteriaProfiles::new()),
+            baselines: RwLock::new(Baselines::new()),
+            recent_results: RwLock::new(Vec::new()),
+            results_dir: dir.clone(),
+            interim_report_path: Mutex::new(None),
+            config_dir: dir,
+            auto_detect_cancel: Arc::new(AtomicBool::new(false)),
+            optimize_generation: AtomicU64::new(0),
+            test_generation: AtomicU64::new(0),
+            metrics_csv: Mutex::new(None),
+            ttff_benchmark_cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// A runner that has just passed, replayed from the recorded session fixture
+    fn passed_runner() -> TestRunner {
+        let samples = include_str!("../tests/fixtures/recorded_session.jsonl").lines().map(|line| {
+            let sample: serde_json::Value = serde_json::from_str(line).unwrap();
+            let data: GpsData = serde_json::from_value(sample["data"].clone()).unwrap();
+            (sample["t"].as_f64().unwrap(), data)
+        });
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        assert_eq!(runner.replay(samples), TestVerdict::Pass);
+        runner
+    }
+
+    #[test]
+    fn test_report_saved_once_after_auto_save() {
+        let state = test_state("save-once");
+        state.settings.write().unwrap().auto_save_on_complete = true;
+        *state.test_runner.write().unwrap() = Some(passed_runner());
+
+        // Auto-save on completion, then the operator clicks save as well
+        let auto_path = auto_save_completed(&state).unwrap().unwrap();
+        assert_eq!(state.recent_results.read().unwrap().len(), 1);
+        let mut runner_lock = state.test_runner.write().unwrap();
+        let manual_path = save_runner_report(&state, runner_lock.as_mut().unwrap()).unwrap();
+        drop(runner_lock);
+        assert_eq!(manual_path, auto_path);
+        assert_eq!(state.recent_results.read().unwrap().len(), 1);
+        assert!(auto_save_completed(&state).is_none());
+
+        // Manual save first: the same single entry
+        let state = test_state("save-once-manual");
+        *state.test_runner.write().unwrap() = Some(passed_runner());
+        let mut runner_lock = state.test_runner.write().unwrap();
+        let runner = runner_lock.as_mut().unwrap();
+        let first = save_runner_report(&state, runner).unwrap();
+        assert_eq!(save_runner_report(&state, runner).unwrap(), first);
+        assert_eq!(state.recent_results.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_failed_save_not_listed_in_recent_results() {
+        let mut state = test_state("save-failed");
+        // A file where the results directory should be makes the save fail
+        state.results_dir = state.results_dir.join("blocked");
+        std::fs::write(&state.results_dir, "").unwrap();
+        let mut runner = passed_runner();
+        assert!(save_runner_report(&state, &mut runner).is_err());
+        assert!(runner.saved_report().is_none());
+        assert!(state.recent_results.read().unwrap().is_empty());
+
+        // The retry once the directory is usable lists it once
+        std::fs::remove_file(&state.results_dir).unwrap();
+        save_runner_report(&state, &mut runner).unwrap();
+        assert_eq!(state.recent_results.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_adhoc_criteria_leave_stored_criteria_alone() {
+        let state = test_state("adhoc-criteria");
+        state.settings.read().unwrap().save(&state.config_dir).unwrap();
+        state.criteria_profiles.write().unwrap().insert(
+            "NEO-M8N".to_string(),
+            TestCriteria { min_satellites: 8, ..TestCriteria::default() },
+        );
+        let m8n = DeviceInfo {
+            product: Some("u-blox NEO-M8N".into()),
+            ..test_device()
+        };
+
+        let adhoc = TestCriteria { max_hdop: 1.0, ..TestCriteria::default() };
+        let runner = build_runner(&state, m8n.clone(), Some(adhoc)).unwrap();
+        assert!(runner.criteria_override);
+        assert_eq!(runner.criteria_profile, None);
+        assert_eq!(runner.criteria.max_hdop, 1.0);
+
+        // Defaults and profile, in memory and on disk, as they were
+        let default_hdop = TestCriteria::default().max_hdop;
+        assert_eq!(state.settings.read().unwrap().criteria.max_hdop, default_hdop);
+        assert_eq!(state.criteria_profiles.read().unwrap()["NEO-M8N"].max_hdop, default_hdop);
+        assert_eq!(AppSettings::load(&state.config_dir).criteria.max_hdop, default_hdop);
+
+        // The next run without overrides is back on the profile
+        let runner = build_runner(&state, m8n, None).unwrap();
+        assert_eq!(runner.criteria_profile.as_deref(), Some("NEO-M8N"));
+        assert_eq!(runner.criteria.max_hdop, default_hdop);
+    }
+
+    #[test]
+    fn test_runner_records_chip_identified_on_this_connection() {
+        let state = test_state("chip-info");
+
+        // MON-VER of an M10, read twice so the optimizer accepts it
+        let mut payload = vec![0u8; 70];
+        payload[0..13].copy_from_slice(b"ROM SPG 5.10 ");
+        payload[30..38].copy_from_slice(b"000A0000");
+        payload[40..52].copy_from_slice(b"MOD=MAX-M10S");
+        {
+            let mut optimizer = state.gps_manager.optimizer.write().unwrap();
+            optimizer.start();
+            optimizer.on_mon_ver_response(&payload);
+            optimizer.on_mon_ver_response(&payload);
+        }
+        advance_optimizer(&state);
+
+        // The optimizer's next run forgets the chip; the connection keeps it
+        state.gps_manager.optimizer.write().unwrap().reset();
+        let runner = build_runner(&state, test_device(), None).unwrap();
+        assert_eq!(runner.chip_info.map(|c| c.chip_name).as_deref(), Some("MAX-M10S"));
+
+        // A new connection may be a different receiver
+        let settings = SerialSettings::default();
+        state.gps_manager.connect("/dev/ttyGPS-TEST-CHIP", 9600, settings, || {}).unwrap();
+        let runner = build_runner(&state, test_device(), None).unwrap();
+        assert!(runner.chip_info.is_none());
+        state.gps_manager.disconnect();
+    }
+}
@@ -0,0 +1,306 @@
+// Coordinate formatting for reports and exports
+//
+// GpsData always carries WGS84 decimal degrees; these helpers are presentation-only.
+
+use serde::{Deserialize, Serialize};
+
+// WGS84 ellipsoid
+const WGS84_A: f64 = 6_378_137.0;
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+// UTM projection parameters
+const UTM_K0: f64 = 0.9996;
+const UTM_FALSE_EASTING: f64 = 500_000.0;
+const UTM_FALSE_NORTHING_SOUTH: f64 = 10_000_000.0;
+
+/// Reference ellipsoid as (semi-major axis in metres, flattening)
+type Ellipsoid = (f64, f64);
+
+const WGS84: Ellipsoid = (WGS84_A, WGS84_F);
+const CLARKE_1866: Ellipsoid = (6_378_206.4, 1.0 / 294.978_698_2);
+const INTERNATIONAL_1924: Ellipsoid = (6_378_388.0, 1.0 / 297.0);
+
+/// Latitude band letters from 80°S to 84°N (8° bands, X is 12°)
+const UTM_BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWXX";
+
+/// Output format for positions in reports and exports
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CoordFormat {
+    #[default]
+    DecimalDegrees,
+    Dms,
+    Utm,
+}
+
+/// Geodetic datum positions are given in for reports and exports
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Datum {
+    #[default]
+    Wgs84,
+    /// North American Datum 1927 (Clarke 1866, CONUS mean shift)
+    Nad27,
+    /// New Zealand Geodetic Datum 1949 (International 1924)
+    Nzgd49,
+}
+
+impl Datum {
+    /// Ellipsoid and geocentric shift (dX, dY, dZ in metres) from this datum to WGS84,
+    /// as published in NIMA TR8350.2
+    fn parameters(self) -> (Ellipsoid, [f64; 3]) {
+        match self {
+            Datum::Wgs84 => (WGS84, [0.0; 3]),
+            Datum::Nad27 => (CLARKE_1866, [-8.0, 160.0, 176.0]),
+            Datum::Nzgd49 => (INTERNATIONAL_1924, [84.0, -22.0, 209.0]),
+        }
+    }
+}
+
+/// A position in Universal Transverse Mercator coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UtmCoord {
+    pub zone: u8,
+    pub band: char,
+    pub easting: f64,
+    pub northing: f64,
+}
+
+impl std::fmt::Display for UtmCoord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{} {:.0}E {:.0}N",
+            self.zone, self.band, self.easting, self.northing
+        )
+    }
+}
+
+/// Standard Molodensky transformation of a position on the ellipsoid surface (height
+/// taken as 0, which moves the result by well under a millimetre) between ellipsoids,
+/// shifting the origin by `shift` metres. Good to a few metres against the datums'
+/// own accuracy.
+fn molodensky(lat: f64, lon: f64, from: Ellipsoid, to: Ellipsoid, shift: [f64; 3]) -> (f64, f64) {
+    let (a, f) = from;
+    let (da, df) = (to.0 - a, to.1 - f);
+    let [dx, dy, dz] = shift;
+    let b = a * (1.0 - f);
+    let e2 = f * (2.0 - f);
+
+    let (sin_phi, cos_phi) = lat.to_radians().sin_cos();
+    let (sin_lam, cos_lam) = lon.to_radians().sin_cos();
+    let w2 = 1.0 - e2 * sin_phi * sin_phi;
+    // Prime vertical and meridian radii of curvature
+    let rn = a / w2.sqrt();
+    let rm = a * (1.0 - e2) / w2.powf(1.5);
+
+    let d_phi = (-dx * sin_phi * cos_lam - dy * sin_phi * sin_lam
+        + dz * cos_phi
+        + da * rn * e2 * sin_phi * cos_phi / a
+        + df * (rm * a / b + rn * b / a) * sin_phi * cos_phi)
+        / rm;
+    let d_lam = (-dx * sin_lam + dy * cos_lam) / (rn * cos_phi);
+    (lat + d_phi.to_degrees(), lon + d_lam.to_degrees())
+}
+
+/// Convert a position from one datum to another, via WGS84
+pub fn transform(lat: f64, lon: f64, from: Datum, to: Datum) -> (f64, f64) {
+    if from == to {
+        return (lat, lon);
+    }
+    let (from_ellipsoid, from_shift) = from.parameters();
+    let (to_ellipsoid, to_shift) = to.parameters();
+    let (lat, lon) = match from {
+        Datum::Wgs84 => (lat, lon),
+        _ => molodensky(lat, lon, from_ellipsoid, WGS84, from_shift),
+    };
+    match to {
+        Datum::Wgs84 => (lat, lon),
+        _ => molodensky(lat, lon, WGS84, to_ellipsoid, to_shift.map(|d| -d)),
+    }
+}
+
+/// Format a single angle as degrees-minutes-seconds with a hemisphere suffix
+fn dms_component(value: f64, positive: char, negative: char) -> String {
+    let hemisphere = if value < 0.0 { negative } else { positive };
+    // Round at the output precision first so 59.995" doesn't print as 60.00"
+    let total_hundredths = (value.abs() * 360_000.0).round() as u64;
+    let degrees = total_hundredths / 360_000;
+    let minutes = (total_hundredths % 360_000) / 6_000;
+    let seconds = (total_hundredths % 6_000) as f64 / 100.0;
+    format!(
+        "{}°{:02}'{:05.2}\"{}",
+        degrees, minutes, seconds, hemisphere
+    )
+}
+
+/// Convert decimal degrees to a DMS string, e.g. `36°50'54.12"S 174°45'43.86"E`
+pub fn to_dms(lat: f64, lon: f64) -> String {
+    format!(
+        "{} {}",
+        dms_component(lat, 'N', 'S'),
+        dms_component(lon, 'E', 'W')
+    )
+}
+
+/// Convert decimal degrees in `datum` to UTM on that datum's ellipsoid (standard zones,
+/// no Norway/Svalbard exceptions). Returns None outside the UTM latitude range of 80°S
+/// to 84°N.
+pub fn to_utm(lat: f64, lon: f64, datum: Datum) -> Option<UtmCoord> {
+    if !(-80.0..=84.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return None;
+    }
+
+    let ((semi_major, flattening), _) = datum.parameters();
+    let zone = (((lon + 180.0) / 6.0).floor() as u8).min(59) + 1;
+    let band = UTM_BANDS[(((lat + 80.0) / 8.0).floor() as usize).min(UTM_BANDS.len() - 1)] as char;
+    let central_meridian = (zone as f64 - 1.0) * 6.0 - 180.0 + 3.0;
+
+    let e2 = flattening * (2.0 - flattening);
+    let ep2 = e2 / (1.0 - e2);
+
+    let phi = lat.to_radians();
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    let tan_phi = phi.tan();
+
+    let n = semi_major / (1.0 - e2 * sin_phi * sin_phi).sqrt();
+    let t = tan_phi * tan_phi;
+    let c = ep2 * cos_phi * cos_phi;
+    let a = cos_phi * (lon - central_meridian).to_radians();
+
+    // Meridional arc length
+    let e4 = e2 * e2;
+    let e6 = e4 * e2;
+    let m = semi_major
+        * ((1.0 - e2 / 4.0 - 3.0 * e4 / 64.0 - 5.0 * e6 / 256.0) * phi
+            - (3.0 * e2 / 8.0 + 3.0 * e4 / 32.0 + 45.0 * e6 / 1024.0) * (2.0 * phi).sin()
+            + (15.0 * e4 / 256.0 + 45.0 * e6 / 1024.0) * (4.0 * phi).sin()
+            - (35.0 * e6 / 3072.0) * (6.0 * phi).sin());
+
+    let easting = UTM_K0
+        * n
+        * (a + (1.0 - t + c) * a.powi(3) / 6.0
+            + (5.0 - 18.0 * t + t * t + 72.0 * c - 58.0 * ep2) * a.powi(5) / 120.0)
+        + UTM_FALSE_EASTING;
+
+    let mut northing = UTM_K0
+        * (m + n
+            * tan_phi
+            * (a * a / 2.0
+                + (5.0 - t + 9.0 * c + 4.0 * c * c) * a.powi(4) / 24.0
+                + (61.0 - 58.0 * t + t * t + 600.0 * c - 330.0 * ep2) * a.powi(6) / 720.0));
+    if lat < 0.0 {
+        northing += UTM_FALSE_NORTHING_SOUTH;
+    }
+
+    Some(UtmCoord {
+        zone,
+        band,
+        easting,
+        northing,
+    })
+}
+
+/// Format a WGS84 position in the requested output format and datum. UTM is projected
+/// on the datum's own ellipsoid, as legacy grid references are.
+pub fn format_position(lat: f64, lon: f64, format: CoordFormat, datum: Datum) -> String {
+    let (lat, lon) = transform(lat, lon, Datum::Wgs84, datum);
+    match format {
+        CoordFormat::DecimalDegrees => format!("{:.6}, {:.6}", lat, lon),
+        CoordFormat::Dms => to_dms(lat, lon),
+        CoordFormat::Utm => match to_utm(lat, lon, datum) {
+            Some(utm) => utm.to_string(),
+            // Polar regions fall outside UTM; fall back to decimal degrees
+            None => format!("{:.6}, {:.6}", lat, lon),
+        },
+    }
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dms_known_point() {
+        // Auckland Sky Tower
+        assert_eq!(
+            to_dms(-36.848_367, 174.762_183),
+            "36°50'54.12\"S 174°45'43.86\"E"
+        );
+        assert_eq!(to_dms(0.5, -0.25), "0°30'00.00\"N 0°15'00.00\"W");
+    }
+
+    #[test]
+    fn test_to_dms_rounds_seconds_up_to_minute() {
+        // 10.9999999° must print 11°00'00.00", never 10°59'60.00"
+        assert_eq!(to_dms(10.999_999_9, 0.0), "11°00'00.00\"N 0°00'00.00\"E");
+    }
+
+    #[test]
+    fn test_to_utm_known_points() {
+        // New York City
+        let nyc = to_utm(40.714_35, -74.005_97, Datum::Wgs84).unwrap();
+        assert_eq!((nyc.zone, nyc.band), (18, 'T'));
+        assert!((nyc.easting - 583_960.0).abs() < 1.0);
+        assert!((nyc.northing - 4_507_523.0).abs() < 1.0);
+
+        // Sydney (southern hemisphere false northing)
+        let syd = to_utm(-33.867_85, 151.204_37, Datum::Wgs84).unwrap();
+        assert_eq!((syd.zone, syd.band), (56, 'H'));
+        assert!((syd.easting - 333_911.0).abs() < 1.0);
+        assert!((syd.northing - 6_251_046.0).abs() < 1.0);
+
+        // Aachen
+        let aachen = to_utm(50.775_35, 6.083_89, Datum::Wgs84).unwrap();
+        assert_eq!((aachen.zone, aachen.band), (32, 'U'));
+        assert!((aachen.easting - 294_409.0).abs() < 1.0);
+        assert!((aachen.northing - 5_628_898.0).abs() < 1.0);
+
+        // Central meridian on the equator
+        let origin = to_utm(0.0, 3.0, Datum::Wgs84).unwrap();
+        assert_eq!(origin.zone, 31);
+        assert!((origin.easting - 500_000.0).abs() < 0.001);
+        assert!(origin.northing.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_to_utm_out_of_range() {
+        assert!(to_utm(85.0, 0.0, Datum::Wgs84).is_none());
+        assert_eq!(
+            format_position(85.0, 0.0, CoordFormat::Utm, Datum::Wgs84),
+            "85.000000, 0.000000"
+        );
+    }
+
+    #[test]
+    fn test_wgs84_to_nad27_known_point() {
+        // Washington Monument; reference from the exact geocentric three-parameter shift
+        let (lat, lon) = transform(38.889_484, -77.035_278, Datum::Wgs84, Datum::Nad27);
+        assert!((lat - 38.889_451_1).abs() < 5e-6, "lat {}", lat);
+        assert!((lon - -77.035_601_9).abs() < 5e-6, "lon {}", lon);
+
+        // And back again
+        let (lat, lon) = transform(lat, lon, Datum::Nad27, Datum::Wgs84);
+        assert!((lat - 38.889_484).abs() < 1e-6);
+        assert!((lon - -77.035_278).abs() < 1e-6);
+
+        // Auckland in NZGD49 is about 200 m south
+        let (lat, lon) = transform(-36.848_367, 174.762_183, Datum::Wgs84, Datum::Nzgd49);
+        assert!((lat - -36.850_200_6).abs() < 5e-6, "lat {}", lat);
+        assert!((lon - 174.762_023_3).abs() < 5e-6, "lon {}", lon);
+    }
+
+    #[test]
+    fn test_format_position_in_datum() {
+        assert_eq!(
+            format_position(38.889_484, -77.035_278, CoordFormat::DecimalDegrees, Datum::Wgs84),
+            "38.889484, -77.035278"
+        );
+        assert_eq!(
+            format_position(38.889_484, -77.035_278, CoordFormat::DecimalDegrees, Datum::Nad27),
+            "38.889451, -77.035602"
+        );
+    }
+}
@@ -1,16 +1,26 @@
 // GPS hardware detection and serial reading module
 // Simplified from VortexNav: single-source, auto-detect, no failover/TCP/simulated
 
-use crate::nmea::{GpsData, NmeaParser};
-use crate::ubx_config;
+use crate::atomic_file::write_atomic;
+use crate::nmea::{self, GpsData, NmeaFilter, NmeaParser, TextMessage, TextSeverity};
+use crate::pmtk;
+use crate::raw_log::{Frame, Framer, RawCapture, RawChunk};
+use crate::snapshot::SnapshotLock;
+use crate::test_criteria::GsvWatch;
+use crate::ubx_config::{
+    self, Constellation, DynamicModel, StartState, UbloxChipInfo, UbloxSeries, UbxAck,
+    UbxConfigFrame,
+};
 use crate::ubx_optimizer::UbxOptimizer;
 use serde::{Deserialize, Serialize};
 use serialport::SerialPortType;
 use std::io::{BufRead, BufReader, Read as _, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{BTreeSet, VecDeque};
+use std::path::Path;
 use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 // ============ GPS Types ============
@@ -23,6 +33,137 @@ pub enum GpsError {
     Io(#[from] std::io::Error),
     #[error("No GPS device detected")]
     NoGpsDetected,
+    #[error("GPS not connected")]
+    NotConnected,
+    #[error("An NMEA capture is already in progress")]
+    CaptureInProgress,
+    #[error("Auto-detect cancelled")]
+    AutoDetectCancelled,
+    #[error("Connected device is not a confirmed u-blox receiver; refusing to send UBX commands")]
+    NotUblox,
+    #[error("Invalid serial settings: {0}")]
+    InvalidSerialSettings(String),
+    #[error("Receiver did not respond at {0} baud; port switched back to {1} baud")]
+    BaudChangeFailed(u32, u32),
+    #[error("Invalid UBX config file: {0}")]
+    InvalidUbxConfig(String),
+    #[error("Invalid constellation selection: {0}")]
+    InvalidConstellations(String),
+    #[error("Receiver kept its fix through a cold start reset")]
+    ColdStartIgnored,
+    #[error("Receiver is not a high-precision u-blox (M8P, F9P); run the optimizer to identify it")]
+    NotHighPrecision,
+    #[error("GPS device removed: {0}")]
+    DeviceRemoved(String),
+    #[error("Serial read error: {0}")]
+    ReadFailed(String),
+}
+
+impl GpsError {
+    /// Stable identifier for the frontend, which should not match on message text
+    pub fn code(&self) -> &'static str {
+        match self {
+            GpsError::SerialPort(_) => "serial_port",
+            GpsError::Io(_) => "io",
+            GpsError::NoGpsDetected => "no_gps_detected",
+            GpsError::NotConnected => "not_connected",
+            GpsError::CaptureInProgress => "capture_in_progress",
+            GpsError::AutoDetectCancelled => "auto_detect_cancelled",
+            GpsError::NotUblox => "not_ublox",
+            GpsError::InvalidSerialSettings(_) => "invalid_serial_settings",
+            GpsError::BaudChangeFailed(..) => "baud_change_failed",
+            GpsError::InvalidUbxConfig(_) => "invalid_ubx_config",
+            GpsError::InvalidConstellations(_) => "invalid_constellations",
+            GpsError::ColdStartIgnored => "cold_start_ignored",
+            GpsError::NotHighPrecision => "not_high_precision",
+            GpsError::DeviceRemoved(_) => "device_removed",
+            GpsError::ReadFailed(_) => "read_error",
+        }
+    }
+}
+
+/// Serial line settings beyond the baud rate. The default is 8N1 with no flow
+/// control, which nearly every GPS uses; some industrial units need otherwise.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SerialSettings {
+    pub data_bits: u8, // 5-8
+    pub parity: SerialParity,
+    pub stop_bits: u8, // 1 or 2
+    pub flow_control: SerialFlowControl,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SerialParity {
+    #[default]
+    None,
+    Odd,
+    Even,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SerialFlowControl {
+    #[default]
+    None,
+    Software,
+    Hardware,
+}
+
+impl Default for SerialSettings {
+    fn default() -> Self {
+        Self {
+            data_bits: 8,
+            parity: SerialParity::None,
+            stop_bits: 1,
+            flow_control: SerialFlowControl::None,
+        }
+    }
+}
+
+impl SerialSettings {
+    /// Port builder configured with these settings
+    pub fn builder(
+        &self,
+        port_name: &str,
+        baud_rate: u32,
+    ) -> Result<serialport::SerialPortBuilder, GpsError> {
+        let data_bits = match self.data_bits {
+            5 => serialport::DataBits::Five,
+            6 => serialport::DataBits::Six,
+            7 => serialport::DataBits::Seven,
+            8 => serialport::DataBits::Eight,
+            n => return Err(GpsError::InvalidSerialSettings(format!("{} data bits", n))),
+        };
+        let stop_bits = match self.stop_bits {
+            1 => serialport::StopBits::One,
+            2 => serialport::StopBits::Two,
+            n => return Err(GpsError::InvalidSerialSettings(format!("{} stop bits", n))),
+        };
+        let parity = match self.parity {
+            SerialParity::None => serialport::Parity::None,
+            SerialParity::Odd => serialport::Parity::Odd,
+            SerialParity::Even => serialport::Parity::Even,
+        };
+        let flow_control = match self.flow_control {
+            SerialFlowControl::None => serialport::FlowControl::None,
+            SerialFlowControl::Software => serialport::FlowControl::Software,
+            SerialFlowControl::Hardware => serialport::FlowControl::Hardware,
+        };
+
+        Ok(serialport::new(port_name, baud_rate)
+            .data_bits(data_bits)
+            .parity(parity)
+            .stop_bits(stop_bits)
+            .flow_control(flow_control))
+    }
+
+    /// Bits on the wire per byte: start bit, data bits, parity bit if any, stop bits
+    pub fn bits_per_byte(&self) -> u32 {
+        let parity = u32::from(self.parity != SerialParity::None);
+        1 + u32::from(self.data_bits) + parity + u32::from(self.stop_bits)
+    }
 }
 
 /// Information about a detected serial port
@@ -38,6 +179,15 @@ pub struct DetectedPort {
     pub is_likely_gps: bool,
 }
 
+/// A port confirmed to be sending NMEA, from a full scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedGps {
+    pub port: DetectedPort,
+    pub baud_rate: u32,
+    /// Receiver family from the USB identity: "u-blox", "MediaTek" or "NMEA"
+    pub receiver: String,
+}
+
 /// GPS connection status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -47,6 +197,10 @@ pub enum GpsConnectionStatus {
     Connected,
     ReceivingData,
     Error,
+    /// The port vanished (USB unplug); reconnects once it reappears
+    DeviceRemoved,
+    /// Reads failed on a port that is still present; reconnect attempts are under way
+    ReadError,
 }
 
 /// Current GPS source status
@@ -55,8 +209,168 @@ pub struct GpsSourceStatus {
     pub port_name: Option<String>,
     pub status: GpsConnectionStatus,
     pub last_error: Option<String>,
+    /// `GpsError::code` of `last_error`
+    pub last_error_code: Option<String>,
     pub sentences_received: u64,
+    /// Checksum-valid UBX frames received since connecting
+    #[serde(default)]
+    pub ubx_frames_received: u64,
     pub last_fix_time: Option<String>,
+    /// Baud rate reported by the opened port (not just the one requested)
+    pub baud_rate: Option<u32>,
+    pub flow_control: Option<String>,
+    pub ubx_config_applied: bool,
+    pub pmtk_config_applied: bool,
+    /// Navigation update rate measured from GGA arrivals
+    pub observed_rate_hz: Option<f32>,
+    /// Connected device identified as u-blox by USB VID or manufacturer/product strings
+    pub ublox_identified: bool,
+    /// Update rate we configured the receiver for; None until a profile is applied
+    #[serde(default)]
+    pub expected_rate_hz: Option<f32>,
+    /// Observed rate differs from `expected_rate_hz`, e.g. a receiver that ignored
+    /// CFG-RATE and kept a 4Hz rate from a prior setup
+    pub rate_mismatch: bool,
+    /// Raw bytes read from the port since connecting (NMEA and UBX)
+    pub bytes_received: u64,
+    /// Raw serial throughput over the last `THROUGHPUT_WINDOW`
+    pub bytes_per_second: Option<f32>,
+    /// Throughput as a fraction of what the baud rate can carry. Near 1.0 the link is
+    /// the bottleneck; a low value with a lagging sentence rate points at the receiver.
+    pub link_utilization: Option<f32>,
+    /// Fixes arrive but no GSV satellite data has, so there is no satellite/SNR data.
+    /// u-blox receivers are sent a command to enable GSV; others need it enabled by hand.
+    pub gsv_missing: bool,
+    /// Unique chip ID from UBX-SEC-UNIQID, once read (u-blox M8 and later)
+    pub chip_id: Option<String>,
+    /// u-blox identity from the optimizer's MON-VER, kept for the connection because
+    /// the optimizer forgets it on its next run
+    #[serde(default)]
+    pub chip_info: Option<UbloxChipInfo>,
+    /// Latest TXT diagnostics from the receiver (boot banner, antenna status), oldest first
+    #[serde(default)]
+    pub text_messages: Vec<TextMessage>,
+}
+
+/// Receiver response to one message of a raw UBX config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UbxConfigResult {
+    pub line: usize,
+    pub class: u8,
+    pub id: u8,
+    /// ACK-ACK (true) or ACK-NAK (false); None if nothing came back in time, or for
+    /// non-CFG messages, which the receiver does not acknowledge
+    pub acked: Option<bool>,
+}
+
+/// Receiver response to one command of the marine profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileCommandResult {
+    pub step: usize, // 1-based, in sending order
+    pub message: String, // e.g. "CFG-GNSS"
+    pub class: u8,
+    pub id: u8,
+    /// ACK-ACK (true) or ACK-NAK (false); None if nothing came back in time
+    pub acked: Option<bool>,
+}
+
+/// Outcome of applying the marine profile once, without measuring its effect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileApplyReport {
+    pub profile: String,
+    pub commands: Vec<ProfileCommandResult>,
+    pub acked: usize,
+    pub nacked: usize,
+    pub unanswered: usize,
+    /// The configuration reached non-volatile memory: the closing CFG-CFG was ACKed,
+    /// or on M9/M10 (whose CFG-VALSETs write flash themselves) every command was
+    pub saved: bool,
+}
+
+impl ProfileApplyReport {
+    pub fn from_results(profile: &str, commands: Vec<ProfileCommandResult>) -> Self {
+        let count = |acked: Option<bool>| commands.iter().filter(|c| c.acked == acked).count();
+        let is_save = |c: &ProfileCommandResult| {
+            c.class == ubx_config::UBX_CLASS_CFG && c.id == ubx_config::UBX_CFG_CFG
+        };
+        let saved = match commands.iter().rfind(|c| is_save(c)) {
+            Some(save) => save.acked == Some(true),
+            None => !commands.is_empty() && commands.iter().all(|c| c.acked == Some(true)),
+        };
+        Self {
+            profile: profile.to_string(),
+            acked: count(Some(true)),
+            nacked: count(Some(false)),
+            unanswered: count(None),
+            saved,
+            commands,
+        }
+    }
+}
+
+/// Outcome of `reset_to_defaults`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigResetReport {
+    /// The CFG-CFG clear was ACKed (false: NAKed, None: no answer)
+    pub cleared: Option<bool>,
+    /// Dynamic model read back after the restart; None if the poll went unanswered
+    pub dynamic_model: Option<DynamicModel>,
+    /// Cleared, and the dynamic model reads back as the factory default
+    pub confirmed: bool,
+}
+
+/// What a one-shot `probe_port` read found on a port
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PortProbe {
+    pub port_name: String,
+    pub baud_rate: u32,
+    pub duration_ms: u64,
+    /// Raw bytes read, NMEA or not; zero means nothing is transmitting at all
+    pub bytes_received: u64,
+    /// NMEA sentences with a correct (or no) checksum
+    pub nmea_sentences: u32,
+    /// Lines that looked like NMEA but failed the checksum, e.g. a wrong baud rate
+    pub invalid_sentences: u32,
+    /// Sentence types seen, e.g. ["GGA", "GSV", "PUBX", "RMC"]
+    pub sentence_types: Vec<String>,
+    /// Talker IDs seen, e.g. ["GL", "GN", "GP"]
+    pub talkers: Vec<String>,
+    /// Checksum-verified UBX frames
+    pub ubx_frames: u32,
+    /// Receiver identity from the answer to a MON-VER poll; None if not a u-blox
+    pub ublox: Option<UbloxChipInfo>,
+}
+
+/// Last port+baud that connected successfully, kept in the config dir so a
+/// station with a fixed GPS can reconnect on launch without a manual connect
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LastPort {
+    pub port_name: String,
+    pub baud_rate: u32,
+}
+
+const LAST_PORT_FILE: &str = "last_port.json";
+
+impl LastPort {
+    /// Load the saved port; None if never saved or unreadable
+    pub fn load(config_dir: &std::path::Path) -> Option<Self> {
+        let path = config_dir.join(LAST_PORT_FILE);
+        let contents = std::fs::read_to_string(&path).ok()?;
+        match serde_json::from_str(&contents) {
+            Ok(last) => Some(last),
+            Err(e) => {
+                log::warn!("Failed to parse {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, config_dir: &std::path::Path) -> Result<(), GpsError> {
+        std::fs::create_dir_all(config_dir)?;
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::from)?;
+        write_atomic(&config_dir.join(LAST_PORT_FILE), json)?;
+        Ok(())
+    }
 }
 
 impl Default for GpsSourceStatus {
@@ -65,15 +379,255 @@ impl Default for GpsSourceStatus {
             port_name: None,
             status: GpsConnectionStatus::Disconnected,
             last_error: None,
+            last_error_code: None,
             sentences_received: 0,
+            ubx_frames_received: 0,
             last_fix_time: None,
+            baud_rate: None,
+            flow_control: None,
+            ubx_config_applied: false,
+            pmtk_config_applied: false,
+            observed_rate_hz: None,
+            ublox_identified: false,
+            expected_rate_hz: None,
+            rate_mismatch: false,
+            bytes_received: 0,
+            bytes_per_second: None,
+            link_utilization: None,
+            gsv_missing: false,
+            chip_id: None,
+            chip_info: None,
+            text_messages: Vec::new(),
         }
     }
 }
 
+/// Active serial connection settings, as negotiated with the port
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionInfo {
+    pub port_name: String,
+    pub baud_rate: u32,
+    pub flow_control: String,
+    pub ubx_config_applied: bool,
+    pub pmtk_config_applied: bool,
+}
+
 // NMEA sentence buffer size
 const NMEA_BUFFER_SIZE: usize = 100;
 
+/// TXT messages kept in the connection status
+const TEXT_MESSAGE_LIMIT: usize = 5;
+
+/// Upper bound on a single timed NMEA capture
+pub const MAX_NMEA_CAPTURE_SECONDS: u64 = 300;
+
+// Serial read timeout in the reader loop; bounds how long the stop flag can go unnoticed
+const READ_TIMEOUT_MS: u64 = 1000;
+
+// Maximum time disconnect waits for the reader thread to release the port
+const READER_JOIN_TIMEOUT_MS: u64 = READ_TIMEOUT_MS + 500;
+
+// Pause before reopening the port after a transient read error, and between checks
+// for a removed device coming back; kept under the reader join timeout
+const RECONNECT_POLL_MS: u64 = 500;
+
+// Reopen attempts after a burst of transient read errors before the reader gives up
+const MAX_READ_ERROR_RECONNECTS: u32 = 3;
+
+// Baud rates tried on each port during auto-detect
+const AUTO_DETECT_BAUD_RATES: [u32; 3] = [4800, 9600, 115200];
+
+// Overall limit on a scan for every connected GPS; checked between probes
+const AUTO_DETECT_ALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Default and longest read for probe_port
+pub const DEFAULT_PROBE_MS: u64 = 3000;
+const MAX_PROBE_MS: u64 = 10_000;
+
+// How long change_baud waits for a valid sentence at the new rate
+const BAUD_CHANGE_VERIFY_MS: u64 = 3000;
+
+// How long to wait for the ACK to each CFG message of a config file
+const UBX_ACK_TIMEOUT_MS: u64 = 1000;
+
+// ACKs kept for matching against sent config messages
+const UBX_ACK_BUFFER_SIZE: usize = 32;
+
+// Time the receiver gets to come back from a warm restart before it is polled
+const RESET_SETTLE_MS: u64 = 1500;
+
+/// Answer to a UBX poll: message class, ID and payload
+type PollResponse = (u8, u8, Vec<u8>);
+
+/// Called by the reader once the port it was started on has opened
+type OnOpen = Box<dyn FnOnce() + Send>;
+
+// Update rate requested by every profile we apply (UBX optimizer, PMTK marine profile)
+pub const CONFIGURED_UPDATE_RATE_HZ: f32 = 1.0;
+
+// Allowed relative deviation of the observed rate before it counts as a mismatch
+const RATE_MISMATCH_TOLERANCE: f32 = 0.25;
+
+// Number of recent GGA arrivals used to measure the update rate
+const RATE_WINDOW_SENTENCES: usize = 10;
+
+/// Measures the navigation update rate from GGA arrival times
+#[derive(Default)]
+struct RateMeter {
+    arrivals: VecDeque<Instant>,
+}
+
+impl RateMeter {
+    fn record(&mut self, at: Instant) {
+        self.arrivals.push_back(at);
+        if self.arrivals.len() > RATE_WINDOW_SENTENCES {
+            self.arrivals.pop_front();
+        }
+    }
+
+    /// Rate from the median GGA interval, so one delayed sentence doesn't skew it.
+    /// None until three GGAs have arrived.
+    fn rate_hz(&self) -> Option<f32> {
+        if self.arrivals.len() < 3 {
+            return None;
+        }
+        let mut intervals: Vec<f32> = self
+            .arrivals
+            .iter()
+            .zip(self.arrivals.iter().skip(1))
+            .map(|(a, b)| b.duration_since(*a).as_secs_f32())
+            .collect();
+        intervals.sort_by(|a, b| a.total_cmp(b));
+        let median = intervals[intervals.len() / 2];
+        (median > 0.0).then(|| 1.0 / median)
+    }
+}
+
+/// Send a MON-VER poll, then read `stream` until `duration` has passed or it ends
+fn probe_stream<S: std::io::Read + Write>(stream: &mut S, duration: Duration) -> PortProbe {
+    let start = Instant::now();
+    let mut probe = PortProbe::default();
+    if let Err(e) = stream.write_all(&ubx_config::build_mon_ver_poll()) {
+        log::warn!("Probe could not send MON-VER poll: {}", e);
+    }
+
+    let mut framer = Framer::default();
+    let mut sentence_types = BTreeSet::new();
+    let mut talkers = BTreeSet::new();
+    let mut buf = [0u8; 512];
+    while start.elapsed() < duration {
+        let n = match stream.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e)
+                if e.kind() == std::io::ErrorKind::TimedOut
+                    || e.kind() == std::io::ErrorKind::WouldBlock =>
+            {
+                continue
+            }
+            Err(e) => {
+                log::warn!("Probe read failed: {}", e);
+                break;
+            }
+        };
+        probe.bytes_received += n as u64;
+
+        for frame in framer.push(&buf[..n]) {
+            match frame {
+                // Noise at the wrong baud rate rarely has a '*', which checksum_ok
+                // would let through, so a probe counts only checksummed sentences
+                Frame::Nmea(line) if line.contains('*') && nmea::checksum_ok(&line) => {
+                    let Some((talker, sentence_type)) = nmea::sentence_address(&line) else {
+                        probe.invalid_sentences += 1;
+                        continue;
+                    };
+                    probe.nmea_sentences += 1;
+                    if talker != "P" {
+                        talkers.insert(talker.to_string());
+                    }
+                    sentence_types.insert(sentence_type.to_string());
+                }
+                Frame::Nmea(_) => probe.invalid_sentences += 1,
+                Frame::Ubx { class, id, payload } => {
+                    probe.ubx_frames += 1;
+                    if class == ubx_config::UBX_CLASS_MON && id == ubx_config::UBX_MON_VER {
+                        probe.ublox = ubx_config::parse_mon_ver(&payload);
+                    }
+                }
+            }
+        }
+    }
+
+    probe.duration_ms = start.elapsed().as_millis() as u64;
+    probe.sentence_types = sentence_types.into_iter().collect();
+    probe.talkers = talkers.into_iter().collect();
+    probe
+}
+
+// Sliding window for the serial throughput measurement
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(5);
+
+/// Measures raw serial throughput from the size and arrival time of each read
+#[derive(Default)]
+struct ByteMeter {
+    reads: VecDeque<(Instant, usize)>,
+    first_read: Option<Instant>,
+    total: u64,
+}
+
+impl ByteMeter {
+    fn record(&mut self, at: Instant, bytes: usize) {
+        self.first_read.get_or_insert(at);
+        self.total += bytes as u64;
+        self.reads.push_back((at, bytes));
+        while self
+            .reads
+            .front()
+            .is_some_and(|(t, _)| at.duration_since(*t) > THROUGHPUT_WINDOW)
+        {
+            self.reads.pop_front();
+        }
+    }
+
+    /// Bytes per second over the window (or since the first read, if more recent).
+    /// None until a second has passed, since NMEA arrives in one burst per epoch.
+    fn bytes_per_second(&self, now: Instant) -> Option<f32> {
+        let span = now.duration_since(self.first_read?).min(THROUGHPUT_WINDOW);
+        if span < Duration::from_secs(1) {
+            return None;
+        }
+        let bytes: usize = self
+            .reads
+            .iter()
+            .filter(|(t, _)| now.duration_since(*t) <= THROUGHPUT_WINDOW)
+            .map(|(_, n)| n)
+            .sum();
+        Some(bytes as f32 / span.as_secs_f32())
+    }
+}
+
+/// True if `observed` is outside the tolerance band around `configured`
+fn is_rate_mismatch(observed: f32, configured: f32) -> bool {
+    (observed - configured).abs() > configured * RATE_MISMATCH_TOLERANCE
+}
+
+/// UBX answers the reader keeps for `poll_ubx` rather than handling itself
+fn is_poll_response(class: u8, id: u8) -> bool {
+    matches!(
+        (class, id),
+        (ubx_config::UBX_CLASS_CFG, ubx_config::UBX_CFG_NAV5)
+            | (ubx_config::UBX_CLASS_CFG, ubx_config::UBX_CFG_VALGET)
+            | (ubx_config::UBX_CLASS_SEC, ubx_config::UBX_SEC_UNIQID)
+            | (ubx_config::UBX_CLASS_NAV, ubx_config::UBX_NAV_STATUS)
+            | (ubx_config::UBX_CLASS_NAV, ubx_config::UBX_NAV_ORB)
+    )
+}
+
+/// GGA from any talker, e.g. $GPGGA or $GNGGA
+fn is_gga(sentence: &str) -> bool {
+    sentence.get(3..6) == Some("GGA")
+}
+
 // ============ Initial UBX Configuration (on connect) ============
 
 /// Configure a u-blox GPS receiver for multi-constellation on connect
@@ -113,36 +667,75 @@ fn configure_ublox_multi_constellation(port: &mut Box<dyn serialport::SerialPort
     Ok(())
 }
 
+// ============ Initial PMTK Configuration (on connect) ============
+
+/// Configure a MediaTek GPS receiver with the marine PMTK profile on connect
+fn configure_mediatek(port: &mut Box<dyn serialport::SerialPort>) -> Result<(), std::io::Error> {
+    log::info!("Configuring MediaTek receiver: {}", pmtk::profile_name());
+    thread::sleep(Duration::from_millis(100));
+
+    // PMTK acknowledgements ($PMTK001) are plain NMEA and pass through the reader
+    for cmd in &pmtk::get_marine_profile_commands() {
+        port.write_all(cmd)?;
+        port.flush()?;
+        thread::sleep(Duration::from_millis(250));
+    }
+
+    log::info!("MediaTek configuration complete");
+    Ok(())
+}
+
 // ============ GPS Manager ============
 
 pub struct GpsManager {
-    pub data: Arc<RwLock<GpsData>>,
-    pub status: Arc<RwLock<GpsSourceStatus>>,
-    stop_flag: Arc<AtomicBool>,
+    pub data: Arc<SnapshotLock<GpsData>>,
+    pub status: Arc<SnapshotLock<GpsSourceStatus>>,
+    /// Stop flag of the current reader. Each reader gets its own, so one detached by
+    /// a slow disconnect stays stopped when the next connection starts.
+    stop_flag: std::sync::Mutex<Arc<AtomicBool>>,
     reader_handle: std::sync::Mutex<Option<thread::JoinHandle<()>>>,
     nmea_buffer: Arc<RwLock<Vec<String>>>,
+    /// Unbounded sentence capture, active only while a timed capture is running
+    nmea_capture: Arc<Mutex<Option<Vec<String>>>>,
+    /// Raw byte capture (NMEA and UBX), active only while a timed raw capture is running
+    raw_capture: Arc<Mutex<Option<RawCapture>>>,
     /// Cloned serial port handle for writing UBX commands
     pub write_port: Arc<Mutex<Option<Box<dyn serialport::SerialPort + Send>>>>,
     /// Optimization engine
     pub optimizer: Arc<RwLock<UbxOptimizer>>,
+    /// Recent ACK-ACK / ACK-NAK responses from the receiver
+    ubx_acks: Arc<Mutex<VecDeque<UbxAck>>>,
+    /// Recent answers to UBX polls (config read-back, SEC-UNIQID)
+    poll_responses: Arc<Mutex<VecDeque<PollResponse>>>,
+    /// SEC-UNIQID went unanswered on this connection, so it isn't polled again
+    chip_id_unanswered: AtomicBool,
 }
 
 impl GpsManager {
     pub fn new() -> Self {
         Self {
-            data: Arc::new(RwLock::new(GpsData::default())),
-            status: Arc::new(RwLock::new(GpsSourceStatus::default())),
-            stop_flag: Arc::new(AtomicBool::new(false)),
+            data: Arc::new(SnapshotLock::new(GpsData::default())),
+            status: Arc::new(SnapshotLock::new(GpsSourceStatus::default())),
+            stop_flag: std::sync::Mutex::new(Arc::new(AtomicBool::new(false))),
             reader_handle: std::sync::Mutex::new(None),
             nmea_buffer: Arc::new(RwLock::new(Vec::with_capacity(NMEA_BUFFER_SIZE))),
+            nmea_capture: Arc::new(Mutex::new(None)),
+            raw_capture: Arc::new(Mutex::new(None)),
             write_port: Arc::new(Mutex::new(None)),
             optimizer: Arc::new(RwLock::new(UbxOptimizer::new())),
+            ubx_acks: Arc::new(Mutex::new(VecDeque::with_capacity(UBX_ACK_BUFFER_SIZE))),
+            poll_responses: Arc::new(Mutex::new(VecDeque::with_capacity(UBX_ACK_BUFFER_SIZE))),
+            chip_id_unanswered: AtomicBool::new(false),
         }
     }
 
-    /// Get recent NMEA sentences
-    pub fn get_nmea_buffer(&self) -> Vec<String> {
-        self.nmea_buffer.read().unwrap().clone()
+    /// Get recent NMEA sentences matching the filter. The buffer itself keeps everything.
+    pub fn get_nmea_buffer(&self, filter: &NmeaFilter) -> Vec<String> {
+        let buffer = self.nmea_buffer.read().unwrap();
+        if filter.is_empty() {
+            return buffer.clone();
+        }
+        buffer.iter().filter(|line| filter.matches(line)).cloned().collect()
     }
 
     /// Clear the NMEA buffer
@@ -150,6 +743,36 @@ impl GpsManager {
         self.nmea_buffer.write().unwrap().clear();
     }
 
+    /// Begin collecting every received sentence (independent of the ring buffer)
+    pub fn start_nmea_capture(&self) -> Result<(), GpsError> {
+        let mut capture = self.nmea_capture.lock().unwrap();
+        if capture.is_some() {
+            return Err(GpsError::CaptureInProgress);
+        }
+        *capture = Some(Vec::new());
+        Ok(())
+    }
+
+    /// Stop the running capture and return the sentences collected
+    pub fn finish_nmea_capture(&self) -> Vec<String> {
+        self.nmea_capture.lock().unwrap().take().unwrap_or_default()
+    }
+
+    /// Begin recording every byte read from the port, with read times
+    pub fn start_raw_capture(&self) -> Result<(), GpsError> {
+        let mut capture = self.raw_capture.lock().unwrap();
+        if capture.is_some() {
+            return Err(GpsError::CaptureInProgress);
+        }
+        *capture = Some(RawCapture::new());
+        Ok(())
+    }
+
+    /// Stop the running raw capture and return the reads recorded
+    pub fn finish_raw_capture(&self) -> Vec<RawChunk> {
+        self.raw_capture.lock().unwrap().take().map(|c| c.chunks).unwrap_or_default()
+    }
+
     /// Send all pending UBX commands from the optimizer via the write port
     pub fn send_pending_commands(&self) {
         let commands: Vec<Vec<u8>> = {
@@ -161,19 +784,345 @@ impl GpsManager {
             return;
         }
 
+        match self.send_commands(&commands) {
+            Ok(()) => {}
+            Err(GpsError::NotConnected) => {
+                log::warn!("Cannot send UBX commands: no write port available");
+            }
+            Err(e) => log::warn!("Failed to send UBX command: {}", e),
+        }
+    }
+
+    /// True once the connected device is known to be u-blox: by USB identity on
+    /// connect, or by a MON-VER response to the optimizer
+    pub fn is_ublox_confirmed(&self) -> bool {
+        self.status.snapshot().ublox_identified
+            || self.optimizer.read().unwrap().chip_info.is_some()
+    }
+
+    /// Write raw UBX commands to the receiver via the write port, pausing between each.
+    /// Every UBX write goes through here, so this is where non-u-blox devices
+    /// (MediaTek, unknown NMEA units) are protected from config they'd misread.
+    pub fn send_commands(&self, commands: &[Vec<u8>]) -> Result<(), GpsError> {
+        if !self.is_ublox_confirmed() {
+            return Err(GpsError::NotUblox);
+        }
+
         let mut port_guard = self.write_port.lock().unwrap();
-        if let Some(ref mut port) = *port_guard {
-            for cmd in &commands {
-                if let Err(e) = port.write_all(cmd) {
-                    log::warn!("Failed to send UBX command: {}", e);
-                    break;
-                }
-                let _ = port.flush();
-                thread::sleep(Duration::from_millis(250));
+        let port = port_guard.as_mut().ok_or(GpsError::NotConnected)?;
+
+        for cmd in commands {
+            port.write_all(cmd)?;
+            let _ = port.flush();
+            thread::sleep(Duration::from_millis(250));
+        }
+        Ok(())
+    }
+
+    /// Send every message of a raw UBX config file (e.g. exported from u-center) in
+    /// order, waiting for each CFG message's ACK. The whole file is validated first,
+    /// so a malformed line means nothing is sent.
+    pub fn apply_ubx_config_file(&self, path: &Path) -> Result<Vec<UbxConfigResult>, GpsError> {
+        let text = std::fs::read_to_string(path)?;
+        let frames =
+            ubx_config::parse_ubx_config_file(&text).map_err(GpsError::InvalidUbxConfig)?;
+
+        let mut results = Vec::with_capacity(frames.len());
+        for UbxConfigFrame { line, frame } in frames {
+            let (class, id) = (frame[2], frame[3]);
+            let acked = self.send_and_wait_for_ack(&frame)?;
+            if acked != Some(true) {
+                log::warn!("UBX config line {} ({:02X} {:02X}): {:?}", line, class, id, acked);
+            }
+            results.push(UbxConfigResult { line, class, id, acked });
+        }
+        Ok(results)
+    }
+
+    /// Apply the marine profile for the connected receiver's series once, command by
+    /// command, recording each ACK/NAK. Unlike the optimizer, nothing is measured.
+    pub fn apply_marine_profile(&self) -> Result<ProfileApplyReport, GpsError> {
+        let series = self
+            .optimizer
+            .read()
+            .unwrap()
+            .chip_info
+            .as_ref()
+            .map_or(UbloxSeries::Unknown, |info| info.series.clone());
+
+        let mut results = Vec::new();
+        for (i, frame) in ubx_config::get_optimization_commands(&series).iter().enumerate() {
+            let (class, id) = (frame[2], frame[3]);
+            let acked = self.send_and_wait_for_ack(frame)?;
+            let message = ubx_config::message_name(class, id);
+            if acked != Some(true) {
+                log::warn!("Marine profile step {} ({}): {:?}", i + 1, message, acked);
+            }
+            results.push(ProfileCommandResult { step: i + 1, message, class, id, acked });
+        }
+        Ok(ProfileApplyReport::from_results(ubx_config::profile_name(&series), results))
+    }
+
+    /// Send one UBX frame and, for CFG messages, wait for its ACK/NAK. Other classes
+    /// are not acknowledged by the receiver, so they report None straight away.
+    fn send_and_wait_for_ack(&self, frame: &[u8]) -> Result<Option<bool>, GpsError> {
+        let (class, id) = (frame[2], frame[3]);
+        self.ubx_acks.lock().unwrap().clear();
+        self.send_commands(&[frame.to_vec()])?;
+        Ok(if class == ubx_config::UBX_CLASS_CFG {
+            self.wait_for_ack(class, id)
+        } else {
+            None
+        })
+    }
+
+    /// Wait up to `UBX_ACK_TIMEOUT_MS` for the receiver's ACK-ACK / ACK-NAK to a message
+    fn wait_for_ack(&self, class: u8, id: u8) -> Option<bool> {
+        let deadline = Instant::now() + Duration::from_millis(UBX_ACK_TIMEOUT_MS);
+        loop {
+            let found = self
+                .ubx_acks
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|ack| ack.class == class && ack.id == id)
+                .map(|ack| ack.acked);
+            if found.is_some() || Instant::now() >= deadline {
+                return found;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// Send a UBX poll and wait up to `UBX_ACK_TIMEOUT_MS` for the receiver's answer
+    fn poll_ubx(&self, frame: &[u8]) -> Result<Option<Vec<u8>>, GpsError> {
+        let (class, id) = (frame[2], frame[3]);
+        self.poll_responses.lock().unwrap().clear();
+        self.send_commands(&[frame.to_vec()])?;
+        let deadline = Instant::now() + Duration::from_millis(UBX_ACK_TIMEOUT_MS);
+        loop {
+            let found = self
+                .poll_responses
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|response| response.0 == class && response.1 == id)
+                .map(|response| response.2.clone());
+            if found.is_some() || Instant::now() >= deadline {
+                return Ok(found);
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// Turn on periodic UBX-MON-HW output for the antenna report. Goes through
+    /// `send_commands`, so only a confirmed u-blox receiver is sent it.
+    pub fn enable_antenna_status(&self) -> Result<(), GpsError> {
+        self.send_commands(&[ubx_config::build_cfg_msg_mon_hw()])
+    }
+
+    /// Unique ID of the connected receiver's silicon, read with UBX-SEC-UNIQID the
+    /// first time and remembered for the connection. None if the receiver doesn't
+    /// answer (u-blox 7 and older have no unique ID); that is remembered too, so the
+    /// poll's timeout is only paid once per connection.
+    pub fn chip_id(&self) -> Result<Option<String>, GpsError> {
+        if let Some(id) = self.status.snapshot().chip_id.clone() {
+            return Ok(Some(id));
+        }
+        if self.chip_id_unanswered.load(Ordering::SeqCst) {
+            return Ok(None);
+        }
+        let chip_id = self
+            .poll_ubx(&ubx_config::build_sec_uniqid_poll())?
+            .and_then(|payload| ubx_config::parse_sec_uniqid(&payload));
+        match &chip_id {
+            Some(id) => {
+                log::info!("Receiver chip ID: {}", id);
+                self.status.write().chip_id = Some(id.clone());
+            }
+            None => self.chip_id_unanswered.store(true, Ordering::SeqCst),
+        }
+        Ok(chip_id)
+    }
+
+    /// Remember the optimizer's chip identification, if it has one, for the rest of
+    /// the connection
+    pub fn remember_chip_info(&self) {
+        let info = self.optimizer.read().unwrap().chip_info.clone();
+        if info.is_some() {
+            self.status.write().chip_info = info;
+        }
+    }
+
+    /// The connected receiver's u-blox identity, once the optimizer has identified it
+    pub fn chip_info(&self) -> Option<UbloxChipInfo> {
+        self.status.snapshot().chip_info.clone()
+    }
+
+    /// Poll NAV-STATUS and NAV-ORB to classify the start the receiver is about to make
+    /// (see `ubx_config::classify_start`). Either poll may go unanswered.
+    pub fn start_state(&self) -> Result<StartState, GpsError> {
+        let status = self
+            .poll_ubx(&ubx_config::build_nav_status_poll())?
+            .and_then(|payload| ubx_config::parse_nav_status(&payload));
+        let orbits = self
+            .poll_ubx(&ubx_config::build_nav_orb_poll())?
+            .and_then(|payload| ubx_config::parse_nav_orb(&payload));
+        let state = ubx_config::classify_start(status.as_ref(), orbits.as_ref());
+        log::info!(
+            "Start state: {:?} (ephemeris {:?}, almanac {:?})",
+            state.start_type,
+            state.ephemeris_satellites,
+            state.almanac_satellites
+        );
+        Ok(state)
+    }
+
+    /// Return the receiver to its factory configuration so every unit starts a test
+    /// from the same state: clear saved config and load the defaults, warm restart,
+    /// then read back the dynamic model to confirm the defaults are running. Over
+    /// UART the baud rate reverts too, so a port opened at another rate goes quiet.
+    pub fn reset_to_defaults(&self) -> Result<ConfigResetReport, GpsError> {
+        let series = self
+            .optimizer
+            .read()
+            .unwrap()
+            .chip_info
+            .as_ref()
+            .map_or(UbloxSeries::Unknown, |info| info.series.clone());
+        let sequence = ubx_config::build_reset_to_defaults(&series);
+
+        let cleared = self.send_and_wait_for_ack(&sequence.clear)?;
+        self.send_commands(&[sequence.restart])?;
+        thread::sleep(Duration::from_millis(RESET_SETTLE_MS));
+
+        let dynamic_model = self
+            .poll_ubx(&sequence.verify)?
+            .and_then(|payload| ubx_config::parse_dynamic_model(sequence.verify[3], &payload))
+            .and_then(DynamicModel::from_code);
+        Ok(ConfigResetReport {
+            cleared,
+            dynamic_model,
+            confirmed: cleared == Some(true)
+                && dynamic_model == Some(ubx_config::DEFAULT_DYNAMIC_MODEL),
+        })
+    }
+
+    /// Enable exactly the given constellations (plus SBAS), within what the connected
+    /// receiver's series can track concurrently
+    pub fn set_constellations(&self, constellations: &[Constellation]) -> Result<(), GpsError> {
+        let series = self
+            .optimizer
+            .read()
+            .unwrap()
+            .chip_info
+            .as_ref()
+            .map_or(UbloxSeries::Unknown, |info| info.series.clone());
+        let commands = ubx_config::build_constellation_commands(&series, constellations)
+            .map_err(GpsError::InvalidConstellations)?;
+        self.send_commands(&commands)
+    }
+
+    /// Turn NMEA high-precision mode on or off. Turning it on is refused unless the
+    /// optimizer identified a high-precision receiver; turning it off always goes out.
+    pub fn set_nmea_high_precision(&self, enable: bool) -> Result<(), GpsError> {
+        let chip_info = self.optimizer.read().unwrap().chip_info.clone();
+        if enable && !chip_info.as_ref().is_some_and(|info| info.is_high_precision()) {
+            return Err(GpsError::NotHighPrecision);
+        }
+        let series = chip_info.map_or(UbloxSeries::Unknown, |info| info.series);
+        self.send_commands(&[ubx_config::build_cfg_nmea_high_precision(&series, enable)])
+    }
+
+    /// Switch the receiver's UART and the open port to `new_baud` without reconnecting.
+    /// The reader thread keeps its handle, so parser, optimizer and test state carry
+    /// over. If no valid sentence arrives at the new rate, the port is switched back.
+    pub fn change_baud(&self, new_baud: u32) -> Result<(), GpsError> {
+        let status = self.status.snapshot();
+        let old_baud = status.baud_rate.ok_or(GpsError::NotConnected)?;
+        if status.pmtk_config_applied {
+            // MediaTek receivers take PMTK251 rather than UBX, from a shorter list
+            let cmd = pmtk::build_set_baud_rate(new_baud).ok_or_else(|| {
+                GpsError::InvalidSerialSettings(format!(
+                    "{} baud is not supported by MediaTek receivers",
+                    new_baud
+                ))
+            })?;
+            if old_baud == new_baud {
+                return Ok(());
             }
+            self.send_pmtk(&cmd)?;
         } else {
-            log::warn!("Cannot send UBX commands: no write port available");
+            if !ubx_config::UBX_BAUD_RATES.contains(&new_baud) {
+                return Err(GpsError::InvalidSerialSettings(format!(
+                    "{} baud is not supported by u-blox receivers",
+                    new_baud
+                )));
+            }
+            if old_baud == new_baud {
+                return Ok(());
+            }
+            let series = self
+                .optimizer
+                .read()
+                .unwrap()
+                .chip_info
+                .as_ref()
+                .map_or(UbloxSeries::Unknown, |info| info.series.clone());
+            // send_commands waits after writing, giving the receiver time to switch
+            self.send_commands(&[ubx_config::build_baud_rate_change(&series, new_baud)])?;
+        }
+
+        // The write handle is a clone of the reader's, so this retunes both
+        self.set_port_baud(new_baud)?;
+        let received_before = self.status.snapshot();
+        let deadline = Instant::now() + Duration::from_millis(BAUD_CHANGE_VERIFY_MS);
+        while Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(100));
+            if self.valid_frame_since(&received_before) {
+                log::info!("Baud rate changed from {} to {}", old_baud, new_baud);
+                self.status.write().baud_rate = Some(new_baud);
+                return Ok(());
+            }
+        }
+
+        log::warn!("No valid NMEA or UBX at {} baud, switching back to {}", new_baud, old_baud);
+        self.set_port_baud(old_baud)?;
+        Err(GpsError::BaudChangeFailed(new_baud, old_baud))
+    }
+
+    /// Write one PMTK sentence to a MediaTek receiver, then give it time to act on it
+    fn send_pmtk(&self, cmd: &[u8]) -> Result<(), GpsError> {
+        let mut port_guard = self.write_port.lock().unwrap();
+        let port = port_guard.as_mut().ok_or(GpsError::NotConnected)?;
+        port.write_all(cmd)?;
+        let _ = port.flush();
+        thread::sleep(Duration::from_millis(250));
+        Ok(())
+    }
+
+    fn set_port_baud(&self, baud_rate: u32) -> Result<(), GpsError> {
+        let mut port_guard = self.write_port.lock().unwrap();
+        let port = port_guard.as_mut().ok_or(GpsError::NotConnected)?;
+        port.set_baud_rate(baud_rate)?;
+        Ok(())
+    }
+
+    /// True if a checksum-verified UBX frame or NMEA sentence has arrived since
+    /// `before`. Line noise at a mismatched rate rarely carries a valid checksum, and a
+    /// sentence without one proves nothing, so those are not counted.
+    fn valid_frame_since(&self, before: &GpsSourceStatus) -> bool {
+        let status = self.status.snapshot();
+        if status.ubx_frames_received > before.ubx_frames_received {
+            return true;
         }
+        let new = status.sentences_received.saturating_sub(before.sentences_received);
+        let buffer = self.nmea_buffer.read().unwrap();
+        buffer
+            .iter()
+            .rev()
+            .take(new as usize)
+            .any(|line| line.contains('*') && nmea::checksum_ok(line))
     }
 
     /// Enumerate all available serial ports
@@ -225,8 +1174,14 @@ impl GpsManager {
     }
 
     /// Test if a port is a GPS device by reading a few sentences
-    pub fn test_port(port_name: &str, baud_rate: u32, timeout_ms: u64) -> Result<bool, GpsError> {
-        let port = serialport::new(port_name, baud_rate)
+    pub fn test_port(
+        port_name: &str,
+        baud_rate: u32,
+        timeout_ms: u64,
+        settings: &SerialSettings,
+    ) -> Result<bool, GpsError> {
+        let port = settings
+            .builder(port_name, baud_rate)?
             .timeout(Duration::from_millis(timeout_ms))
             .open()?;
 
@@ -259,20 +1214,126 @@ impl GpsManager {
         Ok(nmea_count > 0)
     }
 
+    /// Open a port, poll MON-VER and read for `duration_ms` (at most 10 s), reporting
+    /// everything seen so the UI can explain why a port is or is not a GPS
+    pub fn probe_port(
+        port_name: &str,
+        baud_rate: u32,
+        duration_ms: u64,
+        settings: &SerialSettings,
+    ) -> Result<PortProbe, GpsError> {
+        let mut port = settings
+            .builder(port_name, baud_rate)?
+            .timeout(Duration::from_millis(100))
+            .open()?;
+        let duration = Duration::from_millis(duration_ms.min(MAX_PROBE_MS));
+        Ok(PortProbe {
+            port_name: port_name.to_string(),
+            baud_rate,
+            ..probe_stream(&mut port, duration)
+        })
+    }
+
     /// Auto-detect GPS hardware: scan all ports, test likely candidates first
-    pub fn auto_detect_gps() -> Result<(DetectedPort, u32), GpsError> {
+    /// `cancel` is checked between port/baud attempts; setting it aborts the scan
+    /// with `GpsError::AutoDetectCancelled`.
+    pub fn auto_detect_gps(cancel: &AtomicBool) -> Result<(DetectedPort, u32), GpsError> {
         let ports = Self::list_serial_ports()?;
+        Self::scan_ports(ports, cancel, |port_name, baud| {
+            Self::test_port(port_name, baud, 3000, &SerialSettings::default())
+        })
+    }
 
-        // Sort: likely GPS devices first
-        let mut sorted = ports;
-        sorted.sort_by_key(|p| if p.is_likely_gps { 0 } else { 1 });
+    /// Probe every port, unlike `auto_detect_gps`, which stops at the first GPS, so the
+    /// user can choose when several receivers are plugged in
+    pub fn auto_detect_all_gps(cancel: &AtomicBool) -> Result<Vec<DetectedGps>, GpsError> {
+        let ports = Self::list_serial_ports()?;
+        let deadline = Instant::now() + AUTO_DETECT_ALL_TIMEOUT;
+        let found = Self::scan_all_ports(ports, cancel, deadline, |port_name, baud| {
+            Self::test_port(port_name, baud, 3000, &SerialSettings::default())
+        })?;
+
+        Ok(found
+            .into_iter()
+            .map(|(port, baud_rate)| {
+                let receiver = if is_ublox_device(&port.port_name) {
+                    "u-blox"
+                } else if is_mediatek_device(&port.port_name) {
+                    "MediaTek"
+                } else {
+                    "NMEA"
+                };
+                DetectedGps { port, baud_rate, receiver: receiver.to_string() }
+            })
+            .collect())
+    }
 
-        let baud_rates = [4800u32, 9600, 115200];
+    /// Find the saved port if it is still present and sending NMEA at its saved baud,
+    /// otherwise fall back to a full auto-detect
+    pub fn find_last_port(
+        saved: Option<&LastPort>,
+        cancel: &AtomicBool,
+    ) -> Result<(DetectedPort, u32), GpsError> {
+        let ports = Self::list_serial_ports()?;
+        Self::resolve_last_port(ports, saved, cancel, |port_name, baud| {
+            Self::test_port(port_name, baud, 3000, &SerialSettings::default())
+        })
+    }
 
-        for port in &sorted {
-            for &baud in &baud_rates {
+    fn resolve_last_port<F>(
+        ports: Vec<DetectedPort>,
+        saved: Option<&LastPort>,
+        cancel: &AtomicBool,
+        mut probe: F,
+    ) -> Result<(DetectedPort, u32), GpsError>
+    where
+        F: FnMut(&str, u32) -> Result<bool, GpsError>,
+    {
+        if let Some(last) = saved {
+            match ports.iter().find(|p| p.port_name == last.port_name) {
+                Some(port) if probe(&last.port_name, last.baud_rate).unwrap_or(false) => {
+                    log::info!(
+                        "Reconnecting to saved GPS on {} at {} baud",
+                        last.port_name,
+                        last.baud_rate
+                    );
+                    return Ok((port.clone(), last.baud_rate));
+                }
+                Some(_) => log::warn!(
+                    "Saved GPS port {} has no NMEA at {} baud, auto-detecting",
+                    last.port_name,
+                    last.baud_rate
+                ),
+                None => log::warn!(
+                    "Saved GPS port {} no longer exists, auto-detecting",
+                    last.port_name
+                ),
+            }
+        }
+
+        Self::scan_ports(ports, cancel, probe)
+    }
+
+    /// Probe each port at each baud rate until `probe` reports NMEA traffic
+    fn scan_ports<F>(
+        mut ports: Vec<DetectedPort>,
+        cancel: &AtomicBool,
+        mut probe: F,
+    ) -> Result<(DetectedPort, u32), GpsError>
+    where
+        F: FnMut(&str, u32) -> Result<bool, GpsError>,
+    {
+        // Sort: likely GPS devices first
+        ports.sort_by_key(|p| if p.is_likely_gps { 0 } else { 1 });
+
+        for port in &ports {
+            for &baud in &AUTO_DETECT_BAUD_RATES {
+                if cancel.load(Ordering::SeqCst) {
+                    log::info!("Auto-detect cancelled");
+                    return Err(GpsError::AutoDetectCancelled);
+                }
                 log::info!("Testing {} at {} baud...", port.port_name, baud);
-                match Self::test_port(&port.port_name, baud, 3000) {
+                match probe(&port.port_name, baud) {
                     Ok(true) => {
                         log::info!("GPS detected on {} at {} baud", port.port_name, baud);
                         return Ok((port.clone(), baud));
@@ -289,76 +1350,191 @@ impl GpsManager {
         Err(GpsError::NoGpsDetected)
     }
 
+    /// Probe each port at each baud rate, keeping the first rate at which each one
+    /// sends NMEA. Stops early at `deadline`, returning what was found so far.
+    fn scan_all_ports<F>(
+        mut ports: Vec<DetectedPort>,
+        cancel: &AtomicBool,
+        deadline: Instant,
+        mut probe: F,
+    ) -> Result<Vec<(DetectedPort, u32)>, GpsError>
+    where
+        F: FnMut(&str, u32) -> Result<bool, GpsError>,
+    {
+        ports.sort_by_key(|p| if p.is_likely_gps { 0 } else { 1 });
+        let mut found = Vec::new();
+
+        'ports: for port in &ports {
+            for &baud in &AUTO_DETECT_BAUD_RATES {
+                if cancel.load(Ordering::SeqCst) {
+                    log::info!("Auto-detect cancelled");
+                    return Err(GpsError::AutoDetectCancelled);
+                }
+                if Instant::now() >= deadline {
+                    log::warn!("Auto-detect timed out; returning {} GPS found so far", found.len());
+                    break 'ports;
+                }
+                log::info!("Testing {} at {} baud...", port.port_name, baud);
+                match probe(&port.port_name, baud) {
+                    Ok(true) => {
+                        log::info!("GPS detected on {} at {} baud", port.port_name, baud);
+                        found.push((port.clone(), baud));
+                        continue 'ports;
+                    }
+                    Ok(false) => {}
+                    Err(e) => log::debug!("Port test failed for {}: {}", port.port_name, e),
+                }
+            }
+        }
+
+        Ok(found)
+    }
+
     /// Get current GPS data
     pub fn get_data(&self) -> GpsData {
-        self.data.read().unwrap().clone()
+        (*self.data.snapshot()).clone()
     }
 
     /// Get current status
     pub fn get_status(&self) -> GpsSourceStatus {
-        self.status.read().unwrap().clone()
+        (*self.status.snapshot()).clone()
     }
 
-    /// Connect to a specific GPS port and start reading
-    pub fn connect(&self, port_name: &str, baud_rate: u32) -> Result<(), GpsError> {
+    /// Get the active connection settings (None until the port has opened)
+    pub fn get_connection_info(&self) -> Option<ConnectionInfo> {
+        let status = self.status.snapshot();
+        Some(ConnectionInfo {
+            port_name: status.port_name.clone()?,
+            baud_rate: status.baud_rate?,
+            flow_control: status.flow_control.clone().unwrap_or_else(|| "Unknown".into()),
+            ubx_config_applied: status.ubx_config_applied,
+            pmtk_config_applied: status.pmtk_config_applied,
+        })
+    }
+
+    /// Connect to a specific GPS port and start reading. The port is opened by the
+    /// reader thread, which calls `on_open` the first time it succeeds.
+    pub fn connect(
+        &self,
+        port_name: &str,
+        baud_rate: u32,
+        settings: SerialSettings,
+        on_open: impl FnOnce() + Send + 'static,
+    ) -> Result<(), GpsError> {
+        // Reject bad settings before dropping the current connection
+        settings.builder(port_name, baud_rate)?;
+
         // Stop any existing reader
         self.disconnect();
 
-        // Reset stop flag
-        self.stop_flag.store(false, Ordering::SeqCst);
-
         // Update status to connecting
         {
-            let mut status = self.status.write().unwrap();
+            let mut status = self.status.write();
             status.port_name = Some(port_name.to_string());
             status.status = GpsConnectionStatus::Connecting;
             status.last_error = None;
+            status.last_error_code = None;
             status.sentences_received = 0;
+            status.ubx_frames_received = 0;
+            status.baud_rate = None;
+            status.flow_control = None;
+            status.ubx_config_applied = false;
+            status.pmtk_config_applied = false;
+            status.observed_rate_hz = None;
+            status.ublox_identified = false;
+            status.expected_rate_hz = None;
+            status.rate_mismatch = false;
+            status.bytes_received = 0;
+            status.bytes_per_second = None;
+            status.link_utilization = None;
+            status.chip_id = None;
+            status.chip_info = None;
+            status.text_messages.clear();
         }
+        self.chip_id_unanswered.store(false, Ordering::SeqCst);
 
         // Clear previous data
         {
-            let mut data = self.data.write().unwrap();
+            let mut data = self.data.write();
             *data = GpsData::default();
         }
 
-        let stop_flag = Arc::clone(&self.stop_flag);
         let data_lock = Arc::clone(&self.data);
         let status_lock = Arc::clone(&self.status);
         let nmea_buffer_lock = Arc::clone(&self.nmea_buffer);
+        let nmea_capture_lock = Arc::clone(&self.nmea_capture);
+        let raw_capture_lock = Arc::clone(&self.raw_capture);
         let write_port_lock = Arc::clone(&self.write_port);
         let optimizer_lock = Arc::clone(&self.optimizer);
+        let ubx_acks_lock = Arc::clone(&self.ubx_acks);
+        let poll_responses_lock = Arc::clone(&self.poll_responses);
         let port_name_owned = port_name.to_string();
 
-        let handle = thread::spawn(move || {
-            if let Err(e) = Self::read_from_serial(
+        self.spawn_reader(move |stop_flag| {
+            let mut read_error_reconnects = 0;
+            let mut on_open: Option<OnOpen> = Some(Box::new(on_open));
+            while let Err(e) = Self::read_from_serial(
                 &stop_flag,
+                &mut read_error_reconnects,
+                &mut on_open,
                 &data_lock,
                 &status_lock,
                 &nmea_buffer_lock,
+                &nmea_capture_lock,
+                &raw_capture_lock,
                 &write_port_lock,
                 &optimizer_lock,
+                &ubx_acks_lock,
+                &poll_responses_lock,
                 &port_name_owned,
                 baud_rate,
+                settings,
             ) {
                 log::error!("GPS reader error: {}", e);
-                let mut status = status_lock.write().unwrap();
-                status.last_error = Some(e.to_string());
-                status.status = GpsConnectionStatus::Error;
+                {
+                    let mut status = status_lock.write();
+                    status.last_error = Some(e.to_string());
+                    status.last_error_code = Some(e.code().to_string());
+                    status.status = match e {
+                        GpsError::DeviceRemoved(_) => GpsConnectionStatus::DeviceRemoved,
+                        GpsError::ReadFailed(_) => GpsConnectionStatus::ReadError,
+                        _ => GpsConnectionStatus::Error,
+                    };
+                }
+                let reconnect = wait_to_reconnect(
+                    &e,
+                    &mut read_error_reconnects,
+                    &stop_flag,
+                    || port_present(&port_name_owned),
+                    Duration::from_millis(RECONNECT_POLL_MS),
+                );
+                if !reconnect {
+                    break;
+                }
+                log::info!("Reopening GPS port {}", port_name_owned);
             }
         });
 
-        *self.reader_handle.lock().unwrap() = Some(handle);
         Ok(())
     }
 
-    /// Stop GPS reading
+    /// Spawn the reader thread, handing it a new stop flag for `disconnect` to set
+    fn spawn_reader<F>(&self, reader: F)
+    where
+        F: FnOnce(Arc<AtomicBool>) + Send + 'static,
+    {
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        *self.stop_flag.lock().unwrap() = Arc::clone(&stop_flag);
+        let handle = thread::spawn(move || reader(stop_flag));
+        *self.reader_handle.lock().unwrap() = Some(handle);
+    }
+
+    /// Stop GPS reading, waiting for the reader thread to release the port
     pub fn disconnect(&self) {
-        self.stop_flag.store(true, Ordering::SeqCst);
+        self.stop_flag.lock().unwrap().store(true, Ordering::SeqCst);
 
         if let Some(handle) = self.reader_handle.lock().unwrap().take() {
-            thread::sleep(Duration::from_millis(100));
-            drop(handle);
+            join_reader(handle, Duration::from_millis(READER_JOIN_TIMEOUT_MS));
         }
 
         // Clear write port
@@ -367,33 +1543,52 @@ impl GpsManager {
         // Reset optimizer
         self.optimizer.write().unwrap().reset();
 
-        let mut status = self.status.write().unwrap();
+        let mut status = self.status.write();
         status.status = GpsConnectionStatus::Disconnected;
     }
 
     /// Read GPS data from a serial port
     fn read_from_serial(
         stop_flag: &Arc<AtomicBool>,
-        data_lock: &RwLock<GpsData>,
-        status_lock: &RwLock<GpsSourceStatus>,
+        read_error_reconnects: &mut u32,
+        on_open: &mut Option<OnOpen>,
+        data_lock: &SnapshotLock<GpsData>,
+        status_lock: &SnapshotLock<GpsSourceStatus>,
         nmea_buffer_lock: &RwLock<Vec<String>>,
+        nmea_capture_lock: &Mutex<Option<Vec<String>>>,
+        raw_capture_lock: &Mutex<Option<RawCapture>>,
         write_port_lock: &Arc<Mutex<Option<Box<dyn serialport::SerialPort + Send>>>>,
         optimizer_lock: &Arc<RwLock<UbxOptimizer>>,
+        ubx_acks_lock: &Mutex<VecDeque<UbxAck>>,
+        poll_responses_lock: &Mutex<VecDeque<PollResponse>>,
         port_name: &str,
         baud_rate: u32,
+        settings: SerialSettings,
     ) -> Result<(), GpsError> {
-        let mut port = serialport::new(port_name, baud_rate)
-            .timeout(Duration::from_millis(1000))
+        let mut port = settings
+            .builder(port_name, baud_rate)?
+            .timeout(Duration::from_millis(READ_TIMEOUT_MS))
             .open()?;
 
-        // Update status to connected
+        // Update status to connected, recording the settings the port actually opened with
         {
-            let mut status = status_lock.write().unwrap();
+            let mut status = status_lock.write();
             status.status = GpsConnectionStatus::Connected;
             status.last_error = None;
+            status.last_error_code = None;
+            status.baud_rate = Some(port.baud_rate().unwrap_or(baud_rate));
+            status.flow_control = port.flow_control().ok().map(|f| f.to_string());
+        }
+        if let Some(on_open) = on_open.take() {
+            on_open();
         }
 
-        // Clone port for writing before wrapping in BufReader
+        // A reopened port may have a different receiver behind it, so nothing read
+        // before carries over; the parser below starts empty too
+        *data_lock.write() = GpsData::default();
+
+        // Commands (optimizer, config, polls) are written through a clone of the port
+        // while this thread keeps reading from the original
         match port.try_clone() {
             Ok(write_clone) => {
                 *write_port_lock.lock().unwrap() = Some(write_clone);
@@ -404,90 +1599,79 @@ impl GpsManager {
         }
 
         // Only configure via UBX if this looks like a u-blox receiver
-        if is_ublox_device(port_name) {
+        let is_ublox = is_ublox_device(port_name);
+        status_lock.write().ublox_identified = is_ublox;
+        if is_ublox {
             log::info!("u-blox device detected, sending UBX configuration...");
-            if let Err(e) = configure_ublox_multi_constellation(&mut port) {
-                log::warn!("Failed to configure multi-constellation (non-fatal): {}", e);
+            match configure_ublox_multi_constellation(&mut port) {
+                Ok(()) => status_lock.write().ubx_config_applied = true,
+                Err(e) => log::warn!("Failed to configure multi-constellation (non-fatal): {}", e),
+            }
+        } else if is_mediatek_device(port_name) {
+            log::info!("MediaTek device detected, sending PMTK configuration...");
+            match configure_mediatek(&mut port) {
+                Ok(()) => status_lock.write().pmtk_config_applied = true,
+                Err(e) => log::warn!("Failed to configure MediaTek receiver (non-fatal): {}", e),
             }
         } else {
             log::info!("Non-u-blox device, skipping UBX configuration");
         }
 
         let parser = NmeaParser::new();
-        let mut reader = BufReader::new(port);
-        let mut buf = Vec::with_capacity(512);
+        // NMEA lines and binary UBX frames arrive interleaved, and a UBX frame has no
+        // line ending of its own, so the stream is split by the framer, not by lines
+        let mut framer = Framer::default();
+        let mut buf = [0u8; 1024];
         let mut sentences_received: u64 = 0;
         let mut consecutive_errors: u32 = 0;
-
-        // UBX binary frame accumulation buffer
-        let mut ubx_buffer: Vec<u8> = Vec::new();
+        let mut rate_meter = RateMeter::default();
+        let mut gsv_watch = GsvWatch::default();
+        let mut gsv_enable_sent = false;
+        let mut byte_meter = ByteMeter::default();
 
         while !stop_flag.load(Ordering::SeqCst) {
-            buf.clear();
-            match reader.read_until(b'\n', &mut buf) {
+            match port.read(&mut buf) {
                 Ok(0) => {
                     // EOF — device likely disconnected
                     log::warn!("GPS serial port returned EOF (device disconnected?)");
-                    let mut status = status_lock.write().unwrap();
-                    status.status = GpsConnectionStatus::Disconnected;
-                    status.last_error = Some("Device disconnected".to_string());
-                    break;
+                    return Err(classify_read_failure(
+                        "port returned EOF".to_string(),
+                        port_present(port_name),
+                    ));
                 }
-                Ok(_) => {
+                // Disconnected while blocked in the read: the bytes belong to the old connection
+                Ok(_) if stop_flag.load(Ordering::SeqCst) => break,
+                Ok(bytes) => {
                     consecutive_errors = 0;
-
-                    // Check if optimizer is awaiting a UBX binary response
-                    let awaiting_ubx = optimizer_lock.read().unwrap().awaiting_mon_ver;
-
-                    if awaiting_ubx {
-                        // Scan for UBX sync bytes in the raw buffer
-                        if let Some(sync_pos) = buf
-                            .windows(2)
-                            .position(|w| w[0] == ubx_config::UBX_SYNC_1 && w[1] == ubx_config::UBX_SYNC_2)
-                        {
-                            ubx_buffer.extend_from_slice(&buf[sync_pos..]);
-                        } else if !ubx_buffer.is_empty() {
-                            // Continue accumulating binary data
-                            ubx_buffer.extend_from_slice(&buf);
-                        }
-
-                        // Try to extract a complete UBX frame
-                        if ubx_buffer.len() >= 8 {
-                            let payload_len =
-                                u16::from_le_bytes([ubx_buffer[4], ubx_buffer[5]]) as usize;
-                            let total_len = 6 + payload_len + 2; // header(6) + payload + checksum(2)
-
-                            if ubx_buffer.len() >= total_len {
-                                let class = ubx_buffer[2];
-                                let id = ubx_buffer[3];
-
-                                // MON-VER response: class=0x0A, id=0x04
-                                if class == ubx_config::UBX_CLASS_MON
-                                    && id == ubx_config::UBX_MON_VER
-                                {
-                                    let payload = ubx_buffer[6..6 + payload_len].to_vec();
-                                    optimizer_lock
-                                        .write()
-                                        .unwrap()
-                                        .on_mon_ver_response(&payload);
-                                    log::info!(
-                                        "UBX-MON-VER response received ({} bytes payload)",
-                                        payload_len
-                                    );
-                                }
-                                ubx_buffer.clear();
-                            }
-                        }
-                    } else {
-                        ubx_buffer.clear();
+                    // The port works again: a later read error starts a new burst
+                    *read_error_reconnects = 0;
+                    byte_meter.record(Instant::now(), bytes);
+                    if let Some(ref mut capture) = *raw_capture_lock.lock().unwrap() {
+                        capture.record(&buf[..bytes]);
                     }
 
-                    // Process NMEA text data
-                    let line = String::from_utf8_lossy(&buf);
-                    let trimmed = line.trim();
-                    if trimmed.starts_with('$') {
+                    for frame in framer.push(&buf[..bytes]) {
+                        let line = match frame {
+                            Frame::Ubx { class, id, payload } => {
+                                status_lock.write().ubx_frames_received += 1;
+                                Self::handle_ubx_frame(
+                                    class,
+                                    id,
+                                    payload,
+                                    data_lock,
+                                    optimizer_lock,
+                                    ubx_acks_lock,
+                                    poll_responses_lock,
+                                );
+                                continue;
+                            }
+                            Frame::Nmea(line) => line,
+                        };
+                        let trimmed = line.as_str();
                         sentences_received += 1;
-
+                        if is_gga(trimmed) {
+                            rate_meter.record(Instant::now());
+                        }
                         // Add to NMEA buffer (ring buffer)
                         {
                             let mut buffer = nmea_buffer_lock.write().unwrap();
@@ -497,31 +1681,83 @@ impl GpsManager {
                             buffer.push(trimmed.to_string());
                         }
 
+                        // Add to timed capture, if one is running
+                        if let Some(ref mut capture) = *nmea_capture_lock.lock().unwrap() {
+                            capture.push(trimmed.to_string());
+                        }
+
                         // Parse the NMEA sentence
                         if let Ok(new_data) = parser.parse_sentence(trimmed) {
-                            let mut data = data_lock.write().unwrap();
-                            if new_data.latitude.is_some() { data.latitude = new_data.latitude; }
-                            if new_data.longitude.is_some() { data.longitude = new_data.longitude; }
-                            if new_data.speed_knots.is_some() { data.speed_knots = new_data.speed_knots; }
-                            if new_data.course.is_some() { data.course = new_data.course; }
-                            if new_data.heading.is_some() { data.heading = new_data.heading; }
-                            if new_data.altitude.is_some() { data.altitude = new_data.altitude; }
-                            if new_data.fix_quality.is_some() { data.fix_quality = new_data.fix_quality; }
-                            if new_data.satellites.is_some() { data.satellites = new_data.satellites; }
-                            if new_data.hdop.is_some() { data.hdop = new_data.hdop; }
-                            if new_data.vdop.is_some() { data.vdop = new_data.vdop; }
-                            if new_data.pdop.is_some() { data.pdop = new_data.pdop; }
-                            if new_data.timestamp.is_some() { data.timestamp = new_data.timestamp.clone(); }
-                            if new_data.fix_type.is_some() { data.fix_type = new_data.fix_type.clone(); }
-                            if !new_data.satellites_info.is_empty() { data.satellites_info = new_data.satellites_info.clone(); }
+                            data_lock.write().merge(new_data);
+                        }
+                        let gsv_missing = {
+                            let data = data_lock.snapshot();
+                            let has_fix = data.fix_quality.unwrap_or(0) > 0;
+                            gsv_watch.observe(&data, has_fix, Instant::now())
+                        };
+                        if gsv_missing && is_ublox && !gsv_enable_sent {
+                            gsv_enable_sent = true;
+                            let series = optimizer_lock
+                                .read()
+                                .unwrap()
+                                .chip_info
+                                .as_ref()
+                                .map_or(UbloxSeries::Unknown, |info| info.series.clone());
+                            log::warn!("No GSV from receiver, enabling GSV output");
+                            if let Some(ref mut w) = *write_port_lock.lock().unwrap() {
+                                let cmd = ubx_config::build_gsv_enable(&series);
+                                if let Err(e) = w.write_all(&cmd).and_then(|_| w.flush()) {
+                                    log::warn!("Failed to enable GSV output: {}", e);
+                                }
+                            }
+                        } else if gsv_missing && !gsv_enable_sent {
+                            gsv_enable_sent = true;
+                            log::warn!("No GSV from receiver: satellite and SNR data unavailable");
+                        }
+
+                        // Receiver diagnostics: log warnings and keep the latest few
+                        let text_message = (trimmed.get(3..6) == Some("TXT"))
+                            .then(|| nmea::parse_txt(trimmed))
+                            .flatten();
+                        if let Some(message) = &text_message {
+                            match message.severity {
+                                TextSeverity::Error | TextSeverity::Warning => {
+                                    log::warn!("Receiver: {}", message.text)
+                                }
+                                _ => log::info!("Receiver: {}", message.text),
+                            }
                         }
 
                         // Update status
                         {
-                            let mut status = status_lock.write().unwrap();
+                            let mut status = status_lock.write();
+                            if let Some(message) = text_message {
+                                if status.text_messages.len() >= TEXT_MESSAGE_LIMIT {
+                                    status.text_messages.remove(0);
+                                }
+                                status.text_messages.push(message);
+                            }
                             status.status = GpsConnectionStatus::ReceivingData;
                             status.sentences_received = sentences_received;
-                            if let Some(ref ts) = data_lock.read().unwrap().timestamp {
+                            status.observed_rate_hz = rate_meter.rate_hz();
+                            status.bytes_received = byte_meter.total;
+                            status.bytes_per_second = byte_meter.bytes_per_second(Instant::now());
+                            let bits_per_byte = settings.bits_per_byte() as f32;
+                            status.link_utilization = status
+                                .baud_rate
+                                .zip(status.bytes_per_second)
+                                .map(|(baud, bps)| bps * bits_per_byte / baud as f32);
+                            // Only meaningful once we've configured the rate ourselves
+                            let configured =
+                                status.ubx_config_applied || status.pmtk_config_applied;
+                            status.expected_rate_hz =
+                                configured.then_some(CONFIGURED_UPDATE_RATE_HZ);
+                            status.rate_mismatch = status
+                                .observed_rate_hz
+                                .zip(status.expected_rate_hz)
+                                .is_some_and(|(r, expected)| is_rate_mismatch(r, expected));
+                            status.gsv_missing = gsv_missing;
+                            if let Some(ref ts) = data_lock.snapshot().timestamp {
                                 status.last_fix_time = Some(ts.clone());
                             }
                         }
@@ -535,11 +1771,8 @@ impl GpsManager {
                     consecutive_errors += 1;
                     log::warn!("GPS serial read error ({}): {}", consecutive_errors, e);
                     if consecutive_errors >= 3 {
-                        log::error!("GPS device disconnected (too many errors)");
-                        let mut status = status_lock.write().unwrap();
-                        status.status = GpsConnectionStatus::Disconnected;
-                        status.last_error = Some(format!("Device disconnected: {}", e));
-                        break;
+                        log::error!("GPS reader stopping after {} read errors", consecutive_errors);
+                        return Err(classify_read_failure(e.to_string(), port_present(port_name)));
                     }
                     thread::sleep(Duration::from_millis(100));
                 }
@@ -548,6 +1781,66 @@ impl GpsManager {
 
         Ok(())
     }
+
+    /// Route a checksum-verified UBX frame from the reader: optimizer responses, ACKs,
+    /// poll responses and the periodic NAV/MON messages that update the live data
+    fn handle_ubx_frame(
+        class: u8,
+        id: u8,
+        payload: Vec<u8>,
+        data_lock: &SnapshotLock<GpsData>,
+        optimizer_lock: &Arc<RwLock<UbxOptimizer>>,
+        ubx_acks_lock: &Mutex<VecDeque<UbxAck>>,
+        poll_responses_lock: &Mutex<VecDeque<PollResponse>>,
+    ) {
+        // Check if optimizer is awaiting a UBX binary response
+        let awaiting_ubx = {
+            let opt = optimizer_lock.read().unwrap();
+            opt.awaiting_mon_ver || opt.awaiting_cfg_gnss
+        };
+
+        if awaiting_ubx && class == ubx_config::UBX_CLASS_MON && id == ubx_config::UBX_MON_VER {
+            log::info!("UBX-MON-VER response received ({} bytes payload)", payload.len());
+            optimizer_lock.write().unwrap().on_mon_ver_response(&payload);
+        } else if awaiting_ubx
+            && class == ubx_config::UBX_CLASS_CFG
+            && id == ubx_config::UBX_CFG_GNSS
+        {
+            optimizer_lock.write().unwrap().on_cfg_gnss_response(&payload);
+        } else if class == ubx_config::UBX_CLASS_NAV && id == ubx_config::UBX_NAV_PVT {
+            if let Some(pvt) = ubx_config::parse_nav_pvt(&payload) {
+                let mut data = data_lock.write();
+                data.horizontal_accuracy_m = Some(pvt.h_acc_m);
+                data.vertical_accuracy_m = Some(pvt.v_acc_m);
+            }
+        } else if class == ubx_config::UBX_CLASS_NAV && id == ubx_config::UBX_NAV_TIMEGPS {
+            if let Some(time) = ubx_config::parse_nav_timegps(&payload) {
+                let mut data = data_lock.write();
+                data.leap_seconds = Some(time.leap_seconds);
+                data.time_valid = Some(time.valid);
+            }
+        } else if class == ubx_config::UBX_CLASS_ACK {
+            if let Some(ack) = ubx_config::parse_ack(id, &payload) {
+                let mut acks = ubx_acks_lock.lock().unwrap();
+                if acks.len() >= UBX_ACK_BUFFER_SIZE {
+                    acks.pop_front();
+                }
+                acks.push_back(ack);
+            }
+        } else if is_poll_response(class, id) {
+            let mut responses = poll_responses_lock.lock().unwrap();
+            if responses.len() >= UBX_ACK_BUFFER_SIZE {
+                responses.pop_front();
+            }
+            responses.push_back((class, id, payload));
+        } else if class == ubx_config::UBX_CLASS_MON && id == ubx_config::UBX_MON_HW {
+            if let Some(hw) = ubx_config::parse_mon_hw(&payload) {
+                let mut data = data_lock.write();
+                data.antenna_status = hw.antenna_status;
+                data.antenna_power = hw.antenna_power;
+            }
+        }
+    }
 }
 
 impl Drop for GpsManager {
@@ -556,6 +1849,78 @@ impl Drop for GpsManager {
     }
 }
 
+/// True if the port is still listed by the OS (assumed so if ports can't be listed)
+fn port_present(port_name: &str) -> bool {
+    serialport::available_ports()
+        .map_or(true, |ports| ports.iter().any(|p| p.port_name == port_name))
+}
+
+/// A failed read on a port the OS no longer lists is a removed device (USB unplug);
+/// on a port still present it is a read error worth retrying
+fn classify_read_failure(detail: String, port_present: bool) -> GpsError {
+    if port_present {
+        GpsError::ReadFailed(detail)
+    } else {
+        GpsError::DeviceRemoved(detail)
+    }
+}
+
+/// Decide whether the reader should reopen the port after `error`, waiting as the
+/// failure calls for: a short pause for a transient read error (at most
+/// `MAX_READ_ERROR_RECONNECTS` times in a row, the count being reset by the reader
+/// once the reopened port reads), or until a removed device's port reappears.
+/// Other errors, and a raised stop flag, end the reader.
+fn wait_to_reconnect(
+    error: &GpsError,
+    read_error_reconnects: &mut u32,
+    stop_flag: &AtomicBool,
+    port_present: impl Fn() -> bool,
+    poll: Duration,
+) -> bool {
+    match error {
+        GpsError::ReadFailed(_) => {
+            if *read_error_reconnects >= MAX_READ_ERROR_RECONNECTS {
+                return false;
+            }
+            *read_error_reconnects += 1;
+            thread::sleep(poll);
+        }
+        GpsError::DeviceRemoved(_) => {
+            while !port_present() {
+                if stop_flag.load(Ordering::SeqCst) {
+                    return false;
+                }
+                thread::sleep(poll);
+            }
+            // Give the OS a moment to finish setting up the reappeared port
+            thread::sleep(poll);
+        }
+        _ => return false,
+    }
+    !stop_flag.load(Ordering::SeqCst)
+}
+
+/// Join the reader thread, giving up after `timeout` so a wedged read can't hang the UI.
+/// Returns true if the thread exited (and so released the port) in time.
+fn join_reader(handle: thread::JoinHandle<()>, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while !handle.is_finished() {
+        if Instant::now() >= deadline {
+            log::warn!(
+                "GPS reader thread did not stop within {}ms, detaching",
+                timeout.as_millis()
+            );
+            return false;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    if handle.join().is_err() {
+        log::warn!("GPS reader thread panicked");
+    }
+    true
+}
+
 /// Heuristic to detect if a USB device is likely a GPS
 fn is_likely_gps_device(manufacturer: &Option<String>, product: &Option<String>) -> bool {
     let keywords = [
@@ -603,3 +1968,552 @@ pub fn is_ublox_device(port_name: &str) -> bool {
     }
     false
 }
+
+/// Check if a connected device is a MediaTek receiver (safe to send PMTK commands)
+pub fn is_mediatek_device(port_name: &str) -> bool {
+    if let Ok(ports) = serialport::available_ports() {
+        for port in &ports {
+            if port.port_name == port_name {
+                if let SerialPortType::UsbPort(info) = &port.port_type {
+                    // MediaTek USB vendor ID is 0x0E8D (native-USB MT3329/MT3339 units)
+                    if info.vid == 0x0E8D {
+                        return true;
+                    }
+                    // Also check manufacturer/product strings (GlobalTop modules are MT3339-based)
+                    let check = |s: &Option<String>| s.as_deref().is_some_and(is_mediatek_name);
+                    if check(&info.manufacturer) || check(&info.product) {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// True for a USB manufacturer/product string naming MediaTek or an MT33xx module.
+/// "mtk" must stand alone as a word, so unrelated names that merely contain it don't match.
+fn is_mediatek_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.contains("mediatek")
+        || lower.contains("globaltop")
+        || lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| matches!(word, "mtk" | "pmtk" | "gtop") || word.starts_with("mt33"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    #[test]
+    fn test_mediatek_name_needs_a_whole_word() {
+        assert!(is_mediatek_name("MediaTek Inc."));
+        assert!(is_mediatek_name("GlobalTop PA6H"));
+        assert!(is_mediatek_name("MTK GPS Receiver"));
+        assert!(is_mediatek_name("MT3339 GNSS"));
+        assert!(!is_mediatek_name("SmtkLabs Serial"));
+        assert!(!is_mediatek_name("u-blox 8 - GPS/GNSS Receiver"));
+    }
+
+    #[test]
+    fn test_reconnect_waits_for_reader_to_release_port() {
+        let manager = GpsManager::new();
+        let port_busy = Arc::new(AtomicBool::new(false));
+        let busy_errors = Arc::new(AtomicU32::new(0));
+
+        // connect -> disconnect -> connect -> ... on a simulated source whose
+        // "read" blocks for 300ms, longer than the old fixed 100ms disconnect wait
+        for _ in 0..3 {
+            manager.disconnect();
+
+            let busy = Arc::clone(&port_busy);
+            let errors = Arc::clone(&busy_errors);
+            manager.spawn_reader(move |stop_flag| {
+                if busy.swap(true, Ordering::SeqCst) {
+                    errors.fetch_add(1, Ordering::SeqCst);
+                    return;
+                }
+                while !stop_flag.load(Ordering::SeqCst) {
+                    thread::sleep(Duration::from_millis(300));
+                }
+                busy.store(false, Ordering::SeqCst);
+            });
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        manager.disconnect();
+        assert_eq!(busy_errors.load(Ordering::SeqCst), 0, "port was still busy on reconnect");
+        assert!(!port_busy.load(Ordering::SeqCst), "reader still holds the port after disconnect");
+    }
+
+    #[test]
+    fn test_detached_reader_does_not_leak_into_next_connection() {
+        let manager = GpsManager::new();
+
+        // Source A: stuck in a read past the disconnect timeout, then writing its sky
+        let data = Arc::clone(&manager.data);
+        manager.spawn_reader(move |stop_flag| {
+            thread::sleep(Duration::from_millis(READER_JOIN_TIMEOUT_MS + 200));
+            while !stop_flag.load(Ordering::SeqCst) {
+                data.write().satellites_info = vec![crate::nmea::SatelliteInfo {
+                    constellation: "GLONASS".into(),
+                    ..crate::test_support::sat(65, 30.0, 45.0, 40.0)
+                }];
+                thread::sleep(Duration::from_millis(20));
+            }
+        });
+
+        // Source B: connecting detaches A. The port doesn't exist, so B sends nothing
+        // and anything in the data after A wakes up would be A's.
+        manager.connect("/dev/ttyGPS-TEST-B", 9600, SerialSettings::default(), || {}).unwrap();
+        thread::sleep(Duration::from_millis(500));
+        assert!(manager.get_data().satellites_info.is_empty(), "source A's satellites leaked");
+        manager.disconnect();
+    }
+
+    #[test]
+    fn test_reconnect_after_read_error_and_device_removal() {
+        let poll = Duration::from_millis(1);
+        let stop = AtomicBool::new(false);
+
+        // Transient read error on a present port: retried a bounded number of times
+        let error = classify_read_failure("Input/output error".into(), true);
+        assert_eq!(error.code(), "read_error");
+        let mut retries = 0;
+        for _ in 0..MAX_READ_ERROR_RECONNECTS {
+            assert!(wait_to_reconnect(&error, &mut retries, &stop, || true, poll));
+        }
+        assert!(!wait_to_reconnect(&error, &mut retries, &stop, || true, poll));
+
+        // Two bursts with good reads in between: each gets the full allowance
+        let mut retries = 0;
+        for _burst in 0..2 {
+            for _ in 0..MAX_READ_ERROR_RECONNECTS {
+                assert!(wait_to_reconnect(&error, &mut retries, &stop, || true, poll));
+            }
+            // What read_from_serial does once the reopened port reads
+            retries = 0;
+        }
+
+        // Unplugged: no retries until the port is listed again
+        let error = classify_read_failure("port returned EOF".into(), false);
+        assert_eq!(error.code(), "device_removed");
+        let checks = AtomicU32::new(0);
+        let replugged = || checks.fetch_add(1, Ordering::SeqCst) >= 3;
+        assert!(wait_to_reconnect(&error, &mut 0, &stop, replugged, poll));
+        assert_eq!(checks.load(Ordering::SeqCst), 4);
+
+        // Disconnecting while waiting for the device ends the wait
+        stop.store(true, Ordering::SeqCst);
+        assert!(!wait_to_reconnect(&error, &mut 0, &stop, || false, poll));
+        let running = AtomicBool::new(false);
+        assert!(!wait_to_reconnect(&GpsError::NotConnected, &mut 0, &running, || true, poll));
+    }
+
+    #[test]
+    fn test_profile_report_aggregates_acks() {
+        let step = |step: usize, id: u8, acked: Option<bool>| ProfileCommandResult {
+            step,
+            message: ubx_config::message_name(ubx_config::UBX_CLASS_CFG, id),
+            class: ubx_config::UBX_CLASS_CFG,
+            id,
+            acked,
+        };
+        let results = vec![
+            step(1, ubx_config::UBX_CFG_GNSS, Some(true)),
+            step(2, ubx_config::UBX_CFG_NAV5, Some(false)),
+            step(3, ubx_config::UBX_CFG_RATE, None),
+            step(4, ubx_config::UBX_CFG_CFG, Some(true)),
+        ];
+        let report = ProfileApplyReport::from_results("Series 8 Marine", results.clone());
+        assert_eq!((report.acked, report.nacked, report.unanswered), (2, 1, 1));
+        assert_eq!(report.commands[1].message, "CFG-NAV5");
+        assert!(report.saved);
+
+        // Save not acknowledged
+        let mut unsaved = results;
+        unsaved[3].acked = None;
+        assert!(!ProfileApplyReport::from_results("Series 8 Marine", unsaved).saved);
+
+        // M9/M10: no CFG-CFG, saved only if every VALSET was ACKed
+        let valset = |acked| step(1, ubx_config::UBX_CFG_VALSET, acked);
+        assert!(ProfileApplyReport::from_results("M10", vec![valset(Some(true))]).saved);
+        assert!(!ProfileApplyReport::from_results("M10", vec![valset(Some(false))]).saved);
+        assert!(!ProfileApplyReport::from_results("M10", Vec::new()).saved);
+    }
+
+    fn detected_port(name: &str) -> DetectedPort {
+        DetectedPort {
+            port_name: name.into(),
+            port_type: "USB".into(),
+            manufacturer: None,
+            product: None,
+            serial_number: None,
+            vid: None,
+            pid: None,
+            is_likely_gps: false,
+        }
+    }
+
+    #[test]
+    fn test_auto_detect_cancel_between_attempts() {
+        let ports = vec![detected_port("/dev/ttyA"), detected_port("/dev/ttyB")];
+        let cancel = AtomicBool::new(false);
+        let mut attempts = 0;
+
+        let result = GpsManager::scan_ports(ports, &cancel, |_, _| {
+            attempts += 1;
+            // User cancels while the first attempt is in flight
+            cancel.store(true, Ordering::SeqCst);
+            Ok(false)
+        });
+
+        assert!(matches!(result, Err(GpsError::AutoDetectCancelled)));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_auto_detect_scans_all_attempts() {
+        let ports = vec![detected_port("/dev/ttyA"), detected_port("/dev/ttyB")];
+        let cancel = AtomicBool::new(false);
+        let mut attempts = 0;
+
+        let result = GpsManager::scan_ports(ports, &cancel, |port, baud| {
+            attempts += 1;
+            Ok(port == "/dev/ttyB" && baud == 9600)
+        });
+
+        let (port, baud) = result.unwrap();
+        assert_eq!((port.port_name.as_str(), baud), ("/dev/ttyB", 9600));
+        assert_eq!(attempts, 5);
+    }
+
+    #[test]
+    fn test_auto_detect_all_finds_every_gps() {
+        let ports = vec![
+            detected_port("/dev/ttyA"),
+            detected_port("/dev/ttyB"),
+            detected_port("/dev/ttyC"),
+        ];
+        let cancel = AtomicBool::new(false);
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let mut attempts = 0;
+
+        let found = GpsManager::scan_all_ports(ports.clone(), &cancel, deadline, |port, baud| {
+            attempts += 1;
+            Ok((port == "/dev/ttyA" && baud == 4800) || (port == "/dev/ttyC" && baud == 115200))
+        })
+        .unwrap();
+
+        let found: Vec<_> = found.iter().map(|(p, b)| (p.port_name.as_str(), *b)).collect();
+        assert_eq!(found, [("/dev/ttyA", 4800), ("/dev/ttyC", 115200)]);
+        // One probe for A, every rate for B and C
+        assert_eq!(attempts, 7);
+
+        // Past the deadline nothing more is probed
+        let found = GpsManager::scan_all_ports(ports, &cancel, Instant::now(), |_, _| Ok(true));
+        assert!(found.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_last_port_uses_saved_port() {
+        let ports = vec![detected_port("/dev/ttyA"), detected_port("/dev/ttyB")];
+        let saved = LastPort { port_name: "/dev/ttyB".into(), baud_rate: 115200 };
+        let cancel = AtomicBool::new(false);
+        let mut probed = Vec::new();
+
+        let (port, baud) = GpsManager::resolve_last_port(ports, Some(&saved), &cancel, |p, b| {
+            probed.push((p.to_string(), b));
+            Ok(true)
+        })
+        .unwrap();
+
+        assert_eq!((port.port_name.as_str(), baud), ("/dev/ttyB", 115200));
+        assert_eq!(probed, [("/dev/ttyB".to_string(), 115200)]);
+    }
+
+    #[test]
+    fn test_resolve_last_port_missing_falls_back_to_scan() {
+        let ports = vec![detected_port("/dev/ttyA")];
+        let saved = LastPort { port_name: "/dev/ttyUSB9".into(), baud_rate: 9600 };
+        let cancel = AtomicBool::new(false);
+
+        let (port, baud) = GpsManager::resolve_last_port(ports, Some(&saved), &cancel, |p, b| {
+            assert_ne!(p, "/dev/ttyUSB9", "missing port must not be opened");
+            Ok(b == 4800)
+        })
+        .unwrap();
+
+        assert_eq!((port.port_name.as_str(), baud), ("/dev/ttyA", 4800));
+    }
+
+    #[test]
+    fn test_last_port_round_trip() {
+        let dir = crate::test_support::temp_dir("last-port");
+        assert_eq!(LastPort::load(&dir), None);
+
+        let last = LastPort { port_name: "COM7".into(), baud_rate: 9600 };
+        last.save(&dir).unwrap();
+        assert_eq!(LastPort::load(&dir), Some(last));
+
+        std::fs::write(dir.join(LAST_PORT_FILE), "not json").unwrap();
+        assert_eq!(LastPort::load(&dir), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_serial_settings_builder() {
+        // Default matches the plain 8N1 builder used before settings existed
+        let default = SerialSettings::default().builder("/dev/ttyS0", 9600).unwrap();
+        assert_eq!(default, serialport::new("/dev/ttyS0", 9600));
+
+        let json = r#"{"data_bits": 7, "parity": "even", "flow_control": "hardware"}"#;
+        let settings: SerialSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.stop_bits, 1);
+        let builder = settings.builder("/dev/ttyS0", 4800).unwrap();
+        let expected = serialport::new("/dev/ttyS0", 4800)
+            .data_bits(serialport::DataBits::Seven)
+            .parity(serialport::Parity::Even)
+            .stop_bits(serialport::StopBits::One)
+            .flow_control(serialport::FlowControl::Hardware);
+        assert_eq!(builder, expected);
+
+        let bad = SerialSettings { stop_bits: 3, ..SerialSettings::default() };
+        assert!(matches!(
+            bad.builder("/dev/ttyS0", 9600),
+            Err(GpsError::InvalidSerialSettings(_))
+        ));
+    }
+
+    #[test]
+    fn test_observed_rate_from_gga_arrivals() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        // Receiver stuck at 4Hz: a GGA every 250ms
+        let mut meter = RateMeter::default();
+        meter.record(at(0));
+        meter.record(at(250));
+        assert_eq!(meter.rate_hz(), None);
+        for ms in [500, 750, 1000, 1250] {
+            meter.record(at(ms));
+        }
+        let rate = meter.rate_hz().unwrap();
+        assert!((rate - 4.0).abs() < 0.01);
+        assert!(is_rate_mismatch(rate, CONFIGURED_UPDATE_RATE_HZ));
+
+        // 1Hz with one late sentence: the median interval ignores the outlier
+        let mut meter = RateMeter::default();
+        for ms in [0, 1000, 2000, 3600, 4000, 5000] {
+            meter.record(at(ms));
+        }
+        let rate = meter.rate_hz().unwrap();
+        assert!((rate - 1.0).abs() < 0.01);
+        assert!(!is_rate_mismatch(rate, CONFIGURED_UPDATE_RATE_HZ));
+
+        assert!(is_gga("$GNGGA,092750.000,5321.6802,N"));
+        assert!(!is_gga("$GPRMC,225446,A"));
+    }
+
+    /// Serial port stand-in that answers reads from a fixed byte stream, a few bytes at
+    /// a time, then times out like an idle port
+    struct SimulatedPort {
+        rx: std::io::Cursor<Vec<u8>>,
+        tx: Vec<u8>,
+    }
+
+    impl std::io::Read for SimulatedPort {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = buf.len().min(13);
+            let n = self.rx.read(&mut buf[..len])?;
+            if n == 0 {
+                thread::sleep(Duration::from_millis(5));
+                return Err(std::io::ErrorKind::TimedOut.into());
+            }
+            Ok(n)
+        }
+    }
+
+    impl Write for SimulatedPort {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.tx.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_probe_reports_sentences_talkers_and_ublox() {
+        let mut mon_ver = vec![0u8; 40];
+        mon_ver[..22].copy_from_slice(b"ROM CORE 3.01 (107888)");
+        mon_ver[30..38].copy_from_slice(b"00080000");
+        let mut rx = b"$GNGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*68\r\n"
+            .to_vec();
+        rx.extend(ubx_config::build_ubx_message(
+            ubx_config::UBX_CLASS_MON,
+            ubx_config::UBX_MON_VER,
+            &mon_ver,
+        ));
+        rx.extend_from_slice(b"$GLGSV,1,1,02,65,45,090,38,66,30,200,35*60\r\n");
+        rx.extend_from_slice(b"$GPRMC,225446,A,4916.45,N,12311.12,W*00\r\n"); // bad checksum
+        let total = rx.len() as u64;
+        let mut port = SimulatedPort { rx: std::io::Cursor::new(rx), tx: Vec::new() };
+
+        let probe = probe_stream(&mut port, Duration::from_millis(200));
+        assert_eq!(port.tx, ubx_config::build_mon_ver_poll());
+        assert_eq!(probe.bytes_received, total);
+        assert_eq!(probe.nmea_sentences, 2);
+        assert_eq!(probe.invalid_sentences, 1);
+        assert_eq!(probe.sentence_types, ["GGA", "GSV"]);
+        assert_eq!(probe.talkers, ["GL", "GN"]);
+        assert_eq!(probe.ubx_frames, 1);
+        assert_eq!(probe.ublox.map(|info| info.series), Some(UbloxSeries::Series8));
+        assert!(probe.duration_ms >= 200);
+
+        // Wrong baud rate: line noise with '$' and line endings but no NMEA in it
+        let noise =
+            b"\x00\xFF$\xB5\x9A\xC3,\x11\r\n$\xE3GGA,,*\xA0\r\n$GP\x80\r\n$\x7F\x7F\r\n"
+                .to_vec();
+        let mut garbled = SimulatedPort { rx: std::io::Cursor::new(noise), tx: Vec::new() };
+        let probe = probe_stream(&mut garbled, Duration::from_millis(50));
+        assert_eq!(probe.nmea_sentences, 0);
+        assert!(probe.invalid_sentences > 0);
+        assert!(probe.sentence_types.is_empty() && probe.talkers.is_empty());
+
+        // A silent port: nothing arrives, and the probe still ends on time
+        let mut silent = SimulatedPort { rx: std::io::Cursor::new(Vec::new()), tx: Vec::new() };
+        let probe = probe_stream(&mut silent, Duration::from_millis(50));
+        assert_eq!(probe.bytes_received, 0);
+        assert!(probe.ublox.is_none());
+        assert!(probe.duration_ms < 1000);
+    }
+
+    #[test]
+    fn test_byte_meter_accumulates_reads() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        // One 480-byte NMEA burst per second, read line by line
+        let mut meter = ByteMeter::default();
+        for second in 0..8 {
+            for line in 0..6 {
+                meter.record(at(second * 1000 + line * 10), 80);
+            }
+        }
+        assert_eq!(meter.total, 8 * 480);
+        assert_eq!(meter.bytes_per_second(at(500)), None);
+        // Five seconds of window at 480 B/s, the burst at 2s just aged out
+        let bps = meter.bytes_per_second(at(7100)).unwrap();
+        assert!((bps - 480.0).abs() < 1.0, "{}", bps);
+
+        // 480 B/s of 8N1 at 9600 baud fills half the link
+        let bits = SerialSettings::default().bits_per_byte();
+        assert_eq!(bits, 10);
+        assert!((bps * bits as f32 / 9600.0 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_send_commands_refuses_non_ublox() {
+        let manager = GpsManager::new();
+        let cmd = vec![ubx_config::build_cfg_sbas_enable()];
+
+        // e.g. a MediaTek unit: nothing identified it as u-blox
+        assert!(matches!(manager.send_commands(&cmd), Err(GpsError::NotUblox)));
+
+        // Once identified, the guard passes and the missing port is the only problem
+        manager.status.write().ublox_identified = true;
+        assert!(matches!(manager.send_commands(&cmd), Err(GpsError::NotConnected)));
+    }
+
+    #[test]
+    fn test_baud_verification_needs_a_checksummed_frame() {
+        let manager = GpsManager::new();
+        let before = manager.get_status();
+        let arrive = |line: &str| {
+            manager.nmea_buffer.write().unwrap().push(line.to_string());
+            manager.status.write().sentences_received += 1;
+        };
+
+        // Noise at the wrong rate: no checksum, or a wrong one
+        arrive("$GPGGA,\u{fffd}\u{fffd}");
+        arrive("$GPGSA,A,3,05,12,17,,,,,,,,,,1.8,1.0,1.5*00");
+        assert!(!manager.valid_frame_since(&before));
+
+        arrive("$GPGSA,A,3,05,12,17,,,,,,,,,,1.8,1.0,1.5*3E");
+        assert!(manager.valid_frame_since(&before));
+
+        // A UBX frame counts too, for receivers with NMEA output off
+        let before = manager.get_status();
+        assert!(!manager.valid_frame_since(&before));
+        manager.status.write().ubx_frames_received += 1;
+        assert!(manager.valid_frame_since(&before));
+    }
+
+    #[test]
+    fn test_unanswered_chip_id_is_not_polled_again() {
+        let manager = GpsManager::new();
+        manager.status.write().ublox_identified = true;
+        // With no port the poll can't even be sent
+        assert!(matches!(manager.chip_id(), Err(GpsError::NotConnected)));
+
+        // Once a receiver has ignored the poll, the answer stands for the connection
+        manager.chip_id_unanswered.store(true, Ordering::SeqCst);
+        assert!(matches!(manager.chip_id(), Ok(None)));
+
+        // A new connection asks again
+        manager.connect("/dev/ttyGPS-TEST-UNIQID", 9600, SerialSettings::default(), || {}).unwrap();
+        manager.disconnect();
+        manager.status.write().ublox_identified = true;
+        assert!(matches!(manager.chip_id(), Err(GpsError::NotConnected)));
+    }
+
+    #[test]
+    fn test_nmea_capture_is_exclusive() {
+        let manager = GpsManager::new();
+        assert!(manager.start_nmea_capture().is_ok());
+        assert!(matches!(
+            manager.start_nmea_capture(),
+            Err(GpsError::CaptureInProgress)
+        ));
+        manager
+            .nmea_capture
+            .lock()
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .push("$GPGGA".into());
+        assert_eq!(manager.finish_nmea_capture(), vec!["$GPGGA".to_string()]);
+
+        // Finished: a new capture can start, and finishing twice yields nothing
+        assert!(manager.finish_nmea_capture().is_empty());
+        assert!(manager.start_nmea_capture().is_ok());
+    }
+
+    #[test]
+    fn test_on_open_waits_for_the_port_to_open() {
+        let manager = GpsManager::new();
+        let opened = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&opened);
+        let on_open = move || flag.store(true, Ordering::SeqCst);
+        manager
+            .connect("/dev/ttyGPS-TEST-MISSING", 9600, SerialSettings::default(), on_open)
+            .unwrap();
+        thread::sleep(Duration::from_millis(300));
+        assert!(!opened.load(Ordering::SeqCst), "on_open ran for a port that never opened");
+        manager.disconnect();
+    }
+
+    #[test]
+    fn test_join_reader_times_out() {
+        let handle = thread::spawn(|| thread::sleep(Duration::from_millis(500)));
+        assert!(!join_reader(handle, Duration::from_millis(50)));
+
+        let handle = thread::spawn(|| {});
+        assert!(join_reader(handle, Duration::from_millis(500)));
+    }
+}
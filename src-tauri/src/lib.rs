@@ -1,35 +1,74 @@
 // Vortex Marine Limited - GPS Studio
 
+mod analytics;
+mod antenna;
+mod antenna_loss;
+mod atomic_file;
 mod commands;
+mod geo;
 mod gps;
+mod log_buffer;
+mod metrics_csv;
+mod multipath;
 mod nmea;
+mod nmea_log;
+mod pmtk;
+mod raw_log;
+mod regression;
+mod self_test;
+mod settings;
+mod sky_plot;
+mod snapshot;
 mod test_criteria;
 mod test_report;
+#[cfg(test)]
+mod test_support;
+mod ttff_benchmark;
 mod ubx_config;
 mod ubx_optimizer;
 
 use commands::AppState;
 use gps::GpsManager;
-use std::sync::RwLock;
-use test_criteria::TestCriteria;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::{Arc, Mutex, RwLock};
+use settings::AppSettings;
+use test_criteria::{Baselines, CriteriaProfiles};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    env_logger::init();
+    log_buffer::init();
 
-    // Load test criteria from config file, or use defaults
-    let criteria = load_criteria();
-    let results_dir = test_report::default_results_dir();
+    // Load settings (including the default test criteria) from the config dir
+    let config_dir = dirs_config();
+    let settings = AppSettings::load(&config_dir);
+    let criteria_profiles = load_criteria_profiles(&config_dir);
+    let baselines = load_baselines(&config_dir);
+    let results_dir = settings
+        .results_dir
+        .clone()
+        .unwrap_or_else(test_report::default_results_dir);
 
     log::info!("GPS Studio v3.42 starting...");
     log::info!("Results directory: {}", results_dir.display());
 
+    // Tests cut short by a crash or power loss show up in recent results
+    let interrupted = test_report::recover_interrupted_reports(&results_dir);
+
     let app_state = AppState {
         gps_manager: GpsManager::new(),
         test_runner: RwLock::new(None),
-        test_criteria: RwLock::new(criteria),
-        recent_results: RwLock::new(Vec::new()),
+        settings: RwLock::new(settings),
+        criteria_profiles: RwLock::new(criteria_profiles),
+        baselines: RwLock::new(baselines),
+        recent_results: RwLock::new(interrupted),
         results_dir,
+        interim_report_path: Mutex::new(None),
+        config_dir,
+        auto_detect_cancel: Arc::new(AtomicBool::new(false)),
+        optimize_generation: AtomicU64::new(0),
+        test_generation: AtomicU64::new(0),
+        metrics_csv: Mutex::new(None),
+        ttff_benchmark_cancel: Arc::new(AtomicBool::new(false)),
     };
 
     tauri::Builder::default()
@@ -39,55 +78,119 @@ pub fn run() {
             // GPS detection and connection
             commands::list_serial_ports,
             commands::auto_detect_gps,
+            commands::auto_detect_all_gps,
+            commands::cancel_auto_detect,
+            commands::auto_connect_last,
             commands::test_gps_port,
+            commands::probe_port,
             commands::connect_gps,
             commands::disconnect_gps,
+            commands::change_baud,
+            commands::load_ubx_config_file,
+            commands::apply_marine_profile,
+            commands::reset_to_defaults,
+            commands::get_chip_id,
             commands::get_gps_data,
+            commands::get_sky_plot,
+            commands::estimate_antenna_loss,
             commands::get_gps_status,
+            commands::get_connection_info,
             commands::get_nmea_buffer,
             commands::clear_nmea_buffer,
-            // Test criteria
+            commands::capture_nmea,
+            commands::capture_raw,
+            commands::replay_nmea_log,
+            commands::replay_raw_log,
+            commands::replay_test_log,
+            commands::replay_test_corpus,
+            commands::benchmark_parse,
+            commands::run_self_test,
+            // Settings and test criteria
+            commands::get_settings,
+            commands::set_settings,
             commands::get_test_criteria,
             commands::set_test_criteria,
             commands::reset_test_criteria,
+            commands::preview_criteria,
+            commands::get_criteria_profiles,
+            commands::set_criteria_profile,
+            commands::delete_criteria_profile,
+            commands::get_baselines,
+            commands::save_baseline,
+            commands::delete_baseline,
             // Test execution
             commands::start_test,
+            commands::start_test_with_criteria,
             commands::get_test_status,
             commands::abort_test,
+            commands::pause_test,
+            commands::resume_test,
+            commands::start_metrics_csv,
+            commands::stop_metrics_csv,
             commands::save_test_report,
+            commands::format_test_label,
+            commands::validate_report,
+            commands::export_results_archive,
             commands::get_recent_results,
+            commands::get_recent_results_filtered,
+            commands::get_ttff_histogram,
             // GPS optimization
             commands::start_optimize,
             commands::get_optimize_status,
             commands::abort_optimize,
+            // Diagnostics
+            commands::get_logs,
+            commands::clear_logs,
+            // Live diagnostics
+            commands::list_ubx_capabilities,
+            commands::get_start_state,
+            commands::run_ttff_benchmark,
+            commands::cancel_ttff_benchmark,
+            commands::enable_sbas,
+            commands::disable_sbas,
+            commands::set_nmea_41,
+            commands::set_nmea_high_precision,
+            commands::set_dynamic_model,
+            commands::set_constellations,
         ])
         .run(tauri::generate_context!())
         .expect("error while running GPS Studio");
 }
 
-/// Load test criteria from config file, falling back to defaults
-fn load_criteria() -> TestCriteria {
-    let config_dir = dirs_config();
-    let config_file = config_dir.join("criteria.json");
-
-    if config_file.exists() {
-        match std::fs::read_to_string(&config_file) {
-            Ok(contents) => match serde_json::from_str::<TestCriteria>(&contents) {
-                Ok(criteria) => {
-                    log::info!("Loaded test criteria from {}", config_file.display());
-                    return criteria;
-                }
-                Err(e) => {
-                    log::warn!("Failed to parse criteria config: {}, using defaults", e);
-                }
-            },
-            Err(e) => {
-                log::warn!("Failed to read criteria config: {}, using defaults", e);
-            }
+/// Load per-model criteria profiles, if any have been saved
+fn load_criteria_profiles(config_dir: &std::path::Path) -> CriteriaProfiles {
+    let path = config_dir.join(commands::CRITERIA_PROFILES_FILE);
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return CriteriaProfiles::new();
+    };
+    match serde_json::from_str::<CriteriaProfiles>(&contents) {
+        Ok(profiles) => {
+            log::info!("Loaded {} criteria profile(s) from {}", profiles.len(), path.display());
+            profiles
+        }
+        Err(e) => {
+            log::warn!("Failed to parse criteria profiles: {}, ignoring", e);
+            CriteriaProfiles::new()
         }
     }
+}
 
-    TestCriteria::default()
+/// Load golden-unit baselines, if any have been saved
+fn load_baselines(config_dir: &std::path::Path) -> Baselines {
+    let path = config_dir.join(commands::BASELINES_FILE);
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Baselines::new();
+    };
+    match serde_json::from_str::<Baselines>(&contents) {
+        Ok(baselines) => {
+            log::info!("Loaded {} baseline(s) from {}", baselines.len(), path.display());
+            baselines
+        }
+        Err(e) => {
+            log::warn!("Failed to parse baselines: {}, ignoring", e);
+            Baselines::new()
+        }
+    }
 }
 
 /// Get config directory path
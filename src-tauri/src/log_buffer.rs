@@ -0,0 +1,104 @@
+// In-memory log ring buffer - recent backend log lines for the frontend debug panel
+//
+// env_logger still writes to stderr (filtered by RUST_LOG) for development;
+// INFO/WARN/ERROR records are additionally captured here regardless of RUST_LOG,
+// since the packaged app has no visible console.
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+// Maximum number of log lines retained
+const LOG_BUFFER_SIZE: usize = 500;
+
+static LOG_BUFFER: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+/// A single captured log line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Logger forwarding to env_logger while capturing INFO and above into the ring buffer
+struct BufferedLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for BufferedLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+
+        if record.level() <= Level::Info {
+            push_entry(LogEntry {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                level: record.level().to_string(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install the buffered logger (replaces `env_logger::init()`)
+pub fn init() {
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = inner.filter().max(LevelFilter::Info);
+
+    if log::set_boxed_logger(Box::new(BufferedLogger { inner })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+fn push_entry(entry: LogEntry) {
+    let mut buffer = LOG_BUFFER.lock().unwrap();
+    if buffer.len() >= LOG_BUFFER_SIZE {
+        buffer.pop_front();
+    }
+    buffer.push_back(entry);
+}
+
+/// Get the captured log lines, oldest first
+pub fn recent_logs() -> Vec<LogEntry> {
+    LOG_BUFFER.lock().unwrap().iter().cloned().collect()
+}
+
+/// Discard all captured log lines
+pub fn clear_logs() {
+    LOG_BUFFER.lock().unwrap().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_caps_and_keeps_newest() {
+        for i in 0..LOG_BUFFER_SIZE + 25 {
+            push_entry(LogEntry {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                level: "INFO".into(),
+                target: "test".into(),
+                message: format!("line {}", i),
+            });
+        }
+
+        let logs = recent_logs();
+        assert_eq!(logs.len(), LOG_BUFFER_SIZE);
+        assert_eq!(logs.first().unwrap().message, "line 25");
+        assert_eq!(logs.last().unwrap().message, format!("line {}", LOG_BUFFER_SIZE + 24));
+    }
+}
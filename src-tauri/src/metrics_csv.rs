@@ -0,0 +1,101 @@
+// Live metrics CSV: one row of fix metrics per test evaluation tick
+//
+// Sits between the raw NMEA log and the summary report: a plain table of satellites,
+// SNR, DOP and fix quality over the whole test, for spreadsheets and plotting.
+
+use crate::nmea::GpsData;
+use crate::test_criteria::calc_avg_snr;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub const METRICS_CSV_HEADER: &str = "timestamp,satellites,avg_snr,hdop,pdop,fix_quality";
+
+/// An open metrics CSV. Rows are flushed as they are written, so the file is
+/// complete up to the last tick even if the app is killed.
+#[derive(Debug)]
+pub struct MetricsCsv {
+    path: PathBuf,
+    file: File,
+    pub rows: u64,
+}
+
+impl MetricsCsv {
+    /// Open `path` for appending, writing the header if the file is new or empty
+    pub fn open(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{}", METRICS_CSV_HEADER)?;
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            rows: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one row for `data`; missing values are left empty
+    pub fn write_row(&mut self, data: &GpsData) -> io::Result<()> {
+        let optional = |v: Option<f32>| v.map_or(String::new(), |v| format!("{:.2}", v));
+        let line = format!(
+            "{},{},{:.1},{},{},{}\n",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            data.satellites.map_or(String::new(), |s| s.to_string()),
+            calc_avg_snr(&data.satellites_info),
+            optional(data.hdop),
+            optional(data.pdop),
+            data.fix_quality.map_or(String::new(), |q| q.to_string()),
+        );
+        self.file.write_all(line.as_bytes())?;
+        self.file.flush()?;
+        self.rows += 1;
+        Ok(())
+    }
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_metrics_rows_follow_header() {
+        let dir = temp_dir("metrics");
+        let path = dir.join("metrics.csv");
+
+        let mut csv = MetricsCsv::open(&path).unwrap();
+        let data = GpsData {
+            satellites: Some(9),
+            hdop: Some(0.9),
+            pdop: Some(1.6),
+            fix_quality: Some(1),
+            ..GpsData::default()
+        };
+        csv.write_row(&data).unwrap();
+        csv.write_row(&GpsData::default()).unwrap();
+        assert_eq!(csv.rows, 2);
+
+        // Reopening appends without repeating the header
+        let mut csv = MetricsCsv::open(&path).unwrap();
+        csv.write_row(&data).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], METRICS_CSV_HEADER);
+        assert!(lines[1].ends_with(",9,0.0,0.90,1.60,1"), "{}", lines[1]);
+        assert!(lines[2].ends_with(",,0.0,,,"), "{}", lines[2]);
+        assert!(lines[3].ends_with(",9,0.0,0.90,1.60,1"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
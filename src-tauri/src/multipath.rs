@@ -0,0 +1,105 @@
+// Multipath indicator from the per-satellite elevation and SNR in GSV
+//
+// Under open sky the direct signal from a satellite high overhead crosses less
+// atmosphere and sits nearer the antenna's boresight than one near the horizon, so
+// SNR rises with elevation: across the satellites in view the two correlate strongly
+// (a Pearson coefficient of roughly 0.5-0.8 on a good install). Reflections break
+// that pattern. A reflective mount or a poor ground plane adds and cancels signal
+// depending on geometry, which scatters SNR independently of elevation, and
+// low satellites seen only by reflection show up strong.
+//
+// The score is 1 - r, clamped to 0.0-1.0, where r is that correlation: 0 means SNR
+// follows elevation perfectly, 0.5 is no relationship at all, and 1 is SNR falling
+// as elevation rises. It is a heuristic from one instant: it needs a spread of
+// satellites across the sky, and an obstructed sky (a wall blocking one side) also
+// weakens the correlation. Read a high score as "check the installation", not as a
+// measurement of multipath error.
+
+use crate::nmea::SatelliteInfo;
+
+/// Satellites with both an SNR and an elevation needed for a meaningful score
+pub const MIN_MULTIPATH_SATELLITES: usize = 6;
+/// Smallest spread of elevations (standard deviation, degrees) worth correlating
+const MIN_ELEVATION_SPREAD_DEG: f64 = 5.0;
+
+/// Multipath score (0.0-1.0, higher is worse) for the satellites in view, or None if
+/// too few have a signal, they sit at too similar elevations or their SNRs are all
+/// the same
+pub fn multipath_score(satellites: &[SatelliteInfo]) -> Option<f32> {
+    let points: Vec<(f64, f64)> = satellites
+        .iter()
+        .filter_map(|s| match (s.elevation, s.snr) {
+            (Some(elevation), Some(snr)) if snr > 0.0 => Some((elevation as f64, snr as f64)),
+            _ => None,
+        })
+        .collect();
+    if points.len() < MIN_MULTIPATH_SATELLITES {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_elevation = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_snr = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut covariance, mut var_elevation, mut var_snr) = (0.0, 0.0, 0.0);
+    for (elevation, snr) in &points {
+        let (de, ds) = (elevation - mean_elevation, snr - mean_snr);
+        covariance += de * ds;
+        var_elevation += de * de;
+        var_snr += ds * ds;
+    }
+    if (var_elevation / n).sqrt() < MIN_ELEVATION_SPREAD_DEG {
+        return None;
+    }
+    // Identical SNRs leave the correlation undefined: nothing to judge either way
+    if var_snr == 0.0 {
+        return None;
+    }
+
+    let r = covariance / (var_elevation.sqrt() * var_snr.sqrt());
+    Some((1.0 - r).clamp(0.0, 1.0) as f32)
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn sat(elevation: f32, snr: f32) -> SatelliteInfo {
+        test_support::sat(1, elevation, 0.0, snr)
+    }
+
+    #[test]
+    fn test_multipath_score_open_sky_vs_reflective() {
+        // Open sky: SNR climbs with elevation
+        let open: Vec<_> = [(10.0, 28.0), (20.0, 33.0), (35.0, 38.0)]
+            .into_iter()
+            .chain([(50.0, 41.0), (65.0, 44.0), (80.0, 46.0)])
+            .map(|(e, s)| sat(e, s))
+            .collect();
+        let good = multipath_score(&open).unwrap();
+        assert!(good < 0.1, "open sky scored {}", good);
+
+        // Reflective mount: low satellites strong, high ones faded
+        let reflective: Vec<_> = [(10.0, 44.0), (20.0, 30.0), (35.0, 41.0)]
+            .into_iter()
+            .chain([(50.0, 27.0), (65.0, 36.0), (80.0, 29.0)])
+            .map(|(e, s)| sat(e, s))
+            .collect();
+        let bad = multipath_score(&reflective).unwrap();
+        assert!(bad > 0.9, "reflective install scored {}", bad);
+
+        // Too few satellites, or all at one elevation: no score
+        assert_eq!(multipath_score(&open[..5]), None);
+        let flat: Vec<_> = (0..8).map(|i| sat(45.0, 30.0 + i as f32)).collect();
+        assert_eq!(multipath_score(&flat), None);
+
+        // Same SNR at every elevation: undefined, not the worst score
+        let flat_snr: Vec<_> = [10.0, 20.0, 35.0, 50.0, 65.0, 80.0]
+            .into_iter()
+            .map(|e| sat(e, 40.0))
+            .collect();
+        assert_eq!(multipath_score(&flat_snr), None);
+    }
+}
@@ -1,11 +1,28 @@
 // NMEA 0183 parser module for GPS data
 
+use crate::antenna::{AntennaPower, AntennaStatus};
 use nmea::Nmea;
-use nmea::sentences::{FixType, GnssType};
+use nmea::sentences::GnssType;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+/// RMC and VTG readings further apart than this are not compared
+const MOTION_CROSS_CHECK_WINDOW: Duration = Duration::from_secs(2);
+/// Speed disagreement (knots) above which RMC and VTG are flagged
+const MAX_SPEED_DISAGREEMENT_KNOTS: f64 = 1.0;
+/// Course disagreement (degrees) above which RMC and VTG are flagged
+const MAX_COURSE_DISAGREEMENT_DEG: f64 = 20.0;
+/// Below this speed COG is noise: course is not compared and `course_valid` is false
+pub const MIN_SPEED_FOR_COURSE_KNOTS: f64 = 1.0;
+/// Receiver dates before this are implausible; the GPS week-number rollover bug
+/// puts affected receivers about 19.6 years in the past
+const MIN_PLAUSIBLE_YEAR: i32 = 2020;
+/// Receiver dates more than this far past the host's UTC date are implausible
+const MAX_DATE_AHEAD_DAYS: i64 = 1;
+
 #[derive(Error, Debug)]
 #[allow(dead_code)]
 pub enum NmeaError {
@@ -25,6 +42,8 @@ pub struct SatelliteInfo {
     pub azimuth: Option<f32>,    // Azimuth in degrees (0-359)
     pub snr: Option<f32>,        // Signal-to-noise ratio (0-99 dB)
     pub constellation: String,   // GPS, GLONASS, Galileo, etc.
+    #[serde(default)]
+    pub used_in_fix: bool,       // Listed in GSA as part of the position solution
 }
 
 // GPS position data sent to frontend
@@ -34,6 +53,8 @@ pub struct GpsData {
     pub longitude: Option<f64>,
     pub speed_knots: Option<f64>,     // SOG - Speed Over Ground
     pub course: Option<f64>,           // COG - Course Over Ground
+    #[serde(default)]
+    pub course_valid: bool,            // SOG high enough for COG to mean anything
     pub heading: Option<f64>,          // HDG - True heading (from compass)
     pub altitude: Option<f64>,
     pub fix_quality: Option<u8>,
@@ -43,12 +64,162 @@ pub struct GpsData {
     pub pdop: Option<f32>,             // Position dilution of precision
     pub timestamp: Option<String>,
     pub fix_type: Option<String>,      // No fix, 2D, 3D
+    pub fix_age_seconds: Option<f64>,  // Wall clock minus receiver fix time at parse
+    #[serde(default)]
+    pub speed_course_mismatch: Option<bool>,  // RMC vs VTG disagree; None until both seen
+    #[serde(default)]
+    pub nav_status: Option<String>,           // PUBX,00 navStat: NF, DR, G2, G3, D2, D3, RK, TT
+    #[serde(default)]
+    pub horizontal_accuracy_m: Option<f32>,   // hAcc estimate from PUBX,00 or UBX-NAV-PVT
+    #[serde(default)]
+    pub vertical_accuracy_m: Option<f32>,     // vAcc estimate from PUBX,00 or UBX-NAV-PVT
+    #[serde(default)]
+    pub utc_date: Option<String>,             // RMC, ZDA or PUBX,04 UTC date, YYYY-MM-DD
+    #[serde(default)]
+    pub date_suspect: Option<bool>,           // Receiver date implausible (e.g. week rollover)
+    #[serde(default)]
+    pub clock_bias_ns: Option<f64>,           // PUBX,04 receiver clock bias
+    #[serde(default)]
+    pub clock_drift_ns_per_s: Option<f64>,    // PUBX,04 receiver clock drift
+    #[serde(default)]
+    pub leap_seconds: Option<u8>,             // GPS-UTC offset, PUBX,04 or UBX-NAV-TIMEGPS
+    #[serde(default)]
+    pub time_valid: Option<bool>,             // Leap seconds known, not a firmware default
+    #[serde(default)]
+    pub antenna_status: Option<AntennaStatus>, // Antenna supervisor, UBX-MON-HW or TXT
+    #[serde(default)]
+    pub antenna_power: Option<AntennaPower>,   // UBX-MON-HW antenna supply state
+    #[serde(default)]
+    pub gns_modes: Option<BTreeMap<String, String>>, // GNS mode per constellation
     pub satellites_info: Vec<SatelliteInfo>,  // Individual satellite data
 }
 
+impl GpsData {
+    /// Merge a parsed sentence into this state, keeping fields the update doesn't carry
+    pub fn merge(&mut self, update: GpsData) {
+        if update.latitude.is_some() { self.latitude = update.latitude; }
+        if update.longitude.is_some() { self.longitude = update.longitude; }
+        if update.speed_knots.is_some() { self.speed_knots = update.speed_knots; }
+        if update.course.is_some() { self.course = update.course; }
+        if update.heading.is_some() { self.heading = update.heading; }
+        if update.altitude.is_some() { self.altitude = update.altitude; }
+        if update.fix_quality.is_some() { self.fix_quality = update.fix_quality; }
+        if update.satellites.is_some() { self.satellites = update.satellites; }
+        if update.hdop.is_some() { self.hdop = update.hdop; }
+        if update.vdop.is_some() { self.vdop = update.vdop; }
+        if update.pdop.is_some() { self.pdop = update.pdop; }
+        if update.timestamp.is_some() { self.timestamp = update.timestamp; }
+        if update.fix_type.is_some() { self.fix_type = update.fix_type; }
+        if update.fix_age_seconds.is_some() { self.fix_age_seconds = update.fix_age_seconds; }
+        // The parser re-checks RMC against VTG on every sentence, so None clears a stale flag
+        self.speed_course_mismatch = update.speed_course_mismatch;
+        if update.nav_status.is_some() { self.nav_status = update.nav_status; }
+        if update.horizontal_accuracy_m.is_some() { self.horizontal_accuracy_m = update.horizontal_accuracy_m; }
+        if update.vertical_accuracy_m.is_some() { self.vertical_accuracy_m = update.vertical_accuracy_m; }
+        if update.utc_date.is_some() { self.utc_date = update.utc_date; }
+        if update.date_suspect.is_some() { self.date_suspect = update.date_suspect; }
+        if update.clock_bias_ns.is_some() { self.clock_bias_ns = update.clock_bias_ns; }
+        if update.clock_drift_ns_per_s.is_some() { self.clock_drift_ns_per_s = update.clock_drift_ns_per_s; }
+        if update.leap_seconds.is_some() { self.leap_seconds = update.leap_seconds; }
+        if update.time_valid.is_some() { self.time_valid = update.time_valid; }
+        if update.antenna_status.is_some() { self.antenna_status = update.antenna_status; }
+        if update.antenna_power.is_some() { self.antenna_power = update.antenna_power; }
+        if update.gns_modes.is_some() { self.gns_modes = update.gns_modes; }
+        if !update.satellites_info.is_empty() { self.satellites_info = update.satellites_info; }
+        self.course_valid = self.course_is_valid();
+    }
+
+    /// Whether `course` is meaningful at the current speed. When stationary or creeping
+    /// COG swings at random, so the display blanks it rather than spinning the compass.
+    pub fn course_is_valid(&self) -> bool {
+        self.course.is_some()
+            && self.speed_knots.is_some_and(|speed| speed >= MIN_SPEED_FOR_COURSE_KNOTS)
+    }
+}
+
 // NMEA parser state
 pub struct NmeaParser {
     nmea: Mutex<Nmea>,
+    /// PRNs used in the fix, per constellation, from the latest GSA sentences
+    fix_prns: Mutex<HashMap<String, HashSet<u32>>>,
+    /// Latest speed/course reported by each of RMC and VTG
+    motion: Mutex<MotionSources>,
+    /// Latest fields from u-blox $PUBX sentences
+    pubx: Mutex<PubxData>,
+}
+
+/// Fields from u-blox proprietary PUBX,00 (position) and PUBX,04 (time/clock) sentences
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PubxData {
+    time: Option<chrono::NaiveTime>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    altitude: Option<f64>,
+    nav_status: Option<String>,
+    horizontal_accuracy_m: Option<f32>,
+    vertical_accuracy_m: Option<f32>,
+    utc_date: Option<chrono::NaiveDate>,
+    clock_bias_ns: Option<f64>,
+    clock_drift_ns_per_s: Option<f64>,
+    leap_seconds: Option<u8>,
+    /// Leap seconds confirmed from the almanac rather than the firmware default
+    leap_seconds_valid: Option<bool>,
+}
+
+impl PubxData {
+    /// Overwrite fields present in `update`, keeping the rest
+    fn merge(&mut self, update: &PubxData) {
+        if update.time.is_some() { self.time = update.time; }
+        if update.latitude.is_some() { self.latitude = update.latitude; }
+        if update.longitude.is_some() { self.longitude = update.longitude; }
+        if update.altitude.is_some() { self.altitude = update.altitude; }
+        if update.nav_status.is_some() { self.nav_status = update.nav_status.clone(); }
+        if update.horizontal_accuracy_m.is_some() { self.horizontal_accuracy_m = update.horizontal_accuracy_m; }
+        if update.vertical_accuracy_m.is_some() { self.vertical_accuracy_m = update.vertical_accuracy_m; }
+        if update.utc_date.is_some() { self.utc_date = update.utc_date; }
+        if update.clock_bias_ns.is_some() { self.clock_bias_ns = update.clock_bias_ns; }
+        if update.clock_drift_ns_per_s.is_some() { self.clock_drift_ns_per_s = update.clock_drift_ns_per_s; }
+        if update.leap_seconds.is_some() { self.leap_seconds = update.leap_seconds; }
+        if update.leap_seconds_valid.is_some() { self.leap_seconds_valid = update.leap_seconds_valid; }
+    }
+}
+
+/// Speed and course as reported by one sentence type
+#[derive(Debug, Clone, Copy)]
+struct MotionReading {
+    received: Instant,
+    speed_knots: f64,
+    course: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+struct MotionSources {
+    rmc: Option<MotionReading>,
+    vtg: Option<MotionReading>,
+}
+
+impl MotionSources {
+    /// Some(true) if recent RMC and VTG readings disagree, None if there is no recent pair
+    fn mismatch(&self) -> Option<bool> {
+        let (rmc, vtg) = (self.rmc?, self.vtg?);
+        let gap = if rmc.received > vtg.received {
+            rmc.received - vtg.received
+        } else {
+            vtg.received - rmc.received
+        };
+        if gap > MOTION_CROSS_CHECK_WINDOW {
+            return None;
+        }
+
+        let speed_mismatch =
+            (rmc.speed_knots - vtg.speed_knots).abs() > MAX_SPEED_DISAGREEMENT_KNOTS;
+        let moving = rmc.speed_knots.min(vtg.speed_knots) >= MIN_SPEED_FOR_COURSE_KNOTS;
+        let course_mismatch = match (rmc.course, vtg.course) {
+            (Some(a), Some(b)) if moving => course_difference(a, b) > MAX_COURSE_DISAGREEMENT_DEG,
+            _ => false,
+        };
+        Some(speed_mismatch || course_mismatch)
+    }
 }
 
 #[allow(dead_code)]
@@ -56,15 +227,80 @@ impl NmeaParser {
     pub fn new() -> Self {
         Self {
             nmea: Mutex::new(Nmea::default()),
+            fix_prns: Mutex::new(HashMap::new()),
+            motion: Mutex::new(MotionSources::default()),
+            pubx: Mutex::new(PubxData::default()),
         }
     }
 
     /// Parse an NMEA sentence and return updated GPS data
     pub fn parse_sentence(&self, sentence: &str) -> Result<GpsData, NmeaError> {
+        // GSA: record which PRNs are in the solution (the nmea crate keeps only
+        // the last GSA's list, which loses all but one constellation on GN receivers)
+        if let Some((constellation, prns)) = parse_gsa_prns(sentence) {
+            self.fix_prns.lock().unwrap().insert(constellation, prns);
+        }
+
+        // PUBX: u-blox proprietary sentences, which the nmea crate rejects
+        let pubx_update = if sentence.starts_with("$PUBX,") {
+            let update = parse_pubx(sentence).ok_or_else(|| {
+                NmeaError::Parse(format!("Unsupported PUBX sentence: {}", sentence))
+            })?;
+            self.pubx.lock().unwrap().merge(&update);
+            Some(update)
+        } else {
+            None
+        };
+
+        // RMC / ZDA date, checked against the host clock for week-rollover bugs.
+        // The nmea crate doesn't handle ZDA, so it only goes through here.
+        let sentence_date = parse_sentence_date(sentence);
+        let is_zda = sentence.get(3..6) == Some("ZDA");
+        if is_zda && sentence_date.is_none() {
+            return Err(NmeaError::Parse(format!("Invalid ZDA sentence: {}", sentence)));
+        }
+        let date = sentence_date.or(pubx_update.as_ref().and_then(|u| u.utc_date));
+        let date_suspect = date.map(|d| is_date_suspect(d, chrono::Utc::now().date_naive()));
+
+        // GNS: GGA's multi-constellation successor, which the nmea crate doesn't handle
+        let gns_update = if sentence.get(3..6) == Some("GNS") {
+            let update = parse_gns(sentence).ok_or_else(|| {
+                NmeaError::Parse(format!("Invalid GNS sentence: {}", sentence))
+            })?;
+            Some(update)
+        } else {
+            None
+        };
+
+        // TXT: receiver diagnostics; only antenna status feeds into the GPS data
+        let txt_update = if sentence.get(3..6) == Some("TXT") {
+            let update = parse_txt(sentence).ok_or_else(|| {
+                NmeaError::Parse(format!("Invalid TXT sentence: {}", sentence))
+            })?;
+            Some(update)
+        } else {
+            None
+        };
+
         let mut nmea = self.nmea.lock().unwrap();
 
         // Parse the sentence
-        nmea.parse(sentence).map_err(|e| NmeaError::Parse(format!("{:?}", e)))?;
+        if pubx_update.is_none() && gns_update.is_none() && txt_update.is_none() && !is_zda {
+            nmea.parse(sentence).map_err(|e| NmeaError::Parse(format!("{:?}", e)))?;
+        }
+
+        // RMC/VTG: keep each source's speed and course to cross-check them
+        let speed_course_mismatch = {
+            let mut motion = self.motion.lock().unwrap();
+            match parse_motion(sentence) {
+                Some((MotionSource::Rmc, reading)) => motion.rmc = Some(reading),
+                Some((MotionSource::Vtg, reading)) => motion.vtg = Some(reading),
+                None => {}
+            }
+            motion.mismatch()
+        };
+
+        let fix_prns = self.fix_prns.lock().unwrap();
 
         // Extract satellite information
         let satellites_info: Vec<SatelliteInfo> = nmea.satellites()
@@ -79,35 +315,32 @@ impl NmeaParser {
                     GnssType::NavIC => "NavIC",
                 }.to_string();
 
+                let used_in_fix = fix_prns
+                    .get(&constellation)
+                    .is_some_and(|prns| prns.contains(&sat.prn()));
+
                 SatelliteInfo {
                     prn: sat.prn(),
                     elevation: sat.elevation(),
                     azimuth: sat.azimuth(),
                     snr: sat.snr(),
                     constellation,
+                    used_in_fix,
                 }
             })
             .collect();
 
         // Determine fix type string
-        let fix_type = nmea.fix_type.map(|f| match f {
-            FixType::Invalid => "No Fix".to_string(),
-            FixType::Gps => "GPS".to_string(),
-            FixType::DGps => "DGPS".to_string(),
-            FixType::Pps => "PPS".to_string(),
-            FixType::Rtk => "RTK".to_string(),
-            FixType::FloatRtk => "Float RTK".to_string(),
-            FixType::Estimated => "Estimated".to_string(),
-            FixType::Manual => "Manual".to_string(),
-            FixType::Simulation => "Simulation".to_string(),
-        });
+        let fix_type = nmea.fix_type.map(|f| fix_type_name(f as u8).to_string());
 
         // Extract all available data (convert f32 to f64 where needed)
-        let data = GpsData {
+        let pubx = self.pubx.lock().unwrap().clone();
+        let mut data = GpsData {
             latitude: nmea.latitude,
             longitude: nmea.longitude,
             speed_knots: nmea.speed_over_ground.map(|v| v as f64),
             course: nmea.true_course.map(|v| v as f64),
+            course_valid: false,
             heading: None, // Would come from HDT/HDG sentence
             altitude: nmea.altitude.map(|v| v as f64),
             fix_quality: nmea.fix_type.map(|f| f as u8),
@@ -117,47 +350,442 @@ impl NmeaParser {
             pdop: nmea.pdop,
             timestamp: nmea.fix_time.map(|t| t.to_string()),
             fix_type,
+            fix_age_seconds: nmea
+                .fix_time
+                .map(|t| fix_age_seconds(t, chrono::Utc::now().time())),
+            speed_course_mismatch,
+            nav_status: pubx.nav_status,
+            horizontal_accuracy_m: pubx.horizontal_accuracy_m,
+            vertical_accuracy_m: pubx.vertical_accuracy_m,
+            utc_date: sentence_date.or(pubx.utc_date).map(|d| d.to_string()),
+            date_suspect,
+            clock_bias_ns: pubx.clock_bias_ns,
+            clock_drift_ns_per_s: pubx.clock_drift_ns_per_s,
+            leap_seconds: pubx.leap_seconds,
+            time_valid: pubx.leap_seconds_valid,
+            antenna_status: txt_update.as_ref().and_then(TextMessage::antenna_status),
+            antenna_power: None,
+            gns_modes: None,
             satellites_info,
         };
 
+        // A PUBX sentence is the freshest source of what it carries (and PUBX,00 has
+        // more position digits than GGA); later standard sentences take over again
+        if let Some(update) = pubx_update {
+            if update.latitude.is_some() { data.latitude = update.latitude; }
+            if update.longitude.is_some() { data.longitude = update.longitude; }
+            if update.altitude.is_some() { data.altitude = update.altitude; }
+            if let Some(time) = update.time {
+                data.timestamp = Some(time.to_string());
+            }
+        }
+
+        // Likewise for GNS, which stands in for GGA on receivers that emit only GNS
+        if let Some(gns) = gns_update {
+            data.latitude = gns.latitude;
+            data.longitude = gns.longitude;
+            data.altitude = gns.altitude;
+            data.fix_quality = Some(gns.fix_quality);
+            data.fix_type = Some(fix_type_name(gns.fix_quality).to_string());
+            if gns.satellites.is_some() { data.satellites = gns.satellites; }
+            if gns.hdop.is_some() { data.hdop = gns.hdop; }
+            if let Some(time) = gns.time {
+                data.timestamp = Some(time.to_string());
+                data.fix_age_seconds = Some(fix_age_seconds(time, chrono::Utc::now().time()));
+            }
+            data.gns_modes = Some(gns.modes);
+        }
+
+        data.course_valid = data.course_is_valid();
         Ok(data)
     }
 
     /// Parse multiple lines of NMEA data
     pub fn parse_batch(&self, data: &str) -> GpsData {
+        // Reading from an in-memory &str cannot fail
+        self.parse_reader(data.as_bytes()).unwrap_or_default()
+    }
+
+    /// Parse NMEA lines from a reader (e.g. a log file), merging into the latest state
+    pub fn parse_reader(&self, reader: impl std::io::BufRead) -> std::io::Result<GpsData> {
         let mut latest = GpsData::default();
 
-        for line in data.lines() {
+        for line in reader.lines() {
+            let line = line?;
             let trimmed = line.trim();
             if !trimmed.is_empty() {
                 if let Ok(gps) = self.parse_sentence(trimmed) {
-                    // Merge non-None values
-                    if gps.latitude.is_some() { latest.latitude = gps.latitude; }
-                    if gps.longitude.is_some() { latest.longitude = gps.longitude; }
-                    if gps.speed_knots.is_some() { latest.speed_knots = gps.speed_knots; }
-                    if gps.course.is_some() { latest.course = gps.course; }
-                    if gps.heading.is_some() { latest.heading = gps.heading; }
-                    if gps.altitude.is_some() { latest.altitude = gps.altitude; }
-                    if gps.fix_quality.is_some() { latest.fix_quality = gps.fix_quality; }
-                    if gps.satellites.is_some() { latest.satellites = gps.satellites; }
-                    if gps.hdop.is_some() { latest.hdop = gps.hdop; }
-                    if gps.vdop.is_some() { latest.vdop = gps.vdop; }
-                    if gps.pdop.is_some() { latest.pdop = gps.pdop; }
-                    if gps.timestamp.is_some() { latest.timestamp = gps.timestamp; }
-                    if gps.fix_type.is_some() { latest.fix_type = gps.fix_type; }
-                    if !gps.satellites_info.is_empty() { latest.satellites_info = gps.satellites_info; }
+                    latest.merge(gps);
                 }
             }
         }
 
-        latest
+        Ok(latest)
     }
 
     /// Reset parser state
     pub fn reset(&self) {
         let mut nmea = self.nmea.lock().unwrap();
         *nmea = Nmea::default();
+        self.fix_prns.lock().unwrap().clear();
+        *self.motion.lock().unwrap() = MotionSources::default();
+        *self.pubx.lock().unwrap() = PubxData::default();
+    }
+}
+
+/// Filter applied when reading the raw NMEA buffer; unset fields match every line
+#[derive(Debug, Clone, Default)]
+pub struct NmeaFilter {
+    /// Sentence type without the talker, e.g. "GSV" (or "PUBX" for proprietary sentences)
+    pub sentence_type: Option<String>,
+    /// Talker ID, e.g. "GL" or "GN"
+    pub talker: Option<String>,
+}
+
+impl NmeaFilter {
+    pub fn is_empty(&self) -> bool {
+        self.sentence_type.is_none() && self.talker.is_none()
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        // Line noise has no address, so only an empty filter lets it through
+        let Some((talker, sentence_type)) = sentence_address(line) else {
+            return self.is_empty();
+        };
+        let field_matches = |want: &Option<String>, have: &str| {
+            want.as_deref().is_none_or(|want| want.eq_ignore_ascii_case(have))
+        };
+        field_matches(&self.talker, talker) && field_matches(&self.sentence_type, sentence_type)
+    }
+}
+
+/// Talker and sentence type of a line, e.g. ("GN", "GGA"). Proprietary sentences
+/// ($P...) have no two-letter talker and give ("P", "PUBX"). None unless the address
+/// is ASCII letters and digits, as on line noise or bytes read at the wrong baud rate.
+pub fn sentence_address(line: &str) -> Option<(&str, &str)> {
+    let address = line
+        .trim_start()
+        .strip_prefix(['$', '!'])
+        .and_then(|rest| rest.split([',', '*']).next())?;
+    if address.is_empty() || !address.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return None;
+    }
+    match address.strip_prefix('P') {
+        Some(_) => Some(("P", address)),
+        None => Some(address.split_at(address.len().min(2))),
+    }
+}
+
+/// True if a receiver-reported date is before `MIN_PLAUSIBLE_YEAR` or more than
+/// `MAX_DATE_AHEAD_DAYS` past `today` (the host's UTC date)
+pub fn is_date_suspect(date: chrono::NaiveDate, today: chrono::NaiveDate) -> bool {
+    use chrono::Datelike;
+    date.year() < MIN_PLAUSIBLE_YEAR || (date - today).num_days() > MAX_DATE_AHEAD_DAYS
+}
+
+/// UTC date from an RMC (ddmmyy, field 9) or ZDA (dd, mm, yyyy) sentence
+fn parse_sentence_date(sentence: &str) -> Option<chrono::NaiveDate> {
+    if !checksum_ok(sentence) {
+        return None;
+    }
+    let body = sentence.split('*').next()?;
+    let fields: Vec<&str> = body.split(',').collect();
+    match fields[0].get(3..) {
+        Some("RMC") => chrono::NaiveDate::parse_from_str(fields.get(9)?, "%d%m%y").ok(),
+        Some("ZDA") => chrono::NaiveDate::from_ymd_opt(
+            fields.get(4)?.parse().ok()?,
+            fields.get(3)?.parse().ok()?,
+            fields.get(2)?.parse().ok()?,
+        ),
+        _ => None,
+    }
+}
+
+/// Age of a fix in seconds: UTC wall-clock time minus the receiver's UTC fix time.
+/// Both are times of day, so the difference is wrapped into +/-12h to survive midnight.
+/// Negative values mean the system clock is behind the receiver.
+pub fn fix_age_seconds(fix_time: chrono::NaiveTime, now: chrono::NaiveTime) -> f64 {
+    const DAY_SECONDS: f64 = 86_400.0;
+    let diff = (now - fix_time).num_milliseconds() as f64 / 1000.0;
+    (diff + DAY_SECONDS / 2.0).rem_euclid(DAY_SECONDS) - DAY_SECONDS / 2.0
+}
+
+/// Smallest angle between two courses in degrees (0-180)
+fn course_difference(a: f64, b: f64) -> f64 {
+    let diff = (a - b).rem_euclid(360.0);
+    diff.min(360.0 - diff)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MotionSource {
+    Rmc,
+    Vtg,
+}
+
+/// Extract speed (knots) and true course from an RMC or VTG sentence.
+/// RMC: $xxRMC,time,status,lat,N,lon,E,sog,cog,date,...
+/// VTG: $xxVTG,cogt,T,cogm,M,sogn,N,sogk,K[,mode]
+fn parse_motion(sentence: &str) -> Option<(MotionSource, MotionReading)> {
+    let body = sentence.strip_prefix('$')?.split('*').next()?;
+    let fields: Vec<&str> = body.split(',').collect();
+    let address = fields.first()?;
+    if address.len() != 5 {
+        return None;
+    }
+
+    let (source, speed_field, course_field) = match &address[2..] {
+        // Void RMC (status V) carries no usable motion
+        "RMC" if fields.get(2) == Some(&"A") => (MotionSource::Rmc, 7, 8),
+        "VTG" => (MotionSource::Vtg, 5, 1),
+        _ => return None,
+    };
+
+    let speed_knots = fields.get(speed_field)?.trim().parse().ok()?;
+    let course = fields.get(course_field).and_then(|f| f.trim().parse().ok());
+    Some((source, MotionReading { received: Instant::now(), speed_knots, course }))
+}
+
+/// True if the sentence has no checksum or a correct one
+pub fn checksum_ok(sentence: &str) -> bool {
+    let Some((body, checksum)) = sentence.trim_start_matches('$').split_once('*') else {
+        return true;
+    };
+    let computed = body.bytes().fold(0u8, |acc, b| acc ^ b);
+    u8::from_str_radix(checksum.trim(), 16).is_ok_and(|c| c == computed)
+}
+
+/// Parse an NMEA ddmm.mmmm / dddmm.mmmm coordinate with its hemisphere letter
+fn parse_nmea_coordinate(value: &str, hemisphere: &str) -> Option<f64> {
+    let raw: f64 = value.parse().ok()?;
+    let degrees = (raw / 100.0).trunc();
+    let decimal = degrees + (raw - degrees * 100.0) / 60.0;
+    match hemisphere {
+        "N" | "E" => Some(decimal),
+        "S" | "W" => Some(-decimal),
+        _ => None,
+    }
+}
+
+/// Fix fields from a GNS sentence
+#[derive(Debug, Clone, PartialEq)]
+struct GnsFix {
+    time: Option<chrono::NaiveTime>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    altitude: Option<f64>,
+    fix_quality: u8, // GGA-equivalent quality of the best constellation mode
+    satellites: Option<u32>,
+    hdop: Option<f32>,
+    modes: BTreeMap<String, String>,
+}
+
+/// Constellations in GNS mode-indicator order (NMEA 4.10)
+const GNS_MODE_CONSTELLATIONS: [&str; 6] = ["GPS", "GLONASS", "Galileo", "BeiDou", "QZSS", "NavIC"];
+
+/// GNS mode indicator -> (description, GGA fix quality), most useful first, so the
+/// overall fix is the first mode any constellation reports
+const GNS_MODES: [(char, &str, u8); 8] = [
+    ('R', "RTK", 4),
+    ('F', "Float RTK", 5),
+    ('D', "Differential", 2),
+    ('P', "Precise", 3),
+    ('A', "Autonomous", 1),
+    ('E', "Estimated", 6),
+    ('M', "Manual", 7),
+    ('S', "Simulator", 8),
+];
+
+/// Parse a GNS sentence.
+/// Layout: $xxGNS,time,lat,N,lon,E,mode,numSV,HDOP,alt,sep,diffAge,diffStation[,navStatus]*cs
+/// where `mode` has one character per constellation (GPS, GLONASS, Galileo, ...)
+fn parse_gns(sentence: &str) -> Option<GnsFix> {
+    if !checksum_ok(sentence) {
+        return None;
+    }
+    let body = sentence.strip_prefix('$')?.split('*').next()?;
+    let fields: Vec<&str> = body.split(',').map(str::trim).collect();
+    if fields.len() < 10 || !fields[0].ends_with("GNS") {
+        return None;
+    }
+
+    let mode = fields[6];
+    let mut modes = BTreeMap::new();
+    for (constellation, indicator) in GNS_MODE_CONSTELLATIONS.iter().zip(mode.chars()) {
+        let description = GNS_MODES
+            .iter()
+            .find(|(c, _, _)| *c == indicator)
+            .map_or("No fix", |(_, d, _)| d);
+        modes.insert(constellation.to_string(), description.to_string());
+    }
+    let fix_quality = GNS_MODES
+        .iter()
+        .find(|(c, _, _)| mode.contains(*c))
+        .map_or(0, |(_, _, q)| *q);
+    let has_fix = fix_quality > 0;
+
+    Some(GnsFix {
+        time: chrono::NaiveTime::parse_from_str(fields[1], "%H%M%S%.f").ok(),
+        latitude: parse_nmea_coordinate(fields[2], fields[3]).filter(|_| has_fix),
+        longitude: parse_nmea_coordinate(fields[4], fields[5]).filter(|_| has_fix),
+        altitude: fields[9].parse().ok().filter(|_| has_fix),
+        fix_quality,
+        satellites: fields[7].parse().ok(),
+        hdop: fields[8].parse().ok(),
+        modes,
+    })
+}
+
+/// Message type of a TXT sentence
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextSeverity {
+    Error,
+    Warning,
+    Notice,
+    User,
+}
+
+/// Diagnostic text from a TXT sentence, e.g. u-blox boot banners and antenna status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TextMessage {
+    pub severity: TextSeverity,
+    pub text: String,
+}
+
+impl TextMessage {
+    /// Antenna supervisor status from a u-blox "ANTSTATUS=..." message: a quick antenna
+    /// check that needs no MON-HW poll
+    pub fn antenna_status(&self) -> Option<AntennaStatus> {
+        AntennaStatus::from_text(self.text.strip_prefix("ANTSTATUS=")?.trim())
+    }
+}
+
+/// Parse a TXT sentence.
+/// Layout: $xxTXT,numMsg,msgNum,msgType,text*cs where msgType is 00 error, 01 warning,
+/// 02 notice and 07 user
+pub fn parse_txt(sentence: &str) -> Option<TextMessage> {
+    if !checksum_ok(sentence) {
+        return None;
+    }
+    let body = sentence.strip_prefix('$')?.split('*').next()?;
+    // The text may itself contain commas
+    let fields: Vec<&str> = body.splitn(5, ',').collect();
+    if fields.len() < 5 || !fields[0].ends_with("TXT") {
+        return None;
     }
+    let severity = match fields[3].parse::<u8>().ok()? {
+        0 => TextSeverity::Error,
+        1 => TextSeverity::Warning,
+        2 => TextSeverity::Notice,
+        _ => TextSeverity::User,
+    };
+    Some(TextMessage {
+        severity,
+        text: fields[4].trim().to_string(),
+    })
+}
+
+/// Display name for a GGA fix quality
+fn fix_type_name(quality: u8) -> &'static str {
+    match quality {
+        1 => "GPS",
+        2 => "DGPS",
+        3 => "PPS",
+        4 => "RTK",
+        5 => "Float RTK",
+        6 => "Estimated",
+        7 => "Manual",
+        8 => "Simulation",
+        _ => "No Fix",
+    }
+}
+
+/// Parse u-blox PUBX,00 (position) and PUBX,04 (time/clock) sentences.
+/// PUBX,00: $PUBX,00,time,lat,N,lon,E,altRef,navStat,hAcc,vAcc,SOG,COG,vVel,diffAge,
+///                   HDOP,VDOP,TDOP,numSvs,reserved,DR*cs
+/// PUBX,04: $PUBX,04,time,date,utcTow,utcWk,leapSec,clkBias,clkDrift,tpGran*cs
+fn parse_pubx(sentence: &str) -> Option<PubxData> {
+    if !checksum_ok(sentence) {
+        return None;
+    }
+    let body = sentence.strip_prefix('$')?.split('*').next()?;
+    let fields: Vec<&str> = body.split(',').map(str::trim).collect();
+    let number = |i: usize| fields.get(i).and_then(|f| f.parse::<f64>().ok());
+    let time = fields
+        .get(2)
+        .and_then(|t| chrono::NaiveTime::parse_from_str(t, "%H%M%S%.f").ok());
+
+    match fields.get(1).copied() {
+        Some("00") if fields.len() >= 11 => {
+            let nav_status = fields[8].to_string();
+            // Without a fix the position fields are zero-filled, not a real position
+            let has_fix = nav_status != "NF";
+            Some(PubxData {
+                time,
+                latitude: parse_nmea_coordinate(fields[3], fields[4]).filter(|_| has_fix),
+                longitude: parse_nmea_coordinate(fields[5], fields[6]).filter(|_| has_fix),
+                altitude: number(7).filter(|_| has_fix),
+                nav_status: Some(nav_status),
+                horizontal_accuracy_m: number(9).map(|v| v as f32),
+                vertical_accuracy_m: number(10).map(|v| v as f32),
+                ..PubxData::default()
+            })
+        }
+        Some("04") if fields.len() >= 9 => Some(PubxData {
+            time,
+            utc_date: fields
+                .get(3)
+                .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%d%m%y").ok()),
+            clock_bias_ns: number(7),
+            clock_drift_ns_per_s: number(8),
+            // A trailing 'D' marks the firmware default, used until the almanac is read
+            leap_seconds: fields[6].trim_end_matches('D').parse().ok(),
+            leap_seconds_valid: (!fields[6].is_empty()).then(|| !fields[6].ends_with('D')),
+            ..PubxData::default()
+        }),
+        _ => None,
+    }
+}
+
+/// Extract the constellation and used-PRN list from a GSA sentence.
+/// Layout: $xxGSA,mode1,mode2,prn1..prn12,pdop,hdop,vdop[,systemId]*cs
+fn parse_gsa_prns(sentence: &str) -> Option<(String, HashSet<u32>)> {
+    if !checksum_ok(sentence) {
+        return None;
+    }
+    let body = sentence.strip_prefix('$')?.split('*').next()?;
+    let fields: Vec<&str> = body.split(',').collect();
+    let address = fields.first()?;
+    if address.len() != 5 || !address.ends_with("GSA") || fields.len() < 15 {
+        return None;
+    }
+
+    let prns: HashSet<u32> = fields[3..15]
+        .iter()
+        .filter_map(|f| f.trim().parse().ok())
+        .collect();
+
+    let constellation = match &address[..2] {
+        "GP" => "GPS",
+        "GL" => "GLONASS",
+        "GA" => "Galileo",
+        "GB" | "BD" => "BeiDou",
+        "GQ" => "QZSS",
+        "GI" => "NavIC",
+        // GN: use the NMEA 4.10 system ID if present, else infer from PRN range
+        _ => match fields.get(18).map(|f| f.trim()) {
+            Some("1") => "GPS",
+            Some("2") => "GLONASS",
+            Some("3") => "Galileo",
+            Some("4") => "BeiDou",
+            Some("5") => "QZSS",
+            Some("6") => "NavIC",
+            _ if !prns.is_empty() && prns.iter().all(|p| (65..=96).contains(p)) => "GLONASS",
+            _ => "GPS",
+        },
+    };
+
+    Some((constellation.to_string(), prns))
 }
 
 #[cfg(test)]
@@ -179,6 +807,109 @@ mod tests {
         assert!((lon - (-6.50)).abs() < 0.1, "Longitude should be ~-6.50, got {}", lon);
     }
 
+    #[test]
+    fn test_high_precision_gga_keeps_decimals() {
+        // u-blox NMEA high-precision mode: seven decimal places of minutes
+        let sentence = "$GPGGA,092750.000,5321.6802123,N,00630.3372456,W,4,12,0.62,61.725,M,\
+                        55.200,M,1.0,0000*61";
+        let gps = NmeaParser::new().parse_sentence(sentence).unwrap();
+        assert!((gps.latitude.unwrap() - 53.361_336_871_67).abs() < 1e-10);
+        assert!((gps.longitude.unwrap() + 6.505_620_76).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_parse_gns() {
+        let parser = NmeaParser::new();
+        let gps = parser
+            .parse_sentence(
+                "$GNGNS,122310.00,3722.425671,N,12258.856215,W,AAN,12,0.9,1005.543,6.5,,,V*74",
+            )
+            .unwrap();
+        assert!((gps.latitude.unwrap() - 37.373_761).abs() < 1e-5);
+        assert!((gps.longitude.unwrap() + 122.980_937).abs() < 1e-5);
+        assert_eq!(gps.altitude, Some(1005.543));
+        assert_eq!(gps.fix_quality, Some(1));
+        assert_eq!(gps.fix_type.as_deref(), Some("GPS"));
+        assert_eq!(gps.satellites, Some(12));
+        assert_eq!(gps.hdop, Some(0.9));
+        assert_eq!(gps.timestamp.as_deref(), Some("12:23:10"));
+        let modes = gps.gns_modes.unwrap();
+        assert_eq!(modes["GPS"], "Autonomous");
+        assert_eq!(modes["GLONASS"], "Autonomous");
+        assert_eq!(modes["Galileo"], "No fix");
+        assert_eq!(modes.len(), 3);
+
+        // No fix on any constellation: no position, quality 0
+        let gps = parser.parse_sentence("$GNGNS,122311.00,,,,,NNN,00,,,,,,V*4B").unwrap();
+        assert_eq!(gps.latitude, None);
+        assert_eq!(gps.fix_quality, Some(0));
+        assert_eq!(gps.fix_type.as_deref(), Some("No Fix"));
+
+        assert!(parser.parse_sentence("$GNGNS,122311.00,,,,,NNN,00,,,,,,V*4C").is_err());
+    }
+
+    #[test]
+    fn test_implausible_dates_flagged() {
+        // Week-rollover receiver reporting 1999
+        let parser = NmeaParser::new();
+        let gps = parser
+            .parse_sentence("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191199,020.3,E*65")
+            .unwrap();
+        assert_eq!(gps.utc_date.as_deref(), Some("1999-11-19"));
+        assert_eq!(gps.date_suspect, Some(true));
+
+        let gps = parser.parse_sentence("$GPZDA,201530.00,04,07,2025,00,00*65").unwrap();
+        assert_eq!(gps.utc_date.as_deref(), Some("2025-07-04"));
+        assert_eq!(gps.date_suspect, Some(false));
+
+        // Sentences without a date leave the flag unset
+        let gga = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+        assert_eq!(parser.parse_sentence(gga).unwrap().date_suspect, None);
+
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert!(!is_date_suspect(today.succ_opt().unwrap(), today));
+        assert!(is_date_suspect(chrono::NaiveDate::from_ymd_opt(2046, 3, 1).unwrap(), today));
+    }
+
+    #[test]
+    fn test_nmea_filter() {
+        let buffer = [
+            "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76",
+            "$GPGSV,2,1,08,01,40,083,46,02,17,308,41,12,07,344,39,14,22,228,45*75",
+            "$GLGSV,1,1,02,65,45,090,38,66,30,200,35*6A",
+            "$GNRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*76",
+            "$PUBX,00,225446.00,4916.45,N,12311.12,W,0.0,G3,2.1,2.5*00",
+        ];
+        let filtered = |filter: &NmeaFilter| -> Vec<&str> {
+            buffer.iter().copied().filter(|line| filter.matches(line)).collect()
+        };
+
+        let gsv = NmeaFilter { sentence_type: Some("gsv".into()), talker: None };
+        assert_eq!(filtered(&gsv), [buffer[1], buffer[2]]);
+
+        let glonass_gsv = NmeaFilter { talker: Some("GL".into()), ..gsv };
+        assert_eq!(filtered(&glonass_gsv), [buffer[2]]);
+
+        let pubx = NmeaFilter { sentence_type: Some("PUBX".into()), talker: None };
+        assert_eq!(filtered(&pubx), [buffer[4]]);
+
+        assert!(NmeaFilter::default().is_empty());
+        assert_eq!(filtered(&NmeaFilter::default()).len(), buffer.len());
+    }
+
+    #[test]
+    fn test_sentence_address_rejects_non_ascii() {
+        // A stray 0xB5 (UBX sync) in the talker position, after from_utf8_lossy
+        let line = String::from_utf8_lossy(b"$\xB5GGA,092750.000,5321.6802,N*76").into_owned();
+        assert_eq!(sentence_address(&line), None);
+        assert_eq!(sentence_address("$GPGGA,1*00"), Some(("GP", "GGA")));
+        assert_eq!(sentence_address("noise"), None);
+
+        let gsv = NmeaFilter { sentence_type: Some("GSV".into()), talker: None };
+        assert!(!gsv.matches(&line));
+        assert!(NmeaFilter::default().matches(&line));
+    }
+
     #[test]
     fn test_parse_rmc() {
         let parser = NmeaParser::new();
@@ -189,4 +920,203 @@ mod tests {
         assert!(gps.speed_knots.is_some(), "Speed should be parsed");
         assert!(gps.course.is_some(), "Course should be parsed");
     }
+
+    #[test]
+    fn test_course_invalid_below_speed_threshold() {
+        // 0.5 kn: the receiver still reports a course, but it is noise
+        let parser = NmeaParser::new();
+        let slow = "$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*68";
+        let gps = parser.parse_sentence(slow).unwrap();
+        assert!(gps.course.is_some());
+        assert!(!gps.course_valid);
+
+        // Merged state follows the latest speed
+        let mut state = gps;
+        state.merge(GpsData { speed_knots: Some(6.2), ..GpsData::default() });
+        assert!(state.course_valid);
+        state.merge(GpsData { speed_knots: Some(0.4), ..GpsData::default() });
+        assert!(!state.course_valid);
+    }
+
+    #[test]
+    fn test_fix_age_seconds() {
+        let t = |h, m, s, ms| chrono::NaiveTime::from_hms_milli_opt(h, m, s, ms).unwrap();
+        assert!((fix_age_seconds(t(12, 0, 0, 0), t(12, 0, 2, 500)) - 2.5).abs() < 1e-9);
+        // Receiver ahead of the system clock
+        assert!((fix_age_seconds(t(12, 0, 1, 0), t(12, 0, 0, 0)) + 1.0).abs() < 1e-9);
+        // Across midnight
+        assert!((fix_age_seconds(t(23, 59, 59, 0), t(0, 0, 1, 0)) - 2.0).abs() < 1e-9);
+        assert!((fix_age_seconds(t(0, 0, 1, 0), t(23, 59, 59, 0)) + 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_gsa_marks_used_satellites() {
+        let parser = NmeaParser::new();
+        let _ = parser.parse_sentence("$GPGSA,A,3,05,12,17,,,,,,,,,,1.8,1.0,1.5*3E");
+        // A corrupted GSA must not replace the solution's PRNs
+        assert!(parser.parse_sentence("$GPGSA,A,3,24,,,,,,,,,,,,1.8,1.0,1.5*3E").is_err());
+        let gps = parser
+            .parse_sentence("$GPGSV,1,1,04,05,45,120,40,12,30,200,35,17,60,310,42,24,10,050,*7C")
+            .expect("Failed to parse GSV");
+
+        let used = |prn: u32| {
+            gps.satellites_info
+                .iter()
+                .find(|s| s.prn == prn)
+                .map(|s| s.used_in_fix)
+                .unwrap_or_else(|| panic!("PRN {} missing", prn))
+        };
+        assert!(used(5));
+        assert!(used(12));
+        assert!(used(17));
+        assert!(!used(24), "PRN 24 is tracked but not in the GSA solution");
+    }
+
+    #[test]
+    fn test_parse_gsa_prns_multi_constellation() {
+        let (c, prns) = parse_gsa_prns("$GNGSA,A,3,05,12,17,,,,,,,,,,1.8,1.0,1.5,1*3D").unwrap();
+        assert_eq!(c, "GPS");
+        assert_eq!(prns, HashSet::from([5, 12, 17]));
+
+        let (c, prns) = parse_gsa_prns("$GNGSA,A,3,65,,,,,,,,,,,,1.8,1.0,1.5,2*3D").unwrap();
+        assert_eq!(c, "GLONASS");
+        assert_eq!(prns, HashSet::from([65]));
+
+        assert!(parse_gsa_prns("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76").is_none());
+    }
+
+    #[test]
+    fn test_rmc_vtg_speed_mismatch_flagged() {
+        let parser = NmeaParser::new();
+        let rmc = "$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*68";
+
+        // Only one source so far: nothing to compare
+        let gps = parser.parse_sentence(rmc).unwrap();
+        assert_eq!(gps.speed_course_mismatch, None);
+
+        let gps = parser
+            .parse_sentence("$GPVTG,054.7,T,034.4,M,000.5,N,000.9,K,A*2A")
+            .unwrap();
+        assert_eq!(gps.speed_course_mismatch, Some(false));
+
+        // VTG says 12 knots while RMC says 0.5
+        let gps = parser
+            .parse_sentence("$GPVTG,054.7,T,034.4,M,012.0,N,022.2,K,A*27")
+            .unwrap();
+        assert_eq!(gps.speed_course_mismatch, Some(true));
+        let mut state = gps;
+
+        parser.reset();
+        let gps = parser.parse_sentence(rmc).unwrap();
+        assert_eq!(gps.speed_course_mismatch, None);
+        state.merge(gps);
+        assert_eq!(state.speed_course_mismatch, None, "no current pair clears the flag");
+    }
+
+    #[test]
+    fn test_rmc_vtg_course_mismatch_flagged() {
+        let parser = NmeaParser::new();
+        let batch = "$GPRMC,225446,A,4916.45,N,12311.12,W,010.0,054.7,191194,020.3,E*6C\n\
+                     $GPVTG,140.0,T,119.7,M,010.1,N,018.7,K,A*26\n";
+        assert_eq!(parser.parse_batch(batch).speed_course_mismatch, Some(true));
+    }
+
+    #[test]
+    fn test_parse_txt_antenna_open() {
+        let sentence = "$GPTXT,01,01,01,ANTSTATUS=OPEN*28";
+        let message = parse_txt(sentence).unwrap();
+        assert_eq!(message.severity, TextSeverity::Warning);
+        assert_eq!(message.text, "ANTSTATUS=OPEN");
+        assert_eq!(message.antenna_status(), Some(AntennaStatus::Open));
+
+        let parser = NmeaParser::new();
+        let gps = parser.parse_sentence(sentence).unwrap();
+        assert_eq!(gps.antenna_status, Some(AntennaStatus::Open));
+
+        // Other text is kept whole and says nothing about the antenna
+        let banner = parse_txt("$GPTXT,01,01,02,u-blox AG - www.u-blox.com*50").unwrap();
+        assert_eq!(banner.severity, TextSeverity::Notice);
+        assert_eq!(banner.text, "u-blox AG - www.u-blox.com");
+        assert_eq!(banner.antenna_status(), None);
+        assert!(parse_txt("$GPTXT,01,01,01,ANTSTATUS=OPEN*29").is_none());
+    }
+
+    #[test]
+    fn test_parse_pubx_00_position() {
+        let parser = NmeaParser::new();
+        let gps = parser
+            .parse_sentence("$PUBX,00,081350.00,4717.113210,N,00833.915187,E,546.589,G3,2.1,2.0,0.007,77.52,0.007,,0.92,1.19,0.77,9,0,0*5F")
+            .expect("Failed to parse PUBX,00");
+
+        assert_eq!(gps.nav_status.as_deref(), Some("G3"));
+        assert!((gps.latitude.unwrap() - 47.285_220_17).abs() < 1e-8);
+        assert!((gps.longitude.unwrap() - 8.565_253_12).abs() < 1e-8);
+        assert_eq!(gps.altitude, Some(546.589));
+        assert_eq!(gps.horizontal_accuracy_m, Some(2.1));
+        assert_eq!(gps.vertical_accuracy_m, Some(2.0));
+        assert_eq!(gps.timestamp.as_deref(), Some("08:13:50"));
+
+        // No fix: status is kept but the zero-filled position is not
+        let parser = NmeaParser::new();
+        let gps = parser
+            .parse_sentence("$PUBX,00,000000.00,0000.00000,N,00000.00000,E,0.000,NF,5303302,3750002,0.000,0.00,0.000,,99.99,99.99,99.99,0,0,0*2B")
+            .unwrap();
+        assert_eq!(gps.nav_status.as_deref(), Some("NF"));
+        assert_eq!(gps.latitude, None);
+    }
+
+    #[test]
+    fn test_parse_pubx_04_time_and_clock() {
+        let parser = NmeaParser::new();
+        let gps = parser
+            .parse_sentence("$PUBX,04,073731.00,091202,113851.00,1196,15D,1930035,-2660.664,43,*5D")
+            .expect("Failed to parse PUBX,04");
+
+        assert_eq!(gps.utc_date.as_deref(), Some("2002-12-09"));
+        assert_eq!(gps.timestamp.as_deref(), Some("07:37:31"));
+        assert_eq!(gps.clock_bias_ns, Some(1_930_035.0));
+        assert_eq!(gps.clock_drift_ns_per_s, Some(-2660.664));
+
+        // PUBX fields persist alongside standard sentences
+        let gps = parser
+            .parse_sentence("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+            .unwrap();
+        assert_eq!(gps.utc_date.as_deref(), Some("2002-12-09"));
+
+        // Corrupt checksum and unknown PUBX message IDs are rejected
+        assert!(parser
+            .parse_sentence("$PUBX,04,073731.00,091202,113851.00,1196,15D,1930035,-2660.664,43,*00")
+            .is_err());
+        assert!(parser.parse_sentence("$PUBX,41,1,0007,0003,19200,0*25").is_err());
+    }
+
+    #[test]
+    fn test_pubx_04_leap_seconds() {
+        // Firmware default (15 s for this old receiver) until the almanac confirms it
+        let gps = NmeaParser::new()
+            .parse_sentence("$PUBX,04,073731.00,091202,113851.00,1196,15D,1930035,-2660.664,43,*5D")
+            .unwrap();
+        assert_eq!(gps.leap_seconds, Some(15));
+        assert_eq!(gps.time_valid, Some(false));
+
+        let gps = NmeaParser::new()
+            .parse_sentence("$PUBX,04,120000.00,160126,475200.00,2401,18,12345,-12.500,21,*17")
+            .unwrap();
+        assert_eq!(gps.leap_seconds, Some(18));
+        assert_eq!(gps.time_valid, Some(true));
+
+        // Standard sentences carry no leap seconds
+        let gps = NmeaParser::new()
+            .parse_sentence("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+            .unwrap();
+        assert_eq!(gps.leap_seconds, None);
+        assert_eq!(gps.time_valid, None);
+    }
+
+    #[test]
+    fn test_course_difference_wraps() {
+        assert!((course_difference(350.0, 10.0) - 20.0).abs() < 1e-9);
+        assert!((course_difference(10.0, 350.0) - 20.0).abs() < 1e-9);
+        assert!((course_difference(90.0, 270.0) - 180.0).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,180 @@
+// NMEA log files: plain `.nmea` captures or gzip-compressed `.nmea.gz` archives
+
+use crate::nmea::{GpsData, NmeaParser};
+use chrono::NaiveTime;
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::time::Instant;
+
+/// Parser throughput over a log
+#[derive(Debug, Clone, Serialize)]
+pub struct ParseBenchmark {
+    pub sentences: u64,
+    pub errors: u64, // Lines the parser rejected (included in `sentences`)
+    pub elapsed_seconds: f64,
+    pub sentences_per_second: f64,
+}
+
+/// Open a log for line-by-line reading, decompressing `.gz` files transparently
+pub fn open_log(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    let compressed = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"));
+    if compressed {
+        Ok(Box::new(BufReader::new(GzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Replay a log through a fresh parser and return the GPS state at its end
+pub fn replay_log(path: &Path) -> io::Result<GpsData> {
+    NmeaParser::new().parse_reader(open_log(path)?)
+}
+
+/// Replay a log as `(seconds since the first fix time, GpsData)` samples, one per
+/// epoch, for driving `TestRunner::replay`. Logs carry no capture time, so the NMEA
+/// fix time stands in for it: a sample is taken each time it moves on, holding the
+/// state merged from every sentence of the epoch before. Lines before the first
+/// timestamped sentence still contribute to the first sample.
+pub fn timed_samples(path: &Path) -> io::Result<Vec<(f64, GpsData)>> {
+    let parser = NmeaParser::new();
+    let mut samples = Vec::new();
+    let mut latest = GpsData::default();
+    let mut first: Option<NaiveTime> = None;
+    let mut epoch: Option<NaiveTime> = None;
+
+    for line in open_log(path)?.lines() {
+        let line = line?;
+        let Ok(gps) = parser.parse_sentence(line.trim()) else {
+            continue;
+        };
+        let time = gps.timestamp.as_deref().and_then(parse_fix_time);
+        if let (Some(time), Some(current)) = (time, epoch) {
+            if time != current {
+                samples.push((seconds_between(first.unwrap_or(current), current), latest.clone()));
+            }
+        }
+        if let Some(time) = time {
+            first.get_or_insert(time);
+            epoch = Some(time);
+        }
+        latest.merge(gps);
+    }
+    if let Some(current) = epoch {
+        samples.push((seconds_between(first.unwrap_or(current), current), latest));
+    }
+    Ok(samples)
+}
+
+/// Time `NmeaParser::parse_sentence` over every non-empty line of a log. The log is
+/// read into memory first, so only parsing is timed, not disk or decompression.
+pub fn benchmark_parse(path: &Path) -> io::Result<ParseBenchmark> {
+    let lines: Vec<String> = open_log(path)?
+        .lines()
+        .filter(|line| line.as_ref().map_or(true, |l| !l.trim().is_empty()))
+        .collect::<io::Result<_>>()?;
+
+    let parser = NmeaParser::new();
+    let mut errors = 0;
+    let start = Instant::now();
+    for line in &lines {
+        if parser.parse_sentence(line.trim()).is_err() {
+            errors += 1;
+        }
+    }
+    let elapsed_seconds = start.elapsed().as_secs_f64();
+
+    Ok(ParseBenchmark {
+        sentences: lines.len() as u64,
+        errors,
+        elapsed_seconds,
+        sentences_per_second: if elapsed_seconds > 0.0 {
+            lines.len() as f64 / elapsed_seconds
+        } else {
+            0.0
+        },
+    })
+}
+
+/// Parse a `GpsData::timestamp` ("HH:MM:SS" with optional fraction)
+pub(crate) fn parse_fix_time(timestamp: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(timestamp, "%H:%M:%S%.f").ok()
+}
+
+/// Seconds from `start` to `time`, assuming a log crossing midnight UTC wraps once
+fn seconds_between(start: NaiveTime, time: NaiveTime) -> f64 {
+    let secs = (time - start).num_milliseconds() as f64 / 1000.0;
+    if secs < 0.0 {
+        secs + 86_400.0
+    } else {
+        secs
+    }
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_gzipped_log_matches_plain() {
+        let plain = replay_log(&fixture("short_fix.nmea")).unwrap();
+        let gzipped = replay_log(&fixture("short_fix.nmea.gz")).unwrap();
+
+        assert!(plain.latitude.is_some());
+        assert_eq!(gzipped.latitude, plain.latitude);
+        assert_eq!(gzipped.longitude, plain.longitude);
+        assert_eq!(gzipped.fix_quality, plain.fix_quality);
+        assert_eq!(gzipped.satellites, plain.satellites);
+        assert_eq!(gzipped.hdop, plain.hdop);
+        assert_eq!(gzipped.speed_knots, plain.speed_knots);
+        assert_eq!(gzipped.timestamp, plain.timestamp);
+        assert_eq!(gzipped.satellites_info.len(), plain.satellites_info.len());
+    }
+
+    #[test]
+    fn test_timed_samples_one_per_epoch() {
+        let samples = timed_samples(&fixture("replay/stable_fix_pass.nmea")).unwrap();
+        let times: Vec<f64> = samples.iter().map(|(t, _)| *t).collect();
+        assert_eq!(times, (0..15).map(f64::from).collect::<Vec<_>>());
+
+        // Each sample holds the whole epoch, not just its last sentence
+        let (_, first) = &samples[0];
+        assert_eq!(first.satellites, Some(10));
+        assert_eq!(first.pdop, Some(1.5));
+        assert_eq!(first.satellites_info.len(), 7);
+
+        let before = parse_fix_time("23:59:59").unwrap();
+        let after = parse_fix_time("00:00:01").unwrap();
+        assert_eq!(seconds_between(before, after), 2.0);
+    }
+
+    #[test]
+    fn test_benchmark_parse_counts_sentences() {
+        let plain = benchmark_parse(&fixture("short_fix.nmea")).unwrap();
+        let gzipped = benchmark_parse(&fixture("short_fix.nmea.gz")).unwrap();
+        assert!(plain.sentences > 0);
+        assert_eq!(gzipped.sentences, plain.sentences);
+        assert_eq!(gzipped.errors, plain.errors);
+        assert!(plain.errors < plain.sentences);
+        assert!(plain.sentences_per_second > 0.0);
+    }
+
+    #[test]
+    fn test_missing_log_is_an_error() {
+        assert!(replay_log(&fixture("does_not_exist.nmea.gz")).is_err());
+    }
+}
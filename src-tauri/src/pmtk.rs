@@ -0,0 +1,108 @@
+// PMTK command definitions for MediaTek (MT3339 / MT3329) GPS receivers
+//
+// MediaTek chips do not speak UBX; they are configured with proprietary NMEA
+// sentences of the form `$PMTKxxx,...*CS\r\n`.
+//
+// References:
+//   PMTK Command Packet (MTK-3301/3329/3339 Platform NMEA Message Specification)
+
+// ============ PMTK Command Numbers ============
+
+pub const PMTK_SET_NMEA_BAUDRATE: u16 = 251;
+pub const PMTK_API_SET_NMEA_OUTPUT: u16 = 314;
+pub const PMTK_API_SET_FIX_CTL: u16 = 220;
+
+/// Baud rates accepted by PMTK251
+pub const PMTK_SUPPORTED_BAUD_RATES: [u32; 6] = [4800, 9600, 14400, 19200, 38400, 115200];
+
+// ============ Message Construction ============
+
+/// Calculate the NMEA checksum (XOR of all bytes between '$' and '*')
+pub fn pmtk_checksum(body: &str) -> u8 {
+    body.bytes().fold(0u8, |acc, b| acc ^ b)
+}
+
+/// Build a complete PMTK sentence (with '$', checksum and CRLF) from its body,
+/// e.g. `PMTK220,1000` -> `$PMTK220,1000*1F\r\n`
+pub fn build_pmtk_message(body: &str) -> Vec<u8> {
+    format!("${}*{:02X}\r\n", body, pmtk_checksum(body)).into_bytes()
+}
+
+// ============ Commands ============
+
+/// PMTK251: change the NMEA port baud rate. Returns None for unsupported rates.
+/// The receiver switches immediately, so the port must follow straight afterwards.
+pub fn build_set_baud_rate(baud_rate: u32) -> Option<Vec<u8>> {
+    if !PMTK_SUPPORTED_BAUD_RATES.contains(&baud_rate) {
+        return None;
+    }
+    Some(build_pmtk_message(&format!(
+        "PMTK{},{}",
+        PMTK_SET_NMEA_BAUDRATE, baud_rate
+    )))
+}
+
+/// PMTK314: per-sentence output rates (0 = disabled, N = once every N fixes).
+/// Remaining fields (GRS, GST, ZDA, MCHN, ...) are left disabled.
+pub fn build_set_nmea_output(gll: u8, rmc: u8, vtg: u8, gga: u8, gsa: u8, gsv: u8) -> Vec<u8> {
+    let mut fields = vec![gll, rmc, vtg, gga, gsa, gsv];
+    fields.resize(19, 0);
+    let rates: Vec<String> = fields.iter().map(|r| r.to_string()).collect();
+    build_pmtk_message(&format!(
+        "PMTK{},{}",
+        PMTK_API_SET_NMEA_OUTPUT,
+        rates.join(",")
+    ))
+}
+
+/// PMTK220: position fix interval in milliseconds (100-10000)
+pub fn build_set_fix_rate(interval_ms: u32) -> Vec<u8> {
+    let interval_ms = interval_ms.clamp(100, 10000);
+    build_pmtk_message(&format!("PMTK{},{}", PMTK_API_SET_FIX_CTL, interval_ms))
+}
+
+// ============ Marine Profile ============
+
+/// Commands applied on connect to a MediaTek receiver: 1Hz fixes with
+/// GGA, RMC, VTG, GSA and GSV on every fix, GLL disabled
+pub fn get_marine_profile_commands() -> Vec<Vec<u8>> {
+    vec![
+        build_set_fix_rate(1000),
+        build_set_nmea_output(0, 1, 1, 1, 1, 1),
+    ]
+}
+
+/// Human-readable name for the MediaTek profile
+pub fn profile_name() -> &'static str {
+    "MediaTek Marine (1Hz, GGA/RMC/VTG/GSA/GSV)"
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pmtk_checksum_fix_rate() {
+        assert_eq!(pmtk_checksum("PMTK220,1000"), 0x1F);
+        assert_eq!(build_set_fix_rate(1000), b"$PMTK220,1000*1F\r\n".to_vec());
+    }
+
+    #[test]
+    fn test_set_baud_rate() {
+        assert_eq!(
+            build_set_baud_rate(9600).unwrap(),
+            b"$PMTK251,9600*17\r\n".to_vec()
+        );
+        assert!(build_set_baud_rate(12345).is_none());
+    }
+
+    #[test]
+    fn test_set_nmea_output_field_count() {
+        let msg = String::from_utf8(build_set_nmea_output(0, 1, 0, 1, 1, 1)).unwrap();
+        assert!(msg.starts_with("$PMTK314,0,1,0,1,1,1,0,"));
+        let body = msg.trim_start_matches('$').split('*').next().unwrap();
+        assert_eq!(body.split(',').count(), 20); // command + 19 rate fields
+    }
+}
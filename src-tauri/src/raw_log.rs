@@ -0,0 +1,368 @@
+// Raw serial captures: the byte stream exactly as read (NMEA and UBX), with read times
+//
+// A `.ubx` capture starts with `RAW_LOG_MAGIC`, followed by one record per port read:
+// u32 LE milliseconds since the capture started, u32 LE byte count, then the bytes.
+// Replaying splits the stream back into NMEA lines and UBX frames with `Framer`, so
+// binary traffic the NMEA logger drops (ACKs, MON-VER, NAV-PVT) is kept.
+
+use crate::nmea::{GpsData, NmeaParser};
+use crate::ubx_config;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::Instant;
+
+pub const RAW_LOG_MAGIC: &[u8; 8] = b"GPSRAW01";
+
+/// Longest UBX payload accepted; a larger length field is taken as line noise
+const MAX_UBX_PAYLOAD: usize = 8192;
+/// Longest partial NMEA line held while waiting for its newline
+const MAX_NMEA_LINE: usize = 1024;
+
+/// One read from the port
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawChunk {
+    pub at_ms: u32, // Since the capture started
+    pub bytes: Vec<u8>,
+}
+
+/// A capture in progress
+#[derive(Debug)]
+pub struct RawCapture {
+    started: Instant,
+    pub chunks: Vec<RawChunk>,
+}
+
+impl RawCapture {
+    pub fn new() -> Self {
+        Self { started: Instant::now(), chunks: Vec::new() }
+    }
+
+    pub fn record(&mut self, bytes: &[u8]) {
+        let at_ms = self.started.elapsed().as_millis().min(u32::MAX as u128) as u32;
+        self.chunks.push(RawChunk { at_ms, bytes: bytes.to_vec() });
+    }
+}
+
+pub fn write_chunks(mut writer: impl Write, chunks: &[RawChunk]) -> io::Result<()> {
+    writer.write_all(RAW_LOG_MAGIC)?;
+    for chunk in chunks {
+        writer.write_all(&chunk.at_ms.to_le_bytes())?;
+        writer.write_all(&(chunk.bytes.len() as u32).to_le_bytes())?;
+        writer.write_all(&chunk.bytes)?;
+    }
+    writer.flush()
+}
+
+pub fn read_chunks(mut reader: impl Read) -> io::Result<Vec<RawChunk>> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != RAW_LOG_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a raw GPS capture"));
+    }
+
+    let mut chunks = Vec::new();
+    let mut header = [0u8; 8];
+    loop {
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e),
+        }
+        let at_ms = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let mut bytes = vec![0u8; len];
+        reader.read_exact(&mut bytes)?;
+        chunks.push(RawChunk { at_ms, bytes });
+    }
+    Ok(chunks)
+}
+
+pub fn save_capture(path: &Path, chunks: &[RawChunk]) -> io::Result<()> {
+    write_chunks(BufWriter::new(File::create(path)?), chunks)
+}
+
+pub fn load_capture(path: &Path) -> io::Result<Vec<RawChunk>> {
+    read_chunks(BufReader::new(File::open(path)?))
+}
+
+/// A complete message split out of the byte stream
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    Nmea(String),
+    Ubx { class: u8, id: u8, payload: Vec<u8> },
+}
+
+/// Splits a byte stream, fed in arbitrary pieces, into NMEA lines and UBX frames.
+/// Bytes that belong to neither (a partial message at the start, corrupt UBX) are
+/// skipped until the next `$` or UBX sync.
+#[derive(Debug, Default)]
+pub struct Framer {
+    buf: Vec<u8>,
+}
+
+impl Framer {
+    /// Add bytes and return every message they complete
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<Frame> {
+        self.buf.extend_from_slice(bytes);
+        let mut frames = Vec::new();
+
+        loop {
+            let Some(start) = self.buf.iter().enumerate().position(|(i, &b)| {
+                b == b'$' || (b == ubx_config::UBX_SYNC_1 && self.buf.get(i + 1) != Some(&0))
+            }) else {
+                self.buf.clear();
+                break;
+            };
+            self.buf.drain(..start);
+
+            if self.buf[0] == ubx_config::UBX_SYNC_1 {
+                match self.take_ubx() {
+                    Some(Some(frame)) => frames.push(frame),
+                    Some(None) => continue,
+                    None => break,
+                }
+            } else {
+                match self.take_nmea() {
+                    Some(Some(frame)) => frames.push(frame),
+                    Some(None) => continue,
+                    None => break,
+                }
+            }
+        }
+        frames
+    }
+
+    /// UBX frame at the start of the buffer: None = need more bytes, Some(None) =
+    /// not a valid frame (one byte dropped to resync)
+    fn take_ubx(&mut self) -> Option<Option<Frame>> {
+        if self.buf.len() < 2 {
+            return None;
+        }
+        if self.buf[1] != ubx_config::UBX_SYNC_2 {
+            self.buf.remove(0);
+            return Some(None);
+        }
+        if self.buf.len() < 6 {
+            return None;
+        }
+        let payload_len = u16::from_le_bytes([self.buf[4], self.buf[5]]) as usize;
+        if payload_len > MAX_UBX_PAYLOAD {
+            self.buf.remove(0);
+            return Some(None);
+        }
+        let total = 8 + payload_len;
+        if self.buf.len() < total {
+            return None;
+        }
+        let (ck_a, ck_b) = ubx_config::ubx_checksum(&self.buf[2..6 + payload_len]);
+        if self.buf[6 + payload_len..total] != [ck_a, ck_b] {
+            self.buf.remove(0);
+            return Some(None);
+        }
+        let frame: Vec<u8> = self.buf.drain(..total).collect();
+        Some(Some(Frame::Ubx {
+            class: frame[2],
+            id: frame[3],
+            payload: frame[6..6 + payload_len].to_vec(),
+        }))
+    }
+
+    /// NMEA line at the start of the buffer, with the same return convention. A line
+    /// cut off by a UBX frame is dropped.
+    fn take_nmea(&mut self) -> Option<Option<Frame>> {
+        let end = self.buf.iter().position(|&b| b == b'\n');
+        let sync = self
+            .buf
+            .windows(2)
+            .position(|w| w[0] == ubx_config::UBX_SYNC_1 && w[1] == ubx_config::UBX_SYNC_2);
+        match (end, sync) {
+            (_, Some(sync)) if end.is_none_or(|end| sync < end) => {
+                self.buf.drain(..sync);
+                Some(None)
+            }
+            (Some(end), _) => {
+                let line: Vec<u8> = self.buf.drain(..=end).collect();
+                let text = String::from_utf8_lossy(&line).trim().to_string();
+                Some((!text.is_empty()).then_some(Frame::Nmea(text)))
+            }
+            (None, _) if self.buf.len() > MAX_NMEA_LINE => {
+                self.buf.remove(0);
+                Some(None)
+            }
+            (None, _) => None,
+        }
+    }
+}
+
+/// Outcome of replaying a raw capture
+#[derive(Debug, Clone, Serialize)]
+pub struct RawReplay {
+    pub duration_seconds: f64,
+    pub nmea_sentences: u64,
+    /// UBX frames by "class-id" in hex, e.g. "01-07" for NAV-PVT
+    pub ubx_frames: BTreeMap<String, u64>,
+    /// GPS state at the end, including what NAV-PVT and MON-HW contributed
+    pub gps_data: GpsData,
+}
+
+/// Feed a capture's bytes through the framer, parsing NMEA as the reader does and
+/// applying the UBX messages the reader understands
+pub fn replay_chunks(chunks: &[RawChunk]) -> RawReplay {
+    let parser = NmeaParser::new();
+    let mut framer = Framer::default();
+    let mut gps_data = GpsData::default();
+    let mut nmea_sentences = 0;
+    let mut ubx_frames = BTreeMap::new();
+
+    for chunk in chunks {
+        for frame in framer.push(&chunk.bytes) {
+            match frame {
+                Frame::Nmea(line) => {
+                    nmea_sentences += 1;
+                    if let Ok(update) = parser.parse_sentence(&line) {
+                        gps_data.merge(update);
+                    }
+                }
+                Frame::Ubx { class, id, payload } => {
+                    *ubx_frames.entry(format!("{:02X}-{:02X}", class, id)).or_insert(0) += 1;
+                    apply_ubx(&mut gps_data, class, id, &payload);
+                }
+            }
+        }
+    }
+
+    RawReplay {
+        duration_seconds: chunks.last().map_or(0.0, |c| c.at_ms as f64 / 1000.0),
+        nmea_sentences,
+        ubx_frames,
+        gps_data,
+    }
+}
+
+pub fn replay_capture(path: &Path) -> io::Result<RawReplay> {
+    Ok(replay_chunks(&load_capture(path)?))
+}
+
+fn apply_ubx(data: &mut GpsData, class: u8, id: u8, payload: &[u8]) {
+    match (class, id) {
+        (ubx_config::UBX_CLASS_NAV, ubx_config::UBX_NAV_PVT) => {
+            if let Some(pvt) = ubx_config::parse_nav_pvt(payload) {
+                data.horizontal_accuracy_m = Some(pvt.h_acc_m);
+                data.vertical_accuracy_m = Some(pvt.v_acc_m);
+            }
+        }
+        (ubx_config::UBX_CLASS_NAV, ubx_config::UBX_NAV_TIMEGPS) => {
+            if let Some(time) = ubx_config::parse_nav_timegps(payload) {
+                data.leap_seconds = Some(time.leap_seconds);
+                data.time_valid = Some(time.valid);
+            }
+        }
+        (ubx_config::UBX_CLASS_MON, ubx_config::UBX_MON_HW) => {
+            if let Some(hw) = ubx_config::parse_mon_hw(payload) {
+                data.antenna_status = hw.antenna_status;
+                data.antenna_power = hw.antenna_power;
+            }
+        }
+        _ => {}
+    }
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GGA: &str = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76\r\n";
+    const RMC: &str = "$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*68\r\n";
+
+    fn mixed_stream() -> Vec<u8> {
+        let mut pvt = [0u8; 92];
+        pvt[40..44].copy_from_slice(&1_500u32.to_le_bytes());
+        pvt[44..48].copy_from_slice(&2_250u32.to_le_bytes());
+        let ack = [ubx_config::UBX_CLASS_CFG, ubx_config::UBX_CFG_RATE];
+
+        let mut stream = b",N,00630.3372,W*00\r\n".to_vec(); // tail of a cut-off sentence
+        stream.extend_from_slice(GGA.as_bytes());
+        stream.extend(ubx_config::build_ubx_message(0x01, 0x07, &pvt));
+        stream.extend(ubx_config::build_ubx_message(0x05, 0x01, &ack));
+        stream.extend_from_slice(RMC.as_bytes());
+        stream
+    }
+
+    #[test]
+    fn test_mixed_stream_round_trip() {
+        // Reads split the stream at arbitrary points, including mid-frame
+        let chunks: Vec<RawChunk> = mixed_stream()
+            .chunks(7)
+            .enumerate()
+            .map(|(i, bytes)| RawChunk { at_ms: i as u32 * 10, bytes: bytes.to_vec() })
+            .collect();
+        let mut file = Vec::new();
+        write_chunks(&mut file, &chunks).unwrap();
+        let read_back = read_chunks(file.as_slice()).unwrap();
+        assert_eq!(read_back, chunks);
+
+        let mut framer = Framer::default();
+        let frames: Vec<Frame> = read_back.iter().flat_map(|c| framer.push(&c.bytes)).collect();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0], Frame::Nmea(GGA.trim().to_string()));
+        assert!(matches!(frames[1], Frame::Ubx { class: 0x01, id: 0x07, .. }));
+        assert_eq!(
+            frames[2],
+            Frame::Ubx { class: 0x05, id: 0x01, payload: vec![0x06, 0x08] }
+        );
+        assert_eq!(frames[3], Frame::Nmea(RMC.trim().to_string()));
+
+        let replay = replay_chunks(&read_back);
+        assert_eq!(replay.nmea_sentences, 2);
+        assert_eq!(replay.ubx_frames["01-07"], 1);
+        assert_eq!(replay.ubx_frames["05-01"], 1);
+        assert_eq!(replay.gps_data.satellites, Some(8));
+        assert_eq!(replay.gps_data.horizontal_accuracy_m, Some(1.5));
+    }
+
+    #[test]
+    fn test_ubx_with_newlines_split_from_nmea() {
+        // A MON-VER response whose payload holds '\n' and '$' bytes, with no line ending
+        // after it, wedged between sentences and read one byte at a time
+        let mut payload = vec![0u8; 40];
+        payload[..13].copy_from_slice(b"ROM CORE 3.01");
+        payload[20] = b'\n';
+        payload[21] = b'$';
+        let mon_ver = ubx_config::build_ubx_message(0x0A, 0x04, &payload);
+        let ack = ubx_config::build_ubx_message(0x05, 0x01, &[0x06, 0x24]);
+
+        let mut stream = GGA.as_bytes().to_vec();
+        stream.extend(&mon_ver);
+        stream.extend(&ack);
+        stream.extend_from_slice(RMC.as_bytes());
+
+        let mut framer = Framer::default();
+        let frames: Vec<Frame> = stream.iter().flat_map(|b| framer.push(&[*b])).collect();
+        assert_eq!(
+            frames,
+            [
+                Frame::Nmea(GGA.trim().to_string()),
+                Frame::Ubx { class: 0x0A, id: 0x04, payload },
+                Frame::Ubx { class: 0x05, id: 0x01, payload: vec![0x06, 0x24] },
+                Frame::Nmea(RMC.trim().to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_corrupt_ubx_is_skipped() {
+        let mut frame = ubx_config::build_mon_ver_poll();
+        frame[6] ^= 0xFF; // Bad checksum
+        let mut stream = frame;
+        stream.extend_from_slice(GGA.as_bytes());
+
+        let frames = Framer::default().push(&stream);
+        assert_eq!(frames, [Frame::Nmea(GGA.trim().to_string())]);
+        assert!(read_chunks(&b"NOTRAW01"[..]).is_err());
+    }
+}
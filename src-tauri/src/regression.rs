@@ -0,0 +1,171 @@
+// Regression checks: replay labeled NMEA logs through the test engine
+//
+// A corpus entry is a log (`name.nmea` or `name.nmea.gz`) beside `name.expected.json`,
+// which holds the verdict the log should produce and, optionally, whether named
+// criteria should pass at the end of the replay. Replaying the corpus after changing
+// the criteria or the evaluation code shows which recorded outcomes would change.
+
+use crate::nmea_log;
+use crate::test_criteria::{DeviceInfo, TestCriteria, TestRunner, TestVerdict};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const EXPECTED_SUFFIX: &str = ".expected.json";
+
+/// What a log is expected to produce
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayExpectation {
+    pub verdict: TestVerdict,
+    /// Criterion name -> expected to pass; criteria not listed are not compared
+    #[serde(default)]
+    pub criteria_results: BTreeMap<String, bool>,
+}
+
+/// A criterion whose outcome differs from the expectation
+#[derive(Debug, Clone, Serialize)]
+pub struct CriterionDiff {
+    pub name: String,
+    pub expected_passed: bool,
+    pub actual_passed: Option<bool>, // None if the criterion was never evaluated
+    pub actual: String,
+}
+
+/// Outcome of replaying one log against its expectation
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayCheck {
+    pub log: String,
+    pub expected_verdict: TestVerdict,
+    pub actual_verdict: TestVerdict,
+    pub verdict_summary: String,
+    pub elapsed_seconds: f64,
+    pub criteria_diffs: Vec<CriterionDiff>,
+    pub matches: bool,
+}
+
+/// Replay a log with the given criteria and compare the outcome with `expected`.
+/// A log expected to pass also reports every failing criterion as a difference.
+pub fn check_log(
+    path: &Path,
+    criteria: &TestCriteria,
+    expected: &ReplayExpectation,
+) -> io::Result<ReplayCheck> {
+    let samples = nmea_log::timed_samples(path)?;
+    let device = DeviceInfo {
+        port_name: path.display().to_string(),
+        port_type: "Replay".into(),
+        manufacturer: None,
+        product: None,
+        serial_number: None,
+        vid: None,
+        pid: None,
+        chip_id: None,
+    };
+    let mut runner = TestRunner::new(criteria.clone(), device);
+    let actual_verdict = runner.replay(samples);
+    let result = runner.get_result(None);
+
+    let mut criteria_diffs = Vec::new();
+    for (name, &expected_passed) in &expected.criteria_results {
+        let actual = result.criteria_results.iter().find(|r| &r.name == name);
+        if actual.map(|r| r.passed) != Some(expected_passed) {
+            criteria_diffs.push(CriterionDiff {
+                name: name.clone(),
+                expected_passed,
+                actual_passed: actual.map(|r| r.passed),
+                actual: actual.map_or_else(|| "not evaluated".into(), |r| r.actual.clone()),
+            });
+        }
+    }
+    if matches!(expected.verdict, TestVerdict::Pass | TestVerdict::PassWithWarnings) {
+        for r in &result.criteria_results {
+            if !r.passed && !expected.criteria_results.contains_key(&r.name) {
+                criteria_diffs.push(CriterionDiff {
+                    name: r.name.clone(),
+                    expected_passed: true,
+                    actual_passed: Some(false),
+                    actual: r.actual.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(ReplayCheck {
+        log: path.display().to_string(),
+        matches: actual_verdict == expected.verdict && criteria_diffs.is_empty(),
+        expected_verdict: expected.verdict.clone(),
+        actual_verdict,
+        verdict_summary: result.verdict_summary,
+        elapsed_seconds: runner.elapsed_seconds(),
+        criteria_diffs,
+    })
+}
+
+/// Check every `<name>.expected.json` in a directory against its `<name>.nmea` or
+/// `<name>.nmea.gz` log, sorted by name
+pub fn check_corpus(dir: &Path, criteria: &TestCriteria) -> io::Result<Vec<ReplayCheck>> {
+    let mut checks = Vec::new();
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(name) = file_name.strip_suffix(EXPECTED_SUFFIX) else {
+            continue;
+        };
+        let log = [".nmea", ".nmea.gz"]
+            .iter()
+            .map(|ext| dir.join(format!("{}{}", name, ext)))
+            .find(|p| p.exists())
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, format!("No log for {}", file_name))
+            })?;
+        let expected: ReplayExpectation = serde_json::from_str(&fs::read_to_string(entry.path())?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        checks.push(check_log(&log, criteria, &expected)?);
+    }
+    Ok(checks)
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn corpus() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/replay")
+    }
+
+    #[test]
+    fn test_corpus_matches_expectations() {
+        let checks = check_corpus(&corpus(), &TestCriteria::default()).unwrap();
+        assert_eq!(checks.len(), 3);
+        for check in &checks {
+            assert!(check.matches, "{:?}", check);
+        }
+    }
+
+    #[test]
+    fn test_changed_criteria_show_up_as_diffs() {
+        let criteria = TestCriteria {
+            max_hdop: 4.0,
+            ..TestCriteria::default()
+        };
+        let expected = ReplayExpectation {
+            verdict: TestVerdict::Fail,
+            criteria_results: BTreeMap::from([("HDOP".to_string(), false)]),
+        };
+        let check = check_log(&corpus().join("high_hdop_fail.nmea"), &criteria, &expected).unwrap();
+
+        // Still fails on the other criteria, but no longer on HDOP
+        assert!(!check.matches);
+        assert_eq!(check.actual_verdict, TestVerdict::Fail);
+        assert_eq!(check.criteria_diffs.len(), 1);
+        assert_eq!(check.criteria_diffs[0].name, "HDOP");
+        assert_eq!(check.criteria_diffs[0].actual_passed, Some(true));
+    }
+}
@@ -0,0 +1,201 @@
+// Software self-test: known NMEA sentences and UBX payloads run through the parsers
+//
+// Needs no hardware. A failure means the parsing the test engine relies on is broken
+// in this build, so results from the station should not be trusted.
+
+use crate::antenna::{AntennaPower, AntennaStatus};
+use crate::nmea::NmeaParser;
+use crate::ubx_config::{self, UbloxSeries};
+use serde::Serialize;
+
+const GGA: &str = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+const GGA_BAD_CHECKSUM: &str =
+    "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,9,1.03,61.7,M,55.2,M,,*76";
+const RMC: &str = "$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*68";
+const GSV: &str = "$GPGSV,1,1,04,05,45,120,40,12,30,200,35,17,60,310,42,24,10,050,*7C";
+const PUBX_00: &str = "$PUBX,00,081350.00,4717.113210,N,00833.915187,E,546.589,G3,2.1,2.0,\
+                       0.007,77.52,0.007,,0.92,1.19,0.77,9,0,0*5F";
+
+/// One parser check
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Self-test outcome; passes only if every check does
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+/// Run every check and collect the results
+pub fn run_self_test() -> SelfTestReport {
+    let checks: Vec<SelfTestCheck> = [
+        ("NMEA GGA", check_gga as fn() -> Result<String, String>),
+        ("NMEA RMC", check_rmc),
+        ("NMEA GSV", check_gsv),
+        ("NMEA checksum", check_nmea_checksum),
+        ("PUBX,00", check_pubx),
+        ("UBX checksum", check_ubx_checksum),
+        ("UBX MON-VER", check_mon_ver),
+        ("UBX NAV-PVT", check_nav_pvt),
+        ("UBX MON-HW", check_mon_hw),
+    ]
+    .into_iter()
+    .map(|(name, check)| {
+        let (passed, detail) = match check() {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        SelfTestCheck { name: name.to_string(), passed, detail }
+    })
+    .collect();
+
+    SelfTestReport {
+        passed: checks.iter().all(|c| c.passed),
+        checks,
+    }
+}
+
+fn expect_close(
+    label: &str,
+    actual: Option<f64>,
+    expected: f64,
+    tolerance: f64,
+) -> Result<(), String> {
+    match actual {
+        Some(value) if (value - expected).abs() <= tolerance => Ok(()),
+        other => Err(format!("{}: expected {}, got {:?}", label, expected, other)),
+    }
+}
+
+fn check_gga() -> Result<String, String> {
+    let gps = NmeaParser::new()
+        .parse_sentence(GGA)
+        .map_err(|e| e.to_string())?;
+    expect_close("latitude", gps.latitude, 53.361_337, 1e-5)?;
+    expect_close("longitude", gps.longitude, -6.505_62, 1e-5)?;
+    expect_close("HDOP", gps.hdop.map(f64::from), 1.03, 1e-3)?;
+    if gps.fix_quality != Some(1) || gps.satellites != Some(8) {
+        return Err(format!(
+            "fix quality {:?} / satellites {:?}, expected 1 / 8",
+            gps.fix_quality, gps.satellites
+        ));
+    }
+    Ok("Position, fix quality, satellites and HDOP parsed".into())
+}
+
+fn check_rmc() -> Result<String, String> {
+    let gps = NmeaParser::new()
+        .parse_sentence(RMC)
+        .map_err(|e| e.to_string())?;
+    expect_close("speed", gps.speed_knots, 0.5, 1e-6)?;
+    expect_close("course", gps.course, 54.7, 1e-6)?;
+    Ok("Speed and course parsed".into())
+}
+
+fn check_gsv() -> Result<String, String> {
+    let gps = NmeaParser::new()
+        .parse_sentence(GSV)
+        .map_err(|e| e.to_string())?;
+    let mut prns: Vec<u32> = gps.satellites_info.iter().map(|s| s.prn).collect();
+    prns.sort_unstable();
+    if prns != [5, 12, 17, 24] {
+        return Err(format!("expected PRNs [5, 12, 17, 24], got {:?}", prns));
+    }
+    Ok("4 satellites in view parsed".into())
+}
+
+fn check_nmea_checksum() -> Result<String, String> {
+    match NmeaParser::new().parse_sentence(GGA_BAD_CHECKSUM) {
+        Ok(_) => Err("sentence with a wrong checksum was accepted".into()),
+        Err(_) => Ok("Sentence with a wrong checksum rejected".into()),
+    }
+}
+
+fn check_pubx() -> Result<String, String> {
+    let gps = NmeaParser::new()
+        .parse_sentence(PUBX_00)
+        .map_err(|e| e.to_string())?;
+    expect_close("horizontal accuracy", gps.horizontal_accuracy_m.map(f64::from), 2.1, 1e-3)?;
+    expect_close("vertical accuracy", gps.vertical_accuracy_m.map(f64::from), 2.0, 1e-3)?;
+    Ok("Accuracy estimates parsed".into())
+}
+
+fn check_ubx_checksum() -> Result<String, String> {
+    // MON-VER poll as documented in the u-blox protocol specification
+    let expected = [0xB5, 0x62, 0x0A, 0x04, 0x00, 0x00, 0x0E, 0x34];
+    let built = ubx_config::build_mon_ver_poll();
+    if built != expected {
+        return Err(format!("MON-VER poll encoded as {:02X?}", built));
+    }
+    Ok("MON-VER poll frame and checksum match".into())
+}
+
+fn check_mon_ver() -> Result<String, String> {
+    let mut payload = vec![0u8; 100];
+    payload[..8].copy_from_slice(b"ROM CORE");
+    payload[30..38].copy_from_slice(b"00080000");
+    payload[40..51].copy_from_slice(b"MOD=NEO-M8N");
+    payload[70..78].copy_from_slice(b"GPS;GLO;");
+    let info = ubx_config::parse_mon_ver(&payload).ok_or("payload rejected")?;
+    if info.series != UbloxSeries::Series8 || info.chip_name != "NEO-M8N" {
+        return Err(format!("identified as {} ({:?})", info.chip_name, info.series));
+    }
+    Ok("NEO-M8N identified".into())
+}
+
+fn check_nav_pvt() -> Result<String, String> {
+    let mut payload = [0u8; 92];
+    payload[20] = 3; // 3D fix
+    payload[23] = 11;
+    payload[24..28].copy_from_slice(&1_745_800_000i32.to_le_bytes());
+    payload[28..32].copy_from_slice(&(-368_500_000i32).to_le_bytes());
+    payload[40..44].copy_from_slice(&1_500u32.to_le_bytes());
+    payload[44..48].copy_from_slice(&2_250u32.to_le_bytes());
+    payload[76..78].copy_from_slice(&140u16.to_le_bytes());
+    let pvt = ubx_config::parse_nav_pvt(&payload).ok_or("payload rejected")?;
+    expect_close("latitude", Some(pvt.latitude), -36.85, 1e-7)?;
+    expect_close("longitude", Some(pvt.longitude), 174.58, 1e-7)?;
+    expect_close("hAcc", Some(f64::from(pvt.h_acc_m)), 1.5, 1e-6)?;
+    expect_close("vAcc", Some(f64::from(pvt.v_acc_m)), 2.25, 1e-6)?;
+    expect_close("PDOP", Some(f64::from(pvt.pdop)), 1.4, 1e-6)?;
+    if pvt.fix_type != 3 || pvt.num_sv != 11 {
+        return Err(format!("fix type {} / {} SVs, expected 3 / 11", pvt.fix_type, pvt.num_sv));
+    }
+    Ok("Position, accuracy and PDOP decoded".into())
+}
+
+fn check_mon_hw() -> Result<String, String> {
+    let mut payload = [0u8; 60];
+    payload[20] = 2; // aStatus OK
+    payload[21] = 1; // aPower ON
+    let hw = ubx_config::parse_mon_hw(&payload).ok_or("payload rejected")?;
+    if hw.antenna_status != Some(AntennaStatus::Ok) || hw.antenna_power != Some(AntennaPower::On) {
+        return Err(format!(
+            "antenna {:?} / power {:?}, expected Ok / On",
+            hw.antenna_status, hw.antenna_power
+        ));
+    }
+    Ok("Antenna supervisor state decoded".into())
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_passes() {
+        let report = run_self_test();
+        for check in &report.checks {
+            assert!(check.passed, "{}: {}", check.name, check.detail);
+        }
+        assert!(report.passed);
+        assert_eq!(report.checks.len(), 9);
+    }
+}
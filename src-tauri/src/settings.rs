@@ -0,0 +1,239 @@
+// Application settings: one `settings.json` in the config dir
+//
+// Holds the default test criteria together with the station's behavior options.
+// Older versions kept the criteria alone in `criteria.json`; that file is still read
+// when no settings file exists yet, and the settings file replaces it on first save.
+// Options that once lived in the criteria are lifted out of it on load (see
+// `MOVED_CRITERIA_KEYS`), so older files keep their values.
+
+use crate::atomic_file::write_atomic;
+use crate::geo::{CoordFormat, Datum};
+use crate::test_criteria::TestCriteria;
+use crate::ubx_optimizer::OptimizerConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+/// Criteria file of earlier versions, read only to migrate it
+const LEGACY_CRITERIA_FILE: &str = "criteria.json";
+/// Settings that earlier versions stored inside the test criteria
+const MOVED_CRITERIA_KEYS: &[&str] = &[
+    "continuous",
+    "append_ndjson",
+    "interim_save_interval_seconds",
+    "coord_format",
+    "datum",
+    "evaluation_interval_ms",
+];
+
+/// Bounds on `AppSettings::evaluation_interval_ms`
+pub const MIN_EVALUATION_INTERVAL_MS: u64 = 100;
+pub const MAX_EVALUATION_INTERVAL_MS: u64 = 5000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Criteria used when no profile matches the connected device
+    pub criteria: TestCriteria,
+    /// Where reports are saved; None for `~/gps-studio-results`. Applies on restart.
+    pub results_dir: Option<PathBuf>,
+    /// Reconnect to the last good port when the app starts
+    pub reconnect_on_startup: bool,
+    /// Save the report as soon as a test reaches a verdict, without waiting for the
+    /// operator to click save (aborted tests are not saved)
+    pub auto_save_on_complete: bool,
+    /// Also append each saved result to `results.ndjson` in the results directory,
+    /// one line per result, for log aggregation
+    pub append_ndjson: bool,
+    /// Continuous monitoring (soak test): evaluate indefinitely, never reach a verdict
+    pub continuous: bool,
+    /// Write the running test's interim result to a `.partial.json` this often, so a
+    /// crash mid-test leaves a record behind (None = off)
+    pub interim_save_interval_seconds: Option<u64>,
+    /// How often the backend evaluates a running test. Faster gives quicker verdicts
+    /// at the cost of more lock traffic; slow receivers gain nothing from a fast tick.
+    pub evaluation_interval_ms: u64,
+    /// u-blox optimizer tuning
+    pub optimizer: OptimizerConfig,
+    /// Position format used in reports and exports (raw data stays in decimal degrees)
+    pub coord_format: CoordFormat,
+    /// Datum positions are converted to for reports and exports, e.g. NAD27 for
+    /// legacy charts (raw data stays in WGS84)
+    pub datum: Datum,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            criteria: TestCriteria::default(),
+            results_dir: None,
+            reconnect_on_startup: true,
+            auto_save_on_complete: false,
+            append_ndjson: false,
+            continuous: false,
+            interim_save_interval_seconds: None,
+            evaluation_interval_ms: 500,
+            optimizer: OptimizerConfig::default(),
+            coord_format: CoordFormat::default(),
+            datum: Datum::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Load `settings.json`, else migrate a legacy `criteria.json`, else defaults.
+    /// An unreadable file is logged and replaced by defaults rather than failing startup.
+    pub fn load(config_dir: &Path) -> Self {
+        let path = config_dir.join(SETTINGS_FILE);
+        if let Ok(contents) = fs::read_to_string(&path) {
+            match serde_json::from_str(&contents).and_then(Self::from_value) {
+                Ok(settings) => {
+                    log::info!("Loaded settings from {}", path.display());
+                    return settings;
+                }
+                Err(e) => {
+                    log::warn!("Failed to parse {}: {}, using defaults", path.display(), e);
+                    return Self::default();
+                }
+            }
+        }
+
+        let legacy = config_dir.join(LEGACY_CRITERIA_FILE);
+        if let Ok(contents) = fs::read_to_string(&legacy) {
+            let wrapped = serde_json::from_str::<Value>(&contents)
+                .and_then(|criteria| Self::from_value(json!({ "criteria": criteria })));
+            match wrapped {
+                Ok(settings) => {
+                    log::info!("Migrating test criteria from {}", legacy.display());
+                    return settings;
+                }
+                Err(e) => log::warn!("Failed to parse criteria config: {}, using defaults", e),
+            }
+        }
+        Self::default()
+    }
+
+    /// Check the criteria, the optimizer tuning and the evaluation interval
+    pub fn validate(&self) -> Result<(), String> {
+        self.criteria.validate().map_err(|e| e.to_string())?;
+        self.optimizer.validate()?;
+        let range = MIN_EVALUATION_INTERVAL_MS..=MAX_EVALUATION_INTERVAL_MS;
+        if !range.contains(&self.evaluation_interval_ms) {
+            return Err(format!(
+                "Evaluation interval must be {}-{} ms, got {}",
+                MIN_EVALUATION_INTERVAL_MS, MAX_EVALUATION_INTERVAL_MS, self.evaluation_interval_ms
+            ));
+        }
+        Ok(())
+    }
+
+    /// Evaluation tick interval, clamped to the valid range in case a hand-edited
+    /// settings file holds something outside it
+    pub fn evaluation_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.evaluation_interval_ms
+                .clamp(MIN_EVALUATION_INTERVAL_MS, MAX_EVALUATION_INTERVAL_MS),
+        )
+    }
+
+    /// Deserialize, first moving any `MOVED_CRITERIA_KEYS` found in the criteria up to
+    /// the top level (a value already at the top level wins)
+    fn from_value(mut value: Value) -> serde_json::Result<Self> {
+        if let Some(root) = value.as_object_mut() {
+            let moved: Vec<_> = match root.get_mut("criteria").and_then(|c| c.as_object_mut()) {
+                Some(criteria) => MOVED_CRITERIA_KEYS
+                    .iter()
+                    .filter_map(|key| criteria.remove(*key).map(|v| (key.to_string(), v)))
+                    .collect(),
+                None => Vec::new(),
+            };
+            for (key, v) in moved {
+                root.entry(key).or_insert(v);
+            }
+        }
+        serde_json::from_value(value)
+    }
+
+    /// Write `settings.json` via a temporary file and rename, so a crash mid-write
+    /// leaves the previous settings intact
+    pub fn save(&self, config_dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(config_dir)?;
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::from)?;
+        write_atomic(&config_dir.join(SETTINGS_FILE), json)
+    }
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_legacy_criteria_migrate_then_settings_win() {
+        let dir = temp_dir("settings");
+        let legacy = TestCriteria { max_hdop: 4.0, ..TestCriteria::default() };
+        fs::write(dir.join(LEGACY_CRITERIA_FILE), serde_json::to_string(&legacy).unwrap())
+            .unwrap();
+
+        let mut settings = AppSettings::load(&dir);
+        assert_eq!(settings.criteria.max_hdop, 4.0);
+        assert!(settings.reconnect_on_startup);
+
+        settings.criteria.max_hdop = 3.0;
+        settings.reconnect_on_startup = false;
+        settings.save(&dir).unwrap();
+        assert!(!dir.join(format!("{}.tmp", SETTINGS_FILE)).exists());
+
+        let reloaded = AppSettings::load(&dir);
+        assert_eq!(reloaded.criteria.max_hdop, 3.0);
+        assert!(!reloaded.reconnect_on_startup);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_options_stored_in_old_criteria_are_moved_up() {
+        let dir = temp_dir("settings_moved");
+        let legacy = TestCriteria { max_hdop: 4.0, ..TestCriteria::default() };
+        let mut criteria = serde_json::to_value(legacy).unwrap();
+        criteria["datum"] = json!("nad27");
+        criteria["coord_format"] = json!("dms");
+        criteria["append_ndjson"] = json!(true);
+        criteria["continuous"] = json!(true);
+        criteria["interim_save_interval_seconds"] = json!(30);
+        criteria["evaluation_interval_ms"] = json!(1000);
+        let old = json!({ "criteria": criteria, "reconnect_on_startup": false });
+        fs::write(dir.join(SETTINGS_FILE), old.to_string()).unwrap();
+
+        let settings = AppSettings::load(&dir);
+        assert_eq!(settings.criteria.max_hdop, 4.0);
+        assert!(!settings.reconnect_on_startup);
+        assert_eq!(settings.datum, Datum::Nad27);
+        assert_eq!(settings.coord_format, CoordFormat::Dms);
+        assert!(settings.append_ndjson);
+        assert!(settings.continuous);
+        assert_eq!(settings.interim_save_interval_seconds, Some(30));
+        assert_eq!(settings.evaluation_interval_ms, 1000);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_evaluation_interval_bounds() {
+        let mut settings = AppSettings::default();
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.evaluation_interval(), Duration::from_millis(500));
+
+        settings.evaluation_interval_ms = 10;
+        assert!(settings.validate().is_err());
+        assert_eq!(settings.evaluation_interval(), Duration::from_millis(100));
+
+        settings.evaluation_interval_ms = 60_000;
+        assert!(settings.validate().is_err());
+        assert_eq!(settings.evaluation_interval(), Duration::from_millis(5000));
+    }
+}
@@ -0,0 +1,139 @@
+// Sky plot data: satellite positions shaped for a polar plot
+//
+// Built from the live GpsData on demand; satellites without both position angles
+// cannot be placed and are only counted.
+
+use crate::nmea::GpsData;
+use serde::Serialize;
+
+/// A satellite placed on the sky plot
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SkyPlotPoint {
+    pub prn: u32,
+    pub elevation: f32, // Degrees above the horizon (0-90)
+    pub azimuth: f32,   // Degrees clockwise from true north (0-359)
+    pub snr: Option<f32>,
+    pub used_in_fix: bool,
+}
+
+/// Satellites of one constellation
+#[derive(Debug, Clone, Serialize)]
+pub struct SkyPlotGroup {
+    pub constellation: String,
+    pub satellites: Vec<SkyPlotPoint>,
+}
+
+/// Sky plot snapshot with aggregate figures for the same instant
+#[derive(Debug, Clone, Serialize)]
+pub struct SkyPlot {
+    pub timestamp: Option<String>,
+    pub fix_type: Option<String>,
+    pub hdop: Option<f32>,
+    pub groups: Vec<SkyPlotGroup>,
+    pub plotted: u32,          // Satellites with valid elevation and azimuth
+    pub used_in_fix: u32,      // Plotted satellites in the position solution
+    pub excluded: u32,         // Satellites missing position angles
+    pub avg_snr: Option<f32>,  // Mean SNR of plotted satellites reporting one
+}
+
+fn valid_angles(elevation: Option<f32>, azimuth: Option<f32>) -> Option<(f32, f32)> {
+    let (elevation, azimuth) = (elevation?, azimuth?);
+    ((0.0..=90.0).contains(&elevation) && (0.0..360.0).contains(&azimuth))
+        .then_some((elevation, azimuth))
+}
+
+/// Build the sky plot from the current GPS data, grouped by constellation (sorted by name)
+pub fn build_sky_plot(data: &GpsData) -> SkyPlot {
+    let mut groups: Vec<SkyPlotGroup> = Vec::new();
+    let mut excluded = 0;
+
+    for sat in &data.satellites_info {
+        let Some((elevation, azimuth)) = valid_angles(sat.elevation, sat.azimuth) else {
+            excluded += 1;
+            continue;
+        };
+        let point = SkyPlotPoint {
+            prn: sat.prn,
+            elevation,
+            azimuth,
+            snr: sat.snr,
+            used_in_fix: sat.used_in_fix,
+        };
+        match groups.iter_mut().find(|g| g.constellation == sat.constellation) {
+            Some(group) => group.satellites.push(point),
+            None => groups.push(SkyPlotGroup {
+                constellation: sat.constellation.clone(),
+                satellites: vec![point],
+            }),
+        }
+    }
+
+    groups.sort_by(|a, b| a.constellation.cmp(&b.constellation));
+    for group in &mut groups {
+        group.satellites.sort_by_key(|s| s.prn);
+    }
+
+    let points = || groups.iter().flat_map(|g| g.satellites.iter());
+    let snrs: Vec<f32> = points().filter_map(|s| s.snr).collect();
+
+    SkyPlot {
+        timestamp: data.timestamp.clone(),
+        fix_type: data.fix_type.clone(),
+        hdop: data.hdop,
+        plotted: points().count() as u32,
+        used_in_fix: points().filter(|s| s.used_in_fix).count() as u32,
+        excluded,
+        avg_snr: (!snrs.is_empty()).then(|| snrs.iter().sum::<f32>() / snrs.len() as f32),
+        groups,
+    }
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::sat;
+    use crate::nmea::SatelliteInfo;
+
+    #[test]
+    fn test_sky_plot_groups_and_excludes() {
+        // In view without a signal, so not in the fix
+        let no_signal = |constellation: &str, s: SatelliteInfo| SatelliteInfo {
+            snr: None,
+            constellation: constellation.into(),
+            used_in_fix: false,
+            ..s
+        };
+        let data = GpsData {
+            satellites_info: vec![
+                sat(12, 30.0, 200.0, 35.0),
+                no_signal("GLONASS", sat(65, 45.0, 90.0, 0.0)),
+                sat(5, 45.0, 120.0, 41.0),
+                no_signal("GPS", SatelliteInfo { elevation: None, ..sat(24, 0.0, 50.0, 0.0) }),
+                no_signal("Galileo", sat(3, -2.0, 10.0, 0.0)),
+            ],
+            ..Default::default()
+        };
+
+        let plot = build_sky_plot(&data);
+        let names: Vec<&str> = plot.groups.iter().map(|g| g.constellation.as_str()).collect();
+        assert_eq!(names, ["GLONASS", "GPS"]);
+
+        let gps: Vec<u32> = plot.groups[1].satellites.iter().map(|s| s.prn).collect();
+        assert_eq!(gps, [5, 12]);
+
+        assert_eq!(plot.plotted, 3);
+        assert_eq!(plot.used_in_fix, 2);
+        assert_eq!(plot.excluded, 2);
+        assert!((plot.avg_snr.unwrap() - 38.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sky_plot_empty() {
+        let plot = build_sky_plot(&GpsData::default());
+        assert!(plot.groups.is_empty());
+        assert_eq!(plot.plotted, 0);
+        assert_eq!(plot.avg_snr, None);
+    }
+}
@@ -0,0 +1,110 @@
+// Snapshot-published shared state: writers take a lock, readers never wait on them
+//
+// The reader thread updates GPS data and status for every NMEA sentence, while the UI
+// polls `get_gps_data` / `get_gps_status` at up to 10Hz. With a plain RwLock every poll
+// queued behind the parser's write lock (and every write behind in-flight clones for
+// the UI). Here writers mutate a private copy and, when their guard drops, publish an
+// immutable `Arc` snapshot. Readers only clone that `Arc` under a lock held for a
+// pointer copy, so a slow writer can no longer stall a poll
+// (see `test_readers_do_not_wait_for_writer`).
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub struct SnapshotLock<T: Clone> {
+    /// Working copy, serialising writers
+    current: Mutex<T>,
+    /// Latest published copy handed to readers
+    published: Mutex<Arc<T>>,
+}
+
+impl<T: Clone> SnapshotLock<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            published: Mutex::new(Arc::new(value.clone())),
+            current: Mutex::new(value),
+        }
+    }
+
+    /// Latest published state; never waits for a writer
+    pub fn snapshot(&self) -> Arc<T> {
+        Arc::clone(&self.published.lock().unwrap())
+    }
+
+    /// Lock for writing; changes are published when the guard drops
+    pub fn write(&self) -> SnapshotWriteGuard<'_, T> {
+        SnapshotWriteGuard {
+            guard: self.current.lock().unwrap(),
+            published: &self.published,
+        }
+    }
+}
+
+pub struct SnapshotWriteGuard<'a, T: Clone> {
+    guard: MutexGuard<'a, T>,
+    published: &'a Mutex<Arc<T>>,
+}
+
+impl<T: Clone> Deref for SnapshotWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T: Clone> DerefMut for SnapshotWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T: Clone> Drop for SnapshotWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        // Clone outside the published lock; swap the pointer inside it
+        let next = Arc::new((*self.guard).clone());
+        let _previous = std::mem::replace(&mut *self.published.lock().unwrap(), next);
+    }
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_write_publishes_on_drop() {
+        let lock = SnapshotLock::new(1u32);
+        {
+            let mut w = lock.write();
+            *w = 2;
+            // Not yet visible while the writer holds the guard
+            assert_eq!(*lock.snapshot(), 1);
+        }
+        assert_eq!(*lock.snapshot(), 2);
+    }
+
+    #[test]
+    fn test_readers_do_not_wait_for_writer() {
+        let lock = Arc::new(SnapshotLock::new(vec![0u8; 1024]));
+
+        let writer_lock = Arc::clone(&lock);
+        let writer = thread::spawn(move || {
+            let mut w = writer_lock.write();
+            thread::sleep(Duration::from_millis(300));
+            w[0] = 1;
+        });
+        thread::sleep(Duration::from_millis(20));
+
+        let start = Instant::now();
+        let snap = lock.snapshot();
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert_eq!(snap[0], 0);
+
+        writer.join().unwrap();
+        assert_eq!(lock.snapshot()[0], 1);
+    }
+}
@@ -1,9 +1,75 @@
 // GPS test criteria engine - configurable pass/fail thresholds
 
+use crate::antenna::{AntennaKind, AntennaMonitor, AntennaReport};
+use crate::geo::{self, CoordFormat, Datum};
+use crate::multipath;
 use crate::nmea::GpsData;
+use crate::nmea_log;
+use crate::ubx_config::{StartType, UbloxChipInfo};
+use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Interval between on-disk metric snapshots in continuous monitoring mode
+pub const MONITOR_SNAPSHOT_INTERVAL_SECONDS: u64 = 60;
+
+/// Verdict summary of a test recovered from an interim save
+pub const INTERRUPTED_SUMMARY: &str = "Interrupted: the app stopped before the test finished";
+
+/// Upper bound on per-criterion sparkline history
+pub const MAX_CRITERION_HISTORY: usize = 120;
+
+/// Upper bound on fixes kept per result, whatever `max_collected_fixes` says
+pub const MAX_COLLECTED_FIXES: usize = 10_000;
+
+/// Satellites a threshold can ask for; more than any receiver reports in its fix
+const MAX_SATELLITES: u32 = 32;
+/// Distinct PRNs a threshold can ask for, across every constellation in view
+const MAX_UNIQUE_PRNS: u32 = 128;
+/// SNR (dB) at which a satellite counts toward `min_unique_prns`; weaker ones are
+/// tracked but rarely used in the fix
+const USABLE_PRN_SNR_DB: f32 = 20.0;
+/// Constellations the parser can tell apart (GPS, GLONASS, Galileo, BeiDou, QZSS, NavIC)
+const MAX_CONSTELLATIONS: u32 = 6;
+/// Highest SNR threshold; receivers report C/N0 of about 50 dB-Hz at best
+const MAX_SNR_DB: f32 = 60.0;
+/// Bounds on the HDOP and PDOP limits; below 0.5 no real sky passes
+const MIN_DOP_LIMIT: f32 = 0.5;
+const MAX_DOP_LIMIT: f32 = 50.0;
+/// Longest TTFF limit and stability window, in seconds
+const MAX_TEST_SECONDS: u64 = 3600;
+
+/// Recent fixes whose median a new fix is compared against for outlier detection
+const POSITION_OUTLIER_WINDOW: usize = 20;
+/// Fixes needed before outlier detection starts, so the median means something
+const POSITION_OUTLIER_MIN_FIXES: usize = 5;
+
+/// Longest the fix time may stay put before `require_advancing_time` fails; allows
+/// for a missed sentence or two at 1 Hz
+const MAX_FIX_TIME_STALL_SECONDS: u64 = 3;
+/// A backwards step of more than this is the UTC day rolling over, not a fault
+const MIDNIGHT_ROLLOVER_SECONDS: i64 = 12 * 3600;
+/// How long a fix may come without any GSV satellite data before the receiver is
+/// taken to have GSV output turned off, rather than to be still acquiring
+pub const GSV_MISSING_SECONDS: u64 = 5;
+/// How long satellites may be in view, all below `USABLE_PRN_SNR_DB`, with no fix
+/// before the antenna is taken to be indoors or disconnected
+pub const WEAK_SIGNAL_SECONDS: u64 = 30;
+/// Hint shown while the weak-signal condition holds
+pub const WEAK_SIGNAL_HINT: &str = "Weak signal: move the antenna to open sky";
+/// Criteria judged from GSV per-satellite data, which only warn when GSV is off
+const GSV_CRITERIA: [&str; 7] = [
+    "Average SNR",
+    "Strong Sats (SNR>=30)",
+    "Constellations",
+    "Required Constellations",
+    "Best Satellite SNR",
+    "Multipath",
+    "Unique PRNs",
+];
 
 /// Configurable test criteria with sensible defaults for u-blox NEO-M8N
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,11 +78,128 @@ pub struct TestCriteria {
     pub max_hdop: f32,
     pub max_pdop: f32,
     pub min_avg_snr: f32,
+    /// SNR (dB) the strongest satellite must reach, e.g. 40 (None = not checked). A
+    /// healthy average can hide an antenna with no good gain toward any satellite.
+    #[serde(default)]
+    pub min_best_satellite_snr: Option<f32>,
+    /// Highest multipath score allowed (0.0-1.0, see `multipath`; None = not checked).
+    /// Needs at least 6 satellites with SNR spread across elevations to score at all.
+    #[serde(default)]
+    pub max_multipath_score: Option<f32>,
     pub min_strong_satellites: u32,
+    /// Elevation mask for the strong satellite count (0 = no mask). Low satellites can
+    /// show a healthy SNR through multipath, so they are excluded when this is set.
+    #[serde(default)]
+    pub strong_sat_min_elevation_deg: f32,
     pub max_ttff_seconds: u64,
+    /// Multiply `max_ttff_seconds` by this when the receiver is expected to cold start
+    /// (no usable ephemeris or almanac, see `get_start_state`), which legitimately
+    /// takes longer (None = the same limit for every start)
+    #[serde(default)]
+    pub cold_start_ttff_factor: Option<f32>,
+    /// End the test as timed out once satellites have been in view for this long, all
+    /// too weak to use and with no fix (None = wait for the full timeout). Indoors or
+    /// with no antenna the test would otherwise run to the timeout for nothing.
+    #[serde(default)]
+    pub weak_signal_timeout_seconds: Option<u64>,
     pub min_constellations: u32,
+    /// Constellations that must all be present in view, e.g. ["GPS", "Galileo"]
+    /// (names as in `SatelliteInfo::constellation`, case-insensitive; empty = not checked)
+    #[serde(default)]
+    pub required_constellations: Vec<String>,
     pub min_fix_quality: u8,
     pub stability_duration_seconds: u64,
+    /// Surveyed antenna position (lat, lon); enables the position error criterion
+    #[serde(default)]
+    pub reference_position: Option<(f64, f64)>,
+    #[serde(default = "default_max_position_error_m")]
+    pub max_position_error_m: f64,
+    /// Distance from the median of recent fixes beyond which a fix counts as an
+    /// outlier (e.g. a multipath jump); outliers are left out of the mean position
+    #[serde(default = "default_position_outlier_threshold_m")]
+    pub position_outlier_threshold_m: f64,
+    /// Maximum number of outlier fixes over the test (None = not checked)
+    #[serde(default)]
+    pub max_position_outliers: Option<u32>,
+    /// Maximum number of fix downgrades (3D to 2D, or any fix to none) over the test
+    /// (None = not checked). Catches a fix that keeps dropping out and coming back.
+    #[serde(default)]
+    pub max_fix_downgrades: Option<u32>,
+    /// Distinct satellites (PRN per constellation) that must be seen with usable SNR
+    /// within the stability window (None = not checked). A brief good moment can pass
+    /// the point-in-time counts; this needs the sky to have been tracked throughout.
+    #[serde(default)]
+    pub min_unique_prns: Option<u32>,
+    /// Maximum age of the latest fix relative to wall-clock time (None = not checked)
+    #[serde(default)]
+    pub max_fix_age_seconds: Option<f64>,
+    /// Maximum receiver-reported horizontal accuracy estimate in meters (None = not
+    /// checked). Skipped while the receiver reports no estimate (no NAV-PVT or PUBX,00).
+    #[serde(default)]
+    pub max_hacc_m: Option<f32>,
+    /// Maximum standard deviation of satellite count over the stability window
+    /// (None = not checked). Catches flaky antennas/cables that a point-in-time count misses.
+    #[serde(default)]
+    pub max_satellite_count_stddev: Option<f64>,
+    /// Number of recent values kept per numeric criterion for sparklines (0 = off).
+    /// Capped at `MAX_CRITERION_HISTORY` to keep reports small.
+    #[serde(default)]
+    pub criterion_history_len: usize,
+    /// Keep every fix of the run in the result, for plotting and offline analysis, up
+    /// to this many (None = off). Capped at `MAX_COLLECTED_FIXES`; a 5-minute test at
+    /// 5 Hz is 1500 fixes. Once full, later fixes are not kept.
+    #[serde(default)]
+    pub max_collected_fixes: Option<usize>,
+    /// Require an RTK-fixed solution (Float RTK is not enough), for RTK units like NEO-M8P
+    #[serde(default)]
+    pub require_rtk_fixed: bool,
+    /// Require the antenna supervisor (UBX-MON-HW) to detect an active antenna drawing
+    /// current, for active-antenna SKUs. Fails if MON-HW is never received.
+    #[serde(default)]
+    pub expect_active_antenna: bool,
+    /// Require HDOP to stay within `max_hdop` for the whole stability window, so a
+    /// single spike keeps failing until it has aged out of the window
+    #[serde(default)]
+    pub require_stable_hdop: bool,
+    /// Fail if the receiver reports an implausible date (before 2020 or ahead of the
+    /// host clock), as receivers hit by the GPS week-number rollover do
+    #[serde(default)]
+    pub reject_suspect_date: bool,
+    /// Require the receiver's fix time to keep moving forward: fails if it is missing,
+    /// stuck for more than 3 s, or went backwards within the stability window, as a
+    /// receiver with a dead RTC or frozen time output does
+    #[serde(default)]
+    pub require_advancing_time: bool,
+    /// Require the receiver to report its GPS-UTC leap seconds as valid (PUBX,04 or
+    /// UBX-NAV-TIMEGPS), for units used as a time source
+    #[serde(default)]
+    pub require_valid_time: bool,
+    /// Golden-unit baseline to compare against (None = no baseline comparison)
+    #[serde(default)]
+    pub baseline_name: Option<String>,
+    /// How far below the baseline's satellite count / SNR, or above its HDOP, a unit
+    /// may fall, as a percentage of the baseline value
+    #[serde(default = "default_baseline_tolerance_pct")]
+    pub baseline_tolerance_pct: f32,
+    /// Warning band beyond each threshold, as a percentage of it (0 = no warnings).
+    /// e.g. 10% turns HDOP 2.1 against a 2.0 limit into a warning rather than a fail.
+    #[serde(default)]
+    pub warning_margin_pct: f32,
+    /// Per-criterion overrides of `warning_margin_pct`, keyed by criterion name
+    #[serde(default)]
+    pub warning_margins: HashMap<String, f32>,
+}
+
+fn default_max_position_error_m() -> f64 {
+    5.0
+}
+
+fn default_position_outlier_threshold_m() -> f64 {
+    50.0
+}
+
+fn default_baseline_tolerance_pct() -> f32 {
+    10.0
 }
 
 impl Default for TestCriteria {
@@ -26,22 +209,220 @@ impl Default for TestCriteria {
             max_hdop: 2.0,
             max_pdop: 3.0,
             min_avg_snr: 25.0,
+            min_best_satellite_snr: None,
+            max_multipath_score: None,
             min_strong_satellites: 4,
+            strong_sat_min_elevation_deg: 0.0,
             max_ttff_seconds: 60,
+            cold_start_ttff_factor: None,
+            weak_signal_timeout_seconds: None,
             min_constellations: 2,
+            required_constellations: Vec::new(),
             min_fix_quality: 1,
             stability_duration_seconds: 10,
+            reference_position: None,
+            max_position_error_m: default_max_position_error_m(),
+            position_outlier_threshold_m: default_position_outlier_threshold_m(),
+            max_position_outliers: None,
+            max_fix_downgrades: None,
+            min_unique_prns: None,
+            max_fix_age_seconds: None,
+            max_hacc_m: None,
+            max_satellite_count_stddev: None,
+            criterion_history_len: 0,
+            max_collected_fixes: None,
+            require_rtk_fixed: false,
+            expect_active_antenna: false,
+            require_stable_hdop: false,
+            reject_suspect_date: false,
+            require_advancing_time: false,
+            require_valid_time: false,
+            baseline_name: None,
+            baseline_tolerance_pct: default_baseline_tolerance_pct(),
+            warning_margin_pct: 0.0,
+            warning_margins: HashMap::new(),
+        }
+    }
+}
+
+/// Criteria per device model, keyed by a USB product or manufacturer string
+pub type CriteriaProfiles = HashMap<String, TestCriteria>;
+
+/// Pick the criteria profile for a device. A key matches when it equals or is contained
+/// in the USB product string (case-insensitive), falling back to the manufacturer; the
+/// longest matching key wins so "NEO-M8N" beats "NEO", and keys of equal length are
+/// decided alphabetically so the choice doesn't depend on map order.
+pub fn select_profile<'a>(
+    profiles: &'a CriteriaProfiles,
+    device: &DeviceInfo,
+) -> Option<(&'a str, &'a TestCriteria)> {
+    let best_match = |field: Option<&str>| {
+        let field = field?.to_lowercase();
+        profiles
+            .iter()
+            .filter(|(key, _)| !key.is_empty() && field.contains(&key.to_lowercase()))
+            .max_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| b.cmp(a)))
+            .map(|(key, criteria)| (key.as_str(), criteria))
+    };
+    best_match(device.product.as_deref()).or_else(|| best_match(device.manufacturer.as_deref()))
+}
+
+/// Criteria for a test run: ad-hoc criteria from the operator if given, else the
+/// device model's profile, else the stored defaults. Returns the profile name used.
+/// Nothing is written back, so an override lasts for one run only.
+pub fn criteria_for_run(
+    profiles: &CriteriaProfiles,
+    defaults: &TestCriteria,
+    device: &DeviceInfo,
+    adhoc: Option<TestCriteria>,
+) -> (Option<String>, TestCriteria) {
+    if let Some(criteria) = adhoc {
+        return (None, criteria);
+    }
+    match select_profile(profiles, device) {
+        Some((name, criteria)) => (Some(name.to_string()), criteria.clone()),
+        None => (None, defaults.clone()),
+    }
+}
+
+/// Metrics of a known-good (golden) unit that production units are compared against
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Baseline {
+    pub satellites: u32,
+    pub avg_snr: f32,
+    pub hdop: f32,
+    /// Serial number of the golden unit, for traceability
+    pub serial_number: Option<String>,
+    /// When the golden unit was tested
+    pub timestamp: String,
+}
+
+/// Saved baselines, keyed by name
+pub type Baselines = HashMap<String, Baseline>;
+
+impl Baseline {
+    /// Take the metrics from a test result's GPS data; None if it has no HDOP
+    /// (the golden unit never reached a fix)
+    pub fn from_result(result: &TestResult) -> Option<Self> {
+        let data = result.best_gps_data.as_ref()?;
+        Some(Self {
+            satellites: data.satellites.unwrap_or(0),
+            avg_snr: calc_avg_snr(&data.satellites_info),
+            hdop: data.hdop?,
+            serial_number: result.device_info.serial_number.clone(),
+            timestamp: result.timestamp.clone(),
+        })
+    }
+}
+
+/// A criterion set outside the range a test could sensibly use
+#[derive(Debug, Clone, PartialEq, Serialize, Error)]
+#[error("{field} must be {range}, got {value}")]
+pub struct CriteriaError {
+    /// `TestCriteria` field name, e.g. "min_satellites"
+    pub field: String,
+    /// Accepted range, e.g. "1-32"
+    pub range: String,
+    pub value: String,
+}
+
+/// Check `value` lies within `min..=max` (NaN never does)
+fn check_range<T: PartialOrd + std::fmt::Display>(
+    field: &str,
+    value: T,
+    min: T,
+    max: T,
+) -> Result<(), CriteriaError> {
+    if value >= min && value <= max {
+        return Ok(());
+    }
+    Err(CriteriaError {
+        field: field.into(),
+        range: format!("{}-{}", min, max),
+        value: value.to_string(),
+    })
+}
+
+impl TestCriteria {
+    /// Warning margin (percent) for a named criterion
+    pub fn warning_margin(&self, criterion: &str) -> f32 {
+        self.warning_margins
+            .get(criterion)
+            .copied()
+            .unwrap_or(self.warning_margin_pct)
+    }
+
+    /// Reject thresholds no unit could meet or that make a check meaningless, such as
+    /// `min_satellites` of 100, naming the first field out of range
+    pub fn validate(&self) -> Result<(), CriteriaError> {
+        check_range("min_satellites", self.min_satellites, 1, MAX_SATELLITES)?;
+        check_range("min_strong_satellites", self.min_strong_satellites, 0, MAX_SATELLITES)?;
+        check_range("max_hdop", self.max_hdop, MIN_DOP_LIMIT, MAX_DOP_LIMIT)?;
+        check_range("max_pdop", self.max_pdop, MIN_DOP_LIMIT, MAX_DOP_LIMIT)?;
+        check_range("min_avg_snr", self.min_avg_snr, 0.0, MAX_SNR_DB)?;
+        if let Some(snr) = self.min_best_satellite_snr {
+            check_range("min_best_satellite_snr", snr, 0.0, MAX_SNR_DB)?;
+        }
+        if let Some(score) = self.max_multipath_score {
+            check_range("max_multipath_score", score, 0.0, 1.0)?;
+        }
+        check_range(
+            "strong_sat_min_elevation_deg",
+            self.strong_sat_min_elevation_deg,
+            0.0,
+            90.0,
+        )?;
+        check_range("max_ttff_seconds", self.max_ttff_seconds, 1, MAX_TEST_SECONDS)?;
+        check_range("min_constellations", self.min_constellations, 0, MAX_CONSTELLATIONS)?;
+        check_range("min_fix_quality", self.min_fix_quality, 0, 8)?;
+        check_range(
+            "stability_duration_seconds",
+            self.stability_duration_seconds,
+            0,
+            MAX_TEST_SECONDS,
+        )?;
+        if let Some(factor) = self.cold_start_ttff_factor {
+            check_range("cold_start_ttff_factor", factor, 1.0, 10.0)?;
+        }
+        if let Some(timeout) = self.weak_signal_timeout_seconds {
+            let range = (WEAK_SIGNAL_SECONDS, MAX_TEST_SECONDS);
+            check_range("weak_signal_timeout_seconds", timeout, range.0, range.1)?;
         }
+        if let Some(prns) = self.min_unique_prns {
+            check_range("min_unique_prns", prns, 1, MAX_UNIQUE_PRNS)?;
+        }
+        if let Some((lat, lon)) = self.reference_position {
+            check_range("reference_position latitude", lat, -90.0, 90.0)?;
+            check_range("reference_position longitude", lon, -180.0, 180.0)?;
+        }
+        check_range("warning_margin_pct", self.warning_margin_pct, 0.0, 100.0)?;
+        for (name, margin) in &self.warning_margins {
+            check_range(&format!("warning_margins[{}]", name), *margin, 0.0, 100.0)?;
+        }
+        check_range("baseline_tolerance_pct", self.baseline_tolerance_pct, 0.0, 100.0)
     }
 }
 
 /// Result of evaluating a single criterion
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CriterionResult {
     pub name: String,
     pub passed: bool,
+    /// Passed only by virtue of the warning margin (pass-with-warning)
+    #[serde(default)]
+    pub warning: bool,
     pub expected: String,
     pub actual: String,
+    /// Recent numeric values, oldest first, for sparklines (empty unless enabled)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<f32>,
+}
+
+/// When a constellation first contributed a satellite with a signal during a test
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConstellationAcquisition {
+    pub constellation: String,
+    pub seconds: f64, // Since the test started
 }
 
 /// Overall test verdict
@@ -49,10 +430,13 @@ pub struct CriterionResult {
 #[serde(rename_all = "snake_case")]
 pub enum TestVerdict {
     Pass,
+    PassWithWarnings,
     Fail,
     Running,
     NotStarted,
     TimedOut,
+    /// Recovered from an interim save after the app stopped mid-test
+    Interrupted,
 }
 
 /// Device hardware identity
@@ -65,63 +449,395 @@ pub struct DeviceInfo {
     pub serial_number: Option<String>,
     pub vid: Option<u16>,
     pub pid: Option<u16>,
+    /// Unique chip ID from UBX-SEC-UNIQID. Ties the report to the silicon, where
+    /// clone modules often share one USB serial number.
+    #[serde(default)]
+    pub chip_id: Option<String>,
 }
 
 /// Complete test result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestResult {
+    /// Unique ID of this test run (UUID v4); empty in reports from older versions
+    #[serde(default)]
+    pub id: String,
+    /// ID of the optimization this test verified, when it followed one
+    #[serde(default)]
+    pub related_optimization_id: Option<String>,
     pub verdict: TestVerdict,
+    /// Human-readable explanation of the verdict, e.g. "Failed: HDOP, Strong Sats (SNR>=30)"
+    #[serde(default)]
+    pub verdict_summary: String,
     pub criteria_results: Vec<CriterionResult>,
     pub ttff_seconds: Option<f64>,
+    /// Time to first fix of any dimension (2D or better)
+    pub ttff_2d_seconds: Option<f64>,
+    /// Time to first 3D fix
+    pub ttff_3d_seconds: Option<f64>,
     pub test_duration_seconds: f64,
     pub device_info: DeviceInfo,
+    /// Firmware and hardware versions from UBX-MON-VER, when the receiver was
+    /// identified by the optimizer before the test
+    #[serde(default)]
+    pub chip_info: Option<UbloxChipInfo>,
+    /// Start the receiver was expected to make, when classified for the TTFF limit
+    #[serde(default)]
+    pub start_type: Option<StartType>,
     pub timestamp: String,
     pub best_gps_data: Option<GpsData>,
+    /// Best fix type reached during the test, e.g. "Float RTK" or "RTK"
+    #[serde(default)]
+    pub best_fix_type: Option<String>,
+    /// Position of `best_gps_data` in the station's `coord_format` and `datum` settings
+    #[serde(default)]
+    pub formatted_position: Option<String>,
+    /// Name of the device-model criteria profile used, if not the default criteria
+    #[serde(default)]
+    pub criteria_profile: Option<String>,
+    /// True when the run used ad-hoc criteria instead of the stored ones
+    #[serde(default)]
+    pub criteria_override: bool,
+    /// Criteria the run was evaluated against
+    #[serde(default)]
+    pub criteria: Option<TestCriteria>,
+    /// Antenna type inferred from MON-HW (None if the receiver never reported it)
+    #[serde(default)]
+    pub antenna: Option<AntennaReport>,
+    /// Fixes flagged as position jumps and left out of the mean position
+    #[serde(default)]
+    pub position_outliers: u32,
+    /// Times the fix dropped from 3D to 2D, or from any fix to none
+    #[serde(default)]
+    pub fix_downgrades: u32,
+    /// Mean multipath score over the test (0.0-1.0, higher is worse; see `multipath`),
+    /// None if the satellites never allowed a score
+    #[serde(default)]
+    pub multipath_score: Option<f32>,
+    /// The receiver gave fixes but no GSV satellite data, so the SNR and
+    /// constellation criteria were only warned about
+    #[serde(default)]
+    pub gsv_missing: bool,
+    /// Advice for the operator about the conditions, e.g. `WEAK_SIGNAL_HINT` when the
+    /// satellites in view are all too weak for a fix
+    #[serde(default)]
+    pub hint: Option<String>,
+    /// The run is paused; its clocks are frozen until it resumes
+    #[serde(default)]
+    pub paused: bool,
+    /// Each constellation in the order it was first received with SNR, e.g. GLONASS
+    /// missing or far behind GPS on a cold start points to a filter or antenna band issue
+    #[serde(default)]
+    pub constellation_acquisition: Vec<ConstellationAcquisition>,
+    /// Every fix seen during the test, when `max_collected_fixes` is set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub collected_fixes: Vec<GpsData>,
+}
+
+/// Time source for `TestRunner`, so recorded datasets can be replayed deterministically
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// Wall-clock time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Manually set clock for `TestRunner::replay` and tests.
+/// Clones share the same time, so a handle can be kept after passing one to a runner.
+#[derive(Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+
+    pub fn set(&self, at: Instant) {
+        *self.now.lock().unwrap() = at;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// Notices a receiver with GSV output turned off: fixes with satellites in use coming
+/// for `GSV_MISSING_SECONDS` without any per-satellite data. Shared by the serial
+/// reader, which turns GSV back on, and the test runner, which softens GSV criteria
+#[derive(Debug, Default)]
+pub struct GsvWatch {
+    fix_without_gsv_since: Option<Instant>,
+    missing: bool,
+}
+
+impl GsvWatch {
+    /// Update from the latest merged data; returns whether GSV is now missing
+    pub fn observe(&mut self, data: &GpsData, has_fix: bool, now: Instant) -> bool {
+        if !data.satellites_info.is_empty() || !has_fix || data.satellites.unwrap_or(0) == 0 {
+            *self = Self::default();
+            return false;
+        }
+        let since = *self.fix_without_gsv_since.get_or_insert(now);
+        let missing = now.duration_since(since).as_secs() >= GSV_MISSING_SECONDS;
+        if missing && !self.missing {
+            log::warn!("Fixes but no GSV data for {}s: GSV output is off", GSV_MISSING_SECONDS);
+        }
+        self.missing = missing;
+        missing
+    }
+
+    pub fn missing(&self) -> bool {
+        self.missing
+    }
 }
 
 /// Test state machine
 pub struct TestRunner {
     pub criteria: TestCriteria,
+    clock: Box<dyn Clock>,
     start_time: Option<Instant>,
     first_fix_time: Option<Instant>,
+    first_2d_fix_time: Option<Instant>,
+    first_3d_fix_time: Option<Instant>,
     stable_since: Option<Instant>,
     pub verdict: TestVerdict,
     pub device_info: DeviceInfo,
+    /// Unique ID of this run, carried into its result
+    pub id: String,
+    /// Optimization this run verifies, set when it follows one (see `test_report`)
+    pub related_optimization_id: Option<String>,
+    /// Criteria profile this run was started with (None = default criteria)
+    pub criteria_profile: Option<String>,
+    /// True when started with ad-hoc criteria that were not persisted
+    pub criteria_override: bool,
+    /// Golden-unit metrics named by `criteria.baseline_name`, loaded by the caller
+    pub baseline: Option<Baseline>,
+    /// u-blox identity (MON-VER) of the receiver under test, if it was read
+    pub chip_info: Option<UbloxChipInfo>,
+    /// Expected start regime, classified before the test (scales the TTFF limit)
+    pub start_type: Option<StartType>,
+    /// Station's report position format and datum, from the settings
+    pub coord_format: CoordFormat,
+    pub datum: Datum,
+    /// Continuous monitoring (soak test): evaluate indefinitely, never reach a verdict
+    pub continuous: bool,
+    /// Write the interim result to a `.partial.json` this often (None = off)
+    pub interim_save_interval_seconds: Option<u64>,
     last_criteria_results: Vec<CriterionResult>,
     best_satellites: u32,
+    best_fix_type: Option<String>,
+    last_snapshot_time: Option<Instant>,
+    last_interim_save_time: Option<Instant>,
+    position_sum: (f64, f64),
+    /// Sum and count of the multipath scores seen, for the test's mean
+    multipath_sum: f64,
+    multipath_samples: u32,
+    position_samples: u32,
+    /// Latest fixes, outliers included, for the median outlier detection compares against
+    recent_fixes: VecDeque<(f64, f64)>,
+    position_outliers: u32,
+    /// Fix dimension at the previous evaluation, and the drops counted from it
+    last_fix_dimension: u8,
+    fix_downgrades: u32,
+    collected_fixes: Vec<GpsData>,
+    /// Satellite counts seen within the last stability window
+    satellite_samples: VecDeque<(Instant, u32)>,
+    /// HDOP values seen within the last stability window
+    hdop_samples: VecDeque<(Instant, f32)>,
+    /// When each satellite (constellation, PRN) was last seen with usable SNR
+    prns_seen: HashMap<(String, u32), Instant>,
+    constellation_acquisition: Vec<ConstellationAcquisition>,
+    /// Latest receiver fix time and when it last moved forward
+    last_fix_time: Option<(NaiveTime, Instant)>,
+    /// When the receiver's fix time last went backwards
+    fix_time_regressed_at: Option<Instant>,
+    /// Whether fixes are coming without any GSV satellite data
+    gsv_watch: GsvWatch,
+    /// Since when satellites have been in view, all too weak to use, with no fix
+    weak_signal_since: Option<Instant>,
+    weak_signal: bool,
+    antenna: AntennaMonitor,
+    /// Recent numeric values per criterion name, when history is enabled
+    criterion_history: HashMap<String, VecDeque<f32>>,
+    /// Clock reading when the run was paused, if it is paused now
+    paused_at: Option<Instant>,
+    /// Total time spent paused, taken off every clock reading
+    paused_for: Duration,
+    /// Set when evaluation ends the test, until `take_completion` reports it
+    completion_pending: bool,
+    /// Where the report of the finished run was saved, so it is only saved once
+    saved_report: Option<String>,
+    aborted: bool,
 }
 
 impl TestRunner {
     pub fn new(criteria: TestCriteria, device_info: DeviceInfo) -> Self {
+        Self::with_clock(criteria, device_info, Box::new(SystemClock))
+    }
+
+    /// Create a runner driven by a custom time source
+    pub fn with_clock(
+        criteria: TestCriteria,
+        device_info: DeviceInfo,
+        clock: Box<dyn Clock>,
+    ) -> Self {
         Self {
             criteria,
+            clock,
             start_time: None,
             first_fix_time: None,
+            first_2d_fix_time: None,
+            first_3d_fix_time: None,
             stable_since: None,
             verdict: TestVerdict::NotStarted,
             device_info,
+            id: uuid::Uuid::new_v4().to_string(),
+            related_optimization_id: None,
+            criteria_profile: None,
+            criteria_override: false,
+            baseline: None,
+            chip_info: None,
+            start_type: None,
+            coord_format: CoordFormat::default(),
+            datum: Datum::default(),
+            continuous: false,
+            interim_save_interval_seconds: None,
             last_criteria_results: Vec::new(),
             best_satellites: 0,
+            best_fix_type: None,
+            last_snapshot_time: None,
+            last_interim_save_time: None,
+            position_sum: (0.0, 0.0),
+            multipath_sum: 0.0,
+            multipath_samples: 0,
+            position_samples: 0,
+            recent_fixes: VecDeque::new(),
+            position_outliers: 0,
+            last_fix_dimension: 0,
+            fix_downgrades: 0,
+            collected_fixes: Vec::new(),
+            satellite_samples: VecDeque::new(),
+            hdop_samples: VecDeque::new(),
+            prns_seen: HashMap::new(),
+            constellation_acquisition: Vec::new(),
+            last_fix_time: None,
+            fix_time_regressed_at: None,
+            gsv_watch: GsvWatch::default(),
+            weak_signal_since: None,
+            weak_signal: false,
+            antenna: AntennaMonitor::default(),
+            criterion_history: HashMap::new(),
+            paused_at: None,
+            paused_for: Duration::ZERO,
+            completion_pending: false,
+            saved_report: None,
+            aborted: false,
         }
     }
 
     /// Start the test
     pub fn start(&mut self) {
-        self.start_time = Some(Instant::now());
+        self.paused_at = None;
+        self.paused_for = Duration::ZERO;
+        self.start_time = Some(self.now());
         self.first_fix_time = None;
+        self.first_2d_fix_time = None;
+        self.first_3d_fix_time = None;
         self.stable_since = None;
         self.verdict = TestVerdict::Running;
         self.last_criteria_results.clear();
         self.best_satellites = 0;
+        self.best_fix_type = None;
+        self.last_snapshot_time = None;
+        self.last_interim_save_time = None;
+        self.position_sum = (0.0, 0.0);
+        self.multipath_sum = 0.0;
+        self.multipath_samples = 0;
+        self.position_samples = 0;
+        self.recent_fixes.clear();
+        self.position_outliers = 0;
+        self.last_fix_dimension = 0;
+        self.fix_downgrades = 0;
+        self.collected_fixes.clear();
+        self.satellite_samples.clear();
+        self.hdop_samples.clear();
+        self.prns_seen.clear();
+        self.constellation_acquisition.clear();
+        self.last_fix_time = None;
+        self.fix_time_regressed_at = None;
+        self.gsv_watch = GsvWatch::default();
+        self.weak_signal_since = None;
+        self.weak_signal = false;
+        self.antenna.clear();
+        self.criterion_history.clear();
+        self.completion_pending = false;
+        self.saved_report = None;
+        self.aborted = false;
+    }
+
+    /// Active time: the clock less the time spent paused, frozen while paused. Every
+    /// baseline (start, first fixes, stability) is taken from this, so the timeout and
+    /// stability windows only count time the test was running.
+    fn now(&self) -> Instant {
+        self.paused_at.unwrap_or_else(|| self.clock.now()) - self.paused_for
+    }
+
+    /// Pause a running test. Returns false if it isn't running or is already paused.
+    pub fn pause(&mut self) -> bool {
+        if self.verdict != TestVerdict::Running || self.paused_at.is_some() {
+            return false;
+        }
+        self.paused_at = Some(self.clock.now());
+        true
+    }
+
+    /// Resume a paused test. Returns false if it isn't paused.
+    pub fn resume(&mut self) -> bool {
+        let Some(paused_at) = self.paused_at.take() else {
+            return false;
+        };
+        self.paused_for += self.clock.now().duration_since(paused_at);
+        true
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
     }
 
-    /// Get elapsed seconds since test start
+    /// Get elapsed seconds since test start, not counting time paused
     pub fn elapsed_seconds(&self) -> f64 {
+        let now = self.now();
         self.start_time
-            .map(|t| t.elapsed().as_secs_f64())
+            .map(|t| now.duration_since(t).as_secs_f64())
             .unwrap_or(0.0)
     }
 
+    /// TTFF limit for this run: `max_ttff_seconds`, scaled by `cold_start_ttff_factor`
+    /// when a cold start is expected
+    pub fn ttff_limit_seconds(&self) -> f64 {
+        let limit = self.criteria.max_ttff_seconds as f64;
+        match (self.criteria.cold_start_ttff_factor, self.start_type) {
+            (Some(factor), Some(StartType::Cold)) => limit * factor as f64,
+            _ => limit,
+        }
+    }
+
     /// Get TTFF in seconds (None if no fix yet)
     pub fn ttff_seconds(&self) -> Option<f64> {
         match (self.start_time, self.first_fix_time) {
@@ -130,126 +846,472 @@ impl TestRunner {
         }
     }
 
+    /// Get time to first 2D-or-better fix in seconds (None if no fix yet)
+    pub fn ttff_2d_seconds(&self) -> Option<f64> {
+        match (self.start_time, self.first_2d_fix_time) {
+            (Some(start), Some(fix)) => Some(fix.duration_since(start).as_secs_f64()),
+            _ => None,
+        }
+    }
+
+    /// Get time to first 3D fix in seconds (None if no 3D fix yet)
+    pub fn ttff_3d_seconds(&self) -> Option<f64> {
+        match (self.start_time, self.first_3d_fix_time) {
+            (Some(start), Some(fix)) => Some(fix.duration_since(start).as_secs_f64()),
+            _ => None,
+        }
+    }
+
+    /// Mean of all fixed positions seen so far (None before the first fix)
+    pub fn mean_position(&self) -> Option<(f64, f64)> {
+        if self.position_samples == 0 {
+            return None;
+        }
+        let n = self.position_samples as f64;
+        Some((self.position_sum.0 / n, self.position_sum.1 / n))
+    }
+
+    /// True if the fix is further than the outlier threshold from the median of the
+    /// recent fixes (never before `POSITION_OUTLIER_MIN_FIXES` fixes)
+    fn is_position_outlier(&self, lat: f64, lon: f64) -> bool {
+        if self.recent_fixes.len() < POSITION_OUTLIER_MIN_FIXES {
+            return false;
+        }
+        let lats: Vec<f64> = self.recent_fixes.iter().map(|(lat, _)| *lat).collect();
+        let lons: Vec<f64> = self.recent_fixes.iter().map(|(_, lon)| *lon).collect();
+        let distance = haversine_m(lat, lon, median(lats), median(lons));
+        distance > self.criteria.position_outlier_threshold_m
+    }
+
+    /// Keep a fix for the result if collection is on and the cap isn't reached. The
+    /// tick can outpace the receiver, so a fix with the last one's timestamp is skipped.
+    fn collect_fix(&mut self, data: &GpsData) {
+        let Some(limit) = self.criteria.max_collected_fixes else {
+            return;
+        };
+        if self.collected_fixes.len() >= limit.min(MAX_COLLECTED_FIXES) {
+            return;
+        }
+        let repeat = self.collected_fixes.last().is_some_and(|last| {
+            last.timestamp.is_some() && last.timestamp == data.timestamp
+        });
+        if !repeat {
+            self.collected_fixes.push(data.clone());
+        }
+    }
+
     /// Evaluate GPS data against criteria, advancing the state machine
     pub fn evaluate(&mut self, data: &GpsData) -> Vec<CriterionResult> {
-        if self.verdict != TestVerdict::Running {
+        if self.verdict != TestVerdict::Running || self.is_paused() {
             return self.last_criteria_results.clone();
         }
 
         // Check TTFF timeout
+        let now = self.now();
         let elapsed = self.elapsed_seconds();
-        let has_fix = data.fix_quality.unwrap_or(0) >= self.criteria.min_fix_quality;
+        let has_fix = has_fix(&self.criteria, data);
 
         // Record first fix
         if has_fix && self.first_fix_time.is_none() {
-            self.first_fix_time = Some(Instant::now());
+            self.first_fix_time = Some(now);
             log::info!("First fix acquired at {:.1}s", elapsed);
         }
 
+        // Record first 2D / 3D fix separately
+        let dimension = fix_dimension(data);
+        if dimension >= 2 && self.first_2d_fix_time.is_none() {
+            self.first_2d_fix_time = Some(now);
+            log::info!("First 2D fix acquired at {:.1}s", elapsed);
+        }
+        if dimension >= 3 && self.first_3d_fix_time.is_none() {
+            self.first_3d_fix_time = Some(now);
+            log::info!("First 3D fix acquired at {:.1}s", elapsed);
+        }
+        if dimension < self.last_fix_dimension {
+            self.fix_downgrades += 1;
+            log::warn!(
+                "Fix dropped from {} to {} at {:.1}s",
+                dimension_name(self.last_fix_dimension),
+                dimension_name(dimension),
+                elapsed
+            );
+        }
+        self.last_fix_dimension = dimension;
+
+        // Accumulate fixed positions for the mean-position accuracy check, leaving out
+        // jumps away from the recent median so one glitch doesn't drag the mean
+        if has_fix {
+            if let (Some(lat), Some(lon)) = (data.latitude, data.longitude) {
+                if self.is_position_outlier(lat, lon) {
+                    self.position_outliers += 1;
+                    log::warn!("Position outlier at {:.1}s: {:.6}, {:.6}", elapsed, lat, lon);
+                } else {
+                    self.position_sum.0 += lat;
+                    self.position_sum.1 += lon;
+                    self.position_samples += 1;
+                }
+                // Outliers stay in the window, so a real move shifts the median over
+                self.recent_fixes.push_back((lat, lon));
+                if self.recent_fixes.len() > POSITION_OUTLIER_WINDOW {
+                    self.recent_fixes.pop_front();
+                }
+            }
+        }
+
+        if has_fix {
+            self.collect_fix(data);
+        }
+
         // Track best satellite count
         let sat_count = data.satellites.unwrap_or(0);
         if sat_count > self.best_satellites {
             self.best_satellites = sat_count;
         }
 
-        // Evaluate all criteria
-        let mut results = Vec::new();
+        // Track best fix type (ranked, since Float RTK's quality code 5 is worse than RTK's 4)
+        if let Some(fix_type) = data.fix_type.as_deref() {
+            let best_rank = self.best_fix_type.as_deref().map_or(0, fix_type_rank);
+            if fix_type_rank(fix_type) > best_rank {
+                self.best_fix_type = Some(fix_type.to_string());
+            }
+        }
 
-        // 1. Satellite count
-        results.push(CriterionResult {
-            name: "Satellite Count".into(),
-            passed: sat_count >= self.criteria.min_satellites,
-            expected: format!(">= {}", self.criteria.min_satellites),
-            actual: format!("{}", sat_count),
-        });
+        // Keep satellite counts and HDOP for the stability window only
+        let window = Duration::from_secs(self.criteria.stability_duration_seconds);
+        self.satellite_samples.push_back((now, sat_count));
+        prune_window(&mut self.satellite_samples, now, window);
+        if let Some(hdop) = data.hdop {
+            self.hdop_samples.push_back((now, hdop));
+        }
+        prune_window(&mut self.hdop_samples, now, window);
+        for sat in data.satellites_info.iter() {
+            if sat.snr.is_some_and(|snr| snr >= USABLE_PRN_SNR_DB) {
+                self.prns_seen.insert((sat.constellation.clone(), sat.prn), now);
+            }
+        }
+        self.prns_seen.retain(|_, seen| now.duration_since(*seen) <= window);
 
-        // 2. HDOP
-        let hdop_pass = data.hdop.map_or(false, |h| h <= self.criteria.max_hdop);
-        results.push(CriterionResult {
-            name: "HDOP".into(),
-            passed: hdop_pass,
-            expected: format!("<= {:.1}", self.criteria.max_hdop),
-            actual: data.hdop.map_or("-".into(), |h| format!("{:.1}", h)),
-        });
+        if let (Some(status), Some(power)) = (data.antenna_status, data.antenna_power) {
+            self.antenna.observe(elapsed, status, power);
+        }
 
-        // 3. PDOP
-        let pdop_pass = data.pdop.map_or(false, |p| p <= self.criteria.max_pdop);
-        results.push(CriterionResult {
-            name: "PDOP".into(),
-            passed: pdop_pass,
-            expected: format!("<= {:.1}", self.criteria.max_pdop),
-            actual: data.pdop.map_or("-".into(), |p| format!("{:.1}", p)),
-        });
+        for sat in data.satellites_info.iter().filter(|s| s.snr.is_some_and(|snr| snr > 0.0)) {
+            let seen = self
+                .constellation_acquisition
+                .iter()
+                .any(|a| a.constellation == sat.constellation);
+            if !seen {
+                log::info!("{} acquired at {:.1}s", sat.constellation, elapsed);
+                self.constellation_acquisition.push(ConstellationAcquisition {
+                    constellation: sat.constellation.clone(),
+                    seconds: elapsed,
+                });
+            }
+        }
 
-        // 4. Average SNR
-        let avg_snr = calc_avg_snr(&data.satellites_info);
-        results.push(CriterionResult {
-            name: "Average SNR".into(),
-            passed: avg_snr >= self.criteria.min_avg_snr,
-            expected: format!(">= {:.1} dB", self.criteria.min_avg_snr),
-            actual: format!("{:.1} dB", avg_snr),
-        });
+        if let Some(time) = data.timestamp.as_deref().and_then(nmea_log::parse_fix_time) {
+            self.observe_fix_time(time, now);
+        }
+        self.gsv_watch.observe(data, has_fix, now);
+        self.observe_weak_signal(data, has_fix, now);
 
-        // 5. Strong satellites (SNR >= 30)
-        let strong = data
-            .satellites_info
-            .iter()
-            .filter(|s| s.snr.unwrap_or(0.0) >= 30.0)
-            .count() as u32;
-        results.push(CriterionResult {
-            name: "Strong Sats (SNR>=30)".into(),
-            passed: strong >= self.criteria.min_strong_satellites,
-            expected: format!(">= {}", self.criteria.min_strong_satellites),
-            actual: format!("{}", strong),
-        });
+        let multipath = multipath::multipath_score(&data.satellites_info);
+        if let Some(score) = multipath {
+            self.multipath_sum += score as f64;
+            self.multipath_samples += 1;
+        }
 
-        // 6. Constellation count
-        let constellations: HashSet<&str> = data
-            .satellites_info
-            .iter()
-            .map(|s| s.constellation.as_str())
-            .collect();
-        results.push(CriterionResult {
-            name: "Constellations".into(),
-            passed: constellations.len() as u32 >= self.criteria.min_constellations,
-            expected: format!(">= {}", self.criteria.min_constellations),
-            actual: format!("{} ({})", constellations.len(), constellations.into_iter().collect::<Vec<_>>().join(", ")),
-        });
+        // Evaluate all criteria, in the same order as `evaluate_snapshot` where they overlap
+        let mut eval = Evaluation::default();
 
-        // 7. Fix quality
-        results.push(CriterionResult {
-            name: "Fix Quality".into(),
-            passed: has_fix,
-            expected: format!(">= {}", self.criteria.min_fix_quality),
-            actual: format!("{}", data.fix_quality.unwrap_or(0)),
-        });
+        // 1-7. Satellites, DOP, signal and fix quality
+        push_core_criteria(&mut eval, &self.criteria, data);
 
         // 8. TTFF
         let ttff = self.ttff_seconds();
-        let ttff_pass = ttff.map_or(false, |t| t <= self.criteria.max_ttff_seconds as f64);
-        results.push(CriterionResult {
+        let ttff_limit = self.ttff_limit_seconds();
+        let ttff_pass = ttff.map_or(false, |t| t <= ttff_limit);
+        eval.push(CriterionResult {
             name: "Time to First Fix".into(),
             passed: ttff_pass || self.first_fix_time.is_some(),
-            expected: format!("<= {}s", self.criteria.max_ttff_seconds),
+            warning: false,
+            expected: format!("<= {:.0}s", ttff_limit),
             actual: ttff.map_or("Waiting...".into(), |t| format!("{:.1}s", t)),
+            history: Vec::new(),
         });
 
+        // 9. Position accuracy of the mean fix against a surveyed reference
+        eval.push_scored(position_error_criterion(&self.criteria, self.mean_position()));
+
+        // 10. Fix age / latency
+        eval.push_scored(fix_age_criterion(&self.criteria, data));
+
+        // 11. Satellite count consistency (only when configured)
+        if let Some(max_stddev) = self.criteria.max_satellite_count_stddev {
+            let counts: Vec<u32> = self.satellite_samples.iter().map(|(_, c)| *c).collect();
+            let stddev = std_dev(&counts);
+            let (passed, warning) = check_max(
+                Some(stddev),
+                max_stddev,
+                self.criteria.warning_margin("Satellite Count Stability"),
+            );
+            let (min, max) = (
+                counts.iter().min().copied().unwrap_or(0),
+                counts.iter().max().copied().unwrap_or(0),
+            );
+            eval.push_scored((
+                CriterionResult {
+                    name: "Satellite Count Stability".into(),
+                    passed,
+                    warning,
+                    expected: format!("stddev <= {:.1}", max_stddev),
+                    actual: format!("{:.1} ({}-{})", stddev, min, max),
+                    history: Vec::new(),
+                },
+                Some(stddev),
+            ));
+        }
+
+        // 12. RTK fixed solution
+        eval.push(rtk_fixed_criterion(&self.criteria, data));
+
+        // 13. HDOP held within the limit across the stability window (only when configured)
+        if self.criteria.require_stable_hdop {
+            let worst = self
+                .hdop_samples
+                .iter()
+                .map(|(_, h)| *h as f64)
+                .reduce(f64::max);
+            let (passed, warning) = check_max(
+                worst,
+                self.criteria.max_hdop as f64,
+                self.criteria.warning_margin("HDOP Stability"),
+            );
+            eval.push_scored((
+                CriterionResult {
+                    name: "HDOP Stability".into(),
+                    passed,
+                    warning,
+                    expected: format!(
+                        "<= {:.1} for {}s",
+                        self.criteria.max_hdop, self.criteria.stability_duration_seconds
+                    ),
+                    actual: worst.map_or("-".into(), |h| format!("worst {:.1}", h)),
+                    history: Vec::new(),
+                },
+                worst,
+            ));
+        }
+
+        // 14. Within tolerance of a golden unit (only when a baseline is loaded)
+        if let Some(baseline) = &self.baseline {
+            let avg_snr = calc_avg_snr(&data.satellites_info);
+            let tolerance = self.criteria.baseline_tolerance_pct as f64 / 100.0;
+            let margin = self.criteria.warning_margin("Baseline Match");
+            let checks = [
+                check_min(
+                    Some(sat_count as f64),
+                    baseline.satellites as f64 * (1.0 - tolerance),
+                    margin,
+                ),
+                check_min(
+                    Some(avg_snr as f64),
+                    baseline.avg_snr as f64 * (1.0 - tolerance),
+                    margin,
+                ),
+                check_max(
+                    data.hdop.map(f64::from),
+                    baseline.hdop as f64 * (1.0 + tolerance),
+                    margin,
+                ),
+            ];
+            let passed = checks.iter().all(|(p, _)| *p);
+            eval.push(CriterionResult {
+                name: "Baseline Match".into(),
+                passed,
+                warning: passed && checks.iter().any(|(_, w)| *w),
+                expected: format!(
+                    "within {:.0}% of '{}'",
+                    self.criteria.baseline_tolerance_pct,
+                    self.criteria.baseline_name.as_deref().unwrap_or("baseline")
+                ),
+                actual: format!(
+                    "sats {}/{}, SNR {:.1}/{:.1} dB, HDOP {}/{:.1}",
+                    sat_count,
+                    baseline.satellites,
+                    avg_snr,
+                    baseline.avg_snr,
+                    data.hdop.map_or("-".into(), |h| format!("{:.1}", h)),
+                    baseline.hdop
+                ),
+                history: Vec::new(),
+            });
+        }
+
+        // 15. Horizontal accuracy estimate
+        eval.push_scored(hacc_criterion(&self.criteria, data));
+
+        // 16. Active antenna detected (only when configured)
+        if self.criteria.expect_active_antenna {
+            let kind = self.antenna.kind();
+            eval.push(CriterionResult {
+                name: "Active Antenna".into(),
+                passed: kind == AntennaKind::Active,
+                warning: false,
+                expected: "active".into(),
+                actual: match kind {
+                    AntennaKind::Active => "active",
+                    AntennaKind::Passive => "passive / none",
+                    AntennaKind::Short => "short circuit",
+                    AntennaKind::Unknown => "unknown",
+                }
+                .into(),
+                history: Vec::new(),
+            });
+        }
+
+        // 17. Position jumps (only when configured)
+        if let Some(max_outliers) = self.criteria.max_position_outliers {
+            let (passed, warning) = check_max(
+                Some(self.position_outliers as f64),
+                max_outliers as f64,
+                self.criteria.warning_margin("Position Outliers"),
+            );
+            eval.push_scored((
+                CriterionResult {
+                    name: "Position Outliers".into(),
+                    passed,
+                    warning,
+                    expected: format!(
+                        "<= {} (> {:.0} m from median)",
+                        max_outliers, self.criteria.position_outlier_threshold_m
+                    ),
+                    actual: format!("{}", self.position_outliers),
+                    history: Vec::new(),
+                },
+                Some(self.position_outliers as f64),
+            ));
+        }
+
+        // 18-20. Required constellations, receiver date and peak SNR
+        eval.push(required_constellations_criterion(&self.criteria, data));
+        eval.push(receiver_date_criterion(&self.criteria, data));
+        eval.push_scored(best_snr_criterion(&self.criteria, data));
+
+        // 21. Receiver time moving forward (only when configured)
+        if self.criteria.require_advancing_time {
+            let window = Duration::from_secs(self.criteria.stability_duration_seconds);
+            let regressed = self
+                .fix_time_regressed_at
+                .is_some_and(|at| now.duration_since(at) <= window);
+            let (passed, actual) = match self.last_fix_time {
+                None => (false, "no time reported".to_string()),
+                Some(_) if regressed => (false, "went backwards".to_string()),
+                Some((time, advanced_at)) => {
+                    let stalled = now.duration_since(advanced_at).as_secs();
+                    if stalled > MAX_FIX_TIME_STALL_SECONDS {
+                        (false, format!("stuck at {} for {}s", time.format("%H:%M:%S"), stalled))
+                    } else {
+                        (true, time.format("%H:%M:%S").to_string())
+                    }
+                }
+            };
+            eval.push(CriterionResult {
+                name: "Fix Time Advancing".into(),
+                passed,
+                warning: false,
+                expected: format!("advancing (stuck <= {}s)", MAX_FIX_TIME_STALL_SECONDS),
+                actual,
+                history: Vec::new(),
+            });
+        }
+
+        // 22-23. GPS-UTC offset and multipath
+        eval.push(time_valid_criterion(&self.criteria, data));
+        eval.push_scored(multipath_criterion(&self.criteria, multipath));
+
+        // 24. Fix downgrades (only when configured)
+        if let Some(max_downgrades) = self.criteria.max_fix_downgrades {
+            let (passed, warning) = check_max(
+                Some(self.fix_downgrades as f64),
+                max_downgrades as f64,
+                self.criteria.warning_margin("Fix Downgrades"),
+            );
+            eval.push_scored((
+                CriterionResult {
+                    name: "Fix Downgrades".into(),
+                    passed,
+                    warning,
+                    expected: format!("<= {}", max_downgrades),
+                    actual: format!("{}", self.fix_downgrades),
+                    history: Vec::new(),
+                },
+                Some(self.fix_downgrades as f64),
+            ));
+        }
+
+        // 25. Distinct satellites tracked across the stability window (only when configured)
+        if let Some(min_prns) = self.criteria.min_unique_prns {
+            let unique = self.prns_seen.len();
+            let (passed, warning) = check_min(
+                Some(unique as f64),
+                min_prns as f64,
+                self.criteria.warning_margin("Unique PRNs"),
+            );
+            eval.push_scored((
+                CriterionResult {
+                    name: "Unique PRNs".into(),
+                    passed,
+                    warning,
+                    expected: format!(
+                        ">= {} in {}s",
+                        min_prns, self.criteria.stability_duration_seconds
+                    ),
+                    actual: format!("{}", unique),
+                    history: Vec::new(),
+                },
+                Some(unique as f64),
+            ));
+        }
+
+        let Evaluation { mut results, numeric } = eval;
+
+        // A fix without satellite data means GSV output is off, not that no satellites
+        // are visible: warn on the criteria that need it rather than failing the unit
+        if self.gsv_watch.missing() {
+            let needs_gsv = |r: &CriterionResult| GSV_CRITERIA.contains(&r.name.as_str());
+            for result in results.iter_mut().filter(|r| !r.passed && needs_gsv(r)) {
+                result.passed = true;
+                result.warning = true;
+                result.actual = "no GSV output".into();
+            }
+        }
+
+        if self.criteria.criterion_history_len > 0 {
+            self.record_history(&mut results, &numeric);
+        }
+
         // Check if all criteria pass (excluding TTFF which just needs to have happened)
         let all_pass = results.iter().all(|r| r.passed);
 
         if all_pass {
             // Track stability
             if self.stable_since.is_none() {
-                self.stable_since = Some(Instant::now());
+                self.stable_since = Some(now);
                 log::info!("All criteria passing, stability timer started");
             }
 
-            // Check if stable long enough
-            if let Some(stable_start) = self.stable_since {
-                let stable_duration = stable_start.elapsed().as_secs();
+            // Check if stable long enough (continuous mode never concludes)
+            if let Some(stable_start) = self.stable_since.filter(|_| !self.continuous) {
+                let stable_duration = now.duration_since(stable_start).as_secs();
                 if stable_duration >= self.criteria.stability_duration_seconds {
-                    self.verdict = TestVerdict::Pass;
-                    log::info!("TEST PASSED - stable for {}s", stable_duration);
+                    if results.iter().any(|r| r.warning) {
+                        self.verdict = TestVerdict::PassWithWarnings;
+                        log::info!("TEST PASSED WITH WARNINGS - stable for {}s", stable_duration);
+                    } else {
+                        self.verdict = TestVerdict::Pass;
+                        log::info!("TEST PASSED - stable for {}s", stable_duration);
+                    }
                 }
             }
         } else {
@@ -261,8 +1323,9 @@ impl TestRunner {
         }
 
         // Check for overall timeout (3x TTFF limit as total test timeout)
-        let total_timeout = self.criteria.max_ttff_seconds * 3 + self.criteria.stability_duration_seconds;
-        if elapsed > total_timeout as f64 {
+        let total_timeout =
+            self.ttff_limit_seconds() * 3.0 + self.criteria.stability_duration_seconds as f64;
+        if !self.continuous && elapsed > total_timeout {
             if self.first_fix_time.is_none() {
                 self.verdict = TestVerdict::TimedOut;
                 log::warn!("TEST TIMED OUT - no fix acquired in {}s", elapsed);
@@ -272,40 +1335,2058 @@ impl TestRunner {
             }
         }
 
+        // Indoors or without an antenna no fix is coming, so optionally stop early
+        if let (Some(limit), Some(since)) =
+            (self.criteria.weak_signal_timeout_seconds, self.weak_signal_since)
+        {
+            let weak_for = now.duration_since(since).as_secs();
+            let running = self.verdict == TestVerdict::Running;
+            if running && !self.continuous && weak_for >= limit {
+                self.verdict = TestVerdict::TimedOut;
+                log::warn!("TEST TIMED OUT - weak signal and no fix for {}s", weak_for);
+            }
+        }
+
+        self.completion_pending = self.verdict != TestVerdict::Running;
         self.last_criteria_results = results.clone();
         results
     }
 
-    /// Get current test result snapshot
+    /// True exactly once after evaluation reaches a verdict (never after an abort),
+    /// so the caller can act on completion, e.g. auto-save the report, only once
+    pub fn take_completion(&mut self) -> bool {
+        std::mem::take(&mut self.completion_pending)
+    }
+
+    /// Path the finished run's report was saved to, if it has been
+    pub fn saved_report(&self) -> Option<&str> {
+        self.saved_report.as_deref()
+    }
+
+    /// Record where the report was saved. Only a finished run is marked: a report
+    /// saved while running is a snapshot, and the final one still needs saving.
+    pub fn mark_saved(&mut self, path: &str) {
+        if self.verdict != TestVerdict::Running {
+            self.saved_report = Some(path.to_string());
+        }
+    }
+
+    /// Track the receiver's fix time. The tick can outpace the receiver, so an
+    /// unchanged time is only a stall once it has lasted a while.
+    fn observe_fix_time(&mut self, time: NaiveTime, now: Instant) {
+        match self.last_fix_time {
+            Some((last, _)) if time == last => {}
+            Some((last, _))
+                if time < last && (last - time).num_seconds() <= MIDNIGHT_ROLLOVER_SECONDS =>
+            {
+                log::warn!("Receiver time went backwards: {} -> {}", last, time);
+                self.fix_time_regressed_at = Some(now);
+                self.last_fix_time = Some((time, now));
+            }
+            _ => self.last_fix_time = Some((time, now)),
+        }
+    }
+
+    /// Flag a weak signal once satellites have been in view for `WEAK_SIGNAL_SECONDS`
+    /// without a fix, none of them at `USABLE_PRN_SNR_DB` or better
+    fn observe_weak_signal(&mut self, data: &GpsData, has_fix: bool, now: Instant) {
+        let all_weak = data
+            .satellites_info
+            .iter()
+            .all(|s| s.snr.unwrap_or(0.0) < USABLE_PRN_SNR_DB);
+        if has_fix || data.satellites_info.is_empty() || !all_weak {
+            self.weak_signal_since = None;
+            self.weak_signal = false;
+            return;
+        }
+        let since = *self.weak_signal_since.get_or_insert(now);
+        let weak = now.duration_since(since).as_secs() >= WEAK_SIGNAL_SECONDS;
+        if weak && !self.weak_signal {
+            log::warn!("Satellites in view but none usable for {}s", WEAK_SIGNAL_SECONDS);
+        }
+        self.weak_signal = weak;
+    }
+
+    /// Append this tick's numeric values to each criterion's bounded history
+    fn record_history(
+        &mut self,
+        results: &mut [CriterionResult],
+        numeric: &[(String, Option<f64>)],
+    ) {
+        let limit = self.criteria.criterion_history_len.min(MAX_CRITERION_HISTORY);
+        for (name, value) in numeric {
+            let history = self.criterion_history.entry(name.clone()).or_default();
+            if let Some(v) = value {
+                history.push_back(*v as f32);
+            }
+            while history.len() > limit {
+                history.pop_front();
+            }
+        }
+        for result in results.iter_mut() {
+            if let Some(history) = self.criterion_history.get(&result.name) {
+                result.history = history.iter().copied().collect();
+            }
+        }
+    }
+
+    /// Replay a recorded dataset of `(seconds since start, GpsData)` samples.
+    /// Time is taken from the sample timestamps rather than the wall clock, so the
+    /// same dataset always produces the same verdict. Stops at the first verdict.
+    pub fn replay<I>(&mut self, samples: I) -> TestVerdict
+    where
+        I: IntoIterator<Item = (f64, GpsData)>,
+    {
+        let clock = ManualClock::new();
+        let origin = clock.now();
+        self.clock = Box::new(clock.clone());
+        self.start();
+
+        for (t, data) in samples {
+            clock.set(origin + Duration::from_secs_f64(t.max(0.0)));
+            self.evaluate(&data);
+            if self.verdict != TestVerdict::Running {
+                break;
+            }
+        }
+        self.verdict.clone()
+    }
+
+    /// In continuous mode, returns true once per snapshot interval while running
+    pub fn monitor_snapshot_due(&mut self) -> bool {
+        if !self.continuous || self.verdict != TestVerdict::Running {
+            return false;
+        }
+        let now = self.now();
+        interval_elapsed(&mut self.last_snapshot_time, now, MONITOR_SNAPSHOT_INTERVAL_SECONDS)
+    }
+
+    /// Returns true once per `interim_save_interval_seconds` while running, if configured
+    pub fn interim_save_due(&mut self) -> bool {
+        let Some(interval) = self.interim_save_interval_seconds else {
+            return false;
+        };
+        if self.verdict != TestVerdict::Running {
+            return false;
+        }
+        let now = self.now();
+        interval_elapsed(&mut self.last_interim_save_time, now, interval)
+    }
+
+    /// Get current test result snapshot. Collected fixes are left out, since this is
+    /// polled for the live status; see `get_report`.
     pub fn get_result(&self, gps_data: Option<&GpsData>) -> TestResult {
         TestResult {
+            id: self.id.clone(),
+            related_optimization_id: self.related_optimization_id.clone(),
             verdict: self.verdict.clone(),
+            verdict_summary: self.verdict_summary(),
             criteria_results: self.last_criteria_results.clone(),
             ttff_seconds: self.ttff_seconds(),
+            ttff_2d_seconds: self.ttff_2d_seconds(),
+            ttff_3d_seconds: self.ttff_3d_seconds(),
             test_duration_seconds: self.elapsed_seconds(),
             device_info: self.device_info.clone(),
+            chip_info: self.chip_info.clone(),
+            start_type: self.start_type,
             timestamp: chrono::Utc::now().to_rfc3339(),
             best_gps_data: gps_data.cloned(),
+            best_fix_type: self.best_fix_type.clone(),
+            formatted_position: gps_data.and_then(|d| match (d.latitude, d.longitude) {
+                (Some(lat), Some(lon)) => Some(geo::format_position(
+                    lat,
+                    lon,
+                    self.coord_format,
+                    self.datum,
+                )),
+                _ => None,
+            }),
+            criteria_profile: self.criteria_profile.clone(),
+            criteria_override: self.criteria_override,
+            criteria: Some(self.criteria.clone()),
+            antenna: self.antenna.report(self.criteria.expect_active_antenna),
+            position_outliers: self.position_outliers,
+            fix_downgrades: self.fix_downgrades,
+            multipath_score: (self.multipath_samples > 0)
+                .then(|| (self.multipath_sum / self.multipath_samples as f64) as f32),
+            gsv_missing: self.gsv_watch.missing(),
+            hint: self.weak_signal.then(|| WEAK_SIGNAL_HINT.to_string()),
+            paused: self.is_paused(),
+            constellation_acquisition: self.constellation_acquisition.clone(),
+            collected_fixes: Vec::new(),
+        }
+    }
+
+    /// Result to save: `get_result` plus the collected fixes, if any
+    pub fn get_report(&self, gps_data: Option<&GpsData>) -> TestResult {
+        TestResult {
+            collected_fixes: self.collected_fixes.clone(),
+            ..self.get_result(gps_data)
+        }
+    }
+
+    /// One-line explanation of the current verdict
+    pub fn verdict_summary(&self) -> String {
+        let names = |pick: fn(&CriterionResult) -> bool| -> String {
+            self.last_criteria_results
+                .iter()
+                .filter(|r| pick(r))
+                .map(|r| r.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match self.verdict {
+            TestVerdict::NotStarted => "Not started".into(),
+            TestVerdict::Running if self.is_paused() => "Paused".into(),
+            TestVerdict::Running => "Running".into(),
+            TestVerdict::Pass => "Passed".into(),
+            TestVerdict::PassWithWarnings => format!("Passed with warnings: {}", names(|r| r.warning)),
+            TestVerdict::TimedOut if self.weak_signal => format!(
+                "Timed out: weak signal and no fix after {:.0}s, move the antenna to open sky",
+                self.elapsed_seconds()
+            ),
+            TestVerdict::TimedOut => format!(
+                "Timed out: no fix acquired within {:.0}s",
+                self.elapsed_seconds()
+            ),
+            TestVerdict::Interrupted => INTERRUPTED_SUMMARY.into(),
+            TestVerdict::Fail if self.aborted => "Aborted by operator".into(),
+            TestVerdict::Fail => {
+                let failing = names(|r| !r.passed);
+                if failing.is_empty() {
+                    // Criteria passed but never held for the stability window
+                    "Failed: criteria not stable for the required duration".into()
+                } else {
+                    format!("Failed: {} (criteria not met before timeout)", failing)
+                }
+            }
         }
     }
 
     /// Abort the test
     pub fn abort(&mut self) {
+        self.resume();
+        self.aborted = true;
         self.verdict = TestVerdict::Fail;
     }
 }
 
-/// Calculate average SNR across all satellites with signal
-fn calc_avg_snr(satellites: &[crate::nmea::SatelliteInfo]) -> f32 {
-    let with_snr: Vec<f32> = satellites
-        .iter()
-        .filter_map(|s| s.snr)
-        .filter(|&snr| snr > 0.0)
-        .collect();
+/// A criterion result with the numeric value behind it, for sparkline history
+type Scored = (CriterionResult, Option<f64>);
 
-    if with_snr.is_empty() {
-        0.0
-    } else {
-        with_snr.iter().sum::<f32>() / with_snr.len() as f32
+/// Criterion results for one evaluation, in order, with the numeric values behind them
+#[derive(Default)]
+struct Evaluation {
+    results: Vec<CriterionResult>,
+    numeric: Vec<(String, Option<f64>)>,
+}
+
+impl Evaluation {
+    /// Add a result that has no numeric value (nothing to chart); `None` adds nothing
+    fn push(&mut self, result: impl Into<Option<CriterionResult>>) {
+        self.results.extend(result.into());
+    }
+
+    /// Add a result and its numeric value; `None` adds nothing
+    fn push_scored(&mut self, scored: impl Into<Option<Scored>>) {
+        if let Some((result, value)) = scored.into() {
+            self.numeric.push((result.name.clone(), value));
+            self.results.push(result);
+        }
+    }
+}
+
+/// Evaluate a single `GpsData` against `criteria` with no test running: only the
+/// criteria that can be judged from one fix, in the order `TestRunner::evaluate`
+/// reports them. Criteria that need history (TTFF, stability windows, baseline,
+/// antenna, outliers, advancing time, downgrades) are left out, and the reference
+/// position is compared with this fix rather than a mean.
+pub fn evaluate_snapshot(criteria: &TestCriteria, data: &GpsData) -> Vec<CriterionResult> {
+    let mut eval = Evaluation::default();
+    push_core_criteria(&mut eval, criteria, data);
+    let position = data.latitude.zip(data.longitude).filter(|_| has_fix(criteria, data));
+    eval.push_scored(position_error_criterion(criteria, position));
+    eval.push_scored(fix_age_criterion(criteria, data));
+    eval.push(rtk_fixed_criterion(criteria, data));
+    eval.push_scored(hacc_criterion(criteria, data));
+    eval.push(required_constellations_criterion(criteria, data));
+    eval.push(receiver_date_criterion(criteria, data));
+    eval.push_scored(best_snr_criterion(criteria, data));
+    eval.push(time_valid_criterion(criteria, data));
+    let multipath = multipath::multipath_score(&data.satellites_info);
+    eval.push_scored(multipath_criterion(criteria, multipath));
+    eval.results
+}
+
+/// Whether `data` has a fix of at least the required quality
+fn has_fix(criteria: &TestCriteria, data: &GpsData) -> bool {
+    data.fix_quality.unwrap_or(0) >= criteria.min_fix_quality
+}
+
+/// Criteria 1-7, which are always checked: satellite count, HDOP, PDOP, average SNR,
+/// strong satellites, constellation count and fix quality
+fn push_core_criteria(eval: &mut Evaluation, criteria: &TestCriteria, data: &GpsData) {
+    // 1. Satellite count
+    let sat_count = data.satellites.unwrap_or(0);
+    let (passed, warning) = check_min(
+        Some(sat_count as f64),
+        criteria.min_satellites as f64,
+        criteria.warning_margin("Satellite Count"),
+    );
+    eval.push_scored((
+        CriterionResult {
+            name: "Satellite Count".into(),
+            passed,
+            warning,
+            expected: format!(">= {}", criteria.min_satellites),
+            actual: format!("{}", sat_count),
+            history: Vec::new(),
+        },
+        Some(sat_count as f64),
+    ));
+
+    // 2. HDOP
+    let (passed, warning) = check_max(
+        data.hdop.map(|h| h as f64),
+        criteria.max_hdop as f64,
+        criteria.warning_margin("HDOP"),
+    );
+    eval.push_scored((
+        CriterionResult {
+            name: "HDOP".into(),
+            passed,
+            warning,
+            expected: format!("<= {:.1}", criteria.max_hdop),
+            actual: data.hdop.map_or("-".into(), |h| format!("{:.1}", h)),
+            history: Vec::new(),
+        },
+        data.hdop.map(f64::from),
+    ));
+
+    // 3. PDOP
+    let (passed, warning) = check_max(
+        data.pdop.map(|p| p as f64),
+        criteria.max_pdop as f64,
+        criteria.warning_margin("PDOP"),
+    );
+    eval.push_scored((
+        CriterionResult {
+            name: "PDOP".into(),
+            passed,
+            warning,
+            expected: format!("<= {:.1}", criteria.max_pdop),
+            actual: data.pdop.map_or("-".into(), |p| format!("{:.1}", p)),
+            history: Vec::new(),
+        },
+        data.pdop.map(f64::from),
+    ));
+
+    // 4. Average SNR
+    let avg_snr = calc_avg_snr(&data.satellites_info);
+    let (passed, warning) = check_min(
+        Some(avg_snr as f64),
+        criteria.min_avg_snr as f64,
+        criteria.warning_margin("Average SNR"),
+    );
+    eval.push_scored((
+        CriterionResult {
+            name: "Average SNR".into(),
+            passed,
+            warning,
+            expected: format!(">= {:.1} dB", criteria.min_avg_snr),
+            actual: format!("{:.1} dB", avg_snr),
+            history: Vec::new(),
+        },
+        Some(avg_snr as f64),
+    ));
+
+    // 5. Strong satellites (SNR >= 30, above the elevation mask if one is set)
+    let min_elevation = criteria.strong_sat_min_elevation_deg;
+    let strong = data
+        .satellites_info
+        .iter()
+        .filter(|s| s.snr.unwrap_or(0.0) >= 30.0)
+        .filter(|s| min_elevation <= 0.0 || s.elevation.is_some_and(|e| e >= min_elevation))
+        .count() as u32;
+    let (passed, warning) = check_min(
+        Some(strong as f64),
+        criteria.min_strong_satellites as f64,
+        criteria.warning_margin("Strong Sats (SNR>=30)"),
+    );
+    eval.push_scored((
+        CriterionResult {
+            name: "Strong Sats (SNR>=30)".into(),
+            passed,
+            warning,
+            expected: if min_elevation > 0.0 {
+                format!(">= {} (elev >= {:.0}°)", criteria.min_strong_satellites, min_elevation)
+            } else {
+                format!(">= {}", criteria.min_strong_satellites)
+            },
+            actual: format!("{}", strong),
+            history: Vec::new(),
+        },
+        Some(strong as f64),
+    ));
+
+    // 6. Constellation count
+    let constellations: HashSet<&str> = data
+        .satellites_info
+        .iter()
+        .map(|s| s.constellation.as_str())
+        .collect();
+    let (passed, warning) = check_min(
+        Some(constellations.len() as f64),
+        criteria.min_constellations as f64,
+        criteria.warning_margin("Constellations"),
+    );
+    eval.push_scored((
+        CriterionResult {
+            name: "Constellations".into(),
+            passed,
+            warning,
+            expected: format!(">= {}", criteria.min_constellations),
+            actual: format!(
+                "{} ({})",
+                constellations.len(),
+                constellations.iter().copied().collect::<Vec<_>>().join(", ")
+            ),
+            history: Vec::new(),
+        },
+        Some(constellations.len() as f64),
+    ));
+
+    // 7. Fix quality
+    eval.push_scored((
+        CriterionResult {
+            name: "Fix Quality".into(),
+            passed: has_fix(criteria, data),
+            warning: false,
+            expected: format!(">= {}", criteria.min_fix_quality),
+            actual: format!("{}", data.fix_quality.unwrap_or(0)),
+            history: Vec::new(),
+        },
+        data.fix_quality.map(f64::from),
+    ));
+}
+
+/// 9. Position accuracy against a surveyed reference (only when configured)
+fn position_error_criterion(
+    criteria: &TestCriteria,
+    position: Option<(f64, f64)>,
+) -> Option<Scored> {
+    let (ref_lat, ref_lon) = criteria.reference_position?;
+    let error_m = position.map(|(lat, lon)| haversine_m(lat, lon, ref_lat, ref_lon));
+    let (passed, warning) = check_max(
+        error_m,
+        criteria.max_position_error_m,
+        criteria.warning_margin("Position Error"),
+    );
+    let result = CriterionResult {
+        name: "Position Error".into(),
+        passed,
+        warning,
+        expected: format!("<= {:.1} m", criteria.max_position_error_m),
+        actual: error_m.map_or("-".into(), |e| format!("{:.1} m", e)),
+        history: Vec::new(),
+    };
+    Some((result, error_m))
+}
+
+/// 10. Fix age / latency (only when configured)
+fn fix_age_criterion(criteria: &TestCriteria, data: &GpsData) -> Option<Scored> {
+    let max_age = criteria.max_fix_age_seconds?;
+    let age = data.fix_age_seconds.map(f64::abs);
+    let (passed, warning) = check_max(age, max_age, criteria.warning_margin("Fix Age"));
+    let result = CriterionResult {
+        name: "Fix Age".into(),
+        passed,
+        warning,
+        expected: format!("<= {:.1}s", max_age),
+        actual: data.fix_age_seconds.map_or("-".into(), |a| format!("{:.1}s", a)),
+        history: Vec::new(),
+    };
+    Some((result, age))
+}
+
+/// 12. RTK fixed solution (only when configured)
+fn rtk_fixed_criterion(criteria: &TestCriteria, data: &GpsData) -> Option<CriterionResult> {
+    criteria.require_rtk_fixed.then(|| CriterionResult {
+        name: "RTK Fixed".into(),
+        passed: data.fix_type.as_deref() == Some("RTK"),
+        warning: false,
+        expected: "RTK".into(),
+        actual: data.fix_type.clone().unwrap_or_else(|| "-".into()),
+        history: Vec::new(),
+    })
+}
+
+/// 15. Horizontal accuracy estimate (only when configured and reported)
+fn hacc_criterion(criteria: &TestCriteria, data: &GpsData) -> Option<Scored> {
+    let (max_hacc, hacc) = criteria.max_hacc_m.zip(data.horizontal_accuracy_m)?;
+    let (passed, warning) = check_max(
+        Some(hacc as f64),
+        max_hacc as f64,
+        criteria.warning_margin("Horizontal Accuracy"),
+    );
+    let result = CriterionResult {
+        name: "Horizontal Accuracy".into(),
+        passed,
+        warning,
+        expected: format!("<= {:.1} m", max_hacc),
+        actual: format!("{:.2} m", hacc),
+        history: Vec::new(),
+    };
+    Some((result, Some(hacc as f64)))
+}
+
+/// 18. Specific constellations in view (only when configured)
+fn required_constellations_criterion(
+    criteria: &TestCriteria,
+    data: &GpsData,
+) -> Option<CriterionResult> {
+    if criteria.required_constellations.is_empty() {
+        return None;
+    }
+    let missing: Vec<&str> = criteria
+        .required_constellations
+        .iter()
+        .filter(|required| {
+            !data
+                .satellites_info
+                .iter()
+                .any(|s| s.constellation.eq_ignore_ascii_case(required))
+        })
+        .map(String::as_str)
+        .collect();
+    Some(CriterionResult {
+        name: "Required Constellations".into(),
+        passed: missing.is_empty(),
+        warning: false,
+        expected: criteria.required_constellations.join(", "),
+        actual: if missing.is_empty() {
+            "all present".into()
+        } else {
+            format!("missing {}", missing.join(", "))
+        },
+        history: Vec::new(),
+    })
+}
+
+/// 19. Receiver date plausibility (only when configured)
+fn receiver_date_criterion(criteria: &TestCriteria, data: &GpsData) -> Option<CriterionResult> {
+    criteria.reject_suspect_date.then(|| CriterionResult {
+        name: "Receiver Date".into(),
+        passed: data.date_suspect != Some(true),
+        warning: false,
+        expected: "plausible".into(),
+        actual: data.utc_date.clone().unwrap_or_else(|| "-".into()),
+        history: Vec::new(),
+    })
+}
+
+/// 20. Peak SNR of the strongest satellite (only when configured)
+fn best_snr_criterion(criteria: &TestCriteria, data: &GpsData) -> Option<Scored> {
+    let min_best_snr = criteria.min_best_satellite_snr?;
+    let best_snr = data
+        .satellites_info
+        .iter()
+        .filter_map(|s| s.snr)
+        .reduce(f32::max);
+    let (passed, warning) = check_min(
+        best_snr.map(f64::from),
+        min_best_snr as f64,
+        criteria.warning_margin("Best Satellite SNR"),
+    );
+    let result = CriterionResult {
+        name: "Best Satellite SNR".into(),
+        passed,
+        warning,
+        expected: format!(">= {:.1} dB", min_best_snr),
+        actual: best_snr.map_or_else(|| "-".into(), |snr| format!("{:.1} dB", snr)),
+        history: Vec::new(),
+    };
+    Some((result, best_snr.map(f64::from)))
+}
+
+/// 22. GPS-UTC offset known to the receiver (only when configured)
+fn time_valid_criterion(criteria: &TestCriteria, data: &GpsData) -> Option<CriterionResult> {
+    criteria.require_valid_time.then(|| CriterionResult {
+        name: "Time Valid".into(),
+        passed: data.time_valid == Some(true),
+        warning: false,
+        expected: "leap seconds valid".into(),
+        actual: match (data.leap_seconds, data.time_valid) {
+            (_, None) => "not reported".into(),
+            (Some(leap), Some(true)) => format!("valid ({} s)", leap),
+            (Some(leap), Some(false)) => format!("default ({} s)", leap),
+            (None, Some(_)) => "no leap seconds".into(),
+        },
+        history: Vec::new(),
+    })
+}
+
+/// 23. Multipath indication from SNR vs elevation (only when configured)
+fn multipath_criterion(criteria: &TestCriteria, score: Option<f32>) -> Option<Scored> {
+    let max_score = criteria.max_multipath_score?;
+    let (passed, warning) = check_max(
+        score.map(f64::from),
+        max_score as f64,
+        criteria.warning_margin("Multipath"),
+    );
+    let result = CriterionResult {
+        name: "Multipath".into(),
+        passed,
+        warning,
+        expected: format!("<= {:.2}", max_score),
+        actual: score.map_or_else(|| "not enough satellites".into(), |s| format!("{:.2}", s)),
+        history: Vec::new(),
+    };
+    Some((result, score.map(f64::from)))
+}
+
+/// Fix dimension: 0 = no fix, 2 = 2D, 3 = 3D.
+/// NMEA GGA quality doesn't distinguish 2D from 3D, so a valid fix with
+/// at least 4 satellites in the solution is treated as 3D.
+fn fix_dimension(data: &GpsData) -> u8 {
+    if data.fix_quality.unwrap_or(0) == 0 {
+        0
+    } else if data.satellites.unwrap_or(0) >= 4 {
+        3
+    } else {
+        2
+    }
+}
+
+/// Display name of a `fix_dimension` value
+fn dimension_name(dimension: u8) -> &'static str {
+    match dimension {
+        3 => "3D",
+        2 => "2D",
+        _ => "no fix",
+    }
+}
+
+/// Great-circle distance in metres between two WGS84 positions (haversine)
+pub fn haversine_m(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    const EARTH_RADIUS_M: f64 = 6_371_000.0;
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * a.sqrt().asin()
+}
+
+/// Check `actual >= limit`, with a warning band `margin_pct` percent below the limit.
+/// Returns (passed, warning); a missing value fails.
+fn check_min(actual: Option<f64>, limit: f64, margin_pct: f32) -> (bool, bool) {
+    match actual {
+        Some(v) if v >= limit => (true, false),
+        Some(v) if v >= limit * (1.0 - margin_pct as f64 / 100.0) => (true, true),
+        _ => (false, false),
+    }
+}
+
+/// True on the first call and then whenever `interval_seconds` have passed since the
+/// last true result, which is recorded in `last`
+fn interval_elapsed(last: &mut Option<Instant>, now: Instant, interval_seconds: u64) -> bool {
+    let due = last.is_none_or(|t| now.duration_since(t).as_secs() >= interval_seconds);
+    if due {
+        *last = Some(now);
+    }
+    due
+}
+
+/// Check `actual <= limit`, with a warning band `margin_pct` percent above the limit.
+/// Returns (passed, warning); a missing value fails.
+fn check_max(actual: Option<f64>, limit: f64, margin_pct: f32) -> (bool, bool) {
+    match actual {
+        Some(v) if v <= limit => (true, false),
+        Some(v) if v <= limit * (1.0 + margin_pct as f64 / 100.0) => (true, true),
+        _ => (false, false),
+    }
+}
+
+/// Rank a fix type string (as produced by the NMEA parser) from worst to best
+fn fix_type_rank(fix_type: &str) -> u8 {
+    match fix_type {
+        "Estimated" | "Manual" | "Simulation" => 1,
+        "GPS" => 2,
+        "DGPS" | "PPS" => 3,
+        "Float RTK" => 4,
+        "RTK" => 5,
+        _ => 0,
+    }
+}
+
+/// Drop samples older than `window` from the front of a time-ordered queue
+fn prune_window<T>(samples: &mut VecDeque<(Instant, T)>, now: Instant, window: Duration) {
+    while samples
+        .front()
+        .is_some_and(|(t, _)| now.duration_since(*t) > window)
+    {
+        samples.pop_front();
+    }
+}
+
+/// Median of a series (mean of the middle two for an even count)
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let n = values.len();
+    (values[(n - 1) / 2] + values[n / 2]) / 2.0
+}
+
+/// Population standard deviation of a series of counts (0 for fewer than 2 samples)
+fn std_dev(samples: &[u32]) -> f64 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let n = samples.len() as f64;
+    let mean = samples.iter().map(|&x| x as f64).sum::<f64>() / n;
+    let variance = samples
+        .iter()
+        .map(|&x| (x as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    variance.sqrt()
+}
+
+/// Calculate average SNR across all satellites with signal
+pub fn calc_avg_snr(satellites: &[crate::nmea::SatelliteInfo]) -> f32 {
+    let with_snr: Vec<f32> = satellites
+        .iter()
+        .filter_map(|s| s.snr)
+        .filter(|&snr| snr > 0.0)
+        .collect();
+
+    if with_snr.is_empty() {
+        0.0
+    } else {
+        with_snr.iter().sum::<f32>() / with_snr.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nmea::SatelliteInfo;
+    use crate::test_support::{self, test_device};
+
+    fn fix_data(fix_quality: u8, satellites: u32) -> GpsData {
+        GpsData {
+            fix_quality: Some(fix_quality),
+            satellites: Some(satellites),
+            ..GpsData::default()
+        }
+    }
+
+    #[test]
+    fn test_fix_dimension() {
+        assert_eq!(fix_dimension(&GpsData::default()), 0);
+        assert_eq!(fix_dimension(&fix_data(0, 8)), 0);
+        assert_eq!(fix_dimension(&fix_data(1, 3)), 2);
+        assert_eq!(fix_dimension(&fix_data(1, 4)), 3);
+    }
+
+    #[test]
+    fn test_ttff_2d_then_3d() {
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        runner.start();
+
+        runner.evaluate(&fix_data(0, 0));
+        assert!(runner.ttff_2d_seconds().is_none());
+        assert!(runner.ttff_3d_seconds().is_none());
+
+        // 2D fix with only 3 satellites
+        runner.evaluate(&fix_data(1, 3));
+        let ttff_2d = runner.ttff_2d_seconds().expect("2D fix should be recorded");
+        assert!(runner.ttff_3d_seconds().is_none());
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+
+        // Upgrade to 3D
+        runner.evaluate(&fix_data(1, 7));
+        let ttff_3d = runner.ttff_3d_seconds().expect("3D fix should be recorded");
+        assert!(ttff_3d > ttff_2d);
+        assert_eq!(runner.ttff_2d_seconds(), Some(ttff_2d));
+
+        let result = runner.get_result(None);
+        assert_eq!(result.ttff_2d_seconds, Some(ttff_2d));
+        assert_eq!(result.ttff_3d_seconds, Some(ttff_3d));
+    }
+
+    #[test]
+    fn test_continuous_mode_never_concludes() {
+        let criteria = TestCriteria {
+            min_satellites: 1,
+            min_strong_satellites: 0,
+            min_constellations: 0,
+            min_avg_snr: 0.0,
+            max_ttff_seconds: 0,
+            stability_duration_seconds: 0,
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.continuous = true;
+        runner.start();
+
+        let data = GpsData {
+            hdop: Some(0.9),
+            pdop: Some(1.5),
+            ..fix_data(1, 8)
+        };
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        for _ in 0..3 {
+            let results = runner.evaluate(&data);
+            assert!(results.iter().all(|r| r.passed));
+        }
+        // Would have passed (zero stability) and timed out (zero TTFF limit) in one-shot mode
+        assert_eq!(runner.verdict, TestVerdict::Running);
+
+        // First snapshot is due immediately, the next only after the interval
+        assert!(runner.monitor_snapshot_due());
+        assert!(!runner.monitor_snapshot_due());
+    }
+
+    #[test]
+    fn test_horizontal_accuracy_criterion() {
+        let criteria = TestCriteria {
+            max_hacc_m: Some(2.5),
+            ..TestCriteria::default()
+        };
+        let hacc = |data: &GpsData| {
+            let mut runner = TestRunner::new(criteria.clone(), test_device());
+            runner.start();
+            runner
+                .evaluate(data)
+                .into_iter()
+                .find(|r| r.name == "Horizontal Accuracy")
+        };
+
+        let mut data = fix_data(1, 10);
+        data.horizontal_accuracy_m = Some(1.85);
+        let result = hacc(&data).unwrap();
+        assert!(result.passed);
+        assert_eq!(result.actual, "1.85 m");
+
+        data.horizontal_accuracy_m = Some(4.0);
+        assert!(!hacc(&data).unwrap().passed);
+
+        // No accuracy estimate from the receiver: criterion skipped
+        data.horizontal_accuracy_m = None;
+        assert!(hacc(&data).is_none());
+    }
+
+    #[test]
+    fn test_active_antenna_criterion() {
+        use crate::antenna::{AntennaPower, AntennaStatus};
+
+        let criteria = TestCriteria {
+            expect_active_antenna: true,
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+        let antenna = |runner: &mut TestRunner, status| {
+            let data = GpsData {
+                antenna_status: Some(status),
+                antenna_power: Some(AntennaPower::On),
+                ..fix_data(1, 8)
+            };
+            let results = runner.evaluate(&data);
+            results.into_iter().find(|r| r.name == "Active Antenna").unwrap()
+        };
+
+        // Supervisor still initialising after a cold start, then sees current drawn
+        assert!(!antenna(&mut runner, AntennaStatus::Init).passed);
+        assert!(antenna(&mut runner, AntennaStatus::Ok).passed);
+        let report = runner.get_result(None).antenna.unwrap();
+        assert_eq!(report.kind, AntennaKind::Active);
+        assert!(!report.mismatch);
+
+        // Passive antenna fitted to an active-antenna SKU
+        let mut runner = TestRunner::new(runner.criteria.clone(), test_device());
+        runner.start();
+        let result = antenna(&mut runner, AntennaStatus::Open);
+        assert!(!result.passed);
+        assert_eq!(result.actual, "passive / none");
+        assert!(runner.get_result(None).antenna.unwrap().mismatch);
+    }
+
+    #[test]
+    fn test_interim_save_due() {
+        let clock = ManualClock::new();
+        let criteria = TestCriteria::default();
+        let mut runner = TestRunner::with_clock(criteria, test_device(), Box::new(clock.clone()));
+        runner.interim_save_interval_seconds = Some(30);
+        assert!(!runner.interim_save_due(), "not due before the test starts");
+
+        runner.start();
+        assert!(runner.interim_save_due());
+        clock.advance(Duration::from_secs(29));
+        assert!(!runner.interim_save_due());
+        clock.advance(Duration::from_secs(1));
+        assert!(runner.interim_save_due());
+
+        runner.abort();
+        clock.advance(Duration::from_secs(60));
+        assert!(!runner.interim_save_due(), "never due once finished");
+
+        let mut off = TestRunner::new(TestCriteria::default(), test_device());
+        off.start();
+        assert!(!off.interim_save_due());
+    }
+
+    #[test]
+    fn test_position_error_against_reference() {
+        // Reference 5m due north of the measured position (1 deg lat ~= 111195 m)
+        let (lat, lon) = (-36.8485, 174.7633);
+        let reference = (lat + 5.0 / 111_195.0, lon);
+        assert!((haversine_m(lat, lon, reference.0, reference.1) - 5.0).abs() < 0.05);
+
+        let data = GpsData {
+            latitude: Some(lat),
+            longitude: Some(lon),
+            ..fix_data(1, 8)
+        };
+        let position_result = |max_error_m: f64| {
+            let criteria = TestCriteria {
+                reference_position: Some(reference),
+                max_position_error_m: max_error_m,
+                ..TestCriteria::default()
+            };
+            let mut runner = TestRunner::new(criteria, test_device());
+            runner.start();
+            runner
+                .evaluate(&data)
+                .into_iter()
+                .find(|r| r.name == "Position Error")
+                .expect("position criterion should be present")
+        };
+
+        let within = position_result(10.0);
+        assert!(within.passed);
+        assert_eq!(within.actual, "5.0 m");
+        assert!(!position_result(3.0).passed);
+
+        // No reference set: criterion skipped entirely
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        runner.start();
+        assert!(runner.evaluate(&data).iter().all(|r| r.name != "Position Error"));
+    }
+
+    #[test]
+    fn test_position_outlier_excluded_from_mean() {
+        let (lat, lon) = (-36.8485, 174.7633);
+        let criteria = TestCriteria {
+            reference_position: Some((lat, lon)),
+            max_position_error_m: 1.0,
+            max_position_outliers: Some(0),
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+
+        let fix = |lat: f64| GpsData {
+            latitude: Some(lat),
+            longitude: Some(lon),
+            ..fix_data(1, 8)
+        };
+        let find = |results: &[CriterionResult], name: &str| {
+            results.iter().find(|r| r.name == name).cloned().unwrap()
+        };
+        for _ in 0..10 {
+            runner.evaluate(&fix(lat));
+        }
+        // One fix ~500 m north among stable ones
+        runner.evaluate(&fix(lat + 500.0 / 111_195.0));
+        let mut results = Vec::new();
+        for _ in 0..10 {
+            results = runner.evaluate(&fix(lat));
+        }
+
+        assert_eq!(runner.get_result(None).position_outliers, 1);
+        // Without the exclusion the jump would pull the mean ~24 m off
+        assert!(find(&results, "Position Error").passed);
+        let outliers = find(&results, "Position Outliers");
+        assert!(!outliers.passed);
+        assert_eq!(outliers.actual, "1");
+    }
+
+    #[test]
+    fn test_fix_downgrade_counted() {
+        let criteria = TestCriteria { max_fix_downgrades: Some(0), ..TestCriteria::default() };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+
+        // No fix while acquiring, then 3D -> 2D -> 3D: only the drop to 2D counts
+        runner.evaluate(&fix_data(0, 0));
+        runner.evaluate(&fix_data(1, 8));
+        runner.evaluate(&fix_data(1, 3));
+        runner.evaluate(&fix_data(1, 3));
+        let results = runner.evaluate(&fix_data(1, 8));
+
+        assert_eq!(runner.get_result(None).fix_downgrades, 1);
+        let downgrades = results.iter().find(|r| r.name == "Fix Downgrades").unwrap();
+        assert!(!downgrades.passed);
+        assert_eq!(downgrades.actual, "1");
+
+        // Losing the fix entirely is a downgrade too
+        runner.evaluate(&fix_data(0, 0));
+        assert_eq!(runner.get_result(None).fix_downgrades, 2);
+    }
+
+    #[test]
+    fn test_unique_prns_cycling_never_enough() {
+        let clock = ManualClock::new();
+        let criteria = TestCriteria {
+            min_unique_prns: Some(8),
+            min_strong_satellites: 0,
+            min_constellations: 0,
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::with_clock(criteria, test_device(), Box::new(clock.clone()));
+        runner.start();
+        let sky = |prns: &[u32]| GpsData {
+            hdop: Some(1.0),
+            pdop: Some(1.5),
+            satellites_info: prns
+                .iter()
+                .map(|&prn| test_support::sat(prn, 45.0, 90.0, 40.0))
+                .collect(),
+            ..fix_data(1, 8)
+        };
+        let unique = |results: &[CriterionResult]| {
+            results.iter().find(|r| r.name == "Unique PRNs").unwrap().clone()
+        };
+
+        // Four satellites at a time, rotating through six: never eight distinct ones
+        for t in 0..30u32 {
+            let prns: Vec<u32> = (0..4).map(|k| (t + k) % 6 + 1).collect();
+            let results = runner.evaluate(&sky(&prns));
+            assert!(!unique(&results).passed);
+            clock.advance(Duration::from_secs(1));
+        }
+        assert_eq!(runner.verdict, TestVerdict::Running);
+        assert_eq!(unique(&runner.evaluate(&sky(&[1]))).actual, "6");
+
+        // Satellites seen before the stability window no longer count
+        clock.advance(Duration::from_secs(11));
+        assert_eq!(unique(&runner.evaluate(&sky(&[20, 21]))).actual, "2");
+    }
+
+    #[test]
+    fn test_fix_age_criterion() {
+        let criteria = TestCriteria {
+            max_fix_age_seconds: Some(1.0),
+            ..TestCriteria::default()
+        };
+        let fix_age = |age: Option<f64>| {
+            let mut runner = TestRunner::new(criteria.clone(), test_device());
+            runner.start();
+            let data = GpsData {
+                fix_age_seconds: age,
+                ..fix_data(1, 8)
+            };
+            runner
+                .evaluate(&data)
+                .into_iter()
+                .find(|r| r.name == "Fix Age")
+                .expect("fix age criterion should be present")
+        };
+
+        assert!(fix_age(Some(0.4)).passed);
+        assert!(!fix_age(Some(3.2)).passed);
+        assert!(!fix_age(None).passed);
+    }
+
+    #[test]
+    fn test_satellite_count_stability() {
+        let criteria = TestCriteria {
+            max_satellite_count_stddev: Some(1.5),
+            ..TestCriteria::default()
+        };
+        let stability = |series: &[u32]| {
+            let mut runner = TestRunner::new(criteria.clone(), test_device());
+            runner.start();
+            let mut results = Vec::new();
+            for &sats in series {
+                results = runner.evaluate(&fix_data(1, sats));
+            }
+            results
+                .into_iter()
+                .find(|r| r.name == "Satellite Count Stability")
+                .expect("stability criterion should be present")
+        };
+
+        // Flaky antenna: count bouncing between 4 and 12
+        let flaky = stability(&[4, 12, 5, 11, 4, 12, 6, 10]);
+        assert!(!flaky.passed);
+        assert!(flaky.actual.contains("(4-12)"));
+
+        // Healthy receiver: small natural variation
+        assert!(stability(&[9, 10, 10, 9, 10, 11, 10, 10]).passed);
+
+        assert_eq!(std_dev(&[7]), 0.0);
+        assert!((std_dev(&[4, 12]) - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hdop_stability_catches_mid_window_spike() {
+        let clock = ManualClock::new();
+        let criteria = TestCriteria {
+            require_stable_hdop: true,
+            stability_duration_seconds: 10,
+            ..TestCriteria::default()
+        };
+        let mut runner =
+            TestRunner::with_clock(criteria, test_device(), Box::new(clock.clone()));
+        runner.start();
+
+        let mut hdop_stability = |hdop: f32| {
+            let data = GpsData { hdop: Some(hdop), ..fix_data(1, 8) };
+            let result = runner
+                .evaluate(&data)
+                .into_iter()
+                .find(|r| r.name == "HDOP Stability")
+                .expect("HDOP stability criterion should be present");
+            clock.advance(Duration::from_secs(1));
+            result
+        };
+
+        for _ in 0..4 {
+            assert!(hdop_stability(0.9).passed);
+        }
+
+        // Spike at t=4, then back to a great HDOP
+        let spike = hdop_stability(4.5);
+        assert!(!spike.passed);
+        assert_eq!(spike.actual, "worst 4.5");
+
+        // Still failing while the spike is inside the 10s window
+        for _ in 5..=14 {
+            let result = hdop_stability(0.9);
+            assert!(!result.passed);
+            assert_eq!(result.actual, "worst 4.5");
+        }
+
+        // Spike has aged out of the window
+        let recovered = hdop_stability(0.9);
+        assert!(recovered.passed);
+        assert_eq!(recovered.actual, "worst 0.9");
+    }
+
+    #[test]
+    fn test_fix_time_must_advance() {
+        let clock = ManualClock::new();
+        let criteria = TestCriteria {
+            require_advancing_time: true,
+            stability_duration_seconds: 10,
+            ..TestCriteria::default()
+        };
+        let mut runner =
+            TestRunner::with_clock(criteria, test_device(), Box::new(clock.clone()));
+        runner.start();
+
+        let mut fix_time = |timestamp: &str| {
+            let data = GpsData { timestamp: Some(timestamp.into()), ..fix_data(1, 8) };
+            let result = runner
+                .evaluate(&data)
+                .into_iter()
+                .find(|r| r.name == "Fix Time Advancing")
+                .expect("fix time criterion should be present");
+            clock.advance(Duration::from_secs(1));
+            result
+        };
+
+        assert!(fix_time("12:00:00").passed);
+        assert!(fix_time("12:00:01").passed);
+        assert!(fix_time("12:00:02").passed);
+
+        // Frozen time output: fine for a missed sentence, fails once it lasts
+        for _ in 0..3 {
+            assert!(fix_time("12:00:02").passed);
+        }
+        let stuck = fix_time("12:00:02");
+        assert!(!stuck.passed);
+        assert_eq!(stuck.actual, "stuck at 12:00:02 for 4s");
+
+        // Going backwards fails even though the time moves again
+        assert!(fix_time("12:00:03").passed);
+        assert_eq!(fix_time("11:59:50").actual, "went backwards");
+        assert!(!fix_time("11:59:51").passed);
+
+        // Midnight rollover is not a regression
+        let criteria = TestCriteria { require_advancing_time: true, ..TestCriteria::default() };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+        for timestamp in ["23:59:59", "00:00:00"] {
+            runner.evaluate(&GpsData { timestamp: Some(timestamp.into()), ..fix_data(1, 8) });
+        }
+        assert!(runner.fix_time_regressed_at.is_none());
+    }
+
+    #[test]
+    fn test_constellation_acquisition_order() {
+        let clock = ManualClock::new();
+        let mut runner = TestRunner::with_clock(
+            TestCriteria::default(),
+            test_device(),
+            Box::new(clock.clone()),
+        );
+        runner.start();
+
+        let sat = |prn: u32, constellation: &str, snr: Option<f32>| SatelliteInfo {
+            snr,
+            constellation: constellation.into(),
+            used_in_fix: false,
+            ..test_support::sat(prn, 45.0, 90.0, 0.0)
+        };
+        // GPS at 2s, Galileo in view without signal until 5s, GLONASS at 9s
+        let epochs = [
+            (0, vec![]),
+            (2, vec![sat(5, "GPS", Some(31.0)), sat(301, "Galileo", None)]),
+            (5, vec![sat(5, "GPS", Some(33.0)), sat(301, "Galileo", Some(28.0))]),
+            (9, vec![sat(12, "GPS", Some(35.0)), sat(70, "GLONASS", Some(25.0))]),
+        ];
+        let mut elapsed = 0;
+        for (at, satellites_info) in epochs {
+            clock.advance(Duration::from_secs(at - elapsed));
+            elapsed = at;
+            runner.evaluate(&GpsData { satellites_info, ..GpsData::default() });
+        }
+
+        let timeline: Vec<(String, f64)> = runner
+            .get_result(None)
+            .constellation_acquisition
+            .into_iter()
+            .map(|a| (a.constellation, a.seconds))
+            .collect();
+        assert_eq!(
+            timeline,
+            [("GPS".into(), 2.0), ("Galileo".into(), 5.0), ("GLONASS".into(), 9.0)]
+        );
+    }
+
+    #[test]
+    fn test_required_constellations() {
+        let sat = |prn: u32, constellation: &str| SatelliteInfo {
+            constellation: constellation.into(),
+            ..test_support::sat(prn, 45.0, 90.0, 40.0)
+        };
+        let data = GpsData {
+            satellites_info: vec![sat(5, "GPS"), sat(12, "GPS"), sat(70, "GLONASS")],
+            ..fix_data(1, 8)
+        };
+        let criteria = TestCriteria {
+            required_constellations: vec!["GPS".into(), "Galileo".into()],
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+        let results = runner.evaluate(&data);
+
+        // Two constellations satisfy the count, but not the required set
+        let count = results.iter().find(|r| r.name == "Constellations").unwrap();
+        assert!(count.passed);
+        let required = results
+            .iter()
+            .find(|r| r.name == "Required Constellations")
+            .unwrap();
+        assert!(!required.passed);
+        assert_eq!(required.actual, "missing Galileo");
+
+        runner.criteria.required_constellations = vec!["gps".into(), "GLONASS".into()];
+        let results = runner.evaluate(&data);
+        assert!(results
+            .iter()
+            .any(|r| r.name == "Required Constellations" && r.passed));
+    }
+
+    #[test]
+    fn test_best_satellite_snr_needs_a_peak() {
+        let sat = |prn: u32, snr: f32| test_support::sat(prn, 45.0, 90.0, snr);
+        // Plenty of mediocre satellites: the average passes, but nothing reaches 40 dB
+        let mediocre = GpsData {
+            satellites_info: (1..=8).map(|prn| sat(prn, 34.0)).collect(),
+            ..fix_data(1, 8)
+        };
+        let criteria = TestCriteria {
+            min_best_satellite_snr: Some(40.0),
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+
+        let results = runner.evaluate(&mediocre);
+        assert!(results.iter().any(|r| r.name == "Average SNR" && r.passed));
+        let best = results.iter().find(|r| r.name == "Best Satellite SNR").unwrap();
+        assert!(!best.passed);
+        assert_eq!(best.actual, "34.0 dB");
+
+        let mut with_peak = mediocre.clone();
+        with_peak.satellites_info.push(sat(9, 44.0));
+        let results = runner.evaluate(&with_peak);
+        assert!(results.iter().any(|r| r.name == "Best Satellite SNR" && r.passed));
+    }
+
+    #[test]
+    fn test_collected_fixes_capped() {
+        let criteria = TestCriteria {
+            max_collected_fixes: Some(3),
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+        for second in 0..5 {
+            let data = GpsData {
+                timestamp: Some(format!("12:00:0{}", second)),
+                ..fix_data(1, 8)
+            };
+            // Evaluated twice per fix: the repeat is not collected again
+            runner.evaluate(&data);
+            runner.evaluate(&data);
+        }
+
+        let report = runner.get_report(None);
+        let times: Vec<_> = report.collected_fixes.iter().map(|d| d.timestamp.clone()).collect();
+        assert_eq!(times, ["12:00:00", "12:00:01", "12:00:02"].map(|t| Some(t.to_string())));
+        assert!(runner.get_result(None).collected_fixes.is_empty());
+
+        // Off by default
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        runner.start();
+        runner.evaluate(&fix_data(1, 8));
+        assert!(runner.get_report(None).collected_fixes.is_empty());
+    }
+
+    #[test]
+    fn test_criteria_bounds_rejected() {
+        let valid = TestCriteria {
+            min_satellites: 32,
+            max_hdop: 0.5,
+            min_avg_snr: 60.0,
+            max_multipath_score: Some(1.0),
+            ..TestCriteria::default()
+        };
+        assert_eq!(valid.validate(), Ok(()));
+
+        let cases: Vec<(&str, TestCriteria)> = vec![
+            ("min_satellites", TestCriteria { min_satellites: 0, ..valid.clone() }),
+            ("min_satellites", TestCriteria { min_satellites: 33, ..valid.clone() }),
+            ("min_strong_satellites", TestCriteria { min_strong_satellites: 100, ..valid.clone() }),
+            ("max_hdop", TestCriteria { max_hdop: 0.4, ..valid.clone() }),
+            ("max_hdop", TestCriteria { max_hdop: -1.0, ..valid.clone() }),
+            ("max_hdop", TestCriteria { max_hdop: f32::NAN, ..valid.clone() }),
+            ("max_pdop", TestCriteria { max_pdop: 50.5, ..valid.clone() }),
+            ("min_avg_snr", TestCriteria { min_avg_snr: 60.5, ..valid.clone() }),
+            ("min_avg_snr", TestCriteria { min_avg_snr: -5.0, ..valid.clone() }),
+            (
+                "min_best_satellite_snr",
+                TestCriteria { min_best_satellite_snr: Some(99.0), ..valid.clone() },
+            ),
+            (
+                "max_multipath_score",
+                TestCriteria { max_multipath_score: Some(1.5), ..valid.clone() },
+            ),
+            (
+                "strong_sat_min_elevation_deg",
+                TestCriteria { strong_sat_min_elevation_deg: 91.0, ..valid.clone() },
+            ),
+            ("max_ttff_seconds", TestCriteria { max_ttff_seconds: 0, ..valid.clone() }),
+            ("min_constellations", TestCriteria { min_constellations: 7, ..valid.clone() }),
+            ("min_fix_quality", TestCriteria { min_fix_quality: 9, ..valid.clone() }),
+            (
+                "stability_duration_seconds",
+                TestCriteria { stability_duration_seconds: 3601, ..valid.clone() },
+            ),
+            (
+                "reference_position latitude",
+                TestCriteria { reference_position: Some((91.0, 0.0)), ..valid.clone() },
+            ),
+            ("warning_margin_pct", TestCriteria { warning_margin_pct: 150.0, ..valid.clone() }),
+            (
+                "warning_margins[HDOP]",
+                TestCriteria {
+                    warning_margins: HashMap::from([("HDOP".to_string(), -1.0)]),
+                    ..valid.clone()
+                },
+            ),
+            (
+                "baseline_tolerance_pct",
+                TestCriteria { baseline_tolerance_pct: 101.0, ..valid.clone() },
+            ),
+        ];
+        for (field, criteria) in cases {
+            let error = criteria.validate().expect_err(field);
+            assert_eq!(error.field, field);
+        }
+
+        let error = TestCriteria { min_satellites: 100, ..valid }.validate().unwrap_err();
+        assert_eq!(error.to_string(), "min_satellites must be 1-32, got 100");
+    }
+
+    #[test]
+    fn test_suspect_receiver_date() {
+        let criteria = TestCriteria {
+            reject_suspect_date: true,
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+
+        let rolled_over = GpsData {
+            utc_date: Some("2006-03-05".into()),
+            date_suspect: Some(true),
+            ..fix_data(1, 8)
+        };
+        let results = runner.evaluate(&rolled_over);
+        let date = results.iter().find(|r| r.name == "Receiver Date").unwrap();
+        assert!(!date.passed);
+        assert_eq!(date.actual, "2006-03-05");
+
+        // No date reported yet is not a failure
+        let results = runner.evaluate(&fix_data(1, 8));
+        assert!(results.iter().any(|r| r.name == "Receiver Date" && r.passed));
+    }
+
+    #[test]
+    fn test_strong_sat_elevation_mask() {
+        let sat = |prn: u32, elevation: Option<f32>| SatelliteInfo {
+            elevation,
+            ..test_support::sat(prn, 0.0, 90.0, 45.0)
+        };
+        let data = GpsData {
+            satellites_info: vec![sat(5, Some(60.0)), sat(12, Some(35.0)), sat(24, Some(5.0))],
+            ..fix_data(1, 8)
+        };
+        let strong = |mask: f32| {
+            let criteria = TestCriteria {
+                strong_sat_min_elevation_deg: mask,
+                ..TestCriteria::default()
+            };
+            let mut runner = TestRunner::new(criteria, test_device());
+            runner.start();
+            runner
+                .evaluate(&data)
+                .into_iter()
+                .find(|r| r.name == "Strong Sats (SNR>=30)")
+                .unwrap()
+        };
+
+        // No mask: the 5° multipath-prone satellite still counts
+        assert_eq!(strong(0.0).actual, "3");
+        // 15° mask: high SNR but low elevation is excluded
+        let masked = strong(15.0);
+        assert_eq!(masked.actual, "2");
+        assert_eq!(masked.expected, ">= 4 (elev >= 15°)");
+    }
+
+    #[test]
+    fn test_select_profile_by_product() {
+        let min_sats = |min_satellites| TestCriteria {
+            min_satellites,
+            ..TestCriteria::default()
+        };
+        let profiles = CriteriaProfiles::from([
+            ("NEO".to_string(), min_sats(5)),
+            ("NEO-M8N".to_string(), min_sats(8)),
+            ("MediaTek".to_string(), min_sats(4)),
+        ]);
+        let device = |manufacturer: Option<&str>, product: Option<&str>| DeviceInfo {
+            manufacturer: manufacturer.map(Into::into),
+            product: product.map(Into::into),
+            ..test_device()
+        };
+
+        // Longest matching key wins, case-insensitively
+        let m8n = device(Some("u-blox AG"), Some("u-blox neo-m8n GNSS"));
+        let (name, criteria) = select_profile(&profiles, &m8n).unwrap();
+        assert_eq!(name, "NEO-M8N");
+        assert_eq!(criteria.min_satellites, 8);
+
+        // Falls back to the manufacturer string
+        let mediatek = device(Some("MediaTek Inc."), Some("GPS"));
+        let (name, _) = select_profile(&profiles, &mediatek).unwrap();
+        assert_eq!(name, "MediaTek");
+
+        // Equal-length keys: the alphabetically first wins, whatever the map order
+        let mut profiles = profiles;
+        profiles.insert("GNSS".to_string(), min_sats(6));
+        profiles.insert("U-BL".to_string(), min_sats(7));
+        let (name, _) = select_profile(&profiles, &device(None, Some("u-blox GNSS"))).unwrap();
+        assert_eq!(name, "GNSS");
+
+        // No match: caller uses the default criteria
+        assert!(select_profile(&profiles, &device(Some("Prolific"), Some("USB-Serial"))).is_none());
+        assert!(select_profile(&profiles, &test_device()).is_none());
+    }
+
+    #[test]
+    fn test_gsv_watch_needs_fix_without_satellite_data() {
+        let start = Instant::now();
+        let at = |s: u64| start + Duration::from_secs(s);
+        let data = fix_data(1, 9);
+
+        let mut watch = GsvWatch::default();
+        assert!(!watch.observe(&fix_data(0, 0), false, at(60)), "no fix is not a GSV problem");
+        assert!(!watch.observe(&data, true, at(0)));
+        assert!(!watch.observe(&data, true, at(GSV_MISSING_SECONDS - 1)));
+        assert!(watch.observe(&data, true, at(GSV_MISSING_SECONDS)));
+        assert!(watch.missing());
+
+        let with_gsv = GpsData {
+            satellites_info: vec![test_support::sat(5, 45.0, 90.0, 30.0)],
+            ..data
+        };
+        assert!(!watch.observe(&with_gsv, true, at(GSV_MISSING_SECONDS + 1)));
+        assert!(!watch.missing());
+    }
+
+    #[test]
+    fn test_gsv_disabled_warns_but_no_satellites_fails() {
+        let clock = ManualClock::new();
+        let mut runner =
+            TestRunner::with_clock(TestCriteria::default(), test_device(), Box::new(clock.clone()));
+        let avg_snr = |results: &[CriterionResult]| {
+            results.iter().find(|r| r.name == "Average SNR").unwrap().clone()
+        };
+
+        // No satellites visible: no fix and no GSV is a genuine failure
+        runner.start();
+        for _ in 0..8 {
+            clock.advance(Duration::from_secs(1));
+            runner.evaluate(&fix_data(0, 0));
+        }
+        assert!(!avg_snr(&runner.last_criteria_results).passed);
+        assert!(!runner.get_result(None).gsv_missing);
+
+        // GSV not enabled: a fix with 9 satellites in use, but no per-satellite data
+        runner.start();
+        let data = GpsData { hdop: Some(0.9), pdop: Some(1.5), ..fix_data(1, 9) };
+        clock.advance(Duration::from_secs(1));
+        runner.evaluate(&data);
+        assert!(!avg_snr(&runner.last_criteria_results).passed, "fails until the grace period");
+        clock.advance(Duration::from_secs(GSV_MISSING_SECONDS));
+        let snr = avg_snr(&runner.evaluate(&data));
+        assert!(snr.passed && snr.warning);
+        assert_eq!(snr.actual, "no GSV output");
+        assert!(runner.get_result(None).gsv_missing);
+
+        // Once GSV arrives the criteria are judged normally again
+        let with_gsv = GpsData {
+            satellites_info: vec![test_support::sat(5, 45.0, 90.0, 12.0)],
+            ..data
+        };
+        clock.advance(Duration::from_secs(1));
+        assert!(!avg_snr(&runner.evaluate(&with_gsv)).passed);
+        assert!(!runner.get_result(None).gsv_missing);
+    }
+
+    #[test]
+    fn test_weak_signal_hint_and_early_timeout() {
+        let clock = ManualClock::new();
+        let criteria = TestCriteria { weak_signal_timeout_seconds: Some(60), ..Default::default() };
+        let mut runner = TestRunner::with_clock(criteria, test_device(), Box::new(clock.clone()));
+        let sat = |prn: u32, snr: Option<f32>| SatelliteInfo {
+            snr,
+            used_in_fix: false,
+            ..test_support::sat(prn, 40.0, 120.0, 0.0)
+        };
+        // Indoors: satellites listed in GSV, a few faintly tracked, no fix
+        let indoor = GpsData {
+            satellites_info: vec![sat(3, Some(14.0)), sat(8, Some(11.0)), sat(17, None)],
+            ..fix_data(0, 0)
+        };
+
+        runner.start();
+        for _ in 0..=WEAK_SIGNAL_SECONDS {
+            clock.advance(Duration::from_secs(1));
+            runner.evaluate(&indoor);
+        }
+        assert_eq!(runner.get_result(None).hint.as_deref(), Some(WEAK_SIGNAL_HINT));
+
+        // One usable satellite means it may yet get a fix
+        let mut outdoor = indoor.clone();
+        outdoor.satellites_info.push(sat(22, Some(38.0)));
+        clock.advance(Duration::from_secs(1));
+        runner.evaluate(&outdoor);
+        assert_eq!(runner.get_result(None).hint, None);
+
+        // Weak again: the test ends after 60 s of it, long before the 190 s timeout
+        for _ in 0..60 {
+            clock.advance(Duration::from_secs(1));
+            runner.evaluate(&indoor);
+        }
+        assert_eq!(runner.verdict, TestVerdict::Running);
+        clock.advance(Duration::from_secs(1));
+        runner.evaluate(&indoor);
+        assert_eq!(runner.verdict, TestVerdict::TimedOut);
+        assert!(runner.verdict_summary().starts_with("Timed out: weak signal"));
+    }
+
+    #[test]
+    fn test_adhoc_criteria_leave_stored_criteria_unchanged() {
+        let stored = TestCriteria::default();
+        let profiles = CriteriaProfiles::from([(
+            "NEO-M8N".to_string(),
+            TestCriteria { min_satellites: 8, ..TestCriteria::default() },
+        )]);
+        let m8n = DeviceInfo {
+            product: Some("u-blox NEO-M8N".into()),
+            ..test_device()
+        };
+
+        let (profile, criteria) = criteria_for_run(&profiles, &stored, &m8n, None);
+        assert_eq!(profile.as_deref(), Some("NEO-M8N"));
+        assert_eq!(criteria.min_satellites, 8);
+
+        // Tighten one threshold for this unit only; it also takes precedence over the profile
+        let adhoc = TestCriteria { max_hdop: 1.0, ..stored.clone() };
+        let (profile, criteria) = criteria_for_run(&profiles, &stored, &m8n, Some(adhoc));
+        assert_eq!(profile, None);
+
+        let mut runner = TestRunner::new(criteria, m8n);
+        runner.criteria_override = true;
+        runner.start();
+        let data = GpsData {
+            hdop: Some(1.5),
+            ..fix_data(1, 8)
+        };
+        runner.evaluate(&data);
+        let result = runner.get_result(Some(&data));
+
+        assert!(result.criteria_override);
+        assert_eq!(result.criteria.as_ref().map(|c| c.max_hdop), Some(1.0));
+        let hdop = result.criteria_results.iter().find(|r| r.name == "HDOP").unwrap();
+        assert!(!hdop.passed);
+    }
+
+    #[test]
+    fn test_baseline_match_within_and_outside_tolerance() {
+        let golden = GpsData {
+            hdop: Some(0.8),
+            satellites_info: (1..=4)
+                .map(|prn| test_support::sat(prn, 45.0, 90.0 * prn as f32, 40.0))
+                .collect(),
+            ..fix_data(1, 12)
+        };
+        let golden_result = TestResult {
+            best_gps_data: Some(golden.clone()),
+            ..TestRunner::new(TestCriteria::default(), test_device()).get_result(None)
+        };
+        let baseline = Baseline::from_result(&golden_result).unwrap();
+        assert_eq!((baseline.satellites, baseline.avg_snr, baseline.hdop), (12, 40.0, 0.8));
+
+        let criteria = TestCriteria {
+            baseline_name: Some("golden-1".into()),
+            baseline_tolerance_pct: 10.0,
+            ..TestCriteria::default()
+        };
+        let evaluate = |data: &GpsData| {
+            let mut runner = TestRunner::new(criteria.clone(), test_device());
+            runner.baseline = Some(baseline.clone());
+            runner.start();
+            runner
+                .evaluate(data)
+                .into_iter()
+                .find(|r| r.name == "Baseline Match")
+                .unwrap()
+        };
+
+        // 11 sats, SNR 37 and HDOP 0.85 are all within 10% of the golden unit
+        let mut close = golden.clone();
+        close.satellites = Some(11);
+        close.hdop = Some(0.85);
+        for sat in &mut close.satellites_info {
+            sat.snr = Some(37.0);
+        }
+        let result = evaluate(&close);
+        assert!(result.passed && !result.warning, "{}", result.actual);
+        assert_eq!(result.expected, "within 10% of 'golden-1'");
+
+        // A unit that would pass the absolute thresholds but lags the golden unit
+        let lagging = GpsData {
+            hdop: Some(1.5),
+            ..fix_data(1, 9)
+        };
+        let result = evaluate(&lagging);
+        assert!(!result.passed);
+        assert!(result.actual.starts_with("sats 9/12"));
+
+        // Without a loaded baseline the criterion is not evaluated
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+        assert!(runner.evaluate(&close).iter().all(|r| r.name != "Baseline Match"));
+    }
+
+    #[derive(Deserialize)]
+    struct RecordedSample {
+        t: f64,
+        data: GpsData,
+    }
+
+    fn recorded_session() -> Vec<(f64, GpsData)> {
+        include_str!("../tests/fixtures/recorded_session.jsonl")
+            .lines()
+            .map(|line| {
+                let sample: RecordedSample = serde_json::from_str(line).unwrap();
+                (sample.t, sample.data)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_recorded_session_is_deterministic() {
+        // No fix for 5s, then a good fix with one HDOP spike at t=8 that
+        // restarts the 10s stability timer, so the pass lands at exactly t=19
+        for _ in 0..2 {
+            let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+            assert_eq!(runner.replay(recorded_session()), TestVerdict::Pass);
+            assert_eq!(runner.ttff_seconds(), Some(5.0));
+            assert_eq!(runner.elapsed_seconds(), 19.0);
+        }
+    }
+
+    #[test]
+    fn test_replay_times_out_without_fix() {
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        // Default total timeout is 3 * 60s TTFF + 10s stability = 190s
+        let samples = (0..=200).map(|t| (t as f64, fix_data(0, 0)));
+        assert_eq!(runner.replay(samples), TestVerdict::TimedOut);
+    }
+
+    #[test]
+    fn test_completion_reported_once() {
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        assert_eq!(runner.replay(recorded_session()), TestVerdict::Pass);
+        assert!(runner.take_completion());
+
+        // Ticks after the verdict must not trigger another save
+        runner.evaluate(&fix_data(1, 8));
+        assert!(!runner.take_completion());
+
+        // Once saved, the finished run stays saved until the next start
+        runner.mark_saved("/tmp/report.json");
+        assert_eq!(runner.saved_report(), Some("/tmp/report.json"));
+        runner.start();
+        assert_eq!(runner.saved_report(), None);
+        runner.mark_saved("/tmp/snapshot.json");
+        assert_eq!(runner.saved_report(), None);
+
+        // An aborted run is not a completion
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        runner.start();
+        runner.evaluate(&fix_data(0, 0));
+        runner.abort();
+        runner.evaluate(&fix_data(0, 0));
+        assert!(!runner.take_completion());
+    }
+
+    #[test]
+    fn test_paused_time_not_counted_toward_timeout() {
+        let clock = ManualClock::new();
+        let mut runner = TestRunner::with_clock(
+            TestCriteria::default(),
+            test_device(),
+            Box::new(clock.clone()),
+        );
+        runner.start();
+        let no_fix = fix_data(0, 0);
+
+        // Default total timeout is 190s; pause for 10s at 185s
+        clock.advance(Duration::from_secs(185));
+        runner.evaluate(&no_fix);
+        assert!(runner.pause());
+        assert!(!runner.pause());
+        assert!(runner.get_result(None).paused);
+        clock.advance(Duration::from_secs(10));
+        runner.evaluate(&no_fix);
+        assert_eq!(runner.elapsed_seconds(), 185.0);
+        assert!(runner.resume());
+        runner.evaluate(&no_fix);
+        assert_eq!(runner.verdict, TestVerdict::Running);
+        assert_eq!(runner.elapsed_seconds(), 185.0);
+
+        clock.advance(Duration::from_secs(6));
+        runner.evaluate(&no_fix);
+        assert_eq!(runner.verdict, TestVerdict::TimedOut);
+        assert!(!runner.resume());
+    }
+
+    #[test]
+    fn test_manual_clock_drives_stability() {
+        let clock = ManualClock::new();
+        let criteria = TestCriteria {
+            min_strong_satellites: 0,
+            min_avg_snr: 0.0,
+            min_constellations: 0,
+            ..TestCriteria::default()
+        };
+        let mut runner =
+            TestRunner::with_clock(criteria, test_device(), Box::new(clock.clone()));
+        runner.start();
+
+        let data = GpsData {
+            hdop: Some(1.0),
+            pdop: Some(1.5),
+            ..fix_data(1, 8)
+        };
+        runner.evaluate(&data);
+        clock.advance(Duration::from_secs(9));
+        runner.evaluate(&data);
+        assert_eq!(runner.verdict, TestVerdict::Running);
+        clock.advance(Duration::from_secs(1));
+        runner.evaluate(&data);
+        assert_eq!(runner.verdict, TestVerdict::Pass);
+    }
+
+    #[test]
+    fn test_rtk_fixed_requires_fixed_not_float() {
+        let criteria = TestCriteria {
+            require_rtk_fixed: true,
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+
+        let mut rtk = None;
+        for (quality, fix_type) in [(1, "GPS"), (2, "DGPS"), (5, "Float RTK"), (5, "Float RTK")] {
+            let data = GpsData {
+                fix_type: Some(fix_type.into()),
+                ..fix_data(quality, 10)
+            };
+            rtk = runner.evaluate(&data).into_iter().find(|r| r.name == "RTK Fixed");
+        }
+
+        let rtk = rtk.expect("RTK criterion should be present");
+        assert!(!rtk.passed);
+        assert_eq!(rtk.actual, "Float RTK");
+        let result = runner.get_result(None);
+        assert_eq!(result.best_fix_type.as_deref(), Some("Float RTK"));
+
+        // Reaching RTK fixed passes and becomes the best fix type
+        let data = GpsData {
+            fix_type: Some("RTK".into()),
+            ..fix_data(4, 10)
+        };
+        let results = runner.evaluate(&data);
+        assert!(results.iter().find(|r| r.name == "RTK Fixed").unwrap().passed);
+        assert_eq!(runner.get_result(None).best_fix_type.as_deref(), Some("RTK"));
+    }
+
+    #[test]
+    fn test_snapshot_matches_runner_on_single_fix_criteria() {
+        let sat = |prn: u32, snr: f32, constellation: &str| SatelliteInfo {
+            constellation: constellation.into(),
+            ..test_support::sat(prn, 45.0, 90.0, snr)
+        };
+        let data = GpsData {
+            latitude: Some(-36.8485),
+            longitude: Some(174.7633),
+            hdop: Some(0.9),
+            pdop: Some(1.6),
+            fix_type: Some("GPS".into()),
+            horizontal_accuracy_m: Some(2.5),
+            satellites_info: vec![
+                sat(5, 42.0, "GPS"),
+                sat(12, 38.0, "GPS"),
+                sat(70, 31.0, "GLONASS"),
+            ],
+            ..fix_data(1, 9)
+        };
+        let criteria = TestCriteria {
+            reference_position: Some((-36.8485, 174.7634)),
+            require_rtk_fixed: true,
+            max_hacc_m: Some(5.0),
+            required_constellations: vec!["Galileo".into()],
+            min_best_satellite_snr: Some(40.0),
+            expect_active_antenna: true,
+            max_position_outliers: Some(0),
+            max_fix_downgrades: Some(0),
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria.clone(), test_device());
+        runner.start();
+        let from_runner = runner.evaluate(&data);
+
+        let snapshot = evaluate_snapshot(&criteria, &data);
+        // Same as a first tick, in the same order, minus the criteria that need history
+        let stateful = [
+            "Time to First Fix",
+            "Active Antenna",
+            "Position Outliers",
+            "Fix Downgrades",
+        ];
+        let expected: Vec<_> = from_runner
+            .iter()
+            .filter(|r| !stateful.contains(&r.name.as_str()))
+            .collect();
+        assert_eq!(snapshot.len(), expected.len());
+        for (preview, run) in snapshot.iter().zip(expected) {
+            assert_eq!(preview.name, run.name);
+            assert_eq!(preview.passed, run.passed, "{}", run.name);
+            assert_eq!(preview.warning, run.warning, "{}", run.name);
+            assert_eq!(preview.expected, run.expected);
+            if run.name != "Constellations" {
+                assert_eq!(preview.actual, run.actual);
+            }
+        }
+
+        // Stateless: evaluating again gives the same verdicts
+        let again = evaluate_snapshot(&criteria, &data);
+        assert!(again.iter().zip(&snapshot).all(|(a, b)| a.passed == b.passed));
+    }
+
+    #[test]
+    fn test_snapshot_position_needs_a_fix() {
+        let criteria = TestCriteria {
+            reference_position: Some((-36.8485, 174.7633)),
+            ..TestCriteria::default()
+        };
+        let positioned = |fix_quality: u8| GpsData {
+            latitude: Some(-36.8485),
+            longitude: Some(174.7633),
+            ..fix_data(fix_quality, 8)
+        };
+        let error = |data: &GpsData| {
+            evaluate_snapshot(&criteria, data)
+                .into_iter()
+                .find(|r| r.name == "Position Error")
+                .unwrap()
+        };
+
+        let with_fix = error(&positioned(1));
+        assert!(with_fix.passed);
+        assert_eq!(with_fix.actual, "0.0 m");
+        // A stale position without a fix is not judged
+        let no_fix = error(&positioned(0));
+        assert!(!no_fix.passed);
+        assert_eq!(no_fix.actual, "-");
+
+        // Default criteria only produce the always-on checks
+        let names: Vec<_> = evaluate_snapshot(&TestCriteria::default(), &positioned(1))
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Satellite Count",
+                "HDOP",
+                "PDOP",
+                "Average SNR",
+                "Strong Sats (SNR>=30)",
+                "Constellations",
+                "Fix Quality"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_criterion_history_is_bounded_and_opt_in() {
+        let hdop_data = |hdop: f32| GpsData {
+            hdop: Some(hdop),
+            ..fix_data(1, 8)
+        };
+
+        // Off by default: nothing recorded, nothing serialized
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        runner.start();
+        let results = runner.evaluate(&hdop_data(1.0));
+        assert!(results.iter().all(|r| r.history.is_empty()));
+        assert!(!serde_json::to_string(&results).unwrap().contains("history"));
+
+        let criteria = TestCriteria {
+            criterion_history_len: 3,
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+        let mut results = Vec::new();
+        for hdop in [2.5, 2.0, 1.5, 1.2, 1.0] {
+            results = runner.evaluate(&hdop_data(hdop));
+        }
+        let hdop = results.iter().find(|r| r.name == "HDOP").unwrap();
+        assert_eq!(hdop.history, vec![1.5, 1.2, 1.0]);
+        let sats = results.iter().find(|r| r.name == "Satellite Count").unwrap();
+        assert_eq!(sats.history, vec![8.0, 8.0, 8.0]);
+        // Non-numeric criteria carry no history
+        let ttff = results.iter().find(|r| r.name == "Time to First Fix").unwrap();
+        assert!(ttff.history.is_empty());
+    }
+
+    #[test]
+    fn test_warning_band() {
+        assert_eq!(check_max(Some(2.0), 2.0, 10.0), (true, false));
+        assert_eq!(check_max(Some(2.1), 2.0, 10.0), (true, true));
+        assert_eq!(check_max(Some(2.3), 2.0, 10.0), (false, false));
+        assert_eq!(check_max(Some(2.1), 2.0, 0.0), (false, false));
+        assert_eq!(check_max(None, 2.0, 10.0), (false, false));
+        assert_eq!(check_min(Some(5.5), 6.0, 10.0), (true, true));
+        assert_eq!(check_min(Some(5.0), 6.0, 10.0), (false, false));
+    }
+
+    #[test]
+    fn test_pass_with_warnings_verdict() {
+        let mut margins = HashMap::new();
+        margins.insert("HDOP".to_string(), 10.0);
+        let criteria = TestCriteria {
+            min_satellites: 1,
+            min_strong_satellites: 0,
+            min_constellations: 0,
+            min_avg_snr: 0.0,
+            stability_duration_seconds: 0,
+            warning_margins: margins,
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, test_device());
+        runner.start();
+
+        // HDOP 2.1 against a 2.0 limit is within the 10% HDOP margin
+        let data = GpsData {
+            hdop: Some(2.1),
+            pdop: Some(2.5),
+            ..fix_data(1, 8)
+        };
+        let results = runner.evaluate(&data);
+        let hdop = results.iter().find(|r| r.name == "HDOP").unwrap();
+        assert!(hdop.passed && hdop.warning);
+        assert_eq!(runner.verdict, TestVerdict::PassWithWarnings);
+
+        // The global margin is still zero, so PDOP gets no warning band
+        let mut runner = TestRunner::new(runner.criteria.clone(), test_device());
+        runner.start();
+        runner.evaluate(&GpsData {
+            pdop: Some(3.1),
+            ..data
+        });
+        assert_eq!(runner.verdict, TestVerdict::Running);
+    }
+
+    #[test]
+    fn test_verdict_summary() {
+        let mut runner = TestRunner::new(TestCriteria::default(), test_device());
+        assert_eq!(runner.verdict_summary(), "Not started");
+
+        runner.start();
+        runner.evaluate(&GpsData {
+            hdop: Some(1.0),
+            pdop: Some(5.0),
+            ..fix_data(1, 8)
+        });
+        assert_eq!(runner.get_result(None).verdict_summary, "Running");
+
+        runner.verdict = TestVerdict::Fail;
+        let summary = runner.verdict_summary();
+        assert!(summary.starts_with("Failed: PDOP, Average SNR"), "{}", summary);
+        assert!(!summary.contains("HDOP"));
+
+        runner.verdict = TestVerdict::TimedOut;
+        assert!(runner.verdict_summary().starts_with("Timed out: no fix"));
+
+        runner.abort();
+        assert_eq!(runner.verdict_summary(), "Aborted by operator");
     }
 }
@@ -1,9 +1,27 @@
 // Test report generation - saves JSON per test for factory traceability
 
-use crate::test_criteria::TestResult;
+use crate::atomic_file::write_atomic;
+use crate::test_criteria::{TestResult, TestRunner, TestVerdict, INTERRUPTED_SUMMARY};
+use crate::ubx_optimizer::OptimizationReport;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-/// Save a test result as a JSON file
+/// Rolling log of every completed result, one JSON object per line
+pub const NDJSON_FILE: &str = "results.ndjson";
+
+/// Serializes NDJSON appends within the process
+static NDJSON_LOCK: Mutex<()> = Mutex::new(());
+
+/// Outcome of checking a saved report
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportValidation {
+    pub valid: bool,
+    pub issues: Vec<String>,
+}
+
+/// Save a test result as a JSON file. Written via a temp file and rename, so a crash
+/// mid-write never leaves a truncated report for `load_recent` to trip over.
 pub fn save_report(result: &TestResult, output_dir: &Path) -> Result<PathBuf, std::io::Error> {
     // Ensure output directory exists
     std::fs::create_dir_all(output_dir)?;
@@ -22,12 +40,410 @@ pub fn save_report(result: &TestResult, output_dir: &Path) -> Result<PathBuf, st
     let json = serde_json::to_string_pretty(result)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
 
-    std::fs::write(&path, json)?;
+    write_atomic(&path, json)?;
     log::info!("Test report saved to: {}", path.display());
 
     Ok(path)
 }
 
+/// Suffix of interim reports written while a test runs
+const PARTIAL_SUFFIX: &str = ".partial.json";
+
+/// Interim report path for a test starting now on the given device
+pub fn partial_report_path(serial_number: Option<&str>, output_dir: &Path) -> PathBuf {
+    let ts = chrono::Utc::now().to_rfc3339().replace([':', '.'], "-");
+    let serial = serial_number.unwrap_or("unknown");
+    output_dir.join(format!("gps-test_{}_{}{}", serial, ts, PARTIAL_SUFFIX))
+}
+
+/// Overwrite the interim report for a running test. Written to a temp file and
+/// renamed so a crash mid-write leaves the previous interim report intact.
+pub fn save_partial_report(result: &TestResult, path: &Path) -> Result<(), std::io::Error> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(result).map_err(std::io::Error::other)?;
+    write_atomic(path, json)
+}
+
+/// Turn interim reports left behind by a crash into final reports with an
+/// `Interrupted` verdict, removing the `.partial.json` files. Returns the recovered results.
+pub fn recover_interrupted_reports(output_dir: &Path) -> Vec<TestResult> {
+    let Ok(entries) = std::fs::read_dir(output_dir) else {
+        return Vec::new();
+    };
+    let mut partials: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.to_string_lossy().ends_with(PARTIAL_SUFFIX))
+        .collect();
+    partials.sort();
+
+    let mut recovered = Vec::new();
+    for path in partials {
+        let result = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str::<TestResult>(&json).map_err(|e| e.to_string()));
+        let mut result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("Failed to read interim report {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        result.verdict = TestVerdict::Interrupted;
+        result.verdict_summary = INTERRUPTED_SUMMARY.into();
+
+        match save_report(&result, output_dir) {
+            Ok(_) => {
+                log::warn!("Recovered interrupted test from {}", path.display());
+                if let Err(e) = std::fs::remove_file(&path) {
+                    log::warn!("Failed to remove {}: {}", path.display(), e);
+                }
+                recovered.push(result);
+            }
+            Err(e) => log::warn!("Failed to finalize {}: {}", path.display(), e),
+        }
+    }
+    recovered
+}
+
+/// Cross-reference an optimization and the test run that verifies it, so the archive
+/// shows which tune produced which result. Re-save the report afterwards.
+pub fn link_verification(report: &mut OptimizationReport, runner: &mut TestRunner) {
+    report.related_test_id = Some(runner.id.clone());
+    runner.related_optimization_id = Some(report.id.clone());
+}
+
+/// Save an optimization (before/after) report as a JSON file
+pub fn save_optimization_report_json(
+    report: &OptimizationReport,
+    output_dir: &Path,
+) -> Result<PathBuf, std::io::Error> {
+    std::fs::create_dir_all(output_dir)?;
+
+    // Chip name may contain spaces/parentheses, e.g. "u-blox (HW: 00040007)"
+    let chip: String = report
+        .chip_info
+        .chip_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+
+    let ts = report.timestamp.replace([':', '.'], "-");
+    let filename = format!("gps-optimize_{}_{}.json", chip, ts);
+    let path = output_dir.join(filename);
+
+    let json = serde_json::to_string_pretty(report).map_err(std::io::Error::other)?;
+
+    write_atomic(&path, json)?;
+    log::info!("Optimization report saved to: {}", path.display());
+
+    Ok(path)
+}
+
+/// Append a continuous-monitoring snapshot as one JSON line to the device's monitor log
+pub fn append_monitor_snapshot(result: &TestResult, output_dir: &Path) -> Result<PathBuf, std::io::Error> {
+    use std::io::Write;
+
+    std::fs::create_dir_all(output_dir)?;
+
+    let serial = result
+        .device_info
+        .serial_number
+        .as_deref()
+        .unwrap_or("unknown");
+
+    // One log file per device per day
+    let date = chrono::Utc::now().format("%Y-%m-%d");
+    let filename = format!("gps-monitor_{}_{}.jsonl", serial, date);
+    let path = output_dir.join(filename);
+
+    let json = serde_json::to_string(result).map_err(std::io::Error::other)?;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", json)?;
+
+    Ok(path)
+}
+
+/// Append a completed result as one line to `results.ndjson`, for log shippers.
+/// The line goes out in a single write to an append-mode file, under a process-wide
+/// lock, so concurrent appends never interleave partial lines.
+pub fn append_ndjson(result: &TestResult, output_dir: &Path) -> Result<PathBuf, std::io::Error> {
+    use std::io::Write;
+
+    std::fs::create_dir_all(output_dir)?;
+    let path = output_dir.join(NDJSON_FILE);
+
+    let mut line = serde_json::to_string(result).map_err(std::io::Error::other)?;
+    line.push('\n');
+
+    let _guard = NDJSON_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    file.write_all(line.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Load a saved test report and check it is complete and internally consistent
+pub fn validate_report(path: &Path) -> Result<ReportValidation, std::io::Error> {
+    let json = std::fs::read_to_string(path)?;
+    let issues = match serde_json::from_str::<TestResult>(&json) {
+        Ok(result) => check_report(&result),
+        // Missing required fields, wrong types and truncation all land here
+        Err(e) => vec![format!("Not a valid test report: {}", e)],
+    };
+    Ok(ReportValidation { valid: issues.is_empty(), issues })
+}
+
+/// Invariants every saved `TestResult` should hold
+pub fn check_report(result: &TestResult) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    let timings = [
+        ("TTFF", result.ttff_seconds),
+        ("2D TTFF", result.ttff_2d_seconds),
+        ("3D TTFF", result.ttff_3d_seconds),
+        ("Test duration", Some(result.test_duration_seconds)),
+    ];
+    for (name, value) in timings {
+        if let Some(v) = value.filter(|v| !v.is_finite() || *v < 0.0) {
+            issues.push(format!("{} is negative or not a number: {}", name, v));
+        }
+    }
+    if let Some(ttff) = result.ttff_seconds {
+        if ttff > result.test_duration_seconds {
+            issues.push(format!(
+                "TTFF {:.1}s exceeds the test duration {:.1}s",
+                ttff, result.test_duration_seconds
+            ));
+        }
+    }
+    if let (Some(ttff_2d), Some(ttff_3d)) = (result.ttff_2d_seconds, result.ttff_3d_seconds) {
+        if ttff_2d > ttff_3d {
+            issues.push(format!("2D TTFF {:.1}s is later than 3D TTFF {:.1}s", ttff_2d, ttff_3d));
+        }
+    }
+
+    if chrono::DateTime::parse_from_rfc3339(&result.timestamp).is_err() {
+        issues.push(format!("Timestamp is not RFC 3339: {:?}", result.timestamp));
+    }
+
+    for criterion in result.criteria_results.iter().filter(|r| r.warning && !r.passed) {
+        issues.push(format!("Criterion '{}' is marked both failed and warning", criterion.name));
+    }
+
+    let failed: Vec<&str> = result
+        .criteria_results
+        .iter()
+        .filter(|r| !r.passed)
+        .map(|r| r.name.as_str())
+        .collect();
+    let any_warning = result.criteria_results.iter().any(|r| r.passed && r.warning);
+    match result.verdict {
+        TestVerdict::Pass | TestVerdict::PassWithWarnings => {
+            if result.criteria_results.is_empty() {
+                issues.push("Passing verdict with no criteria results".to_string());
+            }
+            if !failed.is_empty() {
+                issues.push(format!("Passing verdict but criteria failed: {}", failed.join(", ")));
+            }
+            if result.ttff_seconds.is_none() {
+                issues.push("Passing verdict without a TTFF".to_string());
+            }
+            if result.verdict == TestVerdict::Pass && any_warning {
+                issues.push("Verdict is Pass but criteria passed with warnings".to_string());
+            }
+            if result.verdict == TestVerdict::PassWithWarnings && !any_warning {
+                issues.push(
+                    "Verdict is PassWithWarnings but no criterion has a warning".to_string(),
+                );
+            }
+        }
+        TestVerdict::TimedOut => {
+            if result.ttff_seconds.is_some() {
+                issues.push("Verdict is TimedOut but a TTFF was recorded".to_string());
+            }
+        }
+        // Aborted tests fail regardless of criteria; in-progress snapshots can be saved too
+        TestVerdict::Fail
+        | TestVerdict::Running
+        | TestVerdict::NotStarted
+        | TestVerdict::Interrupted => {}
+    }
+
+    issues
+}
+
+/// Characters per line on a 2-inch label
+const LABEL_WIDTH: usize = 24;
+/// ZPL layout: left margin, first line and line pitch in dots (203 dpi), font height
+const ZPL_MARGIN: usize = 20;
+const ZPL_LINE_PITCH: usize = 40;
+const ZPL_FONT_HEIGHT: usize = 30;
+
+/// Unit label output: plain fixed-width text, or the same lines as a ZPL job
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelFormat {
+    #[default]
+    Text,
+    Zpl,
+}
+
+/// Pass/fail label for a unit: serial, verdict, TTFF, satellites and test date, one
+/// `LABEL_WIDTH` line each with the value right-aligned (long values are cut)
+pub fn format_label(result: &TestResult, format: LabelFormat) -> String {
+    let verdict = match result.verdict {
+        TestVerdict::Pass => "PASS",
+        TestVerdict::PassWithWarnings => "PASS (WARN)",
+        TestVerdict::Fail => "FAIL",
+        TestVerdict::TimedOut => "TIMED OUT",
+        TestVerdict::Running => "RUNNING",
+        TestVerdict::NotStarted => "NOT RUN",
+        TestVerdict::Interrupted => "INTERRUPTED",
+    };
+    let fields = [
+        ("SN", result.device_info.serial_number.clone().unwrap_or_else(|| "-".into())),
+        ("RESULT", verdict.to_string()),
+        ("TTFF", result.ttff_seconds.map_or_else(|| "-".into(), |t| format!("{:.1}s", t))),
+        (
+            "SATS",
+            result
+                .best_gps_data
+                .as_ref()
+                .and_then(|d| d.satellites)
+                .map_or_else(|| "-".into(), |n| n.to_string()),
+        ),
+        (
+            "DATE",
+            chrono::DateTime::parse_from_rfc3339(&result.timestamp)
+                .map_or_else(|_| "-".into(), |t| t.format("%Y-%m-%d").to_string()),
+        ),
+    ];
+
+    let mut lines = vec![format!("{:^width$}", "GPS TEST", width = LABEL_WIDTH)];
+    for (name, value) in fields {
+        let room = LABEL_WIDTH - name.len() - 2;
+        let value: String = value.chars().take(room).collect();
+        lines.push(format!("{}: {:>room$}", name, value, room = room));
+    }
+
+    match format {
+        LabelFormat::Text => lines.join("\n") + "\n",
+        LabelFormat::Zpl => {
+            let mut zpl = format!("^XA\n^CF0,{}\n", ZPL_FONT_HEIGHT);
+            for (i, line) in lines.iter().enumerate() {
+                // ^ and ~ start ZPL commands, so they can't appear in field data
+                let text = line.replace(['^', '~'], " ");
+                let y = ZPL_MARGIN + i * ZPL_LINE_PITCH;
+                zpl.push_str(&format!("^FO{},{}^FD{}^FS\n", ZPL_MARGIN, y, text));
+            }
+            zpl + "^XZ\n"
+        }
+    }
+}
+
+/// Name of the manifest written at the root of a results archive
+pub const ARCHIVE_MANIFEST: &str = "manifest.json";
+
+/// A results archive written by `export_results_archive`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultsArchive {
+    pub path: PathBuf,
+    /// Test and optimization reports included
+    pub reports: usize,
+    /// Every file in the archive except the manifest, in archive order
+    pub entries: Vec<String>,
+}
+
+/// Verdict of a saved test report, or None for anything else (optimization reports)
+fn report_verdict(path: &Path) -> Result<Option<TestVerdict>, std::io::Error> {
+    let report: serde_json::Value = serde_json::from_slice(&std::fs::read(path)?)?;
+    Ok(report.get("verdict").and_then(|v| serde_json::from_value(v.clone()).ok()))
+}
+
+/// Collect the saved JSON reports in `results_dir` into one ZIP at `dest`, with a
+/// manifest listing them. `verdict` keeps only the test reports with that verdict.
+/// `include_extras` also adds the NDJSON log, monitor logs and any CSV or HTML
+/// reports, unfiltered. Partial reports from a run still in progress are left out.
+pub fn export_results_archive(
+    results_dir: &Path,
+    dest: &Path,
+    include_extras: bool,
+    verdict: Option<&TestVerdict>,
+) -> Result<ResultsArchive, std::io::Error> {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    let mut reports = Vec::new();
+    let mut extras = Vec::new();
+    for entry in std::fs::read_dir(results_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.ends_with(".json") && !name.ends_with(PARTIAL_SUFFIX) {
+            if let Some(verdict) = verdict {
+                // Unreadable reports can't be matched, so a filtered export skips them
+                if report_verdict(&entry.path()).ok().flatten().as_ref() != Some(verdict) {
+                    continue;
+                }
+            }
+            reports.push(name);
+        } else if include_extras
+            && [".ndjson", ".jsonl", ".csv", ".html"].iter().any(|ext| name.ends_with(ext))
+        {
+            extras.push(name);
+        }
+    }
+    reports.sort();
+    extras.sort();
+
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let mut manifest_entries = Vec::new();
+    for name in reports.iter().chain(&extras) {
+        let contents = std::fs::read(results_dir.join(name))?;
+        zip.start_file(name.as_str(), options).map_err(std::io::Error::other)?;
+        zip.write_all(&contents)?;
+        manifest_entries.push(serde_json::json!({
+            "name": name,
+            "bytes": contents.len(),
+            "report": reports.contains(name),
+        }));
+    }
+
+    let manifest = serde_json::json!({
+        "created": chrono::Utc::now().to_rfc3339(),
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "results_dir": results_dir.display().to_string(),
+        "reports": reports.len(),
+        "verdict": verdict,
+        "entries": manifest_entries,
+    });
+    let manifest = serde_json::to_string_pretty(&manifest).map_err(std::io::Error::other)?;
+    zip.start_file(ARCHIVE_MANIFEST, options).map_err(std::io::Error::other)?;
+    zip.write_all(manifest.as_bytes())?;
+    let bytes = zip.finish().map_err(std::io::Error::other)?.into_inner();
+
+    write_atomic(dest, bytes)?;
+    log::info!("Archived {} reports to {}", reports.len(), dest.display());
+
+    Ok(ResultsArchive {
+        path: dest.to_path_buf(),
+        reports: reports.len(),
+        entries: reports.into_iter().chain(extras).collect(),
+    })
+}
+
 /// Get the default results directory
 pub fn default_results_dir() -> PathBuf {
     let home = std::env::var("HOME")
@@ -35,3 +451,284 @@ pub fn default_results_dir() -> PathBuf {
         .unwrap_or_else(|_| ".".to_string());
     PathBuf::from(home).join("gps-studio-results")
 }
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nmea::GpsData;
+    use crate::test_criteria::{DeviceInfo, TestCriteria, TestRunner};
+    use crate::test_support::{temp_dir, test_device};
+
+    fn passing_result() -> TestResult {
+        let device = DeviceInfo {
+            serial_number: Some("VALIDATE01".into()),
+            ..test_device()
+        };
+        let criteria = TestCriteria {
+            min_satellites: 1,
+            min_strong_satellites: 0,
+            min_constellations: 0,
+            min_avg_snr: 0.0,
+            stability_duration_seconds: 0,
+            ..TestCriteria::default()
+        };
+        let mut runner = TestRunner::new(criteria, device);
+        runner.start();
+        let data = GpsData {
+            fix_quality: Some(1),
+            satellites: Some(8),
+            hdop: Some(0.9),
+            pdop: Some(1.5),
+            ..GpsData::default()
+        };
+        runner.evaluate(&data);
+        runner.get_result(Some(&data))
+    }
+
+    #[test]
+    fn test_label_snapshot() {
+        let mut result = passing_result();
+        result.ttff_seconds = Some(12.34);
+        result.timestamp = "2026-03-04T05:06:07+00:00".into();
+
+        assert_eq!(
+            format_label(&result, LabelFormat::Text),
+            "        GPS TEST        \n\
+             SN:           VALIDATE01\n\
+             RESULT:             PASS\n\
+             TTFF:              12.3s\n\
+             SATS:                  8\n\
+             DATE:         2026-03-04\n"
+        );
+
+        let zpl = format_label(&result, LabelFormat::Zpl);
+        assert!(zpl.starts_with("^XA\n"));
+        assert!(zpl.ends_with("^XZ\n"));
+        assert!(zpl.contains("^FO20,60^FDSN:           VALIDATE01^FS"));
+
+        // Long serials are cut to the label width
+        result.device_info.serial_number = Some("ABCDEFGHIJKLMNOPQRSTUVWXYZ".into());
+        let text = format_label(&result, LabelFormat::Text);
+        assert!(text.lines().all(|l| l.chars().count() == LABEL_WIDTH));
+    }
+
+    #[test]
+    fn test_validate_saved_report() {
+        let result = passing_result();
+        assert_eq!(result.verdict, TestVerdict::Pass);
+
+        let dir = temp_dir("validate-ok");
+        let path = save_report(&result, &dir).unwrap();
+        let validation = validate_report(&path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert!(validation.valid, "unexpected issues: {:?}", validation.issues);
+        assert!(validation.issues.is_empty());
+    }
+
+    #[test]
+    fn test_recover_interrupted_reports() {
+        let dir = temp_dir("recover");
+        let mut running = passing_result();
+        running.verdict = TestVerdict::Running;
+
+        // Saved twice: the second write overwrites the first
+        let path = partial_report_path(Some("SOAK01"), &dir);
+        save_partial_report(&running, &path).unwrap();
+        save_partial_report(&running, &path).unwrap();
+        std::fs::write(dir.join("gps-test_bad_x.partial.json"), "{ truncated").unwrap();
+
+        let recovered = recover_interrupted_reports(&dir);
+        let names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].verdict, TestVerdict::Interrupted);
+        // The good partial became a final report; the unreadable one is left for inspection
+        assert!(!names.iter().any(|n| n.contains("SOAK01") && n.ends_with(PARTIAL_SUFFIX)));
+        assert!(names
+            .iter()
+            .any(|n| n.starts_with("gps-test_VALIDATE01_") && n.ends_with(".json")));
+        assert!(names.contains(&"gps-test_bad_x.partial.json".to_string()));
+    }
+
+    #[test]
+    fn test_export_results_archive_entries() {
+        let dir = temp_dir("archive");
+        let report = save_report(&passing_result(), &dir).unwrap();
+        let mut failed = passing_result();
+        failed.verdict = TestVerdict::Fail;
+        failed.device_info.serial_number = Some("FAILED01".into());
+        let failed_report = save_report(&failed, &dir).unwrap();
+        append_ndjson(&passing_result(), &dir).unwrap();
+        std::fs::write(dir.join("gps-test_soak.partial.json"), "{}").unwrap();
+        std::fs::write(dir.join("summary.csv"), "serial,verdict\n").unwrap();
+        let report_name = report.file_name().unwrap().to_string_lossy().into_owned();
+        let failed_name = failed_report.file_name().unwrap().to_string_lossy().into_owned();
+
+        let dest = dir.join("export.zip");
+        let archive = export_results_archive(&dir, &dest, false, None).unwrap();
+        assert_eq!(archive.reports, 2);
+        let fails = export_results_archive(&dir, &dest, false, Some(&TestVerdict::Fail)).unwrap();
+        assert_eq!(fails.reports, 1);
+        assert_eq!(fails.entries, vec![failed_name]);
+        let passes = export_results_archive(&dir, &dest, false, Some(&TestVerdict::Pass)).unwrap();
+        assert_eq!(passes.entries, vec![report_name.clone()]);
+        std::fs::remove_file(&failed_report).unwrap();
+
+        let with_extras = export_results_archive(&dir, &dest, true, None).unwrap();
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(&dest).unwrap()).unwrap();
+        let mut names: Vec<String> = zip.file_names().map(String::from).collect();
+        names.sort();
+        let manifest: serde_json::Value =
+            serde_json::from_reader(zip.by_name(ARCHIVE_MANIFEST).unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        // The partial report and the archive itself are never included
+        let expected = [report_name.as_str(), ARCHIVE_MANIFEST, NDJSON_FILE, "summary.csv"];
+        assert_eq!(names, expected);
+        assert_eq!(with_extras.entries.len(), 3);
+        assert_eq!(manifest["reports"], 1);
+        assert_eq!(manifest["entries"][0]["name"], report_name);
+        assert_eq!(manifest["entries"][0]["report"], true);
+    }
+
+    #[test]
+    fn test_concurrent_ndjson_appends_stay_whole() {
+        let dir = temp_dir("ndjson");
+        let result = passing_result();
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..10 {
+                        append_ndjson(&result, &dir).unwrap();
+                    }
+                });
+            }
+        });
+        let contents = std::fs::read_to_string(dir.join(NDJSON_FILE)).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 80);
+        for line in lines {
+            let parsed: TestResult = serde_json::from_str(line).unwrap();
+            assert_eq!(parsed.verdict, TestVerdict::Pass);
+        }
+    }
+
+    #[test]
+    fn test_validate_corrupted_report() {
+        // Hand-edited: passing verdict over a failed criterion, with a negative TTFF
+        let mut result = passing_result();
+        result.criteria_results[0].passed = false;
+        result.ttff_seconds = Some(-3.0);
+        let issues = check_report(&result);
+        assert!(issues.iter().any(|i| i.starts_with("Passing verdict but criteria failed")));
+        assert!(issues.iter().any(|i| i.starts_with("TTFF is negative")));
+
+        // Truncated on a crash
+        let dir = temp_dir("validate-bad");
+        let path = save_report(&passing_result(), &dir).unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, &json[..json.len() / 2]).unwrap();
+        let validation = validate_report(&path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert!(!validation.valid);
+        assert_eq!(validation.issues.len(), 1);
+        assert!(validation.issues[0].starts_with("Not a valid test report"));
+    }
+
+    #[test]
+    fn test_verification_test_linked_to_optimization() {
+        use crate::test_criteria::CriterionResult;
+        use crate::ubx_config::{UbloxChipInfo, UbloxSeries};
+
+        let dir = temp_dir("link");
+        let mut report = OptimizationReport {
+            id: uuid::Uuid::new_v4().to_string(),
+            related_test_id: None,
+            chip_info: UbloxChipInfo {
+                sw_version: "ROM CORE 3.01 (107888)".into(),
+                hw_version: "00080000".into(),
+                extensions: vec![],
+                series: UbloxSeries::Series8,
+                chip_name: "u-blox M8".into(),
+            },
+            profile_applied: "M8".into(),
+            before: Default::default(),
+            after: Default::default(),
+            hdop_improvement_pct: 0.0,
+            satellite_improvement_pct: 0.0,
+            snr_improvement_pct: 0.0,
+            constellation_improvement: 0,
+            timestamp: "2026-03-04T05:06:07+00:00".into(),
+            suspected_clone: false,
+            clone_indicators: vec![],
+            clone_check: CriterionResult {
+                name: "Clone Check".into(),
+                passed: true,
+                warning: false,
+                expected: String::new(),
+                actual: String::new(),
+                history: Vec::new(),
+            },
+        };
+        let path = save_optimization_report_json(&report, &dir).unwrap();
+
+        let mut runner = TestRunner::new(TestCriteria::default(), passing_result().device_info);
+        link_verification(&mut report, &mut runner);
+        let resaved = save_optimization_report_json(&report, &dir).unwrap();
+        assert_eq!(resaved, path, "the linked report replaces the original");
+
+        let saved: OptimizationReport =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let result = runner.get_result(None);
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_ne!(result.id, report.id);
+        assert_eq!(saved.related_test_id.as_deref(), Some(result.id.as_str()));
+        assert_eq!(result.related_optimization_id.as_deref(), Some(report.id.as_str()));
+    }
+
+    #[test]
+    fn test_report_carries_identified_chip() {
+        use crate::ubx_optimizer::UbxOptimizer;
+
+        // MON-VER of an M10, read twice so the optimizer accepts it
+        let mut payload = vec![0u8; 70];
+        payload[0..13].copy_from_slice(b"ROM SPG 5.10 ");
+        payload[30..38].copy_from_slice(b"000A0000");
+        payload[40..52].copy_from_slice(b"MOD=MAX-M10S");
+        let mut optimizer = UbxOptimizer::new();
+        optimizer.start();
+        optimizer.on_mon_ver_response(&payload);
+        optimizer.on_mon_ver_response(&payload);
+        assert!(optimizer.chip_info.is_some());
+
+        let mut runner = TestRunner::new(TestCriteria::default(), passing_result().device_info);
+        runner.chip_info = optimizer.chip_info.clone();
+        runner.start();
+        let dir = temp_dir("chip-info");
+        let path = save_report(&runner.get_report(None), &dir).unwrap();
+        let saved: TestResult =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let chip = saved.chip_info.unwrap();
+        assert_eq!(chip.chip_name, "MAX-M10S");
+        assert_eq!(chip.hw_version, "000A0000");
+
+        // Reports from before chip info was recorded still load
+        let mut old = serde_json::to_value(passing_result()).unwrap();
+        old.as_object_mut().unwrap().remove("chip_info");
+        let old: TestResult = serde_json::from_value(old).unwrap();
+        assert!(old.chip_info.is_none());
+    }
+}
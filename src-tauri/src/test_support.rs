@@ -0,0 +1,42 @@
+// Fixtures shared by the unit tests of several modules
+
+use crate::nmea::SatelliteInfo;
+use crate::test_criteria::DeviceInfo;
+use std::path::PathBuf;
+
+/// An empty scratch directory for one test, named after it and this process so
+/// parallel tests and concurrent runs don't collide
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gps-studio-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A USB receiver with no identifying strings; tests that need some fill them in
+/// with a struct update
+pub fn test_device() -> DeviceInfo {
+    DeviceInfo {
+        port_name: "/dev/ttyTEST".into(),
+        port_type: "USB".into(),
+        manufacturer: None,
+        product: None,
+        serial_number: None,
+        vid: None,
+        pid: None,
+        chip_id: None,
+    }
+}
+
+/// A GPS satellite used in the fix; other constellations, a missing field or an
+/// unused satellite are struct updates on this
+pub fn sat(prn: u32, elevation: f32, azimuth: f32, snr: f32) -> SatelliteInfo {
+    SatelliteInfo {
+        prn,
+        elevation: Some(elevation),
+        azimuth: Some(azimuth),
+        snr: Some(snr),
+        constellation: "GPS".into(),
+        used_in_fix: true,
+    }
+}
@@ -0,0 +1,282 @@
+// Cold-start TTFF repeatability benchmark for u-blox receivers
+//
+// Each iteration cold starts the receiver (UBX-CFG-RST clearing ephemeris, almanac,
+// position and time), waits for the fix to drop so a fix left over from before the
+// reset is not counted, then times the first fix from when the reset was sent. The
+// spread over several iterations shows whether a unit acquires consistently; a single
+// TTFF can be lucky or unlucky with the sky.
+//
+// An iteration without a fix within `FIX_TIMEOUT_SECONDS` is counted as a timeout and
+// left out of the statistics, as is one whose reset the receiver ignored after earlier
+// resets worked. The whole run stops at `MAX_BENCHMARK_SECONDS`, or when cancelled,
+// returning the TTFFs measured so far.
+
+use crate::gps::{GpsError, GpsManager};
+use crate::nmea::GpsData;
+use crate::ubx_config;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Most cold starts one benchmark may run
+pub const MAX_BENCHMARK_ITERATIONS: u32 = 50;
+/// Longest a whole benchmark may run
+pub const MAX_BENCHMARK_SECONDS: u64 = 3600;
+/// Longest one iteration waits for a fix; u-blox specify about 30 s under open sky
+const FIX_TIMEOUT_SECONDS: u64 = 300;
+/// How long the fix may survive the reset before the reset is taken to have failed
+const FIX_DROP_SECONDS: u64 = 5;
+/// How often the latest fix is checked
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Waits of a benchmark run, shortened by the tests
+struct Timing {
+    fix_drop: Duration,
+    fix_timeout: Duration,
+    run_limit: Duration,
+    poll: Duration,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            fix_drop: Duration::from_secs(FIX_DROP_SECONDS),
+            fix_timeout: Duration::from_secs(FIX_TIMEOUT_SECONDS),
+            run_limit: Duration::from_secs(MAX_BENCHMARK_SECONDS),
+            poll: POLL_INTERVAL,
+        }
+    }
+}
+
+/// Spread of the measured TTFFs, in seconds
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TtffStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Sample standard deviation (0 for a single TTFF)
+    pub stddev: f64,
+}
+
+/// Outcome of a benchmark run
+#[derive(Debug, Clone, Serialize)]
+pub struct TtffBenchmark {
+    /// Iterations asked for
+    pub iterations: u32,
+    /// TTFF of each iteration that got a fix, in order
+    pub ttffs_seconds: Vec<f64>,
+    /// Iterations without a fix within the per-iteration timeout
+    pub timeouts: u32,
+    /// Iterations after the first whose reset the receiver ignored, keeping its fix
+    pub cold_starts_ignored: u32,
+    /// None if no iteration got a fix
+    pub stats: Option<TtffStats>,
+    /// Stopped early by `cancel_ttff_benchmark`
+    pub aborted: bool,
+    /// Stopped early at `MAX_BENCHMARK_SECONDS`
+    pub time_limit_reached: bool,
+}
+
+/// Min, max, mean and sample standard deviation, or None for an empty list
+pub fn ttff_stats(ttffs: &[f64]) -> Option<TtffStats> {
+    let n = ttffs.len();
+    if n == 0 {
+        return None;
+    }
+    let mean = ttffs.iter().sum::<f64>() / n as f64;
+    let variance = if n > 1 {
+        ttffs.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+    } else {
+        0.0
+    };
+    Some(TtffStats {
+        min: ttffs.iter().copied().fold(f64::INFINITY, f64::min),
+        max: ttffs.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        mean,
+        stddev: variance.sqrt(),
+    })
+}
+
+fn has_fix(data: &GpsData) -> bool {
+    data.fix_quality.unwrap_or(0) > 0
+}
+
+/// How a wait for the fix ended
+enum Wait {
+    Done,
+    TimedOut,
+    Cancelled,
+}
+
+/// Poll until `done` holds for the latest data, `deadline` passes or `cancel` is set
+fn wait_for(
+    latest: &impl Fn() -> GpsData,
+    deadline: Instant,
+    cancel: &AtomicBool,
+    poll: Duration,
+    done: impl Fn(&GpsData) -> bool,
+) -> Wait {
+    loop {
+        if cancel.load(Ordering::SeqCst) {
+            return Wait::Cancelled;
+        }
+        if done(&latest()) {
+            return Wait::Done;
+        }
+        if Instant::now() >= deadline {
+            return Wait::TimedOut;
+        }
+        thread::sleep(poll);
+    }
+}
+
+/// Cold start the connected receiver `iterations` times, timing each first fix.
+/// Fails if the receiver isn't a confirmed u-blox or keeps its fix through the first
+/// reset; a later ignored reset only leaves that iteration out.
+pub fn run_ttff_benchmark(
+    gps: &GpsManager,
+    iterations: u32,
+    cancel: &AtomicBool,
+) -> Result<TtffBenchmark, GpsError> {
+    benchmark(
+        iterations,
+        cancel,
+        &Timing::default(),
+        || gps.send_commands(&[ubx_config::build_cfg_rst_cold()]),
+        || gps.get_data(),
+    )
+}
+
+/// `run_ttff_benchmark` against any receiver: `cold_start` resets it and `latest`
+/// returns its latest data
+fn benchmark(
+    iterations: u32,
+    cancel: &AtomicBool,
+    timing: &Timing,
+    mut cold_start: impl FnMut() -> Result<(), GpsError>,
+    latest: impl Fn() -> GpsData,
+) -> Result<TtffBenchmark, GpsError> {
+    let run_deadline = Instant::now() + timing.run_limit;
+    let mut benchmark = TtffBenchmark {
+        iterations,
+        ttffs_seconds: Vec::new(),
+        timeouts: 0,
+        cold_starts_ignored: 0,
+        stats: None,
+        aborted: false,
+        time_limit_reached: false,
+    };
+
+    for iteration in 1..=iterations {
+        let reset_at = Instant::now();
+        cold_start()?;
+
+        let drop_deadline = (reset_at + timing.fix_drop).min(run_deadline);
+        let dropped = wait_for(&latest, drop_deadline, cancel, timing.poll, |d| !has_fix(d));
+        let fixed = match dropped {
+            Wait::Done => {
+                let deadline = (reset_at + timing.fix_timeout).min(run_deadline);
+                wait_for(&latest, deadline, cancel, timing.poll, has_fix)
+            }
+            // Still fixed after the reset: the receiver didn't restart. On the first
+            // iteration it likely never will; later, keep what was measured
+            Wait::TimedOut if Instant::now() < run_deadline => {
+                if iteration == 1 {
+                    return Err(GpsError::ColdStartIgnored);
+                }
+                log::warn!("TTFF benchmark {}/{}: reset ignored", iteration, iterations);
+                benchmark.cold_starts_ignored += 1;
+                continue;
+            }
+            other => other,
+        };
+
+        match fixed {
+            Wait::Done => {
+                let ttff = reset_at.elapsed().as_secs_f64();
+                log::info!("TTFF benchmark {}/{}: {:.1}s", iteration, iterations, ttff);
+                benchmark.ttffs_seconds.push(ttff);
+            }
+            Wait::TimedOut if Instant::now() >= run_deadline => {
+                benchmark.time_limit_reached = true;
+                break;
+            }
+            Wait::TimedOut => {
+                log::warn!("TTFF benchmark {}/{}: no fix", iteration, iterations);
+                benchmark.timeouts += 1;
+            }
+            Wait::Cancelled => {
+                benchmark.aborted = true;
+                break;
+            }
+        }
+    }
+
+    benchmark.stats = ttff_stats(&benchmark.ttffs_seconds);
+    Ok(benchmark)
+}
+
+// ============ Tests ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quick_timing() -> Timing {
+        Timing {
+            fix_drop: Duration::from_millis(50),
+            fix_timeout: Duration::from_millis(500),
+            run_limit: Duration::from_secs(10),
+            poll: Duration::from_millis(1),
+        }
+    }
+
+    /// A receiver that loses its fix on a reset and gets it back 20 ms later, except
+    /// on the resets listed in `ignored` (1-based), which it ignores
+    fn run_against(iterations: u32, ignored: &[u32]) -> Result<TtffBenchmark, GpsError> {
+        let state = std::sync::Mutex::new((0u32, Instant::now()));
+        let cancel = AtomicBool::new(false);
+        benchmark(
+            iterations,
+            &cancel,
+            &quick_timing(),
+            || {
+                let mut state = state.lock().unwrap();
+                *state = (state.0 + 1, Instant::now());
+                Ok(())
+            },
+            || {
+                let (resets, reset_at) = *state.lock().unwrap();
+                let fixed = ignored.contains(&resets)
+                    || reset_at.elapsed() >= Duration::from_millis(20);
+                GpsData { fix_quality: Some(fixed as u8), ..GpsData::default() }
+            },
+        )
+    }
+
+    #[test]
+    fn test_ignored_cold_start_excluded_after_first_iteration() {
+        let benchmark = run_against(3, &[2]).unwrap();
+        assert_eq!(benchmark.ttffs_seconds.len(), 2);
+        assert_eq!(benchmark.cold_starts_ignored, 1);
+        assert_eq!(benchmark.timeouts, 0);
+        assert!(benchmark.stats.is_some());
+
+        // Ignored from the start: the receiver isn't resetting at all
+        assert!(matches!(run_against(3, &[1]), Err(GpsError::ColdStartIgnored)));
+    }
+
+    #[test]
+    fn test_ttff_stats_over_fixed_list() {
+        let stats = ttff_stats(&[26.0, 31.5, 28.0, 24.5, 30.0]).unwrap();
+        assert_eq!(stats.min, 24.5);
+        assert_eq!(stats.max, 31.5);
+        assert_eq!(stats.mean, 28.0);
+        // Squared deviations 4 + 12.25 + 0 + 12.25 + 4 = 32.5, over n - 1 = 4
+        assert!((stats.stddev - 8.125f64.sqrt()).abs() < 1e-12);
+
+        assert_eq!(ttff_stats(&[27.0]).unwrap().stddev, 0.0);
+        assert!(ttff_stats(&[]).is_none());
+    }
+}
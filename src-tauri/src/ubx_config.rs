@@ -4,6 +4,7 @@
 //   u-blox 8/M8 Receiver Description (UBX-13003221)
 //   u-blox 7 Receiver Description (GPS.G7-SW-12001)
 
+use crate::antenna::{AntennaPower, AntennaStatus};
 use serde::{Deserialize, Serialize};
 
 // ============ UBX Protocol Constants ============
@@ -12,11 +13,22 @@ pub const UBX_SYNC_1: u8 = 0xB5;
 pub const UBX_SYNC_2: u8 = 0x62;
 
 // Message classes
+pub const UBX_CLASS_NAV: u8 = 0x01;
+pub const UBX_CLASS_ACK: u8 = 0x05;
 pub const UBX_CLASS_CFG: u8 = 0x06;
 pub const UBX_CLASS_MON: u8 = 0x0A;
+pub const UBX_CLASS_SEC: u8 = 0x27;
 
 // Message IDs
+pub const UBX_ACK_NAK: u8 = 0x00;
+pub const UBX_ACK_ACK: u8 = 0x01;
+pub const UBX_NAV_STATUS: u8 = 0x03;
+pub const UBX_NAV_PVT: u8 = 0x07;
+pub const UBX_NAV_ORB: u8 = 0x34;
+pub const UBX_NAV_TIMEGPS: u8 = 0x20;
 pub const UBX_MON_VER: u8 = 0x04;
+pub const UBX_MON_HW: u8 = 0x09;
+pub const UBX_CFG_PRT: u8 = 0x00;
 pub const UBX_CFG_GNSS: u8 = 0x3E;
 pub const UBX_CFG_NAV5: u8 = 0x24;
 pub const UBX_CFG_RATE: u8 = 0x08;
@@ -24,6 +36,10 @@ pub const UBX_CFG_SBAS: u8 = 0x16;
 pub const UBX_CFG_MSG: u8 = 0x01;
 pub const UBX_CFG_NMEA: u8 = 0x17;
 pub const UBX_CFG_CFG: u8 = 0x09;
+pub const UBX_CFG_RST: u8 = 0x04;
+pub const UBX_CFG_VALSET: u8 = 0x8A;
+pub const UBX_CFG_VALGET: u8 = 0x8B;
+pub const UBX_SEC_UNIQID: u8 = 0x03;
 
 // NMEA message IDs (under class 0xF0)
 const NMEA_GGA: u8 = 0x00;
@@ -33,6 +49,12 @@ const NMEA_GSV: u8 = 0x03;
 const NMEA_RMC: u8 = 0x04;
 const NMEA_VTG: u8 = 0x05;
 
+// CFG-NMEA flags
+const NMEA_FLAG_CONSIDER: u8 = 0x02;
+/// Seven decimal places of minutes in positions (HPG firmware only); not allowed
+/// together with the compat and limit82 flags
+const NMEA_FLAG_HIGH_PREC: u8 = 0x08;
+
 // ============ Chip Identification ============
 
 /// Detected u-blox chip series
@@ -41,21 +63,33 @@ const NMEA_VTG: u8 = 0x05;
 pub enum UbloxSeries {
     Series7,
     Series8,
+    /// M9 and M10 use the key-ID configuration interface (CFG-VALSET)
+    Series9,
+    Series10,
     Unknown,
 }
 
+impl UbloxSeries {
+    /// True for chips configured via CFG-VALSET rather than legacy CFG-* messages
+    pub fn uses_valset(&self) -> bool {
+        matches!(self, UbloxSeries::Series9 | UbloxSeries::Series10)
+    }
+}
+
 impl std::fmt::Display for UbloxSeries {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             UbloxSeries::Series7 => write!(f, "Series 7"),
             UbloxSeries::Series8 => write!(f, "Series 8"),
+            UbloxSeries::Series9 => write!(f, "Series 9"),
+            UbloxSeries::Series10 => write!(f, "Series 10"),
             UbloxSeries::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
 /// Full chip identity parsed from UBX-MON-VER response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct UbloxChipInfo {
     pub sw_version: String,
     pub hw_version: String,
@@ -64,6 +98,15 @@ pub struct UbloxChipInfo {
     pub chip_name: String,
 }
 
+impl UbloxChipInfo {
+    /// True for high-precision (HPG firmware) receivers such as the NEO-M8P and
+    /// ZED-F9P, the only ones that accept NMEA high-precision mode
+    pub fn is_high_precision(&self) -> bool {
+        self.extensions.iter().any(|e| e.starts_with("FWVER=HPG"))
+            || ["M8P", "F9P"].iter().any(|model| self.chip_name.contains(model))
+    }
+}
+
 /// Parse a UBX-MON-VER response payload.
 /// Layout: 30 bytes swVersion + 10 bytes hwVersion + N*30 extension strings
 pub fn parse_mon_ver(payload: &[u8]) -> Option<UbloxChipInfo> {
@@ -119,6 +162,10 @@ pub fn parse_mon_ver(payload: &[u8]) -> Option<UbloxChipInfo> {
                 }
             });
         (UbloxSeries::Series8, name)
+    } else if hw_version.starts_with("00190") {
+        (UbloxSeries::Series9, module_name(&extensions, "u-blox M9"))
+    } else if hw_version.starts_with("000A0") {
+        (UbloxSeries::Series10, module_name(&extensions, "u-blox M10"))
     } else {
         (
             UbloxSeries::Unknown,
@@ -135,6 +182,116 @@ pub fn parse_mon_ver(payload: &[u8]) -> Option<UbloxChipInfo> {
     })
 }
 
+/// Navigation solution fields from UBX-NAV-PVT that NMEA does not carry
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavPvt {
+    pub fix_type: u8, // 0 = no fix, 2 = 2D, 3 = 3D, ...
+    pub num_sv: u8,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub h_acc_m: f32, // Receiver's horizontal accuracy estimate
+    pub v_acc_m: f32, // Receiver's vertical accuracy estimate
+    pub pdop: f32,
+}
+
+/// Parse a UBX-NAV-PVT payload (92 bytes on u-blox 8 and later)
+pub fn parse_nav_pvt(payload: &[u8]) -> Option<NavPvt> {
+    if payload.len() < 92 {
+        return None;
+    }
+    let i32_at = |o: usize| i32::from_le_bytes(payload[o..o + 4].try_into().unwrap());
+    let u32_at = |o: usize| u32::from_le_bytes(payload[o..o + 4].try_into().unwrap());
+
+    Some(NavPvt {
+        fix_type: payload[20],
+        num_sv: payload[23],
+        longitude: i32_at(24) as f64 * 1e-7,
+        latitude: i32_at(28) as f64 * 1e-7,
+        h_acc_m: u32_at(40) as f32 / 1000.0, // mm
+        v_acc_m: u32_at(44) as f32 / 1000.0,
+        pdop: u16::from_le_bytes([payload[76], payload[77]]) as f32 * 0.01,
+    })
+}
+
+/// GPS-UTC offset from UBX-NAV-TIMEGPS
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavTimeGps {
+    pub leap_seconds: u8,
+    /// Time of week, week number and leap seconds all flagged valid
+    pub valid: bool,
+}
+
+/// Parse a UBX-NAV-TIMEGPS payload (16 bytes)
+pub fn parse_nav_timegps(payload: &[u8]) -> Option<NavTimeGps> {
+    if payload.len() < 16 {
+        return None;
+    }
+    // valid bits: 0 = towValid, 1 = weekValid, 2 = leapSValid
+    Some(NavTimeGps {
+        leap_seconds: payload[10], // I1, but never negative since GPS time began
+        valid: payload[11] & 0x07 == 0x07,
+    })
+}
+
+/// Receiver navigation status from UBX-NAV-STATUS
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavStatus {
+    pub gps_fix: u8, // 0 = no fix, 2 = 2D, 3 = 3D, ...
+    /// Fix within the receiver's DOP and accuracy masks (gpsFixOk)
+    pub fix_ok: bool,
+    /// Time to first fix since the last start, 0 until there is one
+    pub ttff_ms: u32,
+    /// Time since startup or reset
+    pub msss_ms: u32,
+}
+
+/// Parse a UBX-NAV-STATUS payload (16 bytes)
+pub fn parse_nav_status(payload: &[u8]) -> Option<NavStatus> {
+    if payload.len() < 16 {
+        return None;
+    }
+    let u32_at = |o: usize| u32::from_le_bytes(payload[o..o + 4].try_into().unwrap());
+    Some(NavStatus {
+        gps_fix: payload[4],
+        fix_ok: payload[5] & 0x01 != 0,
+        ttff_ms: u32_at(8),
+        msss_ms: u32_at(12),
+    })
+}
+
+/// Hardware status from UBX-MON-HW
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonHw {
+    pub noise_per_ms: u16,
+    pub agc_cnt: u16,
+    pub antenna_status: Option<AntennaStatus>,
+    pub antenna_power: Option<AntennaPower>,
+    pub jam_ind: u8,
+}
+
+/// Parse a UBX-MON-HW payload (60 bytes on u-blox 7/8)
+pub fn parse_mon_hw(payload: &[u8]) -> Option<MonHw> {
+    if payload.len() < 60 {
+        return None;
+    }
+    Some(MonHw {
+        noise_per_ms: u16::from_le_bytes([payload[16], payload[17]]),
+        agc_cnt: u16::from_le_bytes([payload[18], payload[19]]),
+        antenna_status: AntennaStatus::from_ubx(payload[20]),
+        antenna_power: AntennaPower::from_ubx(payload[21]),
+        jam_ind: payload[45],
+    })
+}
+
+/// Module name from the MON-VER "MOD=" extension, if reported
+fn module_name(extensions: &[String], fallback: &str) -> String {
+    extensions
+        .iter()
+        .find_map(|e| e.strip_prefix("MOD="))
+        .unwrap_or(fallback)
+        .to_string()
+}
+
 // ============ UBX Message Construction ============
 
 /// Calculate UBX checksum (Fletcher's algorithm over class+id+length+payload)
@@ -165,6 +322,85 @@ pub fn build_ubx_message(class: u8, id: u8, payload: &[u8]) -> Vec<u8> {
     msg
 }
 
+// ============ Raw Config Files ============
+
+/// One message from a raw UBX config file
+#[derive(Debug, Clone, PartialEq)]
+pub struct UbxConfigFrame {
+    pub line: usize, // 1-based line number in the file
+    pub frame: Vec<u8>,
+}
+
+/// ACK-ACK or ACK-NAK from the receiver for a message it was sent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UbxAck {
+    pub class: u8,
+    pub id: u8,
+    pub acked: bool,
+}
+
+/// Parse an ACK-ACK / ACK-NAK payload; `id` is the ACK message's own ID
+pub fn parse_ack(id: u8, payload: &[u8]) -> Option<UbxAck> {
+    if payload.len() < 2 || !matches!(id, UBX_ACK_ACK | UBX_ACK_NAK) {
+        return None;
+    }
+    Some(UbxAck { class: payload[0], id: payload[1], acked: id == UBX_ACK_ACK })
+}
+
+/// Parse a config file of complete UBX frames, one per line as hex bytes
+/// (`B5 62 06 08 06 00 E8 03 01 00 01 00 01 39`, spaces optional). Blank lines and
+/// lines starting with `#` are skipped. Every frame's sync bytes, length and
+/// checksum are checked; the first bad line is reported by number.
+pub fn parse_ubx_config_file(text: &str) -> Result<Vec<UbxConfigFrame>, String> {
+    let mut frames = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let frame = parse_hex_frame(trimmed).map_err(|e| format!("Line {}: {}", line_no, e))?;
+        frames.push(UbxConfigFrame { line: line_no, frame });
+    }
+    if frames.is_empty() {
+        return Err("No UBX messages in file".into());
+    }
+    Ok(frames)
+}
+
+fn parse_hex_frame(line: &str) -> Result<Vec<u8>, String> {
+    let hex: String = line.split_whitespace().collect();
+    if !hex.len().is_multiple_of(2) {
+        return Err("odd number of hex digits".into());
+    }
+    // Pairs of raw bytes, not string slices: a multi-byte character can't panic
+    let bytes = hex
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair).ok().and_then(|s| u8::from_str_radix(s, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| "not a hex byte sequence".to_string())?;
+
+    if bytes.len() < 8 || bytes[0] != UBX_SYNC_1 || bytes[1] != UBX_SYNC_2 {
+        return Err("missing UBX sync bytes (B5 62)".into());
+    }
+    let payload_len = u16::from_le_bytes([bytes[4], bytes[5]]) as usize;
+    if bytes.len() != 8 + payload_len {
+        return Err(format!(
+            "length field says {} payload bytes, frame has {}",
+            payload_len,
+            bytes.len() - 8
+        ));
+    }
+    let (ck_a, ck_b) = ubx_checksum(&bytes[2..6 + payload_len]);
+    if bytes[6 + payload_len..] != [ck_a, ck_b] {
+        return Err(format!("checksum mismatch (expected {:02X} {:02X})", ck_a, ck_b));
+    }
+    Ok(bytes)
+}
+
 // ============ Chip Identification ============
 
 /// Build UBX-MON-VER poll (empty payload = request)
@@ -172,53 +408,266 @@ pub fn build_mon_ver_poll() -> Vec<u8> {
     build_ubx_message(UBX_CLASS_MON, UBX_MON_VER, &[])
 }
 
+/// Build UBX-SEC-UNIQID poll (M8 and later)
+pub fn build_sec_uniqid_poll() -> Vec<u8> {
+    build_ubx_message(UBX_CLASS_SEC, UBX_SEC_UNIQID, &[])
+}
+
+/// Parse a UBX-SEC-UNIQID response into the chip's unique ID as upper-case hex.
+/// Layout: version, 3 reserved bytes, then the ID: 5 bytes in version 1 (M8/M9),
+/// 6 bytes in version 2 (M10).
+pub fn parse_sec_uniqid(payload: &[u8]) -> Option<String> {
+    let id_len = match payload.first()? {
+        1 => 5,
+        2 => 6,
+        _ => return None,
+    };
+    let id = payload.get(4..4 + id_len)?;
+    Some(id.iter().map(|b| format!("{:02X}", b)).collect())
+}
+
 // ============ Constellation Configuration ============
 
-/// Series 7 marine: GPS + SBAS only (Series 7 cannot do concurrent GNSS)
-pub fn build_cfg_gnss_series7_marine() -> Vec<u8> {
-    let mut payload = Vec::new();
-    payload.push(0x00); // msgVer
-    payload.push(0x00); // numTrkChHw (read-only)
-    payload.push(0xFF); // numTrkChUse: all available
-    payload.push(0x02); // numConfigBlocks
+/// A GNSS the receiver can be told to track. SBAS is an augmentation system rather
+/// than a constellation and is always enabled alongside.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Constellation {
+    Gps,
+    Galileo,
+    Beidou,
+    Glonass,
+}
+
+impl Constellation {
+    /// Every constellation, in the order the M9/M10 signal keys are written
+    pub const ALL: [Constellation; 4] = [
+        Constellation::Gps,
+        Constellation::Galileo,
+        Constellation::Beidou,
+        Constellation::Glonass,
+    ];
+
+    /// gnssId in CFG-GNSS config blocks
+    pub fn gnss_id(&self) -> u8 {
+        match self {
+            Constellation::Gps => GNSS_ID_GPS,
+            Constellation::Galileo => GNSS_ID_GALILEO,
+            Constellation::Beidou => GNSS_ID_BEIDOU,
+            Constellation::Glonass => GNSS_ID_GLONASS,
+        }
+    }
+
+    /// Signal enable key for CFG-VALSET (M9/M10)
+    fn valset_key(&self) -> u32 {
+        match self {
+            Constellation::Gps => CFG_SIGNAL_GPS_ENA,
+            Constellation::Galileo => CFG_SIGNAL_GAL_ENA,
+            Constellation::Beidou => CFG_SIGNAL_BDS_ENA,
+            Constellation::Glonass => CFG_SIGNAL_GLO_ENA,
+        }
+    }
+
+    /// Series 7 tracks GPS or GLONASS only; later series track all four
+    fn supported_by(&self, series: &UbloxSeries) -> bool {
+        *series != UbloxSeries::Series7
+            || matches!(self, Constellation::Gps | Constellation::Glonass)
+    }
+}
+
+impl std::fmt::Display for Constellation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Constellation::Gps => write!(f, "GPS"),
+            Constellation::Galileo => write!(f, "Galileo"),
+            Constellation::Beidou => write!(f, "BeiDou"),
+            Constellation::Glonass => write!(f, "GLONASS"),
+        }
+    }
+}
+
+/// Constellations a series can track at the same time (SBAS not counted)
+pub fn max_concurrent_constellations(series: &UbloxSeries) -> usize {
+    match series {
+        UbloxSeries::Series7 => 1,
+        UbloxSeries::Series8 | UbloxSeries::Unknown => 3,
+        UbloxSeries::Series9 | UbloxSeries::Series10 => 4,
+    }
+}
+
+/// Check a constellation selection against what the series can track
+pub fn validate_constellations(
+    series: &UbloxSeries,
+    constellations: &[Constellation],
+) -> Result<(), String> {
+    if constellations.is_empty() {
+        return Err("Select at least one constellation".into());
+    }
+    if let Some(unsupported) = constellations.iter().find(|c| !c.supported_by(series)) {
+        return Err(format!("{} receivers cannot track {}", series, unsupported));
+    }
+    let mut distinct = constellations.to_vec();
+    distinct.sort_by_key(Constellation::gnss_id);
+    distinct.dedup();
+    let max = max_concurrent_constellations(series);
+    if distinct.len() > max {
+        return Err(format!(
+            "{} receivers track at most {} constellation(s) at once, {} selected",
+            series,
+            max,
+            distinct.len()
+        ));
+    }
+    Ok(())
+}
 
-    // GPS (gnssId=0): enable, 8 reserved, 16 max
-    payload.extend_from_slice(&[0x00, 0x08, 0x10, 0x00, 0x01, 0x00, 0x01, 0x01]);
-    // SBAS (gnssId=1): enable, 1 reserved, 3 max
-    payload.extend_from_slice(&[0x01, 0x01, 0x03, 0x00, 0x01, 0x00, 0x01, 0x01]);
+/// Channels (reserved, max) given to a constellation's CFG-GNSS block
+fn gnss_channels(gnss_id: u8) -> (u8, u8) {
+    match gnss_id {
+        GNSS_ID_SBAS => (1, 3),
+        GNSS_ID_GALILEO => (4, 8),
+        GNSS_ID_GLONASS => (8, 14),
+        _ => (8, 16), // GPS, BeiDou
+    }
+}
+
+/// UBX-CFG-GNSS enabling the given constellations plus SBAS, one config block each in
+/// gnssId order, on the L1 signal. Constellations not listed keep their current state.
+pub fn build_cfg_gnss(constellations: &[Constellation]) -> Vec<u8> {
+    let blocks: Vec<(u8, bool)> = constellations.iter().map(|c| (c.gnss_id(), true)).collect();
+    cfg_gnss_message(blocks)
+}
+
+/// UBX-CFG-GNSS like `build_cfg_gnss`, plus a block with the enable bit cleared for
+/// every other constellation the series can track, so they are switched off too
+fn build_cfg_gnss_exact(series: &UbloxSeries, constellations: &[Constellation]) -> Vec<u8> {
+    let blocks: Vec<(u8, bool)> = Constellation::ALL
+        .iter()
+        .filter(|c| c.supported_by(series))
+        .map(|c| (c.gnss_id(), constellations.contains(c)))
+        .collect();
+    cfg_gnss_message(blocks)
+}
 
+/// CFG-GNSS from (gnssId, enabled) blocks, adding SBAS and sorting them by gnssId
+fn cfg_gnss_message(mut blocks: Vec<(u8, bool)>) -> Vec<u8> {
+    blocks.push((GNSS_ID_SBAS, true));
+    blocks.sort_unstable_by_key(|&(gnss_id, _)| gnss_id);
+    blocks.dedup_by_key(|&mut (gnss_id, _)| gnss_id);
+
+    let mut payload = vec![
+        0x00,               // msgVer
+        0x00,               // numTrkChHw (read-only)
+        0xFF,               // numTrkChUse: all available
+        blocks.len() as u8, // numConfigBlocks
+    ];
+    for (gnss_id, enabled) in blocks {
+        let (reserved, max) = gnss_channels(gnss_id);
+        // A disabled constellation reserves no channels
+        let reserved = if enabled { reserved } else { 0 };
+        // flags: enable bit, sigCfgMask 0x01 (L1)
+        payload.extend_from_slice(&[gnss_id, reserved, max, 0x00, enabled as u8, 0x00, 0x01, 0x01]);
+    }
     build_ubx_message(UBX_CLASS_CFG, UBX_CFG_GNSS, &payload)
 }
 
+/// Commands selecting exactly the given constellations (plus SBAS) on a series:
+/// CFG-GNSS on u-blox 7/M8, CFG-VALSET with the others switched off on M9/M10
+pub fn build_constellation_commands(
+    series: &UbloxSeries,
+    constellations: &[Constellation],
+) -> Result<Vec<Vec<u8>>, String> {
+    validate_constellations(series, constellations)?;
+    if !series.uses_valset() {
+        return Ok(vec![build_cfg_gnss_exact(series, constellations)]);
+    }
+    let mut signals = vec![(CFG_SIGNAL_SBAS_ENA, 1)];
+    for c in Constellation::ALL {
+        signals.push((c.valset_key(), constellations.contains(&c) as u64));
+    }
+    Ok(vec![build_cfg_valset(&signals)])
+}
+
+/// Series 7 marine: GPS + SBAS only (Series 7 cannot do concurrent GNSS)
+pub fn build_cfg_gnss_series7_marine() -> Vec<u8> {
+    build_cfg_gnss(&[Constellation::Gps])
+}
+
 /// Series 8 marine: GPS + GLONASS + Galileo + SBAS (3 concurrent on M8, 72 channels)
 pub fn build_cfg_gnss_series8_marine() -> Vec<u8> {
-    let mut payload = Vec::new();
-    payload.push(0x00); // msgVer
-    payload.push(0x00); // numTrkChHw (read-only)
-    payload.push(0xFF); // numTrkChUse: all available
-    payload.push(0x04); // numConfigBlocks
-
-    // GPS (gnssId=0): enable, 8 reserved, 16 max
-    payload.extend_from_slice(&[0x00, 0x08, 0x10, 0x00, 0x01, 0x00, 0x01, 0x01]);
-    // SBAS (gnssId=1): enable, 1 reserved, 3 max
-    payload.extend_from_slice(&[0x01, 0x01, 0x03, 0x00, 0x01, 0x00, 0x01, 0x01]);
-    // Galileo (gnssId=2): enable, 4 reserved, 8 max
-    payload.extend_from_slice(&[0x02, 0x04, 0x08, 0x00, 0x01, 0x00, 0x01, 0x01]);
-    // GLONASS (gnssId=6): enable, 8 reserved, 14 max
-    payload.extend_from_slice(&[0x06, 0x08, 0x0E, 0x00, 0x01, 0x00, 0x01, 0x01]);
-
-    build_ubx_message(UBX_CLASS_CFG, UBX_CFG_GNSS, &payload)
+    build_cfg_gnss(&[Constellation::Gps, Constellation::Galileo, Constellation::Glonass])
 }
 
 // ============ Navigation Configuration ============
 
-/// UBX-CFG-NAV5: Dynamic model = Sea (5), fixMode = Auto 2D/3D (3)
-/// Sea model: max alt 500m, max vel 25 m/s (~49 kn), zero vertical velocity
-pub fn build_cfg_nav5_sea() -> Vec<u8> {
+/// u-blox navigation dynamic platform model (UBX-CFG-NAV5 dynModel)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DynamicModel {
+    Portable,
+    Stationary,
+    Pedestrian,
+    Automotive,
+    Sea,
+    Airborne1g,
+    Airborne2g,
+    Airborne4g,
+}
+
+impl DynamicModel {
+    /// dynModel byte value
+    pub fn code(&self) -> u8 {
+        match self {
+            DynamicModel::Portable => 0,
+            DynamicModel::Stationary => 2,
+            DynamicModel::Pedestrian => 3,
+            DynamicModel::Automotive => 4,
+            DynamicModel::Sea => 5,
+            DynamicModel::Airborne1g => 6,
+            DynamicModel::Airborne2g => 7,
+            DynamicModel::Airborne4g => 8,
+        }
+    }
+
+    /// Model for a dynModel byte value
+    pub fn from_code(code: u8) -> Option<Self> {
+        [
+            DynamicModel::Portable,
+            DynamicModel::Stationary,
+            DynamicModel::Pedestrian,
+            DynamicModel::Automotive,
+            DynamicModel::Sea,
+            DynamicModel::Airborne1g,
+            DynamicModel::Airborne2g,
+            DynamicModel::Airborne4g,
+        ]
+        .into_iter()
+        .find(|model| model.code() == code)
+    }
+}
+
+impl std::fmt::Display for DynamicModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DynamicModel::Portable => write!(f, "Portable"),
+            DynamicModel::Stationary => write!(f, "Stationary"),
+            DynamicModel::Pedestrian => write!(f, "Pedestrian"),
+            DynamicModel::Automotive => write!(f, "Automotive"),
+            DynamicModel::Sea => write!(f, "Sea"),
+            DynamicModel::Airborne1g => write!(f, "Airborne <1g"),
+            DynamicModel::Airborne2g => write!(f, "Airborne <2g"),
+            DynamicModel::Airborne4g => write!(f, "Airborne <4g"),
+        }
+    }
+}
+
+/// UBX-CFG-NAV5: set the dynamic model, fixMode = Auto 2D/3D (3).
+/// Only the dynModel mask bit is set, so the remaining fields are ignored by the receiver.
+pub fn build_cfg_nav5_model(model: DynamicModel) -> Vec<u8> {
     #[rustfmt::skip]
     let payload: [u8; 36] = [
         0x01, 0x00,                         // mask: apply dynModel only
-        0x05,                               // dynModel: Sea
+        model.code(),                       // dynModel
         0x03,                               // fixMode: Auto 2D/3D
         0x00, 0x00, 0x00, 0x00,             // fixedAlt (not used)
         0x10, 0x27, 0x00, 0x00,             // fixedAltVar: 10000 (1.0 m^2)
@@ -228,7 +677,7 @@ pub fn build_cfg_nav5_sea() -> Vec<u8> {
         0xFA, 0x00,                         // tDop: 250 (25.0)
         0x64, 0x00,                         // pAcc: 100m
         0x2C, 0x01,                         // tAcc: 300m
-        0x00,                               // staticHoldThresh: 0 (marine = moving)
+        0x00,                               // staticHoldThresh: 0 (disabled)
         0x00,                               // dgnssTimeout
         0x00, 0x00, 0x00, 0x00,             // cnoThreshNumSVs, cnoThresh, reserved
         0x00, 0x00,                         // staticHoldMaxDist
@@ -238,6 +687,12 @@ pub fn build_cfg_nav5_sea() -> Vec<u8> {
     build_ubx_message(UBX_CLASS_CFG, UBX_CFG_NAV5, &payload)
 }
 
+/// UBX-CFG-NAV5: Dynamic model = Sea (5), fixMode = Auto 2D/3D (3)
+/// Sea model: max alt 500m, max vel 25 m/s (~49 kn), zero vertical velocity
+pub fn build_cfg_nav5_sea() -> Vec<u8> {
+    build_cfg_nav5_model(DynamicModel::Sea)
+}
+
 /// UBX-CFG-RATE: 1Hz measurement rate (1000ms), GPS time reference
 pub fn build_cfg_rate_1hz() -> Vec<u8> {
     #[rustfmt::skip]
@@ -249,6 +704,41 @@ pub fn build_cfg_rate_1hz() -> Vec<u8> {
     build_ubx_message(UBX_CLASS_CFG, UBX_CFG_RATE, &payload)
 }
 
+// ============ Port Configuration ============
+
+/// Baud rates a u-blox UART accepts
+pub const UBX_BAUD_RATES: [u32; 9] = [
+    4800, 9600, 19200, 38400, 57600, 115200, 230400, 460800, 921600,
+];
+
+/// UBX-CFG-PRT: UART1 at `baud_rate`, 8N1, UBX+NMEA in and out. Applies to RAM only,
+/// so a power cycle returns the receiver to its saved rate.
+pub fn build_cfg_prt_uart(baud_rate: u32) -> Vec<u8> {
+    let mut payload = vec![
+        0x01, // portID: UART1
+        0x00, // reserved
+        0x00, 0x00, // txReady: disabled
+    ];
+    payload.extend_from_slice(&0x0000_08C0u32.to_le_bytes()); // mode: 8 bits, no parity, 1 stop
+    payload.extend_from_slice(&baud_rate.to_le_bytes());
+    payload.extend_from_slice(&0x0003u16.to_le_bytes()); // inProtoMask: UBX + NMEA
+    payload.extend_from_slice(&0x0003u16.to_le_bytes()); // outProtoMask: UBX + NMEA
+    payload.extend_from_slice(&[0x00; 4]); // flags, reserved
+    build_ubx_message(UBX_CLASS_CFG, UBX_CFG_PRT, &payload)
+}
+
+/// UART1 baud rate change for the chip series: CFG-PRT on 7/8, CFG-VALSET (RAM layer
+/// only, like CFG-PRT) on M9/M10 where CFG-PRT is deprecated
+pub fn build_baud_rate_change(series: &UbloxSeries, baud_rate: u32) -> Vec<u8> {
+    match series {
+        UbloxSeries::Series9 | UbloxSeries::Series10 => build_cfg_valset_layers(
+            VALSET_LAYER_RAM,
+            &[(CFG_UART1_BAUDRATE, u64::from(baud_rate))],
+        ),
+        _ => build_cfg_prt_uart(baud_rate),
+    }
+}
+
 // ============ SBAS Configuration ============
 
 /// UBX-CFG-SBAS: Enable SBAS with ranging, diff corrections, integrity; auto-scan all PRNs
@@ -264,15 +754,38 @@ pub fn build_cfg_sbas_enable() -> Vec<u8> {
     build_ubx_message(UBX_CLASS_CFG, UBX_CFG_SBAS, &payload)
 }
 
+/// UBX-CFG-SBAS: Disable SBAS entirely (live before/after comparison)
+pub fn build_cfg_sbas_disable() -> Vec<u8> {
+    #[rustfmt::skip]
+    let payload: [u8; 8] = [
+        0x00,                       // mode: disabled
+        0x00,                       // usage: none
+        0x03,                       // maxSBAS: 3
+        0x00,                       // scanmode2
+        0x00, 0x00, 0x00, 0x00,    // scanmode1: 0 = auto-scan all
+    ];
+    build_ubx_message(UBX_CLASS_CFG, UBX_CFG_SBAS, &payload)
+}
+
 // ============ NMEA Message Configuration ============
 
 /// Build UBX-CFG-MSG for a specific NMEA sentence (8-byte form)
 fn build_cfg_msg(nmea_msg_id: u8, rate: u8) -> Vec<u8> {
+    build_cfg_msg_rate(0xF0, nmea_msg_id, rate)
+}
+
+/// Build UBX-CFG-MSG setting the output rate of any message on UART1 and USB
+fn build_cfg_msg_rate(class: u8, id: u8, rate: u8) -> Vec<u8> {
     // 8-byte form: class, id, rate for I2C, UART1, UART2, USB, SPI, reserved
-    let payload = [0xF0, nmea_msg_id, 0x00, rate, 0x00, rate, 0x00, 0x00];
+    let payload = [class, id, 0x00, rate, 0x00, rate, 0x00, 0x00];
     build_ubx_message(UBX_CLASS_CFG, UBX_CFG_MSG, &payload)
 }
 
+/// Output UBX-MON-HW once per navigation solution, for the antenna supervisor status
+pub fn build_cfg_msg_mon_hw() -> Vec<u8> {
+    build_cfg_msg_rate(UBX_CLASS_MON, UBX_MON_HW, 1)
+}
+
 /// All NMEA message config commands: enable GGA, RMC, VTG, GSA, GSV; disable GLL
 pub fn build_nmea_message_config() -> Vec<Vec<u8>> {
     vec![
@@ -285,14 +798,54 @@ pub fn build_nmea_message_config() -> Vec<Vec<u8>> {
     ]
 }
 
-/// UBX-CFG-NMEA: Extended talker IDs for multi-constellation
-pub fn build_cfg_nmea_extended() -> Vec<u8> {
+/// Turn GSV output back on (RAM only) for a receiver that has it disabled
+pub fn build_gsv_enable(series: &UbloxSeries) -> Vec<u8> {
+    if series.uses_valset() {
+        build_cfg_valset_layers(VALSET_LAYER_RAM, &[(CFG_MSGOUT_NMEA_GSV_UART1, 1)])
+    } else {
+        build_cfg_msg(NMEA_GSV, 1)
+    }
+}
+
+/// UBX-CFG-NMEA with an explicit NMEA protocol version.
+/// The version byte is BCD-style: high nibble = major, low nibble = minor (4.1 -> 0x41).
+pub fn build_cfg_nmea_version(major: u8, minor: u8) -> Vec<u8> {
+    let version = ((major & 0x0F) << 4) | (minor & 0x0F);
+    build_cfg_nmea(version, NMEA_FLAG_CONSIDER)
+}
+
+/// UBX-CFG-NMEA with the given version byte and flags (payload byte 3)
+fn build_cfg_nmea(version: u8, flags: u8) -> Vec<u8> {
     let payload = [
-        0x00, 0x23, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x00, version, 0x00, flags, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
     ];
     build_ubx_message(UBX_CLASS_CFG, UBX_CFG_NMEA, &payload)
 }
 
+/// NMEA high-precision mode (RAM only): positions with seven decimal places of
+/// minutes (about 0.2 mm) instead of five, so an RTK fix isn't rounded to a metre
+/// or so. Only HPG receivers accept it (see `UbloxChipInfo::is_high_precision`), and
+/// NMEA parsers that assume fixed-width fields may reject the longer sentences.
+/// Legacy receivers get NMEA 4.10 with it, since CFG-NMEA sets the version too.
+pub fn build_cfg_nmea_high_precision(series: &UbloxSeries, enable: bool) -> Vec<u8> {
+    if series.uses_valset() {
+        build_cfg_valset_layers(VALSET_LAYER_RAM, &[(CFG_NMEA_HIGHPREC, enable as u64)])
+    } else {
+        let high_prec = if enable { NMEA_FLAG_HIGH_PREC } else { 0 };
+        build_cfg_nmea(0x41, NMEA_FLAG_CONSIDER | high_prec)
+    }
+}
+
+/// UBX-CFG-NMEA: Extended talker IDs for multi-constellation
+pub fn build_cfg_nmea_extended() -> Vec<u8> {
+    build_cfg_nmea_version(2, 3)
+}
+
+/// UBX-CFG-NMEA: NMEA 4.10, which gives per-constellation talker IDs (GL/GA/GB)
+pub fn build_cfg_nmea_41() -> Vec<u8> {
+    build_cfg_nmea_version(4, 1)
+}
+
 // ============ Save Configuration ============
 
 /// UBX-CFG-CFG: Save current config to all non-volatile memory (BBR + Flash + EEPROM + SPI)
@@ -307,40 +860,404 @@ pub fn build_cfg_save_all() -> Vec<u8> {
     build_ubx_message(UBX_CLASS_CFG, UBX_CFG_CFG, &payload)
 }
 
+/// UBX-CFG-CFG: Clear the saved config from all non-volatile memory and load the
+/// factory defaults into the running configuration
+pub fn build_cfg_clear_all() -> Vec<u8> {
+    #[rustfmt::skip]
+    let payload: [u8; 13] = [
+        0x1F, 0x1F, 0x00, 0x00,     // clearMask: all sections
+        0x00, 0x00, 0x00, 0x00,     // saveMask: don't save
+        0x1F, 0x1F, 0x00, 0x00,     // loadMask: all sections (now defaults)
+        0x17,                        // deviceMask: BBR + Flash + EEPROM + SPI
+    ];
+    build_ubx_message(UBX_CLASS_CFG, UBX_CFG_CFG, &payload)
+}
+
+// ============ Receiver Reset ============
+
+/// Factory default dynamic model of standard-precision u-blox receivers
+pub const DEFAULT_DYNAMIC_MODEL: DynamicModel = DynamicModel::Portable;
+
+/// UBX-CFG-RST: warm restart (ephemeris cleared, almanac and time kept), as a
+/// controlled software reset of the GNSS only so USB and UART stay up.
+/// The receiver restarts straight away and never ACKs it.
+pub fn build_cfg_rst_warm() -> Vec<u8> {
+    #[rustfmt::skip]
+    let payload: [u8; 4] = [
+        0x01, 0x00,     // navBbrMask: ephemeris
+        0x02,           // resetMode: controlled software reset (GNSS only)
+        0x00,           // reserved
+    ];
+    build_ubx_message(UBX_CLASS_CFG, UBX_CFG_RST, &payload)
+}
+
+/// UBX-CFG-RST: cold start (ephemeris, almanac, position and time all cleared), as a
+/// controlled software reset of the GNSS only. Never ACKed, like the warm restart.
+pub fn build_cfg_rst_cold() -> Vec<u8> {
+    #[rustfmt::skip]
+    let payload: [u8; 4] = [
+        0xFF, 0xFF,     // navBbrMask: everything
+        0x02,           // resetMode: controlled software reset (GNSS only)
+        0x00,           // reserved
+    ];
+    build_ubx_message(UBX_CLASS_CFG, UBX_CFG_RST, &payload)
+}
+
+/// Poll the running dynamic model: CFG-VALGET of CFG-NAVSPG-DYNMODEL on M9/M10,
+/// a CFG-NAV5 poll on older series
+pub fn build_dynamic_model_poll(series: &UbloxSeries) -> Vec<u8> {
+    if series.uses_valset() {
+        let mut payload = vec![
+            0x00,       // version: request
+            0x00,       // layer: RAM
+            0x00, 0x00, // position
+        ];
+        payload.extend_from_slice(&CFG_NAVSPG_DYNMODEL.to_le_bytes());
+        build_ubx_message(UBX_CLASS_CFG, UBX_CFG_VALGET, &payload)
+    } else {
+        build_ubx_message(UBX_CLASS_CFG, UBX_CFG_NAV5, &[])
+    }
+}
+
+/// dynModel code from a CFG-NAV5 or CFG-VALGET response; `id` is the message's own ID
+pub fn parse_dynamic_model(id: u8, payload: &[u8]) -> Option<u8> {
+    match id {
+        UBX_CFG_NAV5 => payload.get(2).copied(),
+        UBX_CFG_VALGET => {
+            // 4 byte header, then key/value pairs sized by each key
+            let mut rest = payload.get(4..)?;
+            while rest.len() >= 4 {
+                let key = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]);
+                let size = cfg_value_size(key);
+                if key == CFG_NAVSPG_DYNMODEL {
+                    return rest.get(4).copied();
+                }
+                rest = rest.get(4 + size..)?;
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Commands that return a receiver to its factory configuration
+#[derive(Debug, Clone)]
+pub struct ResetSequence {
+    /// CFG-CFG clearing saved config and loading the defaults (ACKed)
+    pub clear: Vec<u8>,
+    /// CFG-RST warm restart so the receiver starts over on the defaults (not ACKed)
+    pub restart: Vec<u8>,
+    /// Dynamic model poll whose answer confirms the defaults are running
+    pub verify: Vec<u8>,
+}
+
+/// Reset-to-defaults sequence for a series
+pub fn build_reset_to_defaults(series: &UbloxSeries) -> ResetSequence {
+    ResetSequence {
+        clear: build_cfg_clear_all(),
+        restart: build_cfg_rst_warm(),
+        verify: build_dynamic_model_poll(series),
+    }
+}
+
 // ============ Full Optimization Sequence ============
 
 /// Get the complete ordered list of UBX commands for a marine optimization profile.
 /// The save command is always last.
 pub fn get_optimization_commands(series: &UbloxSeries) -> Vec<Vec<u8>> {
-    let mut commands = Vec::new();
+    marine_profile_steps(series).into_iter().map(|(_, command)| command).collect()
+}
+
+/// Marine profile commands in order, each with a description for the UI
+fn marine_profile_steps(series: &UbloxSeries) -> Vec<(&'static str, Vec<u8>)> {
+    let mut steps = Vec::new();
 
     // 1. Constellation config (series-specific)
     match series {
-        UbloxSeries::Series7 => commands.push(build_cfg_gnss_series7_marine()),
-        UbloxSeries::Series8 | UbloxSeries::Unknown => {
-            commands.push(build_cfg_gnss_series8_marine());
+        UbloxSeries::Series7 => {
+            steps.push(("Constellations: GPS + SBAS", build_cfg_gnss_series7_marine()));
+        }
+        UbloxSeries::Series8 | UbloxSeries::Unknown => steps.push((
+            "Constellations: GPS + GLONASS + Galileo + SBAS",
+            build_cfg_gnss_series8_marine(),
+        )),
+        UbloxSeries::Series9 | UbloxSeries::Series10 => {
+            return get_valset_optimization_steps();
         }
     }
 
     // 2. Dynamic model: Sea
-    commands.push(build_cfg_nav5_sea());
+    steps.push(("Dynamic model: Sea", build_cfg_nav5_sea()));
 
     // 3. Measurement rate: 1Hz
-    commands.push(build_cfg_rate_1hz());
+    steps.push(("Measurement rate: 1 Hz", build_cfg_rate_1hz()));
 
     // 4. SBAS enabled with full corrections
-    commands.push(build_cfg_sbas_enable());
+    steps.push(("SBAS with ranging, corrections and integrity", build_cfg_sbas_enable()));
 
     // 5. Extended NMEA talker IDs
-    commands.push(build_cfg_nmea_extended());
+    steps.push(("Extended NMEA talker IDs", build_cfg_nmea_extended()));
 
     // 6. Enable/disable individual NMEA sentences
-    commands.extend(build_nmea_message_config());
+    let sentences = [
+        "Output GGA (position fix)",
+        "Output RMC (recommended minimum)",
+        "Output VTG (course over ground)",
+        "Output GSA (DOP and active satellites)",
+        "Output GSV (satellites in view)",
+        "Stop GLL (redundant with GGA)",
+    ];
+    steps.extend(sentences.into_iter().zip(build_nmea_message_config()));
 
     // 7. Save to flash (always last)
-    commands.push(build_cfg_save_all());
+    steps.push(("Save configuration to flash", build_cfg_save_all()));
+
+    steps
+}
 
-    commands
+// ============ Configuration Interface (M9/M10) ============
+//
+// M9/M10 chips are configured with key-IDs via UBX-CFG-VALSET instead of legacy
+// CFG-* messages. Bits 28-30 of each key encode the value size.
+// Reference: u-blox M10 SPG 5.10 Interface Description (UBX-21035062)
+
+// Signal (constellation) enable keys, type L
+pub const CFG_SIGNAL_GPS_ENA: u32 = 0x1031_001F;
+pub const CFG_SIGNAL_SBAS_ENA: u32 = 0x1031_0020;
+pub const CFG_SIGNAL_GAL_ENA: u32 = 0x1031_0021;
+pub const CFG_SIGNAL_BDS_ENA: u32 = 0x1031_0022;
+pub const CFG_SIGNAL_GLO_ENA: u32 = 0x1031_0025;
+
+/// Navigation dynamic platform model, type E1 (same codes as CFG-NAV5)
+pub const CFG_NAVSPG_DYNMODEL: u32 = 0x2011_0021;
+/// Measurement period in ms, type U2
+pub const CFG_RATE_MEAS: u32 = 0x3021_0001;
+/// NMEA protocol version, type E1 (41 = NMEA 4.10)
+pub const CFG_NMEA_PROTVER: u32 = 0x2093_0001;
+/// NMEA high-precision mode, type L (F9P and other HPG receivers)
+pub const CFG_NMEA_HIGHPREC: u32 = 0x1093_0006;
+/// UART1 baud rate, type U4
+pub const CFG_UART1_BAUDRATE: u32 = 0x4052_0001;
+
+// NMEA output rates on UART1 and USB, type U1
+pub const CFG_MSGOUT_NMEA_GGA_UART1: u32 = 0x2091_00BB;
+pub const CFG_MSGOUT_NMEA_RMC_UART1: u32 = 0x2091_00AC;
+pub const CFG_MSGOUT_NMEA_VTG_UART1: u32 = 0x2091_00B1;
+pub const CFG_MSGOUT_NMEA_GSA_UART1: u32 = 0x2091_00C0;
+pub const CFG_MSGOUT_NMEA_GSV_UART1: u32 = 0x2091_00C5;
+pub const CFG_MSGOUT_NMEA_GLL_UART1: u32 = 0x2091_00CA;
+/// The USB key for each message is its UART1 key + 2
+const MSGOUT_USB_OFFSET: u32 = 2;
+
+/// VALSET layers: RAM + BBR + Flash (applies now and persists, so no separate save)
+const VALSET_LAYERS_ALL: u8 = 0x07;
+/// VALSET layer: RAM only (lost on power cycle)
+const VALSET_LAYER_RAM: u8 = 0x01;
+
+/// Size in bytes of a configuration value, from the key's size bits
+fn cfg_value_size(key: u32) -> usize {
+    match (key >> 28) & 0x07 {
+        0x01 | 0x02 => 1, // L (one bit, stored as a byte) / U1
+        0x03 => 2,
+        0x04 => 4,
+        0x05 => 8,
+        _ => 1,
+    }
+}
+
+/// Build a UBX-CFG-VALSET message from (key, value) pairs.
+/// Each value is written little-endian with the width encoded in its key.
+pub fn build_cfg_valset(key_value_pairs: &[(u32, u64)]) -> Vec<u8> {
+    build_cfg_valset_layers(VALSET_LAYERS_ALL, key_value_pairs)
+}
+
+/// CFG-VALSET written to the given layers only
+fn build_cfg_valset_layers(layers: u8, key_value_pairs: &[(u32, u64)]) -> Vec<u8> {
+    let mut payload = vec![
+        0x00,       // version
+        layers,     // layers
+        0x00, 0x00, // reserved
+    ];
+    for &(key, value) in key_value_pairs {
+        payload.extend_from_slice(&key.to_le_bytes());
+        payload.extend_from_slice(&value.to_le_bytes()[..cfg_value_size(key)]);
+    }
+    build_ubx_message(UBX_CLASS_CFG, UBX_CFG_VALSET, &payload)
+}
+
+/// Marine profile for M9/M10 via CFG-VALSET
+fn get_valset_optimization_steps() -> Vec<(&'static str, Vec<u8>)> {
+    let mut nmea_output = vec![(CFG_NMEA_PROTVER, 41)];
+    for (key, rate) in [
+        (CFG_MSGOUT_NMEA_GGA_UART1, 1),
+        (CFG_MSGOUT_NMEA_RMC_UART1, 1),
+        (CFG_MSGOUT_NMEA_VTG_UART1, 1),
+        (CFG_MSGOUT_NMEA_GSA_UART1, 1),
+        (CFG_MSGOUT_NMEA_GSV_UART1, 1),
+        (CFG_MSGOUT_NMEA_GLL_UART1, 0),
+    ] {
+        nmea_output.push((key, rate));
+        nmea_output.push((key + MSGOUT_USB_OFFSET, rate));
+    }
+
+    vec![
+        (
+            "Constellations: GPS + GLONASS + Galileo + SBAS",
+            build_cfg_valset(&[
+                (CFG_SIGNAL_GPS_ENA, 1),
+                (CFG_SIGNAL_SBAS_ENA, 1),
+                (CFG_SIGNAL_GAL_ENA, 1),
+                (CFG_SIGNAL_GLO_ENA, 1),
+            ]),
+        ),
+        (
+            "Dynamic model Sea, 1 Hz measurement rate",
+            build_cfg_valset(&[
+                (CFG_NAVSPG_DYNMODEL, DynamicModel::Sea.code() as u64),
+                (CFG_RATE_MEAS, 1000),
+            ]),
+        ),
+        (
+            "NMEA 4.10 with GGA, RMC, VTG, GSA and GSV output, GLL off",
+            build_cfg_valset(&nmea_output),
+        ),
+    ]
+}
+
+// ============ Clone Detection ============
+//
+// Counterfeit NEO-M8N modules commonly answer MON-VER with a plausible identity
+// but lack genuine multi-constellation support. Two heuristics are applied:
+//
+//   1. CFG-GNSS readback: after applying the profile, the constellation config is
+//      polled back. If fewer of the requested constellations are enabled than were
+//      requested, the receiver silently rejected part of the configuration.
+//   2. PROTVER plausibility: genuine u-blox 7 firmware reports protocol 14.xx and
+//      M8 firmware reports 15.00-23.99. A missing or out-of-range PROTVER for the
+//      claimed hardware generation is suspicious.
+//
+// Neither heuristic is proof on its own; they flag a module for closer inspection.
+
+// GNSS identifiers used in CFG-GNSS config blocks
+pub const GNSS_ID_GPS: u8 = 0;
+pub const GNSS_ID_SBAS: u8 = 1;
+pub const GNSS_ID_GALILEO: u8 = 2;
+pub const GNSS_ID_BEIDOU: u8 = 3;
+pub const GNSS_ID_GLONASS: u8 = 6;
+
+/// Build UBX-CFG-GNSS poll (empty payload = request current config)
+pub fn build_cfg_gnss_poll() -> Vec<u8> {
+    build_ubx_message(UBX_CLASS_CFG, UBX_CFG_GNSS, &[])
+}
+
+/// GNSS IDs enabled by the marine profile for a series
+pub fn requested_gnss_ids(series: &UbloxSeries) -> Vec<u8> {
+    match series {
+        UbloxSeries::Series7 => vec![GNSS_ID_GPS, GNSS_ID_SBAS],
+        UbloxSeries::Series8
+        | UbloxSeries::Series9
+        | UbloxSeries::Series10
+        | UbloxSeries::Unknown => {
+            vec![GNSS_ID_GPS, GNSS_ID_SBAS, GNSS_ID_GALILEO, GNSS_ID_GLONASS]
+        }
+    }
+}
+
+/// Parse a UBX-CFG-GNSS response payload into the list of enabled GNSS IDs.
+/// Layout: 4 byte header + numConfigBlocks * 8 byte blocks (flags bit 0 = enable)
+pub fn parse_cfg_gnss_enabled(payload: &[u8]) -> Option<Vec<u8>> {
+    if payload.len() < 4 {
+        return None;
+    }
+    let num_blocks = payload[3] as usize;
+    if payload.len() < 4 + num_blocks * 8 {
+        return None;
+    }
+
+    let enabled = payload[4..4 + num_blocks * 8]
+        .chunks_exact(8)
+        .filter(|block| block[4] & 0x01 != 0)
+        .map(|block| block[0])
+        .collect();
+    Some(enabled)
+}
+
+/// Extract the protocol version from MON-VER extensions ("PROTVER=18.00" or "PROTVER 14.00")
+pub fn protocol_version(info: &UbloxChipInfo) -> Option<f32> {
+    info.extensions
+        .iter()
+        .find_map(|e| e.strip_prefix("PROTVER"))
+        .and_then(|v| v.trim_start_matches(['=', ' ']).trim().parse().ok())
+}
+
+/// Check the claimed identity and CFG-GNSS readback for signs of a clone module.
+/// Returns a human-readable reason for each heuristic that tripped.
+pub fn clone_indicators(
+    info: &UbloxChipInfo,
+    requested_gnss: &[u8],
+    enabled_gnss: Option<&[u8]>,
+) -> Vec<String> {
+    let mut indicators = Vec::new();
+
+    if let Some(enabled) = enabled_gnss {
+        let accepted = requested_gnss
+            .iter()
+            .filter(|id| enabled.contains(id))
+            .count();
+        if accepted < requested_gnss.len() {
+            indicators.push(format!(
+                "CFG-GNSS readback shows {} of {} requested constellations enabled",
+                accepted,
+                requested_gnss.len()
+            ));
+        }
+    }
+
+    let plausible = match info.series {
+        UbloxSeries::Series7 => Some(13.0..15.0),
+        UbloxSeries::Series8 => Some(15.0..24.0),
+        UbloxSeries::Series9 => Some(27.0..33.0),
+        UbloxSeries::Series10 => Some(34.0..40.0),
+        UbloxSeries::Unknown => None,
+    };
+    if let Some(range) = plausible {
+        match protocol_version(info) {
+            Some(v) if !range.contains(&v) => indicators.push(format!(
+                "PROTVER {:.2} is implausible for {}",
+                v, info.series
+            )),
+            None => indicators.push(format!("No PROTVER reported for {}", info.series)),
+            _ => {}
+        }
+    }
+
+    indicators
+}
+
+/// Short name of a UBX message, e.g. "CFG-GNSS", or its class and ID in hex
+pub fn message_name(class: u8, id: u8) -> String {
+    let name = match (class, id) {
+        (UBX_CLASS_CFG, UBX_CFG_PRT) => "CFG-PRT",
+        (UBX_CLASS_CFG, UBX_CFG_MSG) => "CFG-MSG",
+        (UBX_CLASS_CFG, UBX_CFG_RATE) => "CFG-RATE",
+        (UBX_CLASS_CFG, UBX_CFG_CFG) => "CFG-CFG",
+        (UBX_CLASS_CFG, UBX_CFG_SBAS) => "CFG-SBAS",
+        (UBX_CLASS_CFG, UBX_CFG_NMEA) => "CFG-NMEA",
+        (UBX_CLASS_CFG, UBX_CFG_NAV5) => "CFG-NAV5",
+        (UBX_CLASS_CFG, UBX_CFG_GNSS) => "CFG-GNSS",
+        (UBX_CLASS_CFG, UBX_CFG_VALSET) => "CFG-VALSET",
+        (UBX_CLASS_CFG, UBX_CFG_VALGET) => "CFG-VALGET",
+        (UBX_CLASS_CFG, UBX_CFG_RST) => "CFG-RST",
+        (UBX_CLASS_MON, UBX_MON_VER) => "MON-VER",
+        (UBX_CLASS_MON, UBX_MON_HW) => "MON-HW",
+        (UBX_CLASS_NAV, UBX_NAV_STATUS) => "NAV-STATUS",
+        (UBX_CLASS_NAV, UBX_NAV_PVT) => "NAV-PVT",
+        (UBX_CLASS_NAV, UBX_NAV_ORB) => "NAV-ORB",
+        (UBX_CLASS_NAV, UBX_NAV_TIMEGPS) => "NAV-TIMEGPS",
+        (UBX_CLASS_SEC, UBX_SEC_UNIQID) => "SEC-UNIQID",
+        _ => return format!("{:02X}-{:02X}", class, id),
+    };
+    name.to_string()
 }
 
 /// Get a human-readable profile name for a series
@@ -348,10 +1265,242 @@ pub fn profile_name(series: &UbloxSeries) -> &'static str {
     match series {
         UbloxSeries::Series7 => "Series 7 Marine (GPS + SBAS)",
         UbloxSeries::Series8 => "Series 8 Marine (GPS + GLONASS + Galileo + SBAS)",
+        UbloxSeries::Series9 | UbloxSeries::Series10 => {
+            "M9/M10 Marine (GPS + GLONASS + Galileo + SBAS, VALSET)"
+        }
         UbloxSeries::Unknown => "Generic Marine",
     }
 }
 
+// ============ Start State ============
+//
+// How long the first fix takes depends on what the receiver already knows. With
+// current ephemeris it only has to reacquire (hot start, a second or two); with just
+// the almanac it knows which satellites are up but must download each ephemeris,
+// about 30 s (warm start); with neither it searches blind (cold start). UBX-NAV-ORB
+// (u-blox 8 and later) reports the ephemeris and almanac each satellite has, and
+// UBX-NAV-STATUS whether the receiver is already navigating.
+
+/// Satellites with usable orbit data needed to count as having it, as for a 3D fix
+const MIN_ORBIT_SATELLITES: u32 = 4;
+
+/// Orbit data the receiver holds, from UBX-NAV-ORB
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavOrb {
+    /// Satellites with usable ephemeris
+    pub ephemeris_satellites: u32,
+    /// Satellites with usable almanac
+    pub almanac_satellites: u32,
+}
+
+/// Build UBX-NAV-STATUS poll
+pub fn build_nav_status_poll() -> Vec<u8> {
+    build_ubx_message(UBX_CLASS_NAV, UBX_NAV_STATUS, &[])
+}
+
+/// Build UBX-NAV-ORB poll
+pub fn build_nav_orb_poll() -> Vec<u8> {
+    build_ubx_message(UBX_CLASS_NAV, UBX_NAV_ORB, &[])
+}
+
+/// Parse a UBX-NAV-ORB payload: an 8 byte header, then 6 bytes per satellite whose
+/// eph and alm bytes hold a 5-bit usability (0 = not usable) and a 3-bit source
+/// (0 = not available)
+pub fn parse_nav_orb(payload: &[u8]) -> Option<NavOrb> {
+    let num_sv = *payload.get(5)? as usize;
+    let blocks = payload.get(8..8 + num_sv * 6)?;
+    let usable = |b: u8| b & 0x1F != 0 && b >> 5 != 0;
+    let (mut ephemeris, mut almanac) = (0, 0);
+    for sv in blocks.chunks_exact(6) {
+        ephemeris += usable(sv[3]) as u32;
+        almanac += usable(sv[4]) as u32;
+    }
+    Some(NavOrb { ephemeris_satellites: ephemeris, almanac_satellites: almanac })
+}
+
+/// Expected TTFF regime
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartType {
+    Hot,
+    Warm,
+    Cold,
+    /// No fix and no NAV-ORB answer (u-blox 7, or the poll went unanswered)
+    Unknown,
+}
+
+/// What the receiver knows before a test, and the start it is expected to make
+#[derive(Debug, Clone, Serialize)]
+pub struct StartState {
+    pub start_type: StartType,
+    /// Already navigating, so a fix is there straight away
+    pub fix_ok: bool,
+    /// Satellites with usable ephemeris / almanac (None without NAV-ORB)
+    pub ephemeris_satellites: Option<u32>,
+    pub almanac_satellites: Option<u32>,
+    /// Time since the receiver started (None without NAV-STATUS)
+    pub uptime_seconds: Option<f64>,
+}
+
+/// Classify the expected start from whichever of NAV-STATUS and NAV-ORB answered
+pub fn classify_start(status: Option<&NavStatus>, orbits: Option<&NavOrb>) -> StartState {
+    let fix_ok = status.is_some_and(|s| s.fix_ok);
+    let start_type = match orbits {
+        _ if fix_ok => StartType::Hot,
+        Some(orb) if orb.ephemeris_satellites >= MIN_ORBIT_SATELLITES => StartType::Hot,
+        Some(orb) if orb.almanac_satellites >= MIN_ORBIT_SATELLITES => StartType::Warm,
+        Some(_) => StartType::Cold,
+        None => StartType::Unknown,
+    };
+    StartState {
+        start_type,
+        fix_ok,
+        ephemeris_satellites: orbits.map(|o| o.ephemeris_satellites),
+        almanac_satellites: orbits.map(|o| o.almanac_satellites),
+        uptime_seconds: status.map(|s| s.msss_ms as f64 / 1000.0),
+    }
+}
+
+// ============ Capabilities ============
+
+const LEGACY_SERIES: &[UbloxSeries] = &[UbloxSeries::Series7, UbloxSeries::Series8];
+const VALSET_SERIES: &[UbloxSeries] = &[UbloxSeries::Series9, UbloxSeries::Series10];
+const ALL_SERIES: &[UbloxSeries] = &[
+    UbloxSeries::Series7,
+    UbloxSeries::Series8,
+    UbloxSeries::Series9,
+    UbloxSeries::Series10,
+];
+
+/// A configuration builder described for the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct UbxCapability {
+    /// Builder function, e.g. "build_cfg_sbas_enable"
+    pub name: &'static str,
+    pub description: &'static str,
+    /// UBX message it sends, e.g. "CFG-SBAS"
+    pub message: String,
+    /// Chip series it is meant for
+    pub series: Vec<UbloxSeries>,
+    /// True if it changes the saved configuration (BBR/flash), not just RAM
+    pub writes_flash: bool,
+}
+
+/// One command of the marine profile
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileStep {
+    pub description: &'static str,
+    pub message: String,
+    pub writes_flash: bool,
+}
+
+/// The marine profile as applied to some chip series
+#[derive(Debug, Clone, Serialize)]
+pub struct MarineProfile {
+    pub name: &'static str,
+    pub series: Vec<UbloxSeries>,
+    pub steps: Vec<ProfileStep>,
+}
+
+/// Everything the app can configure on a u-blox receiver
+#[derive(Debug, Clone, Serialize)]
+pub struct UbxCapabilities {
+    pub builders: Vec<UbxCapability>,
+    pub marine_profiles: Vec<MarineProfile>,
+}
+
+/// Message name of a built UBX frame
+fn frame_message(frame: &[u8]) -> String {
+    message_name(frame[2], frame[3])
+}
+
+/// True if a built frame changes non-volatile memory: CFG-CFG with a clear or save
+/// mask, or CFG-VALSET to the BBR or flash layer
+fn writes_flash(frame: &[u8]) -> bool {
+    let payload = &frame[6..frame.len() - 2];
+    match (frame[2], frame[3]) {
+        (UBX_CLASS_CFG, UBX_CFG_CFG) => payload[..8].iter().any(|&b| b != 0),
+        (UBX_CLASS_CFG, UBX_CFG_VALSET) => payload[1] & !VALSET_LAYER_RAM != 0,
+        _ => false,
+    }
+}
+
+/// The configuration builders and the marine profile of each series, with the
+/// message and flash behavior read from the frames they build. Unknown chips get the
+/// Series 8 profile.
+pub fn list_ubx_capabilities() -> UbxCapabilities {
+    let constellations = [Constellation::Gps, Constellation::Galileo];
+    #[rustfmt::skip]
+    let builders: Vec<(&'static str, &'static str, &[UbloxSeries], Vec<u8>)> = vec![
+        ("build_cfg_gnss", "Enable constellations (plus SBAS)", LEGACY_SERIES,
+            build_cfg_gnss(&constellations)),
+        ("build_constellation_commands", "Enable exactly the given constellations (plus SBAS)",
+            VALSET_SERIES,
+            build_constellation_commands(&UbloxSeries::Series10, &constellations)
+                .expect("GPS + Galileo is valid on M10").remove(0)),
+        ("build_cfg_nav5_model", "Set the dynamic platform model", LEGACY_SERIES,
+            build_cfg_nav5_model(DynamicModel::Sea)),
+        ("build_cfg_rate_1hz", "1 Hz measurement rate", LEGACY_SERIES, build_cfg_rate_1hz()),
+        ("build_cfg_prt_uart", "Change the UART1 baud rate (RAM only)", LEGACY_SERIES,
+            build_cfg_prt_uart(115200)),
+        ("build_baud_rate_change", "Change the UART1 baud rate (RAM only)", VALSET_SERIES,
+            build_baud_rate_change(&UbloxSeries::Series10, 115200)),
+        ("build_cfg_sbas_enable", "Enable SBAS with ranging, corrections and integrity",
+            LEGACY_SERIES, build_cfg_sbas_enable()),
+        ("build_cfg_sbas_disable", "Disable SBAS", LEGACY_SERIES, build_cfg_sbas_disable()),
+        ("build_cfg_msg_mon_hw", "Output MON-HW for the antenna status", LEGACY_SERIES,
+            build_cfg_msg_mon_hw()),
+        ("build_gsv_enable", "Turn GSV output back on (RAM only)", VALSET_SERIES,
+            build_gsv_enable(&UbloxSeries::Series10)),
+        ("build_cfg_nmea_extended", "Extended NMEA talker IDs", LEGACY_SERIES,
+            build_cfg_nmea_extended()),
+        ("build_cfg_nmea_41", "NMEA 4.10 with per-constellation talker IDs", LEGACY_SERIES,
+            build_cfg_nmea_41()),
+        ("build_cfg_nmea_high_precision", "NMEA high-precision positions (M8P, F9P; RAM only)",
+            &[UbloxSeries::Series8, UbloxSeries::Series9],
+            build_cfg_nmea_high_precision(&UbloxSeries::Series8, true)),
+        ("build_cfg_valset", "Set configuration keys in RAM, BBR and flash", VALSET_SERIES,
+            build_cfg_valset(&[])),
+        ("build_cfg_save_all", "Save the running configuration to flash", LEGACY_SERIES,
+            build_cfg_save_all()),
+        ("build_cfg_clear_all", "Clear the saved configuration and load factory defaults",
+            ALL_SERIES, build_cfg_clear_all()),
+        ("build_cfg_rst_warm", "Warm restart of the GNSS", ALL_SERIES, build_cfg_rst_warm()),
+        ("build_cfg_rst_cold", "Cold start of the GNSS", ALL_SERIES, build_cfg_rst_cold()),
+    ];
+
+    let profile = |series: &[UbloxSeries]| MarineProfile {
+        name: profile_name(&series[0]),
+        series: series.to_vec(),
+        steps: marine_profile_steps(&series[0])
+            .into_iter()
+            .map(|(description, frame)| ProfileStep {
+                description,
+                message: frame_message(&frame),
+                writes_flash: writes_flash(&frame),
+            })
+            .collect(),
+    };
+
+    UbxCapabilities {
+        builders: builders
+            .into_iter()
+            .map(|(name, description, series, frame)| UbxCapability {
+                name,
+                description,
+                message: frame_message(&frame),
+                series: series.to_vec(),
+                writes_flash: writes_flash(&frame),
+            })
+            .collect(),
+        marine_profiles: vec![
+            profile(&[UbloxSeries::Series7]),
+            profile(&[UbloxSeries::Series8, UbloxSeries::Unknown]),
+            profile(VALSET_SERIES),
+        ],
+    }
+}
+
 // ============ Tests ============
 
 #[cfg(test)]
@@ -367,6 +1516,34 @@ mod tests {
         assert_eq!(ck_b, 0x34);
     }
 
+    #[test]
+    fn test_parse_ubx_config_file() {
+        let text = "# 1 Hz, then poll MON-VER\n\
+                    B5 62 06 08 06 00 E8 03 01 00 01 00 01 39\n\
+                    \n\
+                    B5620A0400000E34\n";
+        let frames = parse_ubx_config_file(text).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].line, 2);
+        assert_eq!(frames[0].frame[2..4], [UBX_CLASS_CFG, UBX_CFG_RATE]);
+        assert_eq!(frames[1].line, 4);
+        assert_eq!(frames[1].frame, build_mon_ver_poll());
+
+        let bad_checksum = "B5 62 0A 04 00 00 0E 34\nB5 62 06 08 06 00 E8 03 01 00 01 00 01 38";
+        let err = parse_ubx_config_file(bad_checksum).unwrap_err();
+        assert!(err.starts_with("Line 2: checksum mismatch"), "{}", err);
+        let err = parse_ubx_config_file("62 B5 0A 04 00 00 0E 34").unwrap_err();
+        assert!(err.starts_with("Line 1: missing UBX sync"), "{}", err);
+        let err = parse_ubx_config_file("B5 62 0A 04 01 00 0E 34").unwrap_err();
+        assert!(err.starts_with("Line 1: length field"), "{}", err);
+        assert!(parse_ubx_config_file("B5 62 0A 04 00 00 0E 3").is_err());
+        // Non-ASCII is rejected, not sliced through the middle of a character
+        let err = parse_ubx_config_file("B5 62 0\u{b5} 0").unwrap_err();
+        assert!(err.starts_with("Line 1: not a hex byte"), "{}", err);
+        assert!(parse_ubx_config_file("B5 62 0\u{b5}").is_err());
+        assert!(parse_ubx_config_file("# nothing\n").is_err());
+    }
+
     #[test]
     fn test_build_ubx_message_mon_ver_poll() {
         let msg = build_mon_ver_poll();
@@ -418,6 +1595,51 @@ mod tests {
         assert_eq!(info.chip_name, "u-blox 7");
     }
 
+    #[test]
+    fn test_parse_nav_pvt_accuracy() {
+        let mut payload = [0u8; 92];
+        payload[20] = 3; // 3D fix
+        payload[23] = 14; // numSV
+        payload[24..28].copy_from_slice(&1_747_633_000i32.to_le_bytes()); // lon 174.7633
+        payload[28..32].copy_from_slice(&(-368_485_000i32).to_le_bytes()); // lat -36.8485
+        payload[40..44].copy_from_slice(&1_850u32.to_le_bytes()); // hAcc 1850 mm
+        payload[44..48].copy_from_slice(&3_200u32.to_le_bytes()); // vAcc 3200 mm
+        payload[76..78].copy_from_slice(&145u16.to_le_bytes()); // pDOP 1.45
+
+        let pvt = parse_nav_pvt(&payload).unwrap();
+        assert_eq!(pvt.fix_type, 3);
+        assert_eq!(pvt.num_sv, 14);
+        assert!((pvt.latitude + 36.8485).abs() < 1e-9);
+        assert!((pvt.longitude - 174.7633).abs() < 1e-9);
+        assert_eq!(pvt.h_acc_m, 1.85);
+        assert_eq!(pvt.v_acc_m, 3.2);
+        assert!((pvt.pdop - 1.45).abs() < 1e-6);
+
+        // u-blox 7 NAV-PVT is shorter and not supported
+        assert!(parse_nav_pvt(&payload[..84]).is_none());
+    }
+
+    #[test]
+    fn test_parse_mon_hw_antenna() {
+        let mut payload = [0u8; 60];
+        payload[16..18].copy_from_slice(&87u16.to_le_bytes()); // noisePerMS
+        payload[18..20].copy_from_slice(&4_200u16.to_le_bytes()); // agcCnt
+        payload[20] = 2; // aStatus OK
+        payload[21] = 1; // aPower ON
+        payload[45] = 12; // jamInd
+
+        let hw = parse_mon_hw(&payload).unwrap();
+        assert_eq!(hw.noise_per_ms, 87);
+        assert_eq!(hw.agc_cnt, 4_200);
+        assert_eq!(hw.antenna_status, Some(AntennaStatus::Ok));
+        assert_eq!(hw.antenna_power, Some(AntennaPower::On));
+        assert_eq!(hw.jam_ind, 12);
+        assert!(parse_mon_hw(&payload[..59]).is_none());
+
+        let msg = build_cfg_msg_mon_hw();
+        assert_eq!(&msg[6..10], &[UBX_CLASS_MON, UBX_MON_HW, 0x00, 0x01]);
+    }
+
     #[test]
     fn test_parse_mon_ver_too_short() {
         assert!(parse_mon_ver(&[0u8; 30]).is_none());
@@ -456,6 +1678,14 @@ mod tests {
         assert_eq!(msg[8], 0x05); // Sea
     }
 
+    #[test]
+    fn test_cfg_nav5_model_dynmodel() {
+        assert_eq!(build_cfg_nav5_model(DynamicModel::Portable)[8], 0x00);
+        assert_eq!(build_cfg_nav5_model(DynamicModel::Automotive)[8], 0x04);
+        assert_eq!(build_cfg_nav5_model(DynamicModel::Airborne1g)[8], 0x06);
+        assert_eq!(build_cfg_nav5_model(DynamicModel::Sea), build_cfg_nav5_sea());
+    }
+
     #[test]
     fn test_cfg_rate_1hz() {
         let msg = build_cfg_rate_1hz();
@@ -466,6 +1696,50 @@ mod tests {
         assert_eq!(msg[7], 0x03);
     }
 
+    #[test]
+    fn test_cfg_sbas_disable() {
+        let msg = build_cfg_sbas_disable();
+        assert_eq!(msg[2], 0x06); // class CFG
+        assert_eq!(msg[3], 0x16); // id SBAS
+        // Payload byte 0 (offset 6 in message) = mode
+        assert_eq!(msg[6], 0x00); // disabled
+        assert_eq!(build_cfg_sbas_enable()[6], 0x01);
+    }
+
+    #[test]
+    fn test_cfg_nmea_version_byte() {
+        let msg = build_cfg_nmea_version(4, 1);
+        assert_eq!(msg[2], 0x06); // class CFG
+        assert_eq!(msg[3], 0x17); // id NMEA
+        // Payload byte 1 (offset 7 in message) = nmeaVersion
+        assert_eq!(msg[7], 0x41);
+        assert_eq!(build_cfg_nmea_41(), msg);
+        assert_eq!(build_cfg_nmea_version(4, 0)[7], 0x40);
+        assert_eq!(build_cfg_nmea_extended()[7], 0x23);
+    }
+
+    #[test]
+    fn test_cfg_nmea_high_precision_flag() {
+        // Legacy CFG-NMEA: flags (payload byte 3, offset 9) gain highPrec, bit 3
+        let on = build_cfg_nmea_high_precision(&UbloxSeries::Series8, true);
+        let off = build_cfg_nmea_high_precision(&UbloxSeries::Series8, false);
+        assert_eq!((on[2], on[3]), (UBX_CLASS_CFG, UBX_CFG_NMEA));
+        assert_eq!((on[7], on[9]), (0x41, 0x0A));
+        assert_eq!(off[9], 0x02);
+        assert_eq!(build_cfg_nmea_41(), off);
+
+        // F9P: CFG-NMEA-HIGHPREC in RAM
+        let f9 = build_cfg_nmea_high_precision(&UbloxSeries::Series9, true);
+        assert_eq!(f9[3], UBX_CFG_VALSET);
+        assert_eq!(f9[7], VALSET_LAYER_RAM);
+        assert_eq!(&f9[10..15], &[0x06, 0x00, 0x93, 0x10, 0x01]);
+
+        let mut info = parse_mon_ver(&[0u8; 40]).unwrap();
+        assert!(!info.is_high_precision());
+        info.extensions.push("FWVER=HPG 1.13".into());
+        assert!(info.is_high_precision());
+    }
+
     #[test]
     fn test_cfg_save_all() {
         let msg = build_cfg_save_all();
@@ -475,6 +1749,281 @@ mod tests {
         assert_eq!(msg[18], 0x17);
     }
 
+    #[test]
+    fn test_parse_sec_uniqid() {
+        let poll = build_sec_uniqid_poll();
+        assert_eq!(&poll[2..6], &[UBX_CLASS_SEC, UBX_SEC_UNIQID, 0, 0]);
+
+        let payload = [0x01, 0x00, 0x00, 0x00, 0xE0, 0x95, 0x65, 0x0F, 0x2A];
+        assert_eq!(parse_sec_uniqid(&payload).as_deref(), Some("E095650F2A"));
+        // M10 reports six bytes
+        let m10 = [0x02, 0x00, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        assert_eq!(parse_sec_uniqid(&m10).as_deref(), Some("010203040506"));
+        assert_eq!(parse_sec_uniqid(&payload[..8]), None);
+        assert_eq!(parse_sec_uniqid(&[0x03, 0, 0, 0, 1, 2, 3, 4, 5, 6]), None);
+    }
+
+    #[test]
+    fn test_parse_nav_status_and_classify_start() {
+        let mut payload = [0u8; 16];
+        payload[4] = 3; // 3D fix
+        payload[5] = 0x0D; // gpsFixOk, wknSet, towSet
+        payload[8..12].copy_from_slice(&28_500u32.to_le_bytes()); // ttff
+        payload[12..16].copy_from_slice(&95_000u32.to_le_bytes()); // msss
+        let status = parse_nav_status(&payload).unwrap();
+        let expected = NavStatus { gps_fix: 3, fix_ok: true, ttff_ms: 28_500, msss_ms: 95_000 };
+        assert_eq!(status, expected);
+        assert!(parse_nav_status(&payload[..12]).is_none());
+        assert_eq!(classify_start(Some(&status), None).start_type, StartType::Hot);
+
+        // Not navigating: NAV-ORB with five almanac-only satellites and one
+        // broadcast ephemeris (usability 31, source 1) is a warm start
+        let searching = NavStatus { gps_fix: 0, fix_ok: false, ttff_ms: 0, msss_ms: 2_000 };
+        let mut orb = vec![0u8; 8];
+        orb[5] = 6;
+        for sv in 0..6u8 {
+            let eph = if sv == 0 { 0x3F } else { 0 };
+            orb.extend_from_slice(&[0, sv + 1, 0, eph, 0x3F, 0]);
+        }
+        let orbits = parse_nav_orb(&orb).unwrap();
+        assert_eq!(orbits, NavOrb { ephemeris_satellites: 1, almanac_satellites: 6 });
+        let warm = classify_start(Some(&searching), Some(&orbits));
+        assert_eq!(warm.start_type, StartType::Warm);
+        assert_eq!(warm.uptime_seconds, Some(2.0));
+
+        // Nothing stored is cold; no NAV-ORB at all can't be classified
+        let empty = NavOrb { ephemeris_satellites: 0, almanac_satellites: 0 };
+        assert_eq!(classify_start(Some(&searching), Some(&empty)).start_type, StartType::Cold);
+        assert_eq!(classify_start(Some(&searching), None).start_type, StartType::Unknown);
+        assert!(parse_nav_orb(&orb[..20]).is_none());
+    }
+
+    #[test]
+    fn test_reset_to_defaults_sequence() {
+        let m8 = build_reset_to_defaults(&UbloxSeries::Series8);
+        // CFG-CFG: clearMask and loadMask all sections, nothing saved, all devices
+        assert_eq!((m8.clear[2], m8.clear[3]), (UBX_CLASS_CFG, UBX_CFG_CFG));
+        assert_eq!(&m8.clear[6..19], &[0x1F, 0x1F, 0, 0, 0, 0, 0, 0, 0x1F, 0x1F, 0, 0, 0x17]);
+        // CFG-RST: warm start, GNSS-only software reset
+        assert_eq!((m8.restart[2], m8.restart[3]), (UBX_CLASS_CFG, UBX_CFG_RST));
+        assert_eq!(&m8.restart[6..10], &[0x01, 0x00, 0x02, 0x00]);
+        // Older series verify with an empty CFG-NAV5 poll, M9/M10 with CFG-VALGET
+        assert_eq!(m8.verify, build_ubx_message(UBX_CLASS_CFG, UBX_CFG_NAV5, &[]));
+        let m10 = build_reset_to_defaults(&UbloxSeries::Series10);
+        assert_eq!(m10.verify[3], UBX_CFG_VALGET);
+        assert_eq!(&m10.verify[10..14], &CFG_NAVSPG_DYNMODEL.to_le_bytes());
+        for msg in [&m8.clear, &m8.restart, &m8.verify, &m10.verify] {
+            let (a, b) = ubx_checksum(&msg[2..msg.len() - 2]);
+            assert_eq!(&msg[msg.len() - 2..], &[a, b]);
+        }
+
+        // Answers to either poll give the running model
+        let nav5 = build_cfg_nav5_model(DynamicModel::Portable);
+        assert_eq!(parse_dynamic_model(UBX_CFG_NAV5, &nav5[6..nav5.len() - 2]), Some(0));
+        let mut valget = vec![0x01, 0x00, 0x00, 0x00];
+        valget.extend_from_slice(&CFG_RATE_MEAS.to_le_bytes());
+        valget.extend_from_slice(&1000u16.to_le_bytes());
+        valget.extend_from_slice(&CFG_NAVSPG_DYNMODEL.to_le_bytes());
+        valget.push(DynamicModel::Sea.code());
+        assert_eq!(parse_dynamic_model(UBX_CFG_VALGET, &valget), Some(5));
+        assert_eq!(DynamicModel::from_code(5), Some(DynamicModel::Sea));
+        assert_eq!(DynamicModel::from_code(1), None);
+    }
+
+    fn chip(series: UbloxSeries, extensions: &[&str]) -> UbloxChipInfo {
+        UbloxChipInfo {
+            sw_version: "ROM CORE 3.01 (107888)".into(),
+            hw_version: "00080000".into(),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            series,
+            chip_name: "NEO-M8N".into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_cfg_gnss_enabled() {
+        let msg = build_cfg_gnss_series8_marine();
+        let payload = &msg[6..msg.len() - 2];
+        let enabled = parse_cfg_gnss_enabled(payload).unwrap();
+        assert_eq!(enabled, vec![0, 1, 2, 6]);
+
+        // Truncated block list
+        assert!(parse_cfg_gnss_enabled(&payload[..10]).is_none());
+    }
+
+    #[test]
+    fn test_build_cfg_gnss_for_selected_constellations() {
+        let selected = [Constellation::Gps, Constellation::Galileo];
+        let msg = build_cfg_gnss(&selected);
+        let payload = &msg[6..msg.len() - 2];
+        assert_eq!(payload[3], 3); // GPS and Galileo, plus SBAS
+        assert_eq!(parse_cfg_gnss_enabled(payload).unwrap(), vec![0, 1, 2]);
+
+        // Selecting exactly these also sends the deselected ones with the enable bit
+        // cleared
+        let msg = build_constellation_commands(&UbloxSeries::Series8, &selected)
+            .unwrap()
+            .remove(0);
+        let payload = &msg[6..msg.len() - 2];
+        assert_eq!(payload[3], 5); // SBAS and all four constellations
+        assert_eq!(parse_cfg_gnss_enabled(payload).unwrap(), vec![0, 1, 2]);
+        let glonass = payload[4..].chunks(8).find(|b| b[0] == GNSS_ID_GLONASS).unwrap();
+        assert_eq!(glonass[4] & 0x01, 0);
+        assert_eq!(glonass[1], 0); // no reserved channels
+
+        // Series 7 only gets blocks for what it can track: GPS, SBAS and GLONASS
+        let msg = build_constellation_commands(&UbloxSeries::Series7, &[Constellation::Gps])
+            .unwrap()
+            .remove(0);
+        let payload = &msg[6..msg.len() - 2];
+        let ids: Vec<u8> = payload[4..].chunks(8).map(|b| b[0]).collect();
+        assert_eq!(ids, vec![GNSS_ID_GPS, GNSS_ID_SBAS, GNSS_ID_GLONASS]);
+        assert_eq!(parse_cfg_gnss_enabled(payload).unwrap(), vec![0, 1]);
+
+        assert!(validate_constellations(&UbloxSeries::Series7, &[Constellation::Galileo]).is_err());
+        let all = [
+            Constellation::Gps,
+            Constellation::Galileo,
+            Constellation::Beidou,
+            Constellation::Glonass,
+        ];
+        assert!(validate_constellations(&UbloxSeries::Series8, &all).is_err());
+        assert!(validate_constellations(&UbloxSeries::Series10, &all).is_ok());
+        assert!(validate_constellations(&UbloxSeries::Series8, &[]).is_err());
+
+        // M10 switches the unselected constellations off explicitly
+        let valset = build_constellation_commands(&UbloxSeries::Series10, &[Constellation::Gps])
+            .unwrap()
+            .remove(0);
+        let glonass_off = [CFG_SIGNAL_GLO_ENA.to_le_bytes().as_slice(), &[0]].concat();
+        assert!(valset.windows(5).any(|w| w == glonass_off.as_slice()));
+    }
+
+    #[test]
+    fn test_protocol_version() {
+        let m8 = chip(UbloxSeries::Series8, &["PROTVER=18.00"]);
+        assert_eq!(protocol_version(&m8), Some(18.0));
+        let g7 = chip(UbloxSeries::Series7, &["PROTVER 14.00"]);
+        assert_eq!(protocol_version(&g7), Some(14.0));
+        let missing = chip(UbloxSeries::Series8, &["FWVER=SPG 3.01"]);
+        assert_eq!(protocol_version(&missing), None);
+    }
+
+    #[test]
+    fn test_clone_indicators_genuine() {
+        let info = chip(UbloxSeries::Series8, &["FWVER=SPG 3.01", "PROTVER=18.00"]);
+        let requested = requested_gnss_ids(&info.series);
+        assert!(clone_indicators(&info, &requested, Some(&[0, 1, 2, 6])).is_empty());
+        // Missing readback alone is not flagged
+        assert!(clone_indicators(&info, &requested, None).is_empty());
+    }
+
+    #[test]
+    fn test_clone_indicators_suspect() {
+        // GPS-only readback and a series 7 protocol on claimed M8 hardware
+        let info = chip(UbloxSeries::Series8, &["PROTVER=14.00"]);
+        let requested = requested_gnss_ids(&info.series);
+        let indicators = clone_indicators(&info, &requested, Some(&[0]));
+        assert_eq!(indicators.len(), 2);
+        assert!(indicators[0].contains("1 of 4"));
+        assert!(indicators[1].contains("PROTVER 14.00"));
+    }
+
+    #[test]
+    fn test_cfg_valset_single_key() {
+        let msg = build_cfg_valset(&[(CFG_RATE_MEAS, 1000)]);
+        assert_eq!(msg[2], 0x06); // class CFG
+        assert_eq!(msg[3], 0x8A); // id VALSET
+        // Payload: 4 byte header + 4 byte key + 2 byte U2 value
+        assert_eq!(u16::from_le_bytes([msg[4], msg[5]]), 10);
+        assert_eq!(&msg[6..10], &[0x00, 0x07, 0x00, 0x00]);
+        assert_eq!(&msg[10..14], &[0x01, 0x00, 0x21, 0x30]);
+        assert_eq!(&msg[14..16], &[0xE8, 0x03]);
+        let (ck_a, ck_b) = ubx_checksum(&msg[2..16]);
+        assert_eq!((msg[16], msg[17]), (ck_a, ck_b));
+
+        // L and U1 values are a single byte
+        assert_eq!(build_cfg_valset(&[(CFG_SIGNAL_GPS_ENA, 1)]).len(), 8 + 4 + 5);
+    }
+
+    #[test]
+    fn test_cfg_prt_uart_baud() {
+        let msg = build_cfg_prt_uart(115200);
+        assert_eq!((msg[2], msg[3]), (0x06, 0x00)); // CFG-PRT
+        assert_eq!(u16::from_le_bytes([msg[4], msg[5]]), 20);
+        assert_eq!(msg[6], 0x01); // UART1
+        assert_eq!(&msg[10..14], &[0xC0, 0x08, 0x00, 0x00]); // 8N1
+        assert_eq!(u32::from_le_bytes(msg[14..18].try_into().unwrap()), 115200);
+        let (ck_a, ck_b) = ubx_checksum(&msg[2..26]);
+        assert_eq!((msg[26], msg[27]), (ck_a, ck_b));
+
+        // M10: VALSET to RAM only, so a bad rate doesn't survive a power cycle
+        let msg = build_baud_rate_change(&UbloxSeries::Series10, 115200);
+        assert_eq!(msg[3], 0x8A);
+        assert_eq!(msg[7], 0x01);
+        assert_eq!(&msg[10..14], &CFG_UART1_BAUDRATE.to_le_bytes());
+        assert_eq!(u32::from_le_bytes(msg[14..18].try_into().unwrap()), 115200);
+        assert_eq!(build_baud_rate_change(&UbloxSeries::Series8, 9600), build_cfg_prt_uart(9600));
+    }
+
+    #[test]
+    fn test_series_aware_command_selection() {
+        for series in [UbloxSeries::Series9, UbloxSeries::Series10] {
+            assert!(series.uses_valset());
+            let cmds = get_optimization_commands(&series);
+            assert!(cmds.iter().all(|c| c[2] == 0x06 && c[3] == 0x8A));
+        }
+        for series in [UbloxSeries::Series7, UbloxSeries::Series8] {
+            assert!(!series.uses_valset());
+            let cmds = get_optimization_commands(&series);
+            assert!(cmds.iter().all(|c| c[3] != 0x8A));
+        }
+    }
+
+    #[test]
+    fn test_parse_mon_ver_series10() {
+        let mut payload = vec![0u8; 70];
+        payload[0..13].copy_from_slice(b"ROM SPG 5.10 ");
+        payload[30..38].copy_from_slice(b"000A0000");
+        payload[40..52].copy_from_slice(b"MOD=MAX-M10S");
+
+        let info = parse_mon_ver(&payload).unwrap();
+        assert_eq!(info.series, UbloxSeries::Series10);
+        assert_eq!(info.chip_name, "MAX-M10S");
+    }
+
+    #[test]
+    fn test_capabilities_list_builders_and_profiles() {
+        let capabilities = list_ubx_capabilities();
+        let builder = |name: &str| {
+            capabilities.builders.iter().find(|b| b.name == name).unwrap().clone()
+        };
+        assert_eq!(builder("build_cfg_sbas_enable").message, "CFG-SBAS");
+        assert_eq!(builder("build_cfg_nav5_model").message, "CFG-NAV5");
+        assert_eq!(builder("build_cfg_gnss").message, "CFG-GNSS");
+        assert!(builder("build_cfg_save_all").writes_flash);
+        assert!(builder("build_cfg_valset").writes_flash);
+        assert!(!builder("build_cfg_rate_1hz").writes_flash);
+        assert!(!builder("build_baud_rate_change").writes_flash);
+        assert_eq!(builder("build_baud_rate_change").series, VALSET_SERIES);
+
+        // Every series has a profile whose steps match the commands it sends
+        for series in ALL_SERIES.iter().chain([&UbloxSeries::Unknown]) {
+            let profile = capabilities
+                .marine_profiles
+                .iter()
+                .find(|p| p.series.contains(series))
+                .unwrap();
+            let commands = get_optimization_commands(series);
+            assert_eq!(profile.steps.len(), commands.len());
+            assert!(profile.steps.last().unwrap().writes_flash);
+        }
+        let series8 = &capabilities.marine_profiles[1];
+        assert_eq!(series8.steps.len(), 12);
+        assert_eq!(series8.steps[0].message, "CFG-GNSS");
+        assert_eq!(series8.steps[11].message, "CFG-CFG");
+    }
+
     #[test]
     fn test_last_command_is_save() {
         let cmds = get_optimization_commands(&UbloxSeries::Series8);
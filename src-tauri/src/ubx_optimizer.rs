@@ -4,19 +4,60 @@
 //                -> Stabilizing -> CollectingResult -> Complete | Error
 
 use crate::nmea::GpsData;
+use crate::test_criteria::CriterionResult;
 use crate::ubx_config::{self, UbloxChipInfo, UbloxSeries};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 // Phase durations in seconds
 const BASELINE_DURATION: u64 = 30;
 const STABILIZATION_DURATION: u64 = 30;
 const RESULT_DURATION: u64 = 30;
 const MON_VER_TIMEOUT: u64 = 5;
+/// MON-VER polls before settling on an identification, and how many identical
+/// parses it takes to accept one; a corrupted response on a noisy link is outvoted
+const MON_VER_ATTEMPTS: usize = 3;
+const MON_VER_AGREEMENT: usize = 2;
+/// Default spacing of metrics samples; one per fix on a 1Hz receiver
+const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 1000;
+/// Bounds on `OptimizerConfig::sample_interval_ms`
+const MIN_SAMPLE_INTERVAL_MS: u64 = 100;
+const MAX_SAMPLE_INTERVAL_MS: u64 = 5000;
 
 // ============ Types ============
 
+/// Optimizer tuning, stored as `optimizer` in the app settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OptimizerConfig {
+    /// Minimum spacing of metrics samples. A sample is also only taken when the
+    /// fix time has moved on, so before/after sample counts do not depend on the
+    /// receiver's output rate or on how often the optimizer is ticked.
+    pub sample_interval_ms: u64,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval_ms: DEFAULT_SAMPLE_INTERVAL_MS,
+        }
+    }
+}
+
+impl OptimizerConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let range = MIN_SAMPLE_INTERVAL_MS..=MAX_SAMPLE_INTERVAL_MS;
+        if !range.contains(&self.sample_interval_ms) {
+            return Err(format!(
+                "Optimizer sample interval must be {}-{} ms, got {}",
+                MIN_SAMPLE_INTERVAL_MS, MAX_SAMPLE_INTERVAL_MS, self.sample_interval_ms
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum OptimizePhase {
@@ -40,11 +81,21 @@ pub struct PerformanceSnapshot {
     pub constellations: Vec<String>,
     pub avg_fix_quality: f32,
     pub sample_count: u32,
+    /// Wall-clock span of the collection window (first sample to snapshot)
+    pub window_seconds: f32,
+    /// Fraction of samples (0.0-1.0) that carried a valid fix
+    pub valid_fix_fraction: f32,
 }
 
 /// Before/after comparison report
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptimizationReport {
+    /// Unique ID of this optimization (UUID v4); empty in reports from older versions
+    #[serde(default)]
+    pub id: String,
+    /// ID of the test run next to verify this optimization, once one has started
+    #[serde(default)]
+    pub related_test_id: Option<String>,
     pub chip_info: UbloxChipInfo,
     pub profile_applied: String,
     pub before: PerformanceSnapshot,
@@ -54,6 +105,14 @@ pub struct OptimizationReport {
     pub snr_improvement_pct: f32,
     pub constellation_improvement: i32,
     pub timestamp: String,
+    /// Set when any clone-detection heuristic tripped (see ubx_config)
+    #[serde(default)]
+    pub suspected_clone: bool,
+    #[serde(default)]
+    pub clone_indicators: Vec<String>,
+    /// Empty in reports saved before the clone check was added
+    #[serde(default)]
+    pub clone_check: CriterionResult,
 }
 
 /// Status sent to the frontend each poll cycle
@@ -65,6 +124,8 @@ pub struct OptimizeStatus {
     pub phase_duration_seconds: f32,
     pub error: Option<String>,
     pub report: Option<OptimizationReport>,
+    /// Where the completed report was saved, once persisted
+    pub report_path: Option<String>,
     pub baseline_snapshot: Option<PerformanceSnapshot>,
 }
 
@@ -76,6 +137,11 @@ struct MetricsCollector {
     snr_samples: Vec<f32>,
     fix_quality_samples: Vec<u8>,
     constellation_sets: Vec<HashSet<String>>,
+    start: Option<Instant>,
+    total_samples: u32,
+    valid_fix_samples: u32,
+    /// When the last sample offered through `offer` was taken, and its fix time
+    last_sampled: Option<(Instant, Option<String>)>,
 }
 
 impl MetricsCollector {
@@ -86,10 +152,38 @@ impl MetricsCollector {
             snr_samples: Vec::new(),
             fix_quality_samples: Vec::new(),
             constellation_sets: Vec::new(),
+            start: None,
+            total_samples: 0,
+            valid_fix_samples: 0,
+            last_sampled: None,
+        }
+    }
+
+    /// Take `data` as a sample unless one was taken less than `interval` ago or the
+    /// fix time has not changed since the last one. Returns true if it was taken.
+    fn offer(&mut self, data: &GpsData, now: Instant, interval: Duration) -> bool {
+        if let Some((at, fix_time)) = &self.last_sampled {
+            if now.duration_since(*at) < interval {
+                return false;
+            }
+            if data.timestamp.is_some() && data.timestamp == *fix_time {
+                return false;
+            }
         }
+        self.last_sampled = Some((now, data.timestamp.clone()));
+        self.add_sample(data);
+        true
     }
 
     fn add_sample(&mut self, data: &GpsData) {
+        if self.start.is_none() {
+            self.start = Some(Instant::now());
+        }
+        self.total_samples += 1;
+        if data.fix_quality.unwrap_or(0) >= 1 {
+            self.valid_fix_samples += 1;
+        }
+
         if let Some(hdop) = data.hdop {
             self.hdop_samples.push(hdop);
         }
@@ -161,6 +255,12 @@ impl MetricsCollector {
         let mut sorted_consts: Vec<String> = all_consts.into_iter().collect();
         sorted_consts.sort();
 
+        let valid_fix_fraction = if self.total_samples == 0 {
+            0.0
+        } else {
+            self.valid_fix_samples as f32 / self.total_samples as f32
+        };
+
         PerformanceSnapshot {
             avg_hdop,
             avg_satellites: avg_sats,
@@ -172,6 +272,11 @@ impl MetricsCollector {
                 .hdop_samples
                 .len()
                 .max(self.satellite_samples.len()) as u32,
+            window_seconds: self
+                .start
+                .map(|t| t.elapsed().as_secs_f32())
+                .unwrap_or(0.0),
+            valid_fix_fraction,
         }
     }
 }
@@ -179,6 +284,7 @@ impl MetricsCollector {
 // ============ Optimizer State Machine ============
 
 pub struct UbxOptimizer {
+    pub config: OptimizerConfig,
     pub phase: OptimizePhase,
     pub chip_info: Option<UbloxChipInfo>,
     phase_start: Option<Instant>,
@@ -186,16 +292,26 @@ pub struct UbxOptimizer {
     result_collector: MetricsCollector,
     baseline_snapshot: Option<PerformanceSnapshot>,
     report: Option<OptimizationReport>,
+    report_persisted: bool,
+    /// Path of the saved report file
+    pub report_path: Option<String>,
     error: Option<String>,
     /// Commands queued for GpsManager to send
     pub pending_commands: Vec<Vec<u8>>,
     /// True when waiting for MON-VER binary response
     pub awaiting_mon_ver: bool,
+    /// MON-VER reads so far, None where the payload didn't parse
+    mon_ver_reads: Vec<Option<UbloxChipInfo>>,
+    /// True when waiting for the CFG-GNSS readback after applying the profile
+    pub awaiting_cfg_gnss: bool,
+    /// GNSS IDs the receiver reports as enabled after the profile was applied
+    gnss_readback: Option<Vec<u8>>,
 }
 
 impl UbxOptimizer {
     pub fn new() -> Self {
         Self {
+            config: OptimizerConfig::default(),
             phase: OptimizePhase::Idle,
             chip_info: None,
             phase_start: None,
@@ -203,46 +319,106 @@ impl UbxOptimizer {
             result_collector: MetricsCollector::new(),
             baseline_snapshot: None,
             report: None,
+            report_persisted: false,
+            report_path: None,
             error: None,
             pending_commands: Vec::new(),
             awaiting_mon_ver: false,
+            mon_ver_reads: Vec::new(),
+            awaiting_cfg_gnss: false,
+            gnss_readback: None,
         }
     }
 
-    /// Begin the optimization process
+    /// Begin the optimization process, keeping the current config
     pub fn start(&mut self) {
+        let config = self.config.clone();
         *self = Self::new();
+        self.config = config;
         self.phase = OptimizePhase::IdentifyingChip;
         self.phase_start = Some(Instant::now());
         self.pending_commands.push(ubx_config::build_mon_ver_poll());
         self.awaiting_mon_ver = true;
     }
 
-    /// Called when a UBX-MON-VER response is received from the reader thread
+    /// Called when a UBX-MON-VER response is received from the reader thread. The
+    /// chip is identified once `MON_VER_AGREEMENT` reads agree; until then MON-VER is
+    /// polled again, up to `MON_VER_ATTEMPTS` times.
     pub fn on_mon_ver_response(&mut self, payload: &[u8]) {
+        if !self.awaiting_mon_ver {
+            return;
+        }
+        let read = ubx_config::parse_mon_ver(payload);
+        if read.is_none() {
+            log::warn!("Unparseable MON-VER response ({} bytes)", payload.len());
+        }
+        self.mon_ver_reads.push(read);
+
+        let parsed = || self.mon_ver_reads.iter().flatten();
+        let agreed = parsed()
+            .find(|info| parsed().filter(|other| other == info).count() >= MON_VER_AGREEMENT)
+            .cloned();
+        if let Some(info) = agreed {
+            self.identify(info);
+        } else if self.mon_ver_reads.len() < MON_VER_ATTEMPTS {
+            let reads = self.mon_ver_reads.len();
+            log::info!("MON-VER read {} not yet confirmed, polling again", reads);
+            self.pending_commands.push(ubx_config::build_mon_ver_poll());
+            self.phase_start = Some(Instant::now());
+        } else {
+            self.identify_unconfirmed();
+        }
+    }
+
+    /// Accept a chip identification and move on to the baseline
+    fn identify(&mut self, info: UbloxChipInfo) {
+        log::info!(
+            "Chip identified: {} (HW: {}, series: {})",
+            info.chip_name,
+            info.hw_version,
+            info.series
+        );
         self.awaiting_mon_ver = false;
+        self.chip_info = Some(info);
+        self.phase = OptimizePhase::CollectingBaseline;
+        self.phase_start = Some(Instant::now());
+    }
 
-        match ubx_config::parse_mon_ver(payload) {
+    /// Out of MON-VER polls without two reads agreeing: carry on with the latest
+    /// read as an Unknown series, so only the generic profile is applied
+    fn identify_unconfirmed(&mut self) {
+        match self.mon_ver_reads.iter().rev().flatten().next().cloned() {
             Some(info) => {
-                log::info!(
-                    "Chip identified: {} (HW: {}, series: {})",
-                    info.chip_name,
-                    info.hw_version,
-                    info.series
-                );
-                self.chip_info = Some(info);
-                self.phase = OptimizePhase::CollectingBaseline;
-                self.phase_start = Some(Instant::now());
+                log::warn!("MON-VER reads disagree, treating {} as Unknown series", info.chip_name);
+                self.identify(UbloxChipInfo { series: UbloxSeries::Unknown, ..info });
             }
             None => {
+                self.awaiting_mon_ver = false;
                 self.error = Some("Failed to parse MON-VER response".to_string());
                 self.phase = OptimizePhase::Error;
             }
         }
     }
 
+    /// Called when a UBX-CFG-GNSS readback is received from the reader thread
+    pub fn on_cfg_gnss_response(&mut self, payload: &[u8]) {
+        self.awaiting_cfg_gnss = false;
+
+        match ubx_config::parse_cfg_gnss_enabled(payload) {
+            Some(enabled) => {
+                log::info!("CFG-GNSS readback: enabled GNSS IDs {:?}", enabled);
+                self.gnss_readback = Some(enabled);
+            }
+            None => log::warn!("Failed to parse CFG-GNSS readback"),
+        }
+    }
+
     /// Called when MON-VER poll times out
     fn on_mon_ver_timeout(&mut self) {
+        if !self.mon_ver_reads.is_empty() {
+            self.identify_unconfirmed();
+            return;
+        }
         self.awaiting_mon_ver = false;
         self.error = Some(
             "Could not identify chip — device may not be u-blox or UBX protocol is disabled"
@@ -251,13 +427,16 @@ impl UbxOptimizer {
         self.phase = OptimizePhase::Error;
     }
 
-    /// Feed a GPS data sample; called each poll cycle (~500ms).
+    /// Feed the latest GPS data; called by the backend driver every few hundred ms.
+    /// Metrics are sampled at `config.sample_interval_ms` regardless of the tick rate.
     /// Returns true if there are pending commands to send.
     pub fn tick(&mut self, data: &GpsData) -> bool {
         let elapsed = self
             .phase_start
             .map(|t| t.elapsed().as_secs())
             .unwrap_or(0);
+        let now = Instant::now();
+        let sample_interval = Duration::from_millis(self.config.sample_interval_ms);
 
         match self.phase {
             OptimizePhase::IdentifyingChip => {
@@ -266,12 +445,14 @@ impl UbxOptimizer {
                 }
             }
             OptimizePhase::CollectingBaseline => {
-                self.baseline_collector.add_sample(data);
+                self.baseline_collector.offer(data, now, sample_interval);
                 if elapsed >= BASELINE_DURATION {
                     self.baseline_snapshot = Some(self.baseline_collector.snapshot());
                     log::info!(
-                        "Baseline collected ({} samples): HDOP={:.2}, Sats={:.1}, SNR={:.1}",
+                        "Baseline collected ({} samples over {:.0}s, {:.0}% valid fixes): HDOP={:.2}, Sats={:.1}, SNR={:.1}",
                         self.baseline_snapshot.as_ref().unwrap().sample_count,
+                        self.baseline_snapshot.as_ref().unwrap().window_seconds,
+                        self.baseline_snapshot.as_ref().unwrap().valid_fix_fraction * 100.0,
                         self.baseline_snapshot.as_ref().unwrap().avg_hdop,
                         self.baseline_snapshot.as_ref().unwrap().avg_satellites,
                         self.baseline_snapshot.as_ref().unwrap().avg_snr,
@@ -283,6 +464,13 @@ impl UbxOptimizer {
                         .map(|c| c.series.clone())
                         .unwrap_or(UbloxSeries::Unknown);
                     self.pending_commands = ubx_config::get_optimization_commands(&series);
+                    // Read the constellation config back for clone detection
+                    // (legacy CFG-GNSS is not available on VALSET-configured chips)
+                    if !series.uses_valset() {
+                        self.pending_commands
+                            .push(ubx_config::build_cfg_gnss_poll());
+                        self.awaiting_cfg_gnss = true;
+                    }
                     self.phase = OptimizePhase::ApplyingProfile;
                     self.phase_start = Some(Instant::now());
                     return true;
@@ -297,12 +485,16 @@ impl UbxOptimizer {
             }
             OptimizePhase::Stabilizing => {
                 if elapsed >= STABILIZATION_DURATION {
+                    if self.awaiting_cfg_gnss {
+                        log::warn!("No CFG-GNSS readback received; skipping constellation check");
+                        self.awaiting_cfg_gnss = false;
+                    }
                     self.phase = OptimizePhase::CollectingResult;
                     self.phase_start = Some(Instant::now());
                 }
             }
             OptimizePhase::CollectingResult => {
-                self.result_collector.add_sample(data);
+                self.result_collector.offer(data, now, sample_interval);
                 if elapsed >= RESULT_DURATION {
                     let after = self.result_collector.snapshot();
                     let before = self.baseline_snapshot.clone().unwrap_or_default();
@@ -349,14 +541,39 @@ impl UbxOptimizer {
             .map(|c| &c.series)
             .unwrap_or(&UbloxSeries::Unknown);
 
+        let chip_info = self.chip_info.clone().unwrap_or(UbloxChipInfo {
+            sw_version: "Unknown".into(),
+            hw_version: "Unknown".into(),
+            extensions: vec![],
+            series: UbloxSeries::Unknown,
+            chip_name: "Unknown".into(),
+        });
+
+        let clone_indicators = ubx_config::clone_indicators(
+            &chip_info,
+            &ubx_config::requested_gnss_ids(series),
+            self.gnss_readback.as_deref(),
+        );
+        if !clone_indicators.is_empty() {
+            log::warn!("Suspected clone module: {}", clone_indicators.join("; "));
+        }
+        let clone_check = CriterionResult {
+            name: "Clone Check".to_string(),
+            passed: clone_indicators.is_empty(),
+            warning: false,
+            expected: "Genuine u-blox behaviour".to_string(),
+            actual: if clone_indicators.is_empty() {
+                "No clone indicators".to_string()
+            } else {
+                clone_indicators.join("; ")
+            },
+            history: Vec::new(),
+        };
+
         OptimizationReport {
-            chip_info: self.chip_info.clone().unwrap_or(UbloxChipInfo {
-                sw_version: "Unknown".into(),
-                hw_version: "Unknown".into(),
-                extensions: vec![],
-                series: UbloxSeries::Unknown,
-                chip_name: "Unknown".into(),
-            }),
+            id: uuid::Uuid::new_v4().to_string(),
+            related_test_id: None,
+            chip_info,
             profile_applied: ubx_config::profile_name(series).to_string(),
             before: before.clone(),
             after: after.clone(),
@@ -365,6 +582,9 @@ impl UbxOptimizer {
             snr_improvement_pct: snr_imp,
             constellation_improvement: const_delta,
             timestamp: chrono::Utc::now().to_rfc3339(),
+            suspected_clone: !clone_indicators.is_empty(),
+            clone_indicators,
+            clone_check,
         }
     }
 
@@ -390,10 +610,27 @@ impl UbxOptimizer {
             phase_duration_seconds: duration,
             error: self.error.clone(),
             report: self.report.clone(),
+            report_path: self.report_path.clone(),
             baseline_snapshot: self.baseline_snapshot.clone(),
         }
     }
 
+    /// The saved report, if no test has been linked to it as its verification yet
+    pub fn unverified_report(&mut self) -> Option<&mut OptimizationReport> {
+        self.report_path.as_ref()?;
+        self.report.as_mut().filter(|r| r.related_test_id.is_none())
+    }
+
+    /// Hand out the completed report exactly once, for persisting to disk
+    pub fn take_unsaved_report(&mut self) -> Option<OptimizationReport> {
+        if self.report_persisted {
+            return None;
+        }
+        let report = self.report.clone()?;
+        self.report_persisted = true;
+        Some(report)
+    }
+
     fn phase_elapsed_secs(&self) -> f32 {
         self.phase_start
             .map(|t| t.elapsed().as_secs_f32())
@@ -402,7 +639,9 @@ impl UbxOptimizer {
 
     /// Reset to idle
     pub fn reset(&mut self) {
+        let config = self.config.clone();
         *self = Self::new();
+        self.config = config;
     }
 }
 
@@ -412,6 +651,7 @@ impl UbxOptimizer {
 mod tests {
     use super::*;
     use crate::nmea::SatelliteInfo;
+    use crate::test_support;
 
     fn make_gps_data(
         hdop: f32,
@@ -430,11 +670,8 @@ mod tests {
 
     fn make_sat(constellation: &str, snr: f32) -> SatelliteInfo {
         SatelliteInfo {
-            prn: 1,
-            elevation: Some(45.0),
-            azimuth: Some(180.0),
-            snr: Some(snr),
             constellation: constellation.to_string(),
+            ..test_support::sat(1, 45.0, 180.0, snr)
         }
     }
 
@@ -476,6 +713,61 @@ mod tests {
         assert_eq!(snap.avg_hdop, 0.0);
         assert_eq!(snap.avg_satellites, 0.0);
         assert_eq!(snap.sample_count, 0);
+        assert_eq!(snap.window_seconds, 0.0);
+        assert_eq!(snap.valid_fix_fraction, 0.0);
+    }
+
+    #[test]
+    fn test_metrics_collector_valid_fix_fraction() {
+        let mut collector = MetricsCollector::new();
+
+        // Sparse samples: no-fix ticks carry no HDOP or satellite count
+        collector.add_sample(&make_gps_data(1.2, 8, 1, vec![make_sat("GPS", 35.0)]));
+        collector.add_sample(&GpsData {
+            fix_quality: Some(0),
+            ..GpsData::default()
+        });
+        collector.add_sample(&make_gps_data(1.4, 9, 1, vec![make_sat("GPS", 36.0)]));
+        collector.add_sample(&make_gps_data(1.3, 9, 2, vec![make_sat("GPS", 37.0)]));
+
+        let snap = collector.snapshot();
+        assert_eq!(snap.sample_count, 3);
+        assert!((snap.valid_fix_fraction - 0.75).abs() < 0.001);
+        assert!(snap.window_seconds >= 0.0);
+    }
+
+    #[test]
+    fn test_metrics_collector_samples_once_per_fix_time() {
+        let interval = Duration::from_millis(1000);
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+        let fix = |time: &str| GpsData {
+            timestamp: Some(time.to_string()),
+            ..make_gps_data(1.0, 8, 1, vec![make_sat("GPS", 35.0)])
+        };
+
+        // 1Hz receiver ticked every 250ms: each fix time is seen four times
+        let mut slow = MetricsCollector::new();
+        for tick in 0..12u64 {
+            let time = format!("12:00:0{}", tick / 4);
+            slow.offer(&fix(&time), at(tick * 250), interval);
+        }
+
+        // 5Hz receiver over the same 3s: a new fix time on every tick
+        let mut fast = MetricsCollector::new();
+        for tick in 0..12u64 {
+            let time = format!("12:00:0{}.{:03}", tick / 4, (tick % 4) * 250);
+            fast.offer(&fix(&time), at(tick * 250), interval);
+        }
+
+        assert_eq!(slow.snapshot().sample_count, 3);
+        assert_eq!(fast.snapshot().sample_count, 3);
+
+        // A stalled fix time is not resampled once the interval has passed
+        let mut stalled = MetricsCollector::new();
+        assert!(stalled.offer(&fix("12:00:00"), at(0), interval));
+        assert!(!stalled.offer(&fix("12:00:00"), at(1500), interval));
+        assert!(stalled.offer(&fix("12:00:02"), at(2000), interval));
     }
 
     #[test]
@@ -508,6 +800,10 @@ mod tests {
         // HW version at bytes 30-39
         payload[30..38].copy_from_slice(b"00080000");
 
+        opt.on_mon_ver_response(&payload);
+        // One read is not enough: MON-VER is polled again to confirm it
+        assert_eq!(opt.phase, OptimizePhase::IdentifyingChip);
+        assert_eq!(opt.pending_commands.len(), 2);
         opt.on_mon_ver_response(&payload);
         assert_eq!(opt.phase, OptimizePhase::CollectingBaseline);
         assert!(!opt.awaiting_mon_ver);
@@ -515,6 +811,37 @@ mod tests {
         assert_eq!(opt.chip_info.as_ref().unwrap().series, UbloxSeries::Series8);
     }
 
+    #[test]
+    fn test_corrupt_mon_ver_outvoted() {
+        let mon_ver = |hw: &[u8]| {
+            let mut payload = vec![0u8; 40];
+            payload[30..30 + hw.len()].copy_from_slice(hw);
+            payload
+        };
+        let m8 = mon_ver(b"00080000");
+        // A flipped bit turns the M8 hardware version into a u-blox 7 one
+        let corrupt = mon_ver(b"00070000");
+
+        let mut opt = UbxOptimizer::new();
+        opt.start();
+        opt.on_mon_ver_response(&corrupt);
+        opt.on_mon_ver_response(&m8);
+        assert_eq!(opt.phase, OptimizePhase::IdentifyingChip);
+        opt.on_mon_ver_response(&m8);
+        assert_eq!(opt.phase, OptimizePhase::CollectingBaseline);
+        assert_eq!(opt.chip_info.as_ref().unwrap().series, UbloxSeries::Series8);
+        assert_eq!(opt.pending_commands.len(), 3);
+
+        // Three reads that never agree fall back to Unknown
+        let mut opt = UbxOptimizer::new();
+        opt.start();
+        for payload in [&corrupt, &m8, &m8[..20]] {
+            opt.on_mon_ver_response(payload);
+        }
+        assert_eq!(opt.phase, OptimizePhase::CollectingBaseline);
+        assert_eq!(opt.chip_info.as_ref().unwrap().series, UbloxSeries::Unknown);
+    }
+
     #[test]
     fn test_improvement_calculation_hdop_decrease_is_positive() {
         // HDOP going from 3.0 to 1.5 = 50% improvement
@@ -526,6 +853,8 @@ mod tests {
             constellations: vec!["GPS".into()],
             avg_fix_quality: 1.0,
             sample_count: 10,
+            window_seconds: 30.0,
+            valid_fix_fraction: 1.0,
         };
         let after = PerformanceSnapshot {
             avg_hdop: 1.5,
@@ -535,6 +864,8 @@ mod tests {
             constellations: vec!["GPS".into(), "GLONASS".into(), "Galileo".into()],
             avg_fix_quality: 1.0,
             sample_count: 10,
+            window_seconds: 30.0,
+            valid_fix_fraction: 1.0,
         };
 
         let mut opt = UbxOptimizer::new();
@@ -553,6 +884,56 @@ mod tests {
         assert_eq!(report.constellation_improvement, 2);
     }
 
+    #[test]
+    fn test_report_flags_suspected_clone() {
+        let mut opt = UbxOptimizer::new();
+        opt.chip_info = Some(UbloxChipInfo {
+            sw_version: "ROM CORE 3.01 (107888)".into(),
+            hw_version: "00080000".into(),
+            extensions: vec!["PROTVER=18.00".into()],
+            series: UbloxSeries::Series8,
+            chip_name: "NEO-M8N".into(),
+        });
+
+        // CFG-GNSS readback with only GPS enabled (block 0), GLONASS disabled (block 1)
+        let payload = [
+            0x00, 0x20, 0x20, 0x02, //
+            0x00, 0x08, 0x10, 0x00, 0x01, 0x00, 0x01, 0x01, //
+            0x06, 0x08, 0x0E, 0x00, 0x00, 0x00, 0x01, 0x01,
+        ];
+        opt.awaiting_cfg_gnss = true;
+        opt.on_cfg_gnss_response(&payload);
+        assert!(!opt.awaiting_cfg_gnss);
+
+        let report = opt.build_report(
+            &PerformanceSnapshot::default(),
+            &PerformanceSnapshot::default(),
+        );
+        assert!(report.suspected_clone);
+        assert_eq!(report.clone_indicators.len(), 1);
+        assert!(!report.clone_check.passed);
+        assert_eq!(report.clone_check.name, "Clone Check");
+
+        // Reports saved before the clone check still load
+        let mut json = serde_json::to_value(&report).unwrap();
+        json.as_object_mut().unwrap().remove("clone_check");
+        let old: OptimizationReport = serde_json::from_value(json).unwrap();
+        assert!(old.clone_check.name.is_empty());
+    }
+
+    #[test]
+    fn test_take_unsaved_report_once() {
+        let mut opt = UbxOptimizer::new();
+        assert!(opt.take_unsaved_report().is_none());
+
+        opt.report = Some(opt.build_report(
+            &PerformanceSnapshot::default(),
+            &PerformanceSnapshot::default(),
+        ));
+        assert!(opt.take_unsaved_report().is_some());
+        assert!(opt.take_unsaved_report().is_none());
+    }
+
     #[test]
     fn test_optimizer_reset() {
         let mut opt = UbxOptimizer::new();
@@ -1,8 +1,11 @@
 import { useState, useEffect, useCallback } from 'react';
 import {
-  getTestCriteria,
-  setTestCriteria,
+  getSettings,
+  setSettings,
   resetTestCriteria,
+  type AppSettings,
+  type CoordFormat,
+  type Datum,
   type TestCriteria,
 } from '../hooks/useTauri';
 
@@ -14,18 +17,24 @@ interface ConfigPanelProps {
 
 export function ConfigPanel({ visible, onClose, onCriteriaChanged }: ConfigPanelProps) {
   const [criteria, setCriteria] = useState<TestCriteria | null>(null);
+  const [settings, setLocalSettings] = useState<AppSettings | null>(null);
   const [saving, setSaving] = useState(false);
 
   useEffect(() => {
     if (!visible) return;
-    getTestCriteria().then(setCriteria).catch(console.error);
+    getSettings()
+      .then((loaded) => {
+        setLocalSettings(loaded);
+        setCriteria(loaded.criteria);
+      })
+      .catch(console.error);
   }, [visible]);
 
   const handleSave = useCallback(async () => {
-    if (!criteria) return;
+    if (!criteria || !settings) return;
     setSaving(true);
     try {
-      await setTestCriteria(criteria);
+      await setSettings({ ...settings, criteria });
       onCriteriaChanged(criteria);
       onClose();
     } catch (e) {
@@ -33,7 +42,7 @@ export function ConfigPanel({ visible, onClose, onCriteriaChanged }: ConfigPanel
     } finally {
       setSaving(false);
     }
-  }, [criteria, onClose, onCriteriaChanged]);
+  }, [criteria, settings, onClose, onCriteriaChanged]);
 
   const handleReset = useCallback(async () => {
     try {
@@ -51,7 +60,7 @@ export function ConfigPanel({ visible, onClose, onCriteriaChanged }: ConfigPanel
     }
   };
 
-  if (!visible || !criteria) return null;
+  if (!visible || !criteria || !settings) return null;
 
   return (
     <div className="config-overlay" onClick={onClose}>
@@ -102,6 +111,14 @@ export function ConfigPanel({ visible, onClose, onCriteriaChanged }: ConfigPanel
             min={0} max={20}
           />
 
+          <label>Strong Sat Min Elevation (deg, 0 = off)</label>
+          <input
+            type="number"
+            value={criteria.strong_sat_min_elevation_deg}
+            onChange={(e) => update('strong_sat_min_elevation_deg', Number(e.target.value))}
+            min={0} max={90}
+          />
+
           <label>Max TTFF (seconds)</label>
           <input
             type="number"
@@ -133,6 +150,28 @@ export function ConfigPanel({ visible, onClose, onCriteriaChanged }: ConfigPanel
             onChange={(e) => update('stability_duration_seconds', Number(e.target.value))}
             min={1} max={120}
           />
+
+          <label>Report Coordinate Format</label>
+          <select
+            value={settings.coord_format}
+            onChange={(e) =>
+              setLocalSettings({ ...settings, coord_format: e.target.value as CoordFormat })
+            }
+          >
+            <option value="decimal_degrees">Decimal degrees</option>
+            <option value="dms">Degrees-minutes-seconds</option>
+            <option value="utm">UTM</option>
+          </select>
+
+          <label>Report Datum</label>
+          <select
+            value={settings.datum}
+            onChange={(e) => setLocalSettings({ ...settings, datum: e.target.value as Datum })}
+          >
+            <option value="wgs84">WGS84</option>
+            <option value="nad27">NAD27</option>
+            <option value="nzgd49">NZGD49</option>
+          </select>
         </div>
 
         <div className="config-actions">
@@ -4,6 +4,8 @@ import {
   testGpsPort,
   connectGps,
   disconnectGps,
+  autoConnectLast,
+  getSettings,
   type DetectedPort,
   type GpsSourceStatus,
 } from '../hooks/useTauri';
@@ -210,9 +212,32 @@ export function DeviceDetection({ status, onConnected }: DeviceDetectionProps) {
     }
   }, []);
 
-  // Auto-detect on mount
+  // On mount, reconnect to the last good port (the backend auto-detects if it's gone)
   useEffect(() => {
-    handleAutoDetect();
+    const reconnect = async () => {
+      const settings = await getSettings().catch(() => null);
+      if (settings && !settings.reconnect_on_startup) {
+        await refreshPorts();
+        return;
+      }
+      setDetecting(true);
+      setScanProgress('Reconnecting to last GPS port...');
+      connectedRef.current = false;
+      try {
+        const [port, baud] = await autoConnectLast();
+        connectedRef.current = true;
+        setConnectedPort(port);
+        setConnectedBaud(baud);
+        onConnected(port, baud);
+      } catch (e: any) {
+        setError(e.message || 'Reconnect failed');
+        setShowManual(true);
+        await refreshPorts();
+      } finally {
+        setDetecting(false);
+      }
+    };
+    reconnect();
   }, []);
 
   const isConnected = status?.status === 'connected' || status?.status === 'receiving_data';
@@ -222,9 +247,9 @@ export function DeviceDetection({ status, onConnected }: DeviceDetectionProps) {
     ? '#00ff41'
     : isConnected
       ? '#00aaff'
-      : status?.status === 'connecting'
+      : status?.status === 'connecting' || status?.status === 'read_error'
         ? '#ffaa00'
-        : status?.status === 'error'
+        : status?.status === 'error' || status?.status === 'device_removed'
           ? '#ff3333'
           : '#666';
 
@@ -233,7 +258,15 @@ export function DeviceDetection({ status, onConnected }: DeviceDetectionProps) {
       <div className="conn-bar-left">
         <div className="status-dot" style={{ backgroundColor: statusColor }} />
         <span className="conn-bar-status">
-          {isReceiving ? 'Receiving' : isConnected ? 'Connected' : status?.status || 'Unknown'}
+          {isReceiving
+            ? 'Receiving'
+            : isConnected
+              ? 'Connected'
+              : status?.status === 'device_removed'
+                ? 'Unplugged - waiting for device'
+                : status?.status === 'read_error'
+                  ? 'Read error - reconnecting'
+                  : status?.status || 'Unknown'}
         </span>
         <span className="conn-bar-port">{connectedPort.port_name}</span>
         <span className="conn-bar-detail">{connectedBaud} baud</span>
@@ -106,8 +106,13 @@ export function HardwareInfo({ gpsData, connectedPort, connectedBaud, status }:
       globalMinSnr: allSnr.length > 0 ? Math.min(...allSnr) : 0,
       signalDist: { excellent, good, fair, weak },
       elevDist: { high: highElev, mid: midElev, low: lowElev },
-      hAcc: gpsData.hdop !== null ? (gpsData.hdop * 2.5).toFixed(1) : null,
-      vAcc: gpsData.vdop !== null ? (gpsData.vdop * 3.5).toFixed(1) : null,
+      // Receiver-reported estimates when available, else a rough guess from DOP
+      hAcc: gpsData.horizontal_accuracy_m !== null
+        ? gpsData.horizontal_accuracy_m.toFixed(1)
+        : gpsData.hdop !== null ? (gpsData.hdop * 2.5).toFixed(1) : null,
+      vAcc: gpsData.vertical_accuracy_m !== null
+        ? gpsData.vertical_accuracy_m.toFixed(1)
+        : gpsData.vdop !== null ? (gpsData.vdop * 3.5).toFixed(1) : null,
       pAcc: gpsData.pdop !== null ? (gpsData.pdop * 3.0).toFixed(1) : null,
     };
   }, [gpsData]);
@@ -164,6 +169,43 @@ export function HardwareInfo({ gpsData, connectedPort, connectedBaud, status }:
             {connectedBaud ? `${connectedBaud} baud (${(connectedBaud / 10).toFixed(0)} B/s)` : '-'}
           </span>
 
+          <span className="hw-label">Throughput</span>
+          <span className={`hw-value mono ${(status?.link_utilization ?? 0) > 0.9 ? 'fail' : ''}`}>
+            {status?.bytes_per_second != null
+              ? `${status.bytes_per_second.toFixed(0)} B/s` +
+                (status.link_utilization != null
+                  ? ` (${(status.link_utilization * 100).toFixed(0)}% of link)`
+                  : '')
+              : '-'}
+          </span>
+
+          <span className="hw-label">Update Rate</span>
+          <span className={`hw-value mono ${status?.rate_mismatch ? 'fail' : ''}`}>
+            {status?.observed_rate_hz != null
+              ? `${status.observed_rate_hz.toFixed(1)} Hz` +
+                (status.rate_mismatch ? ` (expected ${status.expected_rate_hz} Hz)` : '')
+              : '-'}
+          </span>
+
+          <span className="hw-label">GSV Output</span>
+          <span className={`hw-value ${status?.gsv_missing ? 'fail' : ''}`}>
+            {status?.gsv_missing ? 'Missing (no satellite data)' : 'OK'}
+          </span>
+
+          <span className="hw-label">Chip ID</span>
+          <span className="hw-value mono">{status?.chip_id || '-'}</span>
+
+          <span className="hw-label">Receiver Messages</span>
+          <span className="hw-value mono">
+            {status?.text_messages.length
+              ? status.text_messages.map((m, i) => (
+                  <div key={i} className={m.severity === 'error' || m.severity === 'warning' ? 'fail' : ''}>
+                    {m.text}
+                  </div>
+                ))
+              : '-'}
+          </span>
+
           <span className="hw-label">Last Fix</span>
           <span className="hw-value mono">{status?.last_fix_time || '-'}</span>
 
@@ -195,7 +237,13 @@ export function HardwareInfo({ gpsData, connectedPort, connectedBaud, status }:
           </span>
 
           <span className="hw-label">Course (COG)</span>
-          <span className="hw-value mono">{gpsData?.course != null ? `${gpsData.course.toFixed(1)}° T` : '-'}</span>
+          <span className="hw-value mono">
+            {gpsData?.course != null && gpsData.course_valid
+              ? `${gpsData.course.toFixed(1)}° T`
+              : gpsData?.course != null
+                ? '- (below 1 kn)'
+                : '-'}
+          </span>
 
           <span className="hw-label">Heading (HDG)</span>
           <span className="hw-value mono">{gpsData?.heading != null ? `${gpsData.heading.toFixed(1)}° T` : '-'}</span>
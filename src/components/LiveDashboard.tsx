@@ -1,3 +1,4 @@
+import { useState } from 'react';
 import type { GpsData, SatelliteInfo, TestCriteria } from '../hooks/useTauri';
 
 interface LiveDashboardProps {
@@ -6,6 +7,9 @@ interface LiveDashboardProps {
 }
 
 export function LiveDashboard({ gpsData, criteria }: LiveDashboardProps) {
+  // Receiver accuracy estimates (NAV-PVT / PUBX) in meters instead of HDOP/VDOP
+  const [showAccuracy, setShowAccuracy] = useState(false);
+
   const getSnrColor = (snr: number | null): string => {
     if (snr === null) return '#666';
     if (snr >= 40) return '#00ff41';
@@ -22,6 +26,14 @@ export function LiveDashboard({ gpsData, criteria }: LiveDashboardProps) {
     return '#ff3333';
   };
 
+  const getAccuracyColor = (meters: number | null, max: number | null | undefined): string => {
+    if (meters === null) return '#666';
+    if (max != null) return meters <= max ? '#00ff41' : '#ff3333';
+    if (meters <= 2.5) return '#00ff41';
+    if (meters <= 10) return '#ffaa00';
+    return '#ff3333';
+  };
+
   const getFixQualityText = (quality: number | null): string => {
     if (quality === null) return 'Unknown';
     switch (quality) {
@@ -56,7 +68,12 @@ export function LiveDashboard({ gpsData, criteria }: LiveDashboardProps) {
 
   return (
     <section className="panel live-dashboard">
-      <h2>Live GPS Data</h2>
+      <div className="live-dashboard-header">
+        <h2>Live GPS Data</h2>
+        <button className="btn btn-small" onClick={() => setShowAccuracy(!showAccuracy)}>
+          {showAccuracy ? 'Show DOP' : 'Show Accuracy'}
+        </button>
+      </div>
 
       {/* Summary metrics */}
       <div className="metrics-grid">
@@ -72,21 +89,52 @@ export function LiveDashboard({ gpsData, criteria }: LiveDashboardProps) {
             {satCount}
           </span>
         </div>
-        <div className="metric">
-          <span className="metric-label">HDOP</span>
-          <span
-            className="metric-value"
-            style={{ color: getDopColor(gpsData?.hdop ?? null, criteria?.max_hdop) }}
-          >
-            {gpsData?.hdop?.toFixed(1) ?? '-'}
-          </span>
-        </div>
-        <div className="metric">
-          <span className="metric-label">VDOP</span>
-          <span className="metric-value" style={{ color: getDopColor(gpsData?.vdop ?? null, undefined) }}>
-            {gpsData?.vdop?.toFixed(1) ?? '-'}
-          </span>
-        </div>
+        {showAccuracy ? (
+          <>
+            <div className="metric">
+              <span className="metric-label">H Acc</span>
+              <span
+                className="metric-value"
+                style={{
+                  color: getAccuracyColor(gpsData?.horizontal_accuracy_m ?? null, criteria?.max_hacc_m),
+                }}
+              >
+                {gpsData?.horizontal_accuracy_m != null
+                  ? `${gpsData.horizontal_accuracy_m.toFixed(1)}m`
+                  : '-'}
+              </span>
+            </div>
+            <div className="metric">
+              <span className="metric-label">V Acc</span>
+              <span
+                className="metric-value"
+                style={{ color: getAccuracyColor(gpsData?.vertical_accuracy_m ?? null, undefined) }}
+              >
+                {gpsData?.vertical_accuracy_m != null
+                  ? `${gpsData.vertical_accuracy_m.toFixed(1)}m`
+                  : '-'}
+              </span>
+            </div>
+          </>
+        ) : (
+          <>
+            <div className="metric">
+              <span className="metric-label">HDOP</span>
+              <span
+                className="metric-value"
+                style={{ color: getDopColor(gpsData?.hdop ?? null, criteria?.max_hdop) }}
+              >
+                {gpsData?.hdop?.toFixed(1) ?? '-'}
+              </span>
+            </div>
+            <div className="metric">
+              <span className="metric-label">VDOP</span>
+              <span className="metric-value" style={{ color: getDopColor(gpsData?.vdop ?? null, undefined) }}>
+                {gpsData?.vdop?.toFixed(1) ?? '-'}
+              </span>
+            </div>
+          </>
+        )}
         <div className="metric">
           <span className="metric-label">PDOP</span>
           <span
@@ -1,6 +1,9 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { getNmeaBuffer, clearNmeaBuffer, isTauri } from '../hooks/useTauri';
 
+const SENTENCE_TYPES = ['GGA', 'GSA', 'GSV', 'RMC', 'VTG', 'GLL', 'PUBX'];
+const TALKERS = ['GP', 'GL', 'GA', 'GB', 'GN'];
+
 interface NmeaTrafficProps {
   visible: boolean;
 }
@@ -9,6 +12,8 @@ export function NmeaTraffic({ visible }: NmeaTrafficProps) {
   const [buffer, setBuffer] = useState<string[]>([]);
   const [autoScroll, setAutoScroll] = useState(true);
   const [paused, setPaused] = useState(false);
+  const [sentenceType, setSentenceType] = useState('');
+  const [talker, setTalker] = useState('');
   const trafficRef = useRef<HTMLDivElement>(null);
 
   useEffect(() => {
@@ -16,7 +21,10 @@ export function NmeaTraffic({ visible }: NmeaTrafficProps) {
 
     const poll = async () => {
       try {
-        const data = await getNmeaBuffer();
+        const data = await getNmeaBuffer({
+          sentenceType: sentenceType || undefined,
+          talker: talker || undefined,
+        });
         setBuffer(data);
       } catch {
         // ignore
@@ -26,7 +34,7 @@ export function NmeaTraffic({ visible }: NmeaTrafficProps) {
     poll();
     const interval = setInterval(poll, 500);
     return () => clearInterval(interval);
-  }, [visible, paused]);
+  }, [visible, paused, sentenceType, talker]);
 
   useEffect(() => {
     if (autoScroll && trafficRef.current) {
@@ -50,6 +58,18 @@ export function NmeaTraffic({ visible }: NmeaTrafficProps) {
       <div className="nmea-header">
         <h3>NMEA Traffic</h3>
         <div className="nmea-controls">
+          <select value={sentenceType} onChange={(e) => setSentenceType(e.target.value)}>
+            <option value="">All sentences</option>
+            {SENTENCE_TYPES.map((type) => (
+              <option key={type} value={type}>{type}</option>
+            ))}
+          </select>
+          <select value={talker} onChange={(e) => setTalker(e.target.value)}>
+            <option value="">All talkers</option>
+            {TALKERS.map((id) => (
+              <option key={id} value={id}>{id}</option>
+            ))}
+          </select>
           <label className="checkbox-label">
             <input
               type="checkbox"
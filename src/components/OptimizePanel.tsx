@@ -1,12 +1,30 @@
-import { useState, useEffect, useRef } from 'react';
-import type { GpsSourceStatus, OptimizeStatus, PerformanceSnapshot } from '../hooks/useTauri';
-import { startOptimize, getOptimizeStatus, abortOptimize } from '../hooks/useTauri';
+import { useState, useEffect } from 'react';
+import type {
+  GpsSourceStatus,
+  OptimizeStatus,
+  PerformanceSnapshot,
+  UbloxSeries,
+} from '../hooks/useTauri';
+import {
+  startOptimize,
+  getOptimizeStatus,
+  abortOptimize,
+  onOptimizeStatus,
+} from '../hooks/useTauri';
 
 interface OptimizePanelProps {
   status: GpsSourceStatus | null;
   isUblox: boolean;
 }
 
+const SERIES_LABELS: Record<UbloxSeries, string> = {
+  series7: 'Series 7',
+  series8: 'Series 8',
+  series9: 'Series 9',
+  series10: 'Series 10',
+  unknown: 'Unknown',
+};
+
 const PHASE_LABELS: Record<string, string> = {
   idle: 'Ready',
   identifying_chip: 'Identifying chip...',
@@ -111,7 +129,6 @@ function SnapshotTable({ before, after, report }: {
 export function OptimizePanel({ status, isUblox }: OptimizePanelProps) {
   const [optStatus, setOptStatus] = useState<OptimizeStatus | null>(null);
   const [error, setError] = useState<string | null>(null);
-  const pollRef = useRef<ReturnType<typeof setInterval> | null>(null);
 
   const isConnected =
     status?.status === 'connected' || status?.status === 'receiving_data';
@@ -121,30 +138,16 @@ export function OptimizePanel({ status, isUblox }: OptimizePanelProps) {
     optStatus.phase !== 'complete' &&
     optStatus.phase !== 'error';
 
-  // Poll optimization status when active
+  // Follow optimizer progress via backend events while active
   useEffect(() => {
-    if (!isActive) {
-      if (pollRef.current) {
-        clearInterval(pollRef.current);
-        pollRef.current = null;
-      }
-      return;
-    }
-
-    const poll = async () => {
-      try {
-        const s = await getOptimizeStatus();
-        setOptStatus(s);
-        if (s.error) setError(s.error);
-      } catch (e) {
-        setError(e instanceof Error ? e.message : String(e));
-      }
-    };
+    if (!isActive) return;
 
-    poll();
-    pollRef.current = setInterval(poll, 500);
+    const unlisten = onOptimizeStatus((s) => {
+      setOptStatus(s);
+      if (s.error) setError(s.error);
+    });
     return () => {
-      if (pollRef.current) clearInterval(pollRef.current);
+      unlisten.then((fn) => fn());
     };
   }, [isActive]);
 
@@ -152,7 +155,7 @@ export function OptimizePanel({ status, isUblox }: OptimizePanelProps) {
     setError(null);
     try {
       await startOptimize();
-      // Immediately poll to get initial status
+      // Fetch the initial status; later updates arrive as events
       const s = await getOptimizeStatus();
       setOptStatus(s);
     } catch (e) {
@@ -186,11 +189,7 @@ export function OptimizePanel({ status, isUblox }: OptimizePanelProps) {
         <div className="opt-chip-info">
           <span className="opt-chip-name">{optStatus.chip_info.chip_name}</span>
           <span className="opt-chip-detail">
-            {optStatus.chip_info.series === 'series7'
-              ? 'Series 7'
-              : optStatus.chip_info.series === 'series8'
-                ? 'Series 8'
-                : 'Unknown'}{' '}
+            {SERIES_LABELS[optStatus.chip_info.series]}{' '}
             — FW: {optStatus.chip_info.sw_version.substring(0, 20)}
           </span>
         </div>
@@ -233,6 +232,13 @@ export function OptimizePanel({ status, isUblox }: OptimizePanelProps) {
           />
         )}
 
+      {/* Clone detection warning */}
+      {optStatus?.report?.suspected_clone && (
+        <div className="error-msg" style={{ marginTop: 8 }}>
+          Suspected clone module: {optStatus.report.clone_indicators.join('; ')}
+        </div>
+      )}
+
       {/* Error display */}
       {(error || optStatus?.error) && (
         <div className="error-msg" style={{ marginTop: 8 }}>
@@ -21,17 +21,21 @@ export function TestHistory({ results }: TestHistoryProps) {
           </tr>
         </thead>
         <tbody>
-          {[...results].reverse().map((r, i) => (
-            <tr key={i} className={r.verdict === 'pass' ? 'row-pass' : 'row-fail'}>
-              <td>{new Date(r.timestamp).toLocaleTimeString()}</td>
-              <td>{r.device_info.serial_number || '-'}</td>
-              <td className={r.verdict === 'pass' ? 'cell-pass' : 'cell-fail'}>
-                {r.verdict.toUpperCase()}
-              </td>
-              <td>{r.ttff_seconds?.toFixed(1) ?? '-'}s</td>
-              <td>{r.best_gps_data?.satellites ?? '-'}</td>
-            </tr>
-          ))}
+          {[...results].reverse().map((r, i) => {
+            const outcome =
+              r.verdict === 'pass' ? 'pass' : r.verdict === 'pass_with_warnings' ? 'warn' : 'fail';
+            return (
+              <tr key={i} className={`row-${outcome}`}>
+                <td>{new Date(r.timestamp).toLocaleTimeString()}</td>
+                <td>{r.device_info.serial_number || '-'}</td>
+                <td className={`cell-${outcome}`}>
+                  {r.verdict.toUpperCase()}
+                </td>
+                <td>{r.ttff_seconds?.toFixed(1) ?? '-'}s</td>
+                <td>{r.best_gps_data?.satellites ?? '-'}</td>
+              </tr>
+            );
+          })}
         </tbody>
       </table>
     </section>
@@ -1,8 +1,9 @@
-import { useState, useCallback } from 'react';
+import { useState, useCallback, useEffect } from 'react';
 import {
   startTest,
   abortTest,
   saveTestReport,
+  onReportSaved,
   type TestResult,
   type GpsSourceStatus,
 } from '../hooks/useTauri';
@@ -13,6 +14,27 @@ interface TestRunnerProps {
   onReset: () => void;
 }
 
+function Sparkline({ values }: { values: number[] }) {
+  if (values.length < 2) return null;
+  const width = 60;
+  const height = 14;
+  const min = Math.min(...values);
+  const range = Math.max(...values) - min || 1;
+  const points = values
+    .map((v, i) => {
+      const x = (i / (values.length - 1)) * width;
+      const y = height - ((v - min) / range) * height;
+      return `${x.toFixed(1)},${y.toFixed(1)}`;
+    })
+    .join(' ');
+
+  return (
+    <svg className="sparkline" width={width} height={height}>
+      <polyline points={points} fill="none" stroke="currentColor" strokeWidth="1" />
+    </svg>
+  );
+}
+
 export function TestRunner({ testResult, status, onReset }: TestRunnerProps) {
   const [saving, setSaving] = useState(false);
   const [savedPath, setSavedPath] = useState<string | null>(null);
@@ -21,7 +43,19 @@ export function TestRunner({ testResult, status, onReset }: TestRunnerProps) {
   const isConnected = status?.status === 'receiving_data' || status?.status === 'connected';
   const verdict = testResult?.verdict || 'not_started';
   const isRunning = verdict === 'running';
-  const isDone = verdict === 'pass' || verdict === 'fail' || verdict === 'timed_out';
+  const isDone =
+    verdict === 'pass' ||
+    verdict === 'pass_with_warnings' ||
+    verdict === 'fail' ||
+    verdict === 'timed_out';
+
+  // A report auto-saved on completion counts as saved
+  useEffect(() => {
+    const unlisten = onReportSaved(setSavedPath);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
 
   const handleStart = useCallback(async () => {
     setError(null);
@@ -66,9 +100,19 @@ export function TestRunner({ testResult, status, onReset }: TestRunnerProps) {
       {/* Verdict banner */}
       {isDone && (
         <div className={`verdict-banner verdict-${verdict}`}>
-          {verdict === 'pass' ? 'PASS' : verdict === 'timed_out' ? 'TIMED OUT' : 'FAIL'}
+          {verdict === 'pass'
+            ? 'PASS'
+            : verdict === 'pass_with_warnings'
+              ? 'PASS (WARNINGS)'
+              : verdict === 'timed_out'
+                ? 'TIMED OUT'
+                : 'FAIL'}
         </div>
       )}
+      {isDone && testResult?.verdict_summary && (
+        <div className="verdict-summary">{testResult.verdict_summary}</div>
+      )}
+      {isRunning && testResult?.hint && <div className="verdict-summary">{testResult.hint}</div>}
 
       {/* Action buttons */}
       <div className="test-actions">
@@ -111,6 +155,9 @@ export function TestRunner({ testResult, status, onReset }: TestRunnerProps) {
           {testResult.ttff_seconds != null && (
             <span>TTFF: {testResult.ttff_seconds.toFixed(1)}s</span>
           )}
+          {testResult.best_fix_type && (
+            <span>Best fix: {testResult.best_fix_type}</span>
+          )}
         </div>
       )}
 
@@ -127,12 +174,17 @@ export function TestRunner({ testResult, status, onReset }: TestRunnerProps) {
           </thead>
           <tbody>
             {testResult.criteria_results.map((cr) => (
-              <tr key={cr.name} className={cr.passed ? 'row-pass' : 'row-fail'}>
+              <tr
+                key={cr.name}
+                className={cr.warning ? 'row-warn' : cr.passed ? 'row-pass' : 'row-fail'}
+              >
                 <td>{cr.name}</td>
                 <td>{cr.expected}</td>
-                <td>{cr.actual}</td>
-                <td className={cr.passed ? 'cell-pass' : 'cell-fail'}>
-                  {cr.passed ? 'PASS' : 'FAIL'}
+                <td>
+                  {cr.actual} {cr.history && <Sparkline values={cr.history} />}
+                </td>
+                <td className={cr.warning ? 'cell-warn' : cr.passed ? 'cell-pass' : 'cell-fail'}>
+                  {cr.warning ? 'WARN' : cr.passed ? 'PASS' : 'FAIL'}
                 </td>
               </tr>
             ))}
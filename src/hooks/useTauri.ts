@@ -1,6 +1,7 @@
 // Tauri command bindings for GPS Studio
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 
 // ============ Response Types ============
 
@@ -18,6 +19,7 @@ export interface SatelliteInfo {
   azimuth: number | null;
   snr: number | null;
   constellation: string;
+  used_in_fix: boolean;
 }
 
 export interface GpsData {
@@ -25,6 +27,8 @@ export interface GpsData {
   longitude: number | null;
   speed_knots: number | null;
   course: number | null;
+  /** False when speed is too low (under 1 kn) for course to mean anything */
+  course_valid: boolean;
   heading: number | null;
   altitude: number | null;
   fix_quality: number | null;
@@ -34,15 +38,81 @@ export interface GpsData {
   pdop: number | null;
   timestamp: string | null;
   fix_type: string | null;
+  fix_age_seconds: number | null;
+  speed_course_mismatch: boolean | null;
+  nav_status: string | null;
+  horizontal_accuracy_m: number | null;
+  vertical_accuracy_m: number | null;
+  utc_date: string | null;
+  date_suspect: boolean | null;
+  clock_bias_ns: number | null;
+  clock_drift_ns_per_s: number | null;
+  leap_seconds: number | null; // GPS-UTC offset from PUBX,04 or UBX-NAV-TIMEGPS
+  time_valid: boolean | null; // Leap seconds confirmed, not the firmware default
+  antenna_status: AntennaStatus | null; // UBX-MON-HW or ANTSTATUS TXT
+  antenna_power: 'off' | 'on' | 'dont_know' | null;
+  gns_modes: Record<string, string> | null;
   satellites_info: SatelliteInfo[];
 }
 
+export interface ReportValidation {
+  valid: boolean;
+  issues: string[];
+}
+
+/** ZIP of the results directory written by `exportResultsArchive` */
+export interface ResultsArchive {
+  path: string;
+  reports: number;
+  /** Archived files, excluding manifest.json */
+  entries: string[];
+}
+
+export type AntennaStatus = 'init' | 'dont_know' | 'ok' | 'short' | 'open';
+
+/** Antenna type inferred from the u-blox antenna supervisor (UBX-MON-HW) */
+export interface AntennaReport {
+  kind: 'active' | 'passive' | 'short' | 'unknown';
+  transitions: {
+    at_seconds: number;
+    status: AntennaStatus;
+    power: 'off' | 'on' | 'dont_know';
+  }[];
+  mismatch: boolean;
+}
+
+export interface SkyPlotPoint {
+  prn: number;
+  elevation: number;
+  azimuth: number;
+  snr: number | null;
+  used_in_fix: boolean;
+}
+
+export interface SkyPlotGroup {
+  constellation: string;
+  satellites: SkyPlotPoint[];
+}
+
+export interface SkyPlot {
+  timestamp: string | null;
+  fix_type: string | null;
+  hdop: number | null;
+  groups: SkyPlotGroup[];
+  plotted: number;
+  used_in_fix: number;
+  excluded: number;
+  avg_snr: number | null;
+}
+
 export type GpsConnectionStatus =
   | 'disconnected'
   | 'connecting'
   | 'connected'
   | 'receiving_data'
-  | 'error';
+  | 'error'
+  | 'device_removed' // Port vanished (unplugged); reconnects when it reappears
+  | 'read_error'; // Reads failing on a present port; reconnecting
 
 export interface DetectedPort {
   port_name: string;
@@ -55,36 +125,196 @@ export interface DetectedPort {
   is_likely_gps: boolean;
 }
 
+export interface DetectedGps {
+  port: DetectedPort;
+  baud_rate: number;
+  receiver: 'u-blox' | 'MediaTek' | 'NMEA';
+}
+
 export interface GpsSourceStatus {
   port_name: string | null;
   status: GpsConnectionStatus;
   last_error: string | null;
+  last_error_code: string | null; // e.g. 'device_removed', 'read_error'
   sentences_received: number;
+  ubx_frames_received: number;
   last_fix_time: string | null;
+  baud_rate: number | null;
+  flow_control: string | null;
+  ubx_config_applied: boolean;
+  pmtk_config_applied: boolean;
+  observed_rate_hz: number | null;
+  ublox_identified: boolean;
+  expected_rate_hz: number | null; // set once a profile has configured the rate
+  rate_mismatch: boolean;
+  bytes_received: number;
+  bytes_per_second: number | null;
+  link_utilization: number | null; // 0-1 of what the baud rate can carry
+  gsv_missing: boolean; // Position sentences but no GSV, so no satellite data
+  chip_id: string | null; // UBX-SEC-UNIQID, once read
+  chip_info: UbloxChipInfo | null; // MON-VER identity from the optimizer, for this connection
+  text_messages: TextMessage[]; // Latest TXT diagnostics, oldest first
+}
+
+/** Diagnostic text from a receiver TXT sentence */
+export interface TextMessage {
+  severity: 'error' | 'warning' | 'notice' | 'user';
+  text: string;
+}
+
+export interface ConnectionInfo {
+  port_name: string;
+  baud_rate: number;
+  flow_control: string;
+  ubx_config_applied: boolean;
+  pmtk_config_applied: boolean;
 }
 
 // ============ Test Types ============
 
+export type CoordFormat = 'decimal_degrees' | 'dms' | 'utm';
+
+export type Datum = 'wgs84' | 'nad27' | 'nzgd49';
+
 export interface TestCriteria {
   min_satellites: number;
   max_hdop: number;
   max_pdop: number;
   min_avg_snr: number;
+  min_best_satellite_snr: number | null;
+  max_multipath_score: number | null; // 0-1, higher is worse
   min_strong_satellites: number;
+  strong_sat_min_elevation_deg: number;
   max_ttff_seconds: number;
+  cold_start_ttff_factor: number | null; // Scales max_ttff_seconds when a cold start is expected
+  weak_signal_timeout_seconds: number | null; // End early after this long with only weak satellites
   min_constellations: number;
+  required_constellations: string[];
   min_fix_quality: number;
   stability_duration_seconds: number;
+  reference_position: [number, number] | null;
+  max_position_error_m: number;
+  position_outlier_threshold_m: number;
+  max_position_outliers: number | null;
+  max_fix_downgrades: number | null; // 3D to 2D, or fix to none
+  min_unique_prns: number | null; // Distinct satellites with SNR >= 20 within the stability window
+  max_fix_age_seconds: number | null;
+  max_hacc_m: number | null;
+  max_satellite_count_stddev: number | null;
+  criterion_history_len: number;
+  max_collected_fixes: number | null;
+  require_rtk_fixed: boolean;
+  expect_active_antenna: boolean;
+  require_stable_hdop: boolean;
+  reject_suspect_date: boolean;
+  require_advancing_time: boolean;
+  require_valid_time: boolean;
+  baseline_name: string | null;
+  baseline_tolerance_pct: number;
+  warning_margin_pct: number;
+  warning_margins: Record<string, number>;
+}
+
+/** Metrics of a golden unit that new units are compared against */
+export interface Baseline {
+  satellites: number;
+  avg_snr: number;
+  hdop: number;
+  serial_number: string | null;
+  timestamp: string;
 }
 
 export interface CriterionResult {
   name: string;
   passed: boolean;
+  warning: boolean;
   expected: string;
   actual: string;
+  history?: number[];
 }
 
-export type TestVerdict = 'pass' | 'fail' | 'running' | 'not_started' | 'timed_out';
+export type TestVerdict =
+  | 'pass'
+  | 'pass_with_warnings'
+  | 'fail'
+  | 'running'
+  | 'not_started'
+  | 'timed_out'
+  | 'interrupted';
+
+export interface SelfTestCheck {
+  name: string;
+  passed: boolean;
+  detail: string;
+}
+
+export interface SelfTestReport {
+  passed: boolean;
+  checks: SelfTestCheck[];
+}
+
+export interface UbxConfigResult {
+  line: number;
+  class: number;
+  id: number;
+  acked: boolean | null; // null: no response, or a non-CFG message
+}
+
+export interface ProfileCommandResult {
+  step: number;
+  message: string; // e.g. "CFG-GNSS"
+  class: number;
+  id: number;
+  acked: boolean | null; // null: no response in time
+}
+
+export interface ProfileApplyReport {
+  profile: string;
+  commands: ProfileCommandResult[];
+  acked: number;
+  nacked: number;
+  unanswered: number;
+  saved: boolean; // Closing CFG-CFG ACKed (M9/M10: every CFG-VALSET ACKed)
+}
+
+/** Outcome of resetting the receiver to its factory configuration */
+export interface ConfigResetReport {
+  cleared: boolean | null; // CFG-CFG clear ACKed; null if unanswered
+  dynamic_model: DynamicModel | null; // Read back after the warm restart
+  confirmed: boolean;
+}
+
+export interface ParseBenchmark {
+  sentences: number;
+  errors: number;
+  elapsed_seconds: number;
+  sentences_per_second: number;
+}
+
+/** Outcome of replaying a `.ubx` raw capture */
+export interface RawReplay {
+  duration_seconds: number;
+  nmea_sentences: number;
+  ubx_frames: Record<string, number>; // "class-id" in hex, e.g. "01-07" for NAV-PVT
+  gps_data: GpsData;
+}
+
+export interface CriterionDiff {
+  name: string;
+  expected_passed: boolean;
+  actual_passed: boolean | null;
+  actual: string;
+}
+
+export interface ReplayCheck {
+  log: string;
+  expected_verdict: TestVerdict;
+  actual_verdict: TestVerdict;
+  verdict_summary: string;
+  elapsed_seconds: number;
+  criteria_diffs: CriterionDiff[];
+  matches: boolean;
+}
 
 export interface DeviceInfo {
   port_name: string;
@@ -94,16 +324,43 @@ export interface DeviceInfo {
   serial_number: string | null;
   vid: number | null;
   pid: number | null;
+  chip_id: string | null; // u-blox unique chip ID (hex), M8 and later
+}
+
+export interface ConstellationAcquisition {
+  constellation: string;
+  seconds: number; // Since the test started
 }
 
 export interface TestResult {
+  id: string; // UUID of the test run
+  related_optimization_id: string | null; // Optimization this test verified
   verdict: TestVerdict;
+  verdict_summary: string;
   criteria_results: CriterionResult[];
   ttff_seconds: number | null;
+  ttff_2d_seconds: number | null;
+  ttff_3d_seconds: number | null;
   test_duration_seconds: number;
   device_info: DeviceInfo;
+  chip_info: UbloxChipInfo | null; // MON-VER identity, when the optimizer identified the chip
+  start_type: StartType | null; // Expected start, when classified for the TTFF limit
   timestamp: string;
   best_gps_data: GpsData | null;
+  best_fix_type: string | null;
+  formatted_position: string | null;
+  criteria_profile: string | null;
+  criteria_override: boolean; // Run used ad-hoc criteria that were not saved
+  criteria: TestCriteria | null; // Criteria the run was evaluated against
+  antenna: AntennaReport | null;
+  position_outliers: number;
+  fix_downgrades: number; // Drops from 3D to 2D, or from a fix to none
+  multipath_score: number | null; // Mean over the test, 0-1, higher is worse
+  gsv_missing: boolean; // Fixes but no GSV: SNR/constellation criteria only warned
+  hint: string | null; // Operator advice, e.g. to move the antenna to open sky
+  paused: boolean; // Clocks frozen until resumed
+  constellation_acquisition: ConstellationAcquisition[]; // In order of acquisition
+  collected_fixes?: GpsData[]; // Only in saved reports, with max_collected_fixes set
 }
 
 // ============ GPS Optimization Types ============
@@ -118,7 +375,75 @@ export type OptimizePhase =
   | 'complete'
   | 'error';
 
-export type UbloxSeries = 'series7' | 'series8' | 'unknown';
+export type UbloxSeries = 'series7' | 'series8' | 'series9' | 'series10' | 'unknown';
+
+export type DynamicModel =
+  | 'portable'
+  | 'stationary'
+  | 'pedestrian'
+  | 'automotive'
+  | 'sea'
+  | 'airborne1g'
+  | 'airborne2g'
+  | 'airborne4g';
+
+/** A UBX configuration builder, from `listUbxCapabilities` */
+export interface UbxCapability {
+  name: string; // Builder function, e.g. build_cfg_sbas_enable
+  description: string;
+  message: string; // e.g. CFG-SBAS
+  series: UbloxSeries[];
+  writes_flash: boolean; // Changes the saved configuration, not just RAM
+}
+
+export interface ProfileStep {
+  description: string;
+  message: string;
+  writes_flash: boolean;
+}
+
+export interface MarineProfile {
+  name: string;
+  series: UbloxSeries[];
+  steps: ProfileStep[];
+}
+
+export interface UbxCapabilities {
+  builders: UbxCapability[];
+  marine_profiles: MarineProfile[];
+}
+
+/** Constellations `setConstellations` can enable (SBAS is always on) */
+export type Constellation = 'gps' | 'galileo' | 'beidou' | 'glonass';
+
+export type StartType = 'hot' | 'warm' | 'cold' | 'unknown';
+
+/** Expected start regime from `getStartState` (NAV-STATUS and NAV-ORB) */
+export interface StartState {
+  start_type: StartType;
+  fix_ok: boolean;
+  ephemeris_satellites: number | null; // Satellites with usable ephemeris
+  almanac_satellites: number | null; // Satellites with usable almanac
+  uptime_seconds: number | null;
+}
+
+/** Spread of the TTFFs from `runTtffBenchmark`, in seconds */
+export interface TtffStats {
+  min: number;
+  max: number;
+  mean: number;
+  stddev: number; // Sample standard deviation
+}
+
+export interface TtffBenchmark {
+  iterations: number;
+  ttffs_seconds: number[]; // Each iteration that got a fix, in order
+  timeouts: number; // Iterations with no fix within 300 s
+  cold_starts_ignored: number; // Later iterations whose reset the receiver ignored
+  stats: TtffStats | null;
+  aborted: boolean;
+  time_limit_reached: boolean; // Stopped at the 1 hour limit
+}
 
 export interface UbloxChipInfo {
   sw_version: string;
@@ -136,9 +461,13 @@ export interface PerformanceSnapshot {
   constellations: string[];
   avg_fix_quality: number;
   sample_count: number;
+  window_seconds: number;
+  valid_fix_fraction: number;
 }
 
 export interface OptimizationReport {
+  id: string; // UUID of the optimization
+  related_test_id: string | null; // Test run that verified it
   chip_info: UbloxChipInfo;
   profile_applied: string;
   before: PerformanceSnapshot;
@@ -148,6 +477,9 @@ export interface OptimizationReport {
   snr_improvement_pct: number;
   constellation_improvement: number;
   timestamp: string;
+  suspected_clone: boolean;
+  clone_indicators: string[];
+  clone_check: CriterionResult;
 }
 
 export interface OptimizeStatus {
@@ -157,9 +489,19 @@ export interface OptimizeStatus {
   phase_duration_seconds: number;
   error: string | null;
   report: OptimizationReport | null;
+  report_path: string | null;
   baseline_snapshot: PerformanceSnapshot | null;
 }
 
+// ============ Log Types ============
+
+export interface LogEntry {
+  timestamp: string;
+  level: string;
+  target: string;
+  message: string;
+}
+
 // ============ Utility ============
 
 export function isTauri(): boolean {
@@ -184,16 +526,100 @@ export async function autoDetectGps(): Promise<[DetectedPort, number]> {
   return result.data;
 }
 
-export async function testGpsPort(portName: string, baudRate: number): Promise<boolean> {
-  const result = await invoke<CommandResult<boolean>>('test_gps_port', { portName, baudRate });
+/** Probe every port and return each GPS found, for the user to choose from */
+export async function autoDetectAllGps(): Promise<DetectedGps[]> {
+  const result = await invoke<CommandResult<DetectedGps[]>>('auto_detect_all_gps');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to auto-detect GPS');
+  }
+  return result.data;
+}
+
+/** Reconnect to the last good port, auto-detecting if it is gone */
+export async function autoConnectLast(): Promise<[DetectedPort, number]> {
+  const result = await invoke<CommandResult<[DetectedPort, number]>>('auto_connect_last');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to reconnect GPS');
+  }
+  return result.data;
+}
+
+/** Error message returned when an auto-detect scan is cancelled */
+export const AUTO_DETECT_CANCELLED = 'Auto-detect cancelled';
+
+export async function cancelAutoDetect(): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('cancel_auto_detect');
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to cancel auto-detect');
+  }
+}
+
+/** Serial framing and flow control; omitted settings mean 8N1 with no flow control */
+export interface SerialSettings {
+  data_bits: 5 | 6 | 7 | 8;
+  parity: 'none' | 'odd' | 'even';
+  stop_bits: 1 | 2;
+  flow_control: 'none' | 'software' | 'hardware';
+}
+
+export async function testGpsPort(
+  portName: string,
+  baudRate: number,
+  settings?: SerialSettings,
+): Promise<boolean> {
+  const result = await invoke<CommandResult<boolean>>('test_gps_port', {
+    portName,
+    baudRate,
+    settings: settings ?? null,
+  });
   if (!result.success) {
     throw new Error(result.error || 'Failed to test GPS port');
   }
   return result.data ?? false;
 }
 
-export async function connectGps(portName: string, baudRate: number): Promise<void> {
-  const result = await invoke<CommandResult<boolean>>('connect_gps', { portName, baudRate });
+/** What a one-shot probe read found on a port */
+export interface PortProbe {
+  port_name: string;
+  baud_rate: number;
+  duration_ms: number;
+  bytes_received: number; // 0 = nothing transmitting at all
+  nmea_sentences: number; // With a correct checksum
+  invalid_sentences: number; // Failed checksum, e.g. wrong baud rate
+  sentence_types: string[]; // e.g. ["GGA", "GSV", "PUBX"]
+  talkers: string[]; // e.g. ["GL", "GN", "GP"]
+  ubx_frames: number;
+  ublox: UbloxChipInfo | null; // Answer to a MON-VER poll
+}
+
+export async function probePort(
+  portName: string,
+  baudRate: number,
+  durationMs?: number, // Default 3000, at most 10000
+  settings?: SerialSettings,
+): Promise<PortProbe> {
+  const result = await invoke<CommandResult<PortProbe>>('probe_port', {
+    portName,
+    baudRate,
+    durationMs: durationMs ?? null,
+    settings: settings ?? null,
+  });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to probe port');
+  }
+  return result.data;
+}
+
+export async function connectGps(
+  portName: string,
+  baudRate: number,
+  settings?: SerialSettings,
+): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('connect_gps', {
+    portName,
+    baudRate,
+    settings: settings ?? null,
+  });
   if (!result.success) {
     throw new Error(result.error || 'Failed to connect GPS');
   }
@@ -206,6 +632,49 @@ export async function disconnectGps(): Promise<void> {
   }
 }
 
+// u-blox only; the receiver's new rate is not saved and reverts on power cycle
+export async function changeBaud(newBaud: number): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('change_baud', { newBaud });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to change baud rate');
+  }
+}
+
+export async function loadUbxConfigFile(path: string): Promise<UbxConfigResult[]> {
+  const result = await invoke<CommandResult<UbxConfigResult[]>>('load_ubx_config_file', { path });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to apply UBX config file');
+  }
+  return result.data;
+}
+
+/** Apply the marine profile once, reporting each command's ACK/NAK */
+export async function applyMarineProfile(): Promise<ProfileApplyReport> {
+  const result = await invoke<CommandResult<ProfileApplyReport>>('apply_marine_profile');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to apply marine profile');
+  }
+  return result.data;
+}
+
+/** Unique chip ID of the connected u-blox receiver (hex), read via UBX-SEC-UNIQID */
+export async function getChipId(): Promise<string> {
+  const result = await invoke<CommandResult<string>>('get_chip_id');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to read chip ID');
+  }
+  return result.data;
+}
+
+/** Clear the receiver's saved config, warm restart and confirm the defaults are running */
+export async function resetToDefaults(): Promise<ConfigResetReport> {
+  const result = await invoke<CommandResult<ConfigResetReport>>('reset_to_defaults');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to reset receiver');
+  }
+  return result.data;
+}
+
 export async function getGpsData(): Promise<GpsData> {
   const result = await invoke<CommandResult<GpsData>>('get_gps_data');
   if (!result.success || !result.data) {
@@ -214,6 +683,30 @@ export async function getGpsData(): Promise<GpsData> {
   return result.data;
 }
 
+export async function getSkyPlot(): Promise<SkyPlot> {
+  const result = await invoke<CommandResult<SkyPlot>>('get_sky_plot');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get sky plot');
+  }
+  return result.data;
+}
+
+/** Excess antenna-path loss estimated from SNR against an open-sky reference curve */
+export interface AntennaLossEstimate {
+  excess_loss_db: number; // Median shortfall; negative = stronger than the reference
+  confidence: number; // 0-1
+  satellites: number;
+  spread_db: number; // Median absolute deviation of the shortfalls
+}
+
+export async function estimateAntennaLoss(): Promise<AntennaLossEstimate> {
+  const result = await invoke<CommandResult<AntennaLossEstimate>>('estimate_antenna_loss');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to estimate antenna loss');
+  }
+  return result.data;
+}
+
 export async function getGpsStatus(): Promise<GpsSourceStatus> {
   const result = await invoke<CommandResult<GpsSourceStatus>>('get_gps_status');
   if (!result.success || !result.data) {
@@ -222,14 +715,105 @@ export async function getGpsStatus(): Promise<GpsSourceStatus> {
   return result.data;
 }
 
-export async function getNmeaBuffer(): Promise<string[]> {
-  const result = await invoke<CommandResult<string[]>>('get_nmea_buffer');
+export async function getConnectionInfo(): Promise<ConnectionInfo> {
+  const result = await invoke<CommandResult<ConnectionInfo>>('get_connection_info');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get connection info');
+  }
+  return result.data;
+}
+
+export interface NmeaFilter {
+  sentenceType?: string; // e.g. 'GSV'
+  talker?: string; // e.g. 'GL'
+}
+
+export async function getNmeaBuffer(filter: NmeaFilter = {}): Promise<string[]> {
+  const result = await invoke<CommandResult<string[]>>('get_nmea_buffer', {
+    sentenceType: filter.sentenceType ?? null,
+    talker: filter.talker ?? null,
+  });
   if (!result.success || !result.data) {
     throw new Error(result.error || 'Failed to get NMEA buffer');
   }
   return result.data;
 }
 
+export async function captureNmea(durationSeconds: number): Promise<string> {
+  const result = await invoke<CommandResult<string>>('capture_nmea', { durationSeconds });
+  if (!result.success || result.data == null) {
+    throw new Error(result.error || 'Failed to capture NMEA');
+  }
+  return result.data;
+}
+
+/** Record the raw NMEA + UBX byte stream to a `.ubx` file; returns the bytes recorded */
+export async function captureRaw(durationSeconds: number, path: string): Promise<number> {
+  const result = await invoke<CommandResult<number>>('capture_raw', { durationSeconds, path });
+  if (!result.success || result.data == null) {
+    throw new Error(result.error || 'Failed to capture raw stream');
+  }
+  return result.data;
+}
+
+/** Replay a saved `.nmea` or `.nmea.gz` log; returns the GPS state at its end */
+export async function replayNmeaLog(path: string): Promise<GpsData> {
+  const result = await invoke<CommandResult<GpsData>>('replay_nmea_log', { path });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to replay NMEA log');
+  }
+  return result.data;
+}
+
+/** Replay a `.ubx` raw capture, splitting it into NMEA sentences and UBX frames */
+export async function replayRawLog(path: string): Promise<RawReplay> {
+  const result = await invoke<CommandResult<RawReplay>>('replay_raw_log', { path });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to replay raw capture');
+  }
+  return result.data;
+}
+
+export async function benchmarkParse(path: string): Promise<ParseBenchmark> {
+  const result = await invoke<CommandResult<ParseBenchmark>>('benchmark_parse', { path });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to benchmark parser');
+  }
+  return result.data;
+}
+
+export async function replayTestLog(
+  path: string,
+  expectedVerdict: TestVerdict,
+  expectedCriteria?: Record<string, boolean>
+): Promise<ReplayCheck> {
+  const result = await invoke<CommandResult<ReplayCheck>>('replay_test_log', {
+    path,
+    expectedVerdict,
+    expectedCriteria: expectedCriteria ?? null,
+  });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to replay test log');
+  }
+  return result.data;
+}
+
+export async function replayTestCorpus(dir: string): Promise<ReplayCheck[]> {
+  const result = await invoke<CommandResult<ReplayCheck[]>>('replay_test_corpus', { dir });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to check replay corpus');
+  }
+  return result.data;
+}
+
+export async function runSelfTest(): Promise<SelfTestReport> {
+  const result = await invoke<CommandResult<SelfTestReport>>('run_self_test');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to run self-test');
+  }
+  return result.data;
+}
+
 export async function clearNmeaBuffer(): Promise<void> {
   const result = await invoke<CommandResult<boolean>>('clear_nmea_buffer');
   if (!result.success) {
@@ -239,6 +823,41 @@ export async function clearNmeaBuffer(): Promise<void> {
 
 // ============ Test Criteria Commands ============
 
+/** Contents of `settings.json` in the config dir */
+export interface AppSettings {
+  criteria: TestCriteria;
+  results_dir: string | null; // null for ~/gps-studio-results; applies on restart
+  reconnect_on_startup: boolean;
+  auto_save_on_complete: boolean; // Save the report when a test reaches a verdict
+  append_ndjson: boolean; // Also append saved results to results.ndjson
+  continuous: boolean; // Soak test: evaluate indefinitely, never reach a verdict
+  interim_save_interval_seconds: number | null; // Keep a .partial.json of the running test
+  evaluation_interval_ms: number; // Backend evaluation tick, 100-5000
+  optimizer: OptimizerConfig;
+  coord_format: CoordFormat; // Position format in reports and exports
+  datum: Datum; // Datum report positions are converted to
+}
+
+/** u-blox optimizer tuning */
+export interface OptimizerConfig {
+  sample_interval_ms: number; // 100-5000; metrics are also sampled once per fix time
+}
+
+export async function getSettings(): Promise<AppSettings> {
+  const result = await invoke<CommandResult<AppSettings>>('get_settings');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get settings');
+  }
+  return result.data;
+}
+
+export async function setSettings(settings: AppSettings): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('set_settings', { settings });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to save settings');
+  }
+}
+
 export async function getTestCriteria(): Promise<TestCriteria> {
   const result = await invoke<CommandResult<TestCriteria>>('get_test_criteria');
   if (!result.success || !result.data) {
@@ -262,6 +881,62 @@ export async function resetTestCriteria(): Promise<TestCriteria> {
   return result.data;
 }
 
+/** Evaluate the current fix against criteria without running a test (single-fix criteria only) */
+export async function previewCriteria(criteria: TestCriteria): Promise<CriterionResult[]> {
+  const result = await invoke<CommandResult<CriterionResult[]>>('preview_criteria', { criteria });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to preview criteria');
+  }
+  return result.data;
+}
+
+/** Criteria profiles keyed by device model (USB product/manufacturer substring) */
+export async function getCriteriaProfiles(): Promise<Record<string, TestCriteria>> {
+  const result = await invoke<CommandResult<Record<string, TestCriteria>>>('get_criteria_profiles');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get criteria profiles');
+  }
+  return result.data;
+}
+
+export async function setCriteriaProfile(name: string, criteria: TestCriteria): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('set_criteria_profile', { name, criteria });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to save criteria profile');
+  }
+}
+
+export async function deleteCriteriaProfile(name: string): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('delete_criteria_profile', { name });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to delete criteria profile');
+  }
+}
+
+export async function getBaselines(): Promise<Record<string, Baseline>> {
+  const result = await invoke<CommandResult<Record<string, Baseline>>>('get_baselines');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get baselines');
+  }
+  return result.data;
+}
+
+/** Save the current test's metrics as a named golden-unit baseline */
+export async function saveBaseline(name: string): Promise<Baseline> {
+  const result = await invoke<CommandResult<Baseline>>('save_baseline', { name });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to save baseline');
+  }
+  return result.data;
+}
+
+export async function deleteBaseline(name: string): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('delete_baseline', { name });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to delete baseline');
+  }
+}
+
 // ============ Test Execution Commands ============
 
 export async function startTest(): Promise<void> {
@@ -271,6 +946,14 @@ export async function startTest(): Promise<void> {
   }
 }
 
+/** Start one test with ad-hoc criteria, leaving the saved criteria unchanged */
+export async function startTestWithCriteria(criteria: TestCriteria): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('start_test_with_criteria', { criteria });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to start test');
+  }
+}
+
 export async function getTestStatus(): Promise<TestResult> {
   const result = await invoke<CommandResult<TestResult>>('get_test_status');
   if (!result.success || !result.data) {
@@ -286,6 +969,38 @@ export async function abortTest(): Promise<void> {
   }
 }
 
+/** Pause the running test; paused time doesn't count toward the timeout or stability */
+export async function pauseTest(): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('pause_test');
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to pause test');
+  }
+}
+
+export async function resumeTest(): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('resume_test');
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to resume test');
+  }
+}
+
+/** Append a row of fix metrics to a CSV at `path` on every evaluation tick of a running test */
+export async function startMetricsCsv(path: string): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('start_metrics_csv', { path });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to start metrics CSV');
+  }
+}
+
+/** Stop the metrics CSV; returns the number of rows written */
+export async function stopMetricsCsv(): Promise<number> {
+  const result = await invoke<CommandResult<number>>('stop_metrics_csv');
+  if (!result.success || result.data === undefined) {
+    throw new Error(result.error || 'Failed to stop metrics CSV');
+  }
+  return result.data;
+}
+
 export async function saveTestReport(): Promise<string> {
   const result = await invoke<CommandResult<string>>('save_test_report');
   if (!result.success || !result.data) {
@@ -294,6 +1009,50 @@ export async function saveTestReport(): Promise<string> {
   return result.data;
 }
 
+/** Subscribe to reports saved by `auto_save_on_complete`; the payload is the report path */
+export function onReportSaved(handler: (path: string) => void): Promise<UnlistenFn> {
+  return listen<string>('report-saved', (event) => handler(event.payload));
+}
+
+export type LabelFormat = 'text' | 'zpl';
+
+/** Pass/fail label for the current test's unit (24-column text, or ZPL) */
+export async function formatTestLabel(format: LabelFormat = 'text'): Promise<string> {
+  const result = await invoke<CommandResult<string>>('format_test_label', { format });
+  if (!result.success || result.data == null) {
+    throw new Error(result.error || 'Failed to format label');
+  }
+  return result.data;
+}
+
+export async function validateReport(filename: string): Promise<ReportValidation> {
+  const result = await invoke<CommandResult<ReportValidation>>('validate_report', { filename });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to validate report');
+  }
+  return result.data;
+}
+
+/**
+ * Bundle all saved reports (and optionally the NDJSON/monitor logs, CSV and HTML) into one ZIP.
+ * `verdict` keeps only the test reports with that verdict, e.g. 'fail'.
+ */
+export async function exportResultsArchive(
+  path: string,
+  includeExtras = false,
+  verdict?: TestVerdict
+): Promise<ResultsArchive> {
+  const result = await invoke<CommandResult<ResultsArchive>>('export_results_archive', {
+    path,
+    includeExtras,
+    verdict,
+  });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to export results archive');
+  }
+  return result.data;
+}
+
 export async function getRecentResults(): Promise<TestResult[]> {
   const result = await invoke<CommandResult<TestResult[]>>('get_recent_results');
   if (!result.success || !result.data) {
@@ -302,6 +1061,43 @@ export async function getRecentResults(): Promise<TestResult[]> {
   return result.data;
 }
 
+/** Recent results with the given verdict, e.g. 'fail' */
+export async function getRecentResultsFiltered(verdict: TestVerdict): Promise<TestResult[]> {
+  const result = await invoke<CommandResult<TestResult[]>>('get_recent_results_filtered', {
+    verdict,
+  });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get filtered results');
+  }
+  return result.data;
+}
+
+export interface TtffBucket {
+  start_seconds: number;
+  end_seconds: number;
+  count: number;
+}
+
+export interface TtffHistogram {
+  bucket_seconds: number;
+  buckets: TtffBucket[];
+  sample_count: number;
+  skipped: number;
+  mean_seconds: number | null;
+  median_seconds: number | null;
+  p95_seconds: number | null;
+}
+
+export async function getTtffHistogram(bucketSeconds: number): Promise<TtffHistogram> {
+  const result = await invoke<CommandResult<TtffHistogram>>('get_ttff_histogram', {
+    bucketSeconds,
+  });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get TTFF histogram');
+  }
+  return result.data;
+}
+
 // ============ GPS Optimization Commands ============
 
 export async function startOptimize(): Promise<void> {
@@ -319,9 +1115,121 @@ export async function getOptimizeStatus(): Promise<OptimizeStatus> {
   return result.data;
 }
 
+/** Subscribe to optimizer status, emitted by the backend on every tick */
+export function onOptimizeStatus(handler: (status: OptimizeStatus) => void): Promise<UnlistenFn> {
+  return listen<OptimizeStatus>('optimize-status', (event) => handler(event.payload));
+}
+
+/** Subscribe to the final report, emitted once when optimization completes */
+export function onOptimizeComplete(
+  handler: (report: OptimizationReport) => void
+): Promise<UnlistenFn> {
+  return listen<OptimizationReport>('optimize-complete', (event) => handler(event.payload));
+}
+
 export async function abortOptimize(): Promise<void> {
   const result = await invoke<CommandResult<boolean>>('abort_optimize');
   if (!result.success) {
     throw new Error(result.error || 'Failed to abort optimization');
   }
 }
+
+// ============ Log Commands ============
+
+export async function getLogs(): Promise<LogEntry[]> {
+  const result = await invoke<CommandResult<LogEntry[]>>('get_logs');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get logs');
+  }
+  return result.data;
+}
+
+export async function clearLogs(): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('clear_logs');
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to clear logs');
+  }
+}
+
+// ============ Live Diagnostic Commands ============
+
+/** Configuration builders and marine profile steps per chip series */
+export async function listUbxCapabilities(): Promise<UbxCapabilities> {
+  const result = await invoke<CommandResult<UbxCapabilities>>('list_ubx_capabilities');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to list UBX capabilities');
+  }
+  return result.data;
+}
+
+/** Cold start the connected u-blox `iterations` times (1-50) and time each first fix */
+export async function runTtffBenchmark(iterations: number): Promise<TtffBenchmark> {
+  const result = await invoke<CommandResult<TtffBenchmark>>('run_ttff_benchmark', {
+    iterations,
+  });
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to run TTFF benchmark');
+  }
+  return result.data;
+}
+
+/** Stop a running TTFF benchmark; it returns the TTFFs measured so far */
+export async function cancelTtffBenchmark(): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('cancel_ttff_benchmark');
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to cancel TTFF benchmark');
+  }
+}
+
+/** Classify the start (hot/warm/cold) the connected u-blox is about to make */
+export async function getStartState(): Promise<StartState> {
+  const result = await invoke<CommandResult<StartState>>('get_start_state');
+  if (!result.success || !result.data) {
+    throw new Error(result.error || 'Failed to get start state');
+  }
+  return result.data;
+}
+
+export async function enableSbas(): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('enable_sbas');
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to enable SBAS');
+  }
+}
+
+export async function disableSbas(): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('disable_sbas');
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to disable SBAS');
+  }
+}
+
+export async function setNmea41(): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('set_nmea_41');
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to set NMEA 4.10 output');
+  }
+}
+
+/** NMEA high-precision positions; enabling is refused unless the receiver is an M8P/F9P */
+export async function setNmeaHighPrecision(enable: boolean): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('set_nmea_high_precision', { enable });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to set NMEA high-precision mode');
+  }
+}
+
+/** Enable only these constellations; rejected if the receiver's series cannot track them */
+export async function setConstellations(constellations: Constellation[]): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('set_constellations', { constellations });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to set constellations');
+  }
+}
+
+export async function setDynamicModel(model: DynamicModel): Promise<void> {
+  const result = await invoke<CommandResult<boolean>>('set_dynamic_model', { model });
+  if (!result.success) {
+    throw new Error(result.error || 'Failed to set dynamic model');
+  }
+}